@@ -0,0 +1,106 @@
+use crate::animation::{AnimationSystem, FrameCommands, FrameContext, HouseEmission, RenderLayer};
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+
+use rand::{Rng, RngExt};
+use std::io;
+
+const UNIT_ART: &str = "[#]";
+/// Rows a drip falls below the unit before landing.
+const DRIP_FALL: f32 = 2.0;
+const DRIP_SPEED: f32 = 0.08;
+/// Frames between drips, before jitter.
+const DRIP_INTERVAL: f32 = 45.0;
+const SHIMMER_SPEED: f32 = 0.15;
+const SHIMMER_WIDTH: u16 = 5;
+
+/// Window air conditioner that takes over from the chimney on hot days,
+/// dripping condensate and pushing out a shimmer of hot air.
+pub struct AirConditionerSystem {
+    drip: Option<f32>,
+    drip_cooldown: f32,
+    phase: f32,
+}
+
+impl AirConditionerSystem {
+    pub fn new() -> Self {
+        Self {
+            drip: None,
+            drip_cooldown: DRIP_INTERVAL,
+            phase: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32, rng: &mut (impl Rng + ?Sized)) {
+        self.phase += SHIMMER_SPEED * dt;
+
+        match self.drip {
+            Some(fallen) if fallen >= DRIP_FALL => self.drip = None,
+            Some(fallen) => self.drip = Some(fallen + DRIP_SPEED * dt),
+            None => {
+                self.drip_cooldown -= dt;
+                if self.drip_cooldown <= 0.0 {
+                    self.drip = Some(0.0);
+                    self.drip_cooldown = DRIP_INTERVAL + rng.random::<f32>() * DRIP_INTERVAL;
+                }
+            }
+        }
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer, x: u16, y: u16) -> io::Result<()> {
+        renderer.render_line_colored(x, y, UNIT_ART, Color::Grey)?;
+
+        if let Some(fallen) = self.drip {
+            renderer.render_char(x + 1, y + 1 + fallen as u16, '.', Color::Cyan)?;
+        }
+
+        // Hot air wavering above the unit
+        if let Some(shimmer_y) = y.checked_sub(1) {
+            let start = x.saturating_sub(1);
+            for i in 0..SHIMMER_WIDTH {
+                let wave = (self.phase + i as f32 * 0.9).sin();
+                if wave > 0.3 {
+                    let ch = if wave > 0.8 { '~' } else { '\'' };
+                    renderer.render_char(start + i, shimmer_y, ch, Color::DarkYellow)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for AirConditionerSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationSystem for AirConditionerSystem {
+    fn id(&self) -> &'static str {
+        "ac_unit"
+    }
+
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::PostScene
+    }
+
+    fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
+        ctx.emission == HouseEmission::AirConditioner && ctx.ac_unit.is_some()
+    }
+
+    fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
+        self.update(ctx.dt, rng);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        ctx: &FrameContext<'_>,
+    ) -> io::Result<()> {
+        let Some(unit) = ctx.ac_unit else {
+            return Ok(());
+        };
+        AirConditionerSystem::render(self, renderer, unit.x, unit.y)
+    }
+}
@@ -16,7 +16,7 @@ pub struct AirplaneSystem {
     planes: Vec<Airplane>,
     terminal_width: u16,
     terminal_height: u16,
-    spawn_cooldown: u16,
+    spawn_cooldown: f32,
 }
 
 impl AirplaneSystem {
@@ -25,7 +25,7 @@ impl AirplaneSystem {
             planes: Vec::with_capacity(2),
             terminal_width,
             terminal_height,
-            spawn_cooldown: 0,
+            spawn_cooldown: 0.0,
         }
     }
 
@@ -33,21 +33,22 @@ impl AirplaneSystem {
         &mut self,
         terminal_width: u16,
         terminal_height: u16,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
         for plane in &mut self.planes {
-            plane.x += plane.speed;
+            plane.x += plane.speed * dt;
         }
 
         self.planes.retain(|p| p.x < terminal_width as f32);
 
-        self.spawn_cooldown = self.spawn_cooldown.saturating_sub(1);
-        if self.spawn_cooldown == 0 && rng.random::<f32>() < 0.001 {
+        self.spawn_cooldown = (self.spawn_cooldown - dt).max(0.0);
+        if self.spawn_cooldown == 0.0 && rng.random::<f32>() < 0.001 * dt {
             self.spawn_plane(rng);
-            self.spawn_cooldown = 600 + (rng.random::<u16>() % 300);
+            self.spawn_cooldown = 600.0 + (rng.random::<u16>() % 300) as f32;
         }
     }
 
@@ -123,7 +124,7 @@ impl AnimationSystem for AirplaneSystem {
     }
 
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
-        self.update(ctx.size.width, ctx.size.height, rng);
+        self.update(ctx.size.width, ctx.size.height, ctx.dt, rng);
     }
 
     fn render(
@@ -0,0 +1,149 @@
+//! Curtains of the aurora rippling across the top of the night sky, when the
+//! Kp forecast says they reach this far.
+
+use crate::animation::ramp::Ramp;
+use crate::animation::{AnimationSystem, FrameCommands, FrameContext, RenderLayer, TerminalSize};
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+
+use rand::Rng;
+use std::io;
+
+/// First sky row, below the HUD.
+const TOP_ROW: u16 = 2;
+/// Rows the curtains hang down at full strength.
+const MAX_ROWS: f32 = 7.0;
+/// Radians per frame the curtains ripple by.
+const RIPPLE_SPEED: f32 = 0.015;
+
+/// From the lower edge of a curtain upwards.
+const SHADES: [(char, Color); 3] = [
+    (
+        '|',
+        Color::Rgb {
+            r: 90,
+            g: 255,
+            b: 140,
+        },
+    ),
+    (
+        ':',
+        Color::Rgb {
+            r: 40,
+            g: 170,
+            b: 100,
+        },
+    ),
+    (
+        '.',
+        Color::Rgb {
+            r: 150,
+            g: 80,
+            b: 170,
+        },
+    ),
+];
+
+pub struct AuroraSystem {
+    terminal_width: u16,
+    terminal_height: u16,
+    phase: f32,
+    /// Easing towards the state's strength, so the aurora fades in and out.
+    level: Ramp,
+}
+
+impl AuroraSystem {
+    pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
+        Self {
+            terminal_width,
+            terminal_height,
+            phase: 0.0,
+            level: Ramp::new(0.0),
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer, horizon_y: u16) -> io::Result<()> {
+        let level = self.level.current();
+        // Leave the lower sky to the house and trees
+        let bottom = horizon_y.saturating_sub(8).min(self.terminal_height);
+        for x in 0..self.terminal_width {
+            let rows = curtain_rows(x, self.phase, level);
+            for depth in 0..rows {
+                let y = TOP_ROW + rows - 1 - depth;
+                if y >= bottom {
+                    continue;
+                }
+                let (ch, color) = SHADES[(depth as usize).min(SHADES.len() - 1)];
+                renderer.render_char(x, y, ch, color)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How far the curtain hangs down at column `x`. Two ripples at different
+/// scales drift against each other, leaving gaps between the folds.
+fn curtain_rows(x: u16, phase: f32, level: f32) -> u16 {
+    let x = x as f32;
+    let fold = (x * 0.12 + phase).sin() * 0.5 + 0.5;
+    let band = (x * 0.031 - phase * 0.7).sin() * 0.5 + 0.5;
+    (fold * band * level * MAX_ROWS).round() as u16
+}
+
+impl AnimationSystem for AuroraSystem {
+    fn id(&self) -> &'static str {
+        "aurora"
+    }
+
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Background
+    }
+
+    /// Stays on while it fades out.
+    fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
+        ctx.state.aurora_strength() > 0.0 || self.level.current() > 0.0
+    }
+
+    fn on_resize(&mut self, size: TerminalSize) {
+        self.terminal_width = size.width;
+        self.terminal_height = size.height;
+    }
+
+    fn update(
+        &mut self,
+        ctx: &FrameContext<'_>,
+        _rng: &mut dyn Rng,
+        _commands: &mut FrameCommands,
+    ) {
+        self.terminal_width = ctx.size.width;
+        self.terminal_height = ctx.size.height;
+        self.level.set_target(ctx.state.aurora_strength());
+        self.level.update(ctx.dt);
+        self.phase += RIPPLE_SPEED * ctx.dt;
+    }
+
+    fn render(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        ctx: &FrameContext<'_>,
+    ) -> io::Result<()> {
+        AuroraSystem::render(self, renderer, ctx.horizon_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curtains_grow_with_strength() {
+        let total = |level| {
+            (0..200)
+                .map(|x| curtain_rows(x, 0.0, level) as u32)
+                .sum::<u32>()
+        };
+        assert_eq!(total(0.0), 0);
+        assert!(total(0.3) < total(1.0));
+        assert!((0..200).all(|x| curtain_rows(x, 1.3, 1.0) <= MAX_ROWS as u16));
+    }
+}
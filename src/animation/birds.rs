@@ -11,7 +11,7 @@ struct Bird {
     speed: f32,
     character: char,
     flap_state: bool, // true = wings up, false = wings down/flat
-    flap_timer: u8,
+    flap_timer: f32,
 }
 
 pub struct BirdSystem {
@@ -33,23 +33,24 @@ impl BirdSystem {
         &mut self,
         terminal_width: u16,
         terminal_height: u16,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
         for bird in &mut self.birds {
-            bird.x += bird.speed;
-            bird.flap_timer += 1;
-            if bird.flap_timer > 5 {
+            bird.x += bird.speed * dt;
+            bird.flap_timer += dt;
+            if bird.flap_timer > 5.0 {
                 bird.flap_state = !bird.flap_state;
-                bird.flap_timer = 0;
+                bird.flap_timer = 0.0;
             }
             bird.character = if bird.flap_state { 'v' } else { '-' };
         }
 
         self.birds.retain(|b| b.x < terminal_width as f32);
-        if self.birds.len() < 3 && rng.random::<f32>() < 0.01 {
+        if self.birds.len() < 3 && rng.random::<f32>() < 0.01 * dt {
             let spawn_band = (terminal_height / 3).max(1);
             let y = (rng.random::<u16>() % spawn_band) as f32;
             let speed = 0.2 + (rng.random::<f32>() * 0.2);
@@ -59,7 +60,7 @@ impl BirdSystem {
                 speed,
                 character: 'v',
                 flap_state: true,
-                flap_timer: 0,
+                flap_timer: 0.0,
             });
         }
     }
@@ -100,7 +101,7 @@ impl AnimationSystem for BirdSystem {
     }
 
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
-        self.update(ctx.size.width, ctx.size.height, rng);
+        self.update(ctx.size.width, ctx.size.height, ctx.dt, rng);
     }
 
     fn render(
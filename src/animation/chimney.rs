@@ -1,4 +1,7 @@
-use crate::animation::{AnimationSystem, FrameCommands, FrameContext, RenderLayer, TerminalSize};
+use crate::animation::system::COMFORT_THRESHOLD;
+use crate::animation::{
+    AnimationSystem, FrameCommands, FrameContext, HouseEmission, RenderLayer, TerminalSize,
+};
 use crate::render::TerminalRenderer;
 use crossterm::style::Color;
 
@@ -11,34 +14,67 @@ const PARTICLE_MAX_AGE_VARIANCE: u32 = 30;
 const PARTICLE_VERTICAL_SPEED: f32 = 0.1;
 const PARTICLE_DRIFT_SCALE: f32 = 0.08;
 const PARTICLE_SPAWN_JITTER_X: f32 = 1.6;
-const DEFAULT_SPAWN_RATE: u32 = 12;
+const DEFAULT_SPAWN_RATE: f32 = 12.0;
+
+/// Temperature (°C) at which the smoke is at its thickest.
+const THICK_SMOKE_TEMP: f64 = -10.0;
+/// Temperature (°C) at which only thin wisps remain.
+const THIN_SMOKE_TEMP: f64 = 10.0;
+/// Frames between spawns at the thick and thin ends of the range.
+const THICK_SPAWN_RATE: f32 = 4.0;
+const THIN_SPAWN_RATE: f32 = 24.0;
+/// Particle lifetime multipliers at the thick and thin ends of the range.
+const THICK_LIFETIME_SCALE: f32 = 1.5;
+const THIN_LIFETIME_SCALE: f32 = 0.6;
+
+/// Spawn interval and lifetime scale for the given outdoor temperature, or
+/// `None` when it's warm enough that the fire is out.
+fn smoke_profile(celsius: f64) -> Option<(f32, f32)> {
+    if celsius > COMFORT_THRESHOLD {
+        return None;
+    }
+
+    let warmth = ((celsius - THICK_SMOKE_TEMP) / (THIN_SMOKE_TEMP - THICK_SMOKE_TEMP))
+        .clamp(0.0, 1.0) as f32;
+    Some((
+        THICK_SPAWN_RATE + (THIN_SPAWN_RATE - THICK_SPAWN_RATE) * warmth,
+        THICK_LIFETIME_SCALE + (THIN_LIFETIME_SCALE - THICK_LIFETIME_SCALE) * warmth,
+    ))
+}
 
 struct SmokeParticle {
     x: f32,
     y: f32,
-    age: u32,
-    max_age: u32,
+    age: f32,
+    max_age: f32,
     drift: f32,
 }
 
 impl SmokeParticle {
-    fn new(chimney_x: u16, chimney_y: u16, rng: &mut (impl Rng + ?Sized)) -> Self {
+    fn new(
+        chimney_x: u16,
+        chimney_y: u16,
+        lifetime_scale: f32,
+        rng: &mut (impl Rng + ?Sized),
+    ) -> Self {
         let drift = (rng.random::<f32>() - 0.5) * PARTICLE_DRIFT_SCALE;
-        let max_age = MIN_PARTICLE_MAX_AGE + (rng.random::<u32>() % PARTICLE_MAX_AGE_VARIANCE);
+        let max_age = (MIN_PARTICLE_MAX_AGE + (rng.random::<u32>() % PARTICLE_MAX_AGE_VARIANCE))
+            as f32
+            * lifetime_scale;
 
         Self {
             x: chimney_x as f32 + (rng.random::<f32>() - 0.5) * PARTICLE_SPAWN_JITTER_X,
             y: chimney_y as f32,
-            age: 0,
+            age: 0.0,
             max_age,
             drift,
         }
     }
 
-    fn update(&mut self) {
-        self.age += 1;
-        self.y -= PARTICLE_VERTICAL_SPEED;
-        self.x += self.drift;
+    fn update(&mut self, dt: f32) {
+        self.age += dt;
+        self.y -= PARTICLE_VERTICAL_SPEED * dt;
+        self.x += self.drift * dt;
     }
 
     fn is_alive(&self) -> bool {
@@ -46,7 +82,7 @@ impl SmokeParticle {
     }
 
     fn get_color(&self) -> Color {
-        let life_ratio = self.age as f32 / self.max_age as f32;
+        let life_ratio = self.age / self.max_age;
         if life_ratio < 0.3 {
             Color::White
         } else if life_ratio < 0.6 {
@@ -59,31 +95,51 @@ impl SmokeParticle {
 
 pub struct ChimneySmoke {
     particles: Vec<SmokeParticle>,
-    spawn_counter: u32,
-    spawn_rate: u32,
+    spawn_counter: f32,
+    spawn_rate: f32,
+    lifetime_scale: f32,
 }
 
 impl ChimneySmoke {
     pub fn new() -> Self {
         Self {
             particles: Vec::with_capacity(MAX_PARTICLES),
-            spawn_counter: 0,
+            spawn_counter: 0.0,
             spawn_rate: DEFAULT_SPAWN_RATE,
+            lifetime_scale: 1.0,
         }
     }
 
-    pub fn update(&mut self, chimney_x: u16, chimney_y: u16, rng: &mut (impl Rng + ?Sized)) {
+    /// Thickens the smoke as it gets colder outside.
+    pub fn set_temperature(&mut self, celsius: f64) {
+        if let Some((spawn_rate, lifetime_scale)) = smoke_profile(celsius) {
+            self.spawn_rate = spawn_rate;
+            self.lifetime_scale = lifetime_scale;
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        chimney_x: u16,
+        chimney_y: u16,
+        dt: f32,
+        rng: &mut (impl Rng + ?Sized),
+    ) {
         for particle in &mut self.particles {
-            particle.update();
+            particle.update(dt);
         }
 
         self.particles.retain(|p| p.is_alive() && p.y >= 0.0);
 
-        self.spawn_counter += 1;
+        self.spawn_counter += dt;
         if self.spawn_counter >= self.spawn_rate && self.particles.len() < MAX_PARTICLES {
-            self.spawn_counter = 0;
-            self.particles
-                .push(SmokeParticle::new(chimney_x, chimney_y, rng));
+            self.spawn_counter = 0.0;
+            self.particles.push(SmokeParticle::new(
+                chimney_x,
+                chimney_y,
+                self.lifetime_scale,
+                rng,
+            ));
         }
     }
 
@@ -93,7 +149,7 @@ impl ChimneySmoke {
             let y = particle.y as i16;
 
             if x >= 0 && y >= 0 {
-                let display_char = match particle.age {
+                let display_char = match particle.age as u32 {
                     0..=6 => 'o',
                     7..=14 => '.',
                     15..=25 => '~',
@@ -123,7 +179,10 @@ impl AnimationSystem for ChimneySmoke {
     }
 
     fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
-        !ctx.conditions.is_raining && !ctx.conditions.is_thunderstorm && ctx.chimney.is_some()
+        ctx.emission == HouseEmission::ChimneySmoke
+            && !ctx.conditions.is_raining
+            && !ctx.conditions.is_thunderstorm
+            && ctx.chimney.is_some()
     }
 
     fn on_resize(&mut self, _size: TerminalSize) {}
@@ -133,7 +192,10 @@ impl AnimationSystem for ChimneySmoke {
             return;
         };
 
-        self.update(chimney.x, chimney.y, rng);
+        if let Some(weather) = &ctx.state.current_weather {
+            self.set_temperature(weather.temperature);
+        }
+        self.update(chimney.x, chimney.y, ctx.dt, rng);
     }
 
     fn render(
@@ -148,3 +210,23 @@ impl AnimationSystem for ChimneySmoke {
         ChimneySmoke::render(self, renderer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smoke_thickens_as_it_gets_colder() {
+        let (cold_rate, cold_life) = smoke_profile(-10.0).unwrap();
+        let (mild_rate, mild_life) = smoke_profile(10.0).unwrap();
+        assert!(cold_rate < mild_rate);
+        assert!(cold_life > mild_life);
+        assert_eq!(smoke_profile(-25.0), smoke_profile(-10.0));
+    }
+
+    #[test]
+    fn test_no_smoke_above_comfort_threshold() {
+        assert!(smoke_profile(COMFORT_THRESHOLD).is_some());
+        assert!(smoke_profile(COMFORT_THRESHOLD + 0.5).is_none());
+    }
+}
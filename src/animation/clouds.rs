@@ -130,13 +130,14 @@ impl CloudSystem {
         terminal_height: u16,
         is_clear: bool,
         cloud_color: Color,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
         for cloud in &mut self.clouds {
-            cloud.x += cloud.speed + cloud.wind_x;
+            cloud.x += (cloud.speed + cloud.wind_x) * dt;
         }
 
         let width_f = terminal_width as f32;
@@ -159,7 +160,7 @@ impl CloudSystem {
 
         let spawn_chance = if is_clear { 0.002 } else { 0.005 };
 
-        if self.clouds.len() < max_clouds && rng.random::<f32>() < spawn_chance {
+        if self.clouds.len() < max_clouds && rng.random::<f32>() < spawn_chance * dt {
             let mut cloud =
                 Self::create_random_cloud(0.0, terminal_height, cloud_color, self.base_wind_x, rng);
             let cloud_width = cloud.shape.iter().map(|line| line.len()).max().unwrap_or(0) as f32;
@@ -221,11 +222,13 @@ impl AnimationSystem for CloudSystem {
     }
 
     fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
-        let is_clear = ctx
-            .state
-            .current_weather
-            .as_ref()
-            .is_some_and(|w| matches!(w.condition, crate::weather::WeatherCondition::Clear));
+        let is_clear = ctx.state.current_weather.as_ref().is_some_and(|w| {
+            matches!(
+                w.condition,
+                crate::weather::WeatherCondition::Clear
+                    | crate::weather::WeatherCondition::MainlyClear
+            )
+        });
 
         ctx.conditions.is_cloudy || is_clear
     }
@@ -242,7 +245,8 @@ impl AnimationSystem for CloudSystem {
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
         let (is_clear, cloud_color) = if let Some(weather) = &ctx.state.current_weather {
             match weather.condition {
-                crate::weather::WeatherCondition::Clear => (true, Color::White),
+                crate::weather::WeatherCondition::Clear
+                | crate::weather::WeatherCondition::MainlyClear => (true, Color::White),
                 crate::weather::WeatherCondition::PartlyCloudy => (false, Color::Grey),
                 _ => (false, Color::DarkGrey),
             }
@@ -251,7 +255,14 @@ impl AnimationSystem for CloudSystem {
         };
 
         self.set_cloud_color(is_clear);
-        self.update(ctx.size.width, ctx.size.height, is_clear, cloud_color, rng);
+        self.update(
+            ctx.size.width,
+            ctx.size.height,
+            is_clear,
+            cloud_color,
+            ctx.dt,
+            rng,
+        );
     }
 
     fn render(
@@ -39,11 +39,17 @@ impl Firefly {
         }
     }
 
-    fn update(&mut self, terminal_width: u16, horizon_y: u16, rng: &mut (impl Rng + ?Sized)) {
-        self.x += self.vx;
-        self.y += self.vy;
+    fn update(
+        &mut self,
+        terminal_width: u16,
+        horizon_y: u16,
+        dt: f32,
+        rng: &mut (impl Rng + ?Sized),
+    ) {
+        self.x += self.vx * dt;
+        self.y += self.vy * dt;
 
-        if rng.random::<f32>() < 0.02 {
+        if rng.random::<f32>() < 0.02 * dt {
             self.vx = (rng.random::<f32>() - 0.5) * 0.3;
             self.vy = (rng.random::<f32>() - 0.5) * 0.2;
         }
@@ -65,10 +71,8 @@ impl Firefly {
             self.vy = -self.vy.abs(); // Bounce up
         }
 
-        self.glow_phase += self.glow_speed;
-        if self.glow_phase > std::f32::consts::PI * 2.0 {
-            self.glow_phase -= std::f32::consts::PI * 2.0;
-        }
+        self.glow_phase += self.glow_speed * dt;
+        self.glow_phase %= std::f32::consts::PI * 2.0;
 
         let glow_value = (self.glow_phase.sin() + 1.0) / 2.0;
         self.brightness = (glow_value * 255.0) as u8;
@@ -133,17 +137,18 @@ impl FireflySystem {
         terminal_width: u16,
         terminal_height: u16,
         horizon_y: u16,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
         for firefly in &mut self.fireflies {
-            firefly.update(terminal_width, horizon_y, rng);
+            firefly.update(terminal_width, horizon_y, dt, rng);
         }
 
         let target_count = std::cmp::max(3, terminal_width / 15) as usize;
-        if self.fireflies.len() < target_count && rng.random::<f32>() < 0.01 {
+        if self.fireflies.len() < target_count && rng.random::<f32>() < 0.01 * dt {
             self.fireflies
                 .push(Firefly::new(terminal_width, horizon_y, rng));
         }
@@ -197,7 +202,7 @@ impl AnimationSystem for FireflySystem {
     }
 
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
-        self.update(ctx.size.width, ctx.size.height, ctx.horizon_y, rng);
+        self.update(ctx.size.width, ctx.size.height, ctx.horizon_y, ctx.dt, rng);
     }
 
     fn render(
@@ -0,0 +1,210 @@
+//! Fireworks over the house on the nights the yard is decorated for them:
+//! rockets climb from behind the rooftops and burst into sparks that droop
+//! and fade.
+
+use crate::animation::{AnimationSystem, FrameCommands, FrameContext, RenderLayer};
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+
+use rand::{Rng, RngExt};
+use std::io;
+
+/// Chance per frame of a rocket going up.
+const LAUNCH_RATE: f32 = 0.04;
+const MAX_ROCKETS: usize = 3;
+/// Rows per frame a rocket climbs by.
+const ROCKET_SPEED: f32 = 0.5;
+/// First sky row, below the HUD.
+const TOP_ROW: f32 = 2.0;
+const SPARKS_PER_BURST: usize = 16;
+/// Rows per frame a spark flies out at; twice as many columns, since cells
+/// are about twice as tall as wide.
+const SPARK_SPEED: f32 = 0.3;
+/// Rows per frame a spark's fall speeds up by each frame.
+const GRAVITY: f32 = 0.006;
+/// Frames a spark burns for.
+const SPARK_LIFE: f32 = 45.0;
+
+const COLORS: [Color; 5] = [
+    Color::Red,
+    Color::Yellow,
+    Color::Green,
+    Color::Magenta,
+    Color::Cyan,
+];
+
+struct Rocket {
+    x: f32,
+    y: f32,
+    burst_y: f32,
+    color: Color,
+}
+
+struct Spark {
+    x: f32,
+    y: f32,
+    vx: f32,
+    vy: f32,
+    life: f32,
+    color: Color,
+}
+
+impl Spark {
+    fn glyph(&self) -> char {
+        match self.life / SPARK_LIFE {
+            share if share > 0.6 => '*',
+            share if share > 0.3 => '+',
+            _ => '.',
+        }
+    }
+}
+
+pub struct FireworkSystem {
+    rockets: Vec<Rocket>,
+    sparks: Vec<Spark>,
+}
+
+impl FireworkSystem {
+    pub fn new() -> Self {
+        Self {
+            rockets: Vec::new(),
+            sparks: Vec::new(),
+        }
+    }
+
+    /// `launch_rate` is the chance per frame of another rocket, 0 once the
+    /// show is over.
+    fn update(
+        &mut self,
+        launch_rate: f32,
+        terminal_width: u16,
+        horizon_y: u16,
+        dt: f32,
+        rng: &mut (impl Rng + ?Sized),
+    ) {
+        let mut bursts = Vec::new();
+        self.rockets.retain_mut(|rocket| {
+            rocket.y -= ROCKET_SPEED * dt;
+            if rocket.y > rocket.burst_y {
+                return true;
+            }
+            bursts.push((rocket.x, rocket.burst_y, rocket.color));
+            false
+        });
+        for (x, y, color) in bursts {
+            for i in 0..SPARKS_PER_BURST {
+                let angle = i as f32 / SPARKS_PER_BURST as f32 * std::f32::consts::TAU;
+                let speed = SPARK_SPEED * (0.7 + 0.3 * rng.random::<f32>());
+                self.sparks.push(Spark {
+                    x,
+                    y,
+                    vx: angle.cos() * speed * 2.0,
+                    vy: angle.sin() * speed,
+                    life: SPARK_LIFE,
+                    color,
+                });
+            }
+        }
+
+        let width = terminal_width as f32;
+        let bottom = horizon_y as f32;
+        for spark in &mut self.sparks {
+            spark.x += spark.vx * dt;
+            spark.y += spark.vy * dt;
+            spark.vx *= 0.96f32.powf(dt);
+            spark.vy += GRAVITY * dt;
+            spark.life -= dt;
+        }
+        self.sparks.retain(|spark| {
+            spark.life > 0.0 && (0.0..width).contains(&spark.x) && spark.y < bottom
+        });
+
+        let room = horizon_y as f32 - TOP_ROW;
+        if self.rockets.len() < MAX_ROCKETS && room > 8.0 && rng.random::<f32>() < launch_rate * dt
+        {
+            self.rockets.push(Rocket {
+                x: width * (0.1 + 0.8 * rng.random::<f32>()),
+                y: bottom,
+                // Bursting in the upper half of the sky
+                burst_y: TOP_ROW + 2.0 + rng.random::<f32>() * (room / 2.0 - 2.0),
+                color: COLORS[rng.random_range(0..COLORS.len())],
+            });
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        for rocket in &self.rockets {
+            let (x, y) = (rocket.x as u16, rocket.y as u16);
+            renderer.render_char(x, y, '|', Color::Yellow)?;
+            renderer.render_char(x, y + 1, '.', Color::DarkGrey)?;
+        }
+        for spark in &self.sparks {
+            if spark.y >= TOP_ROW {
+                renderer.render_char(spark.x as u16, spark.y as u16, spark.glyph(), spark.color)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for FireworkSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationSystem for FireworkSystem {
+    fn id(&self) -> &'static str {
+        "fireworks"
+    }
+
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Background
+    }
+
+    /// Stays on while the last sparks burn out.
+    fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
+        ctx.state.holiday.fireworks || !self.rockets.is_empty() || !self.sparks.is_empty()
+    }
+
+    fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
+        let launch_rate = if ctx.state.holiday.fireworks {
+            LAUNCH_RATE * ctx.particle_density
+        } else {
+            0.0
+        };
+        self.update(launch_rate, ctx.size.width, ctx.horizon_y, ctx.dt, rng);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        _ctx: &FrameContext<'_>,
+    ) -> io::Result<()> {
+        FireworkSystem::render(self, renderer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rockets_burst_and_burn_out() {
+        let mut fireworks = FireworkSystem::new();
+        let mut rng = rand::rng();
+        let mut burst = false;
+        for _ in 0..600 {
+            fireworks.update(LAUNCH_RATE, 120, 30, 1.0, &mut rng);
+            assert!(fireworks.rockets.len() <= MAX_ROCKETS);
+            assert!(fireworks.sparks.iter().all(|spark| spark.y < 30.0));
+            burst |= !fireworks.sparks.is_empty();
+        }
+        assert!(burst);
+
+        for _ in 0..200 {
+            fireworks.update(0.0, 120, 30, 1.0, &mut rng);
+        }
+        assert!(fireworks.rockets.is_empty() && fireworks.sparks.is_empty());
+    }
+}
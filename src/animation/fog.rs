@@ -1,3 +1,4 @@
+use crate::animation::ramp::{Ramp, interpolate};
 use crate::animation::{AnimationSystem, FrameCommands, FrameContext, RenderLayer, TerminalSize};
 use crate::render::TerminalRenderer;
 use crate::weather::types::FogIntensity;
@@ -7,14 +8,26 @@ use rand::{Rng, RngExt};
 use std::collections::VecDeque;
 use std::io;
 
+/// Frames between spawns of a pair of wisps, by fog level.
+const SPAWN_DELAYS: [(f32, f32); 3] = [(0.3, 4.0), (0.6, 2.0), (1.0, 1.0)];
+
+/// Wisps per terminal column at each intensity.
+fn fog_level(intensity: FogIntensity) -> f32 {
+    match intensity {
+        FogIntensity::Light => 0.3,
+        FogIntensity::Medium => 0.6,
+        FogIntensity::Heavy => 1.0,
+    }
+}
+
 struct FogWisp {
     x: f32,
     y: f32,
     speed_x: f32,
     character: char,
     color: Color,
-    lifetime: u32,
-    max_lifetime: u32,
+    lifetime: f32,
+    max_lifetime: f32,
 }
 
 impl FogWisp {
@@ -45,14 +58,14 @@ impl FogWisp {
             speed_x: (rng.random::<f32>() - 0.5) * 0.15,
             character: chars[char_idx],
             color: colors[color_idx],
-            lifetime: 0,
-            max_lifetime: 100 + (rng.random::<u32>() % 200),
+            lifetime: 0.0,
+            max_lifetime: (100 + (rng.random::<u32>() % 200)) as f32,
         }
     }
 
-    fn update(&mut self) {
-        self.x += self.speed_x;
-        self.lifetime += 1;
+    fn update(&mut self, dt: f32) {
+        self.x += self.speed_x * dt;
+        self.lifetime += dt;
     }
 
     fn is_alive(&self, terminal_width: u16) -> bool {
@@ -67,7 +80,9 @@ pub struct FogSystem {
     terminal_width: u16,
     terminal_height: u16,
     intensity: FogIntensity,
-    spawn_timer: u32,
+    /// Wisp count easing towards `intensity`'s, or to nothing as fog lifts.
+    level: Ramp,
+    spawn_timer: f32,
 }
 
 impl AnimationSystem for FogSystem {
@@ -79,8 +94,9 @@ impl AnimationSystem for FogSystem {
         RenderLayer::Foreground
     }
 
+    /// Stays on after the fog lifts until the last wisp has drifted off.
     fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
-        ctx.conditions.is_foggy
+        ctx.conditions.is_foggy || !self.wisps.is_empty()
     }
 
     fn on_resize(&mut self, size: TerminalSize) {
@@ -95,7 +111,13 @@ impl AnimationSystem for FogSystem {
     }
 
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
-        self.update(ctx.size.width, ctx.size.height, rng);
+        let target = if ctx.conditions.is_foggy {
+            fog_level(self.intensity)
+        } else {
+            0.0
+        };
+        self.level.set_target(target);
+        self.update(ctx.size.width, ctx.size.height, ctx.dt, rng);
     }
 
     fn render(
@@ -109,50 +131,47 @@ impl AnimationSystem for FogSystem {
 
 impl FogSystem {
     pub fn new(terminal_width: u16, terminal_height: u16, intensity: FogIntensity) -> Self {
-        let wisps_capacity = match intensity {
-            FogIntensity::Light => (terminal_width as f32 * 0.3) as usize,
-            FogIntensity::Medium => (terminal_width as f32 * 0.6) as usize,
-            FogIntensity::Heavy => terminal_width as usize,
-        };
+        let wisps_capacity = (terminal_width as f32 * fog_level(intensity)) as usize;
 
         Self {
             wisps: VecDeque::with_capacity(wisps_capacity),
             terminal_width,
             terminal_height,
             intensity,
-            spawn_timer: 0,
+            // Fog rolls in from nothing
+            level: Ramp::new(0.0),
+            spawn_timer: 0.0,
         }
     }
 
     pub fn set_intensity(&mut self, intensity: FogIntensity) {
         self.intensity = intensity;
+        self.level.set_target(fog_level(intensity));
     }
 
     pub fn update(
         &mut self,
         terminal_width: u16,
         terminal_height: u16,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
         for wisp in &mut self.wisps {
-            wisp.update();
+            wisp.update(dt);
         }
 
         self.wisps.retain(|w| w.is_alive(terminal_width));
 
-        let (target_multiplier, spawn_delay) = match self.intensity {
-            FogIntensity::Light => (0.3, 4),
-            FogIntensity::Medium => (0.6, 2),
-            FogIntensity::Heavy => (1.0, 1),
-        };
-        let target_count = (terminal_width as f32 * target_multiplier) as usize;
+        let level = self.level.update(dt);
+        let spawn_delay = interpolate(&SPAWN_DELAYS, level);
+        let target_count = (terminal_width as f32 * level) as usize;
 
-        self.spawn_timer += 1;
+        self.spawn_timer += dt;
         if self.spawn_timer >= spawn_delay && self.wisps.len() < target_count {
-            self.spawn_timer = 0;
+            self.spawn_timer = 0.0;
             for _ in 0..2 {
                 if self.wisps.len() < target_count {
                     self.wisps
@@ -175,3 +194,24 @@ impl FogSystem {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fog_rolls_in_gradually() {
+        let mut fog = FogSystem::new(100, 40, FogIntensity::Heavy);
+        fog.set_intensity(FogIntensity::Heavy);
+        let mut rng = rand::rng();
+        for _ in 0..30 {
+            fog.update(100, 40, 1.0, &mut rng);
+        }
+        assert!(fog.wisps.len() <= 10);
+
+        for _ in 0..300 {
+            fog.update(100, 40, 1.0, &mut rng);
+        }
+        assert!(fog.wisps.len() > 50);
+    }
+}
@@ -0,0 +1,93 @@
+//! Brief bursts of stronger wind, for systems whose particles drift with it.
+
+use rand::{Rng, RngExt};
+
+/// Frames a gust lasts, about a second and a half at 30 fps.
+const GUST_FRAMES: f32 = 45.0;
+/// Average frames between gusts, about eight seconds.
+const GUST_INTERVAL: f32 = 240.0;
+/// Cap on how many times the mean wind a gust blows, so a gust reported
+/// on an almost still day doesn't fling everything off screen.
+const MAX_GUST_FACTOR: f32 = 3.0;
+
+/// How strongly the wind blows from moment to moment against its mean,
+/// given the reported gust speed.
+#[derive(Debug, Clone, Copy)]
+pub struct Gusts {
+    /// Gust speed over mean speed; 1.0 without gusts.
+    factor: f32,
+    until_next: f32,
+    /// Frames left in the current gust.
+    remaining: f32,
+}
+
+impl Gusts {
+    pub fn new() -> Self {
+        Self {
+            factor: 1.0,
+            until_next: GUST_INTERVAL,
+            remaining: 0.0,
+        }
+    }
+
+    pub fn set(&mut self, speed_kmh: f32, gust_kmh: Option<f32>) {
+        self.factor = match gust_kmh {
+            Some(gust) if speed_kmh > 0.0 => (gust / speed_kmh).clamp(1.0, MAX_GUST_FACTOR),
+            _ => 1.0,
+        };
+    }
+
+    /// Advances by `dt` frames and returns the multiplier for the mean
+    /// wind: 1.0 between gusts, rising to the gust factor and back.
+    pub fn update(&mut self, dt: f32, rng: &mut (impl Rng + ?Sized)) -> f32 {
+        if self.factor <= 1.0 {
+            return 1.0;
+        }
+        if self.remaining > 0.0 {
+            self.remaining = (self.remaining - dt).max(0.0);
+            let progress = 1.0 - self.remaining / GUST_FRAMES;
+            return 1.0 + (self.factor - 1.0) * (progress * std::f32::consts::PI).sin();
+        }
+
+        self.until_next -= dt;
+        if self.until_next <= 0.0 {
+            self.remaining = GUST_FRAMES;
+            self.until_next = GUST_INTERVAL * (0.5 + rng.random::<f32>());
+        }
+        1.0
+    }
+}
+
+impl Default for Gusts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gusts_come_and_go() {
+        let mut gusts = Gusts::new();
+        gusts.set(20.0, Some(40.0));
+        let mut rng = rand::rng();
+
+        let strengths: Vec<f32> = (0..1000).map(|_| gusts.update(1.0, &mut rng)).collect();
+        let peak = strengths.iter().copied().fold(0.0, f32::max);
+        assert!(peak > 1.9 && peak <= 2.0);
+        assert!(strengths.iter().filter(|&&s| s == 1.0).count() > 500);
+    }
+
+    #[test]
+    fn test_no_gusts_without_gust_speed() {
+        let mut gusts = Gusts::new();
+        let mut rng = rand::rng();
+        gusts.set(20.0, None);
+        assert!((0..1000).all(|_| gusts.update(1.0, &mut rng) == 1.0));
+
+        gusts.set(0.0, Some(30.0));
+        assert!((0..1000).all(|_| gusts.update(1.0, &mut rng) == 1.0));
+    }
+}
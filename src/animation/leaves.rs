@@ -86,16 +86,14 @@ impl Leaf {
         }
     }
 
-    fn update(&mut self) {
-        self.y += self.fall_speed;
+    fn update(&mut self, dt: f32) {
+        self.y += self.fall_speed * dt;
 
-        self.sway_phase += self.sway_speed;
-        if self.sway_phase > std::f32::consts::PI * 2.0 {
-            self.sway_phase -= std::f32::consts::PI * 2.0;
-        }
+        self.sway_phase += self.sway_speed * dt;
+        self.sway_phase %= std::f32::consts::PI * 2.0;
 
         let sway_offset = self.sway_phase.sin() * self.sway_amplitude;
-        self.x += sway_offset * 0.1;
+        self.x += sway_offset * 0.1 * dt;
 
         self.rotation = ((self.sway_phase * 2.0).sin() * 4.0) as u8;
     }
@@ -128,8 +126,8 @@ impl Leaf {
 
 pub struct FallingLeaves {
     leaves: Vec<Leaf>,
-    spawn_counter: u32,
-    spawn_rate: u32,
+    spawn_counter: f32,
+    spawn_rate: f32,
     terminal_width: u16,
     terminal_height: u16,
 }
@@ -148,8 +146,8 @@ impl FallingLeaves {
 
         Self {
             leaves,
-            spawn_counter: 0,
-            spawn_rate: 15,
+            spawn_counter: 0.0,
+            spawn_rate: 15.0,
             terminal_width,
             terminal_height,
         }
@@ -159,20 +157,21 @@ impl FallingLeaves {
         &mut self,
         terminal_width: u16,
         terminal_height: u16,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
         for leaf in &mut self.leaves {
-            leaf.update();
+            leaf.update(dt);
         }
 
         self.leaves.retain(|l| !l.is_offscreen(terminal_height));
 
-        self.spawn_counter += 1;
+        self.spawn_counter += dt;
         if self.spawn_counter >= self.spawn_rate {
-            self.spawn_counter = 0;
+            self.spawn_counter = 0.0;
             if rng.random::<f32>() < 0.7 {
                 self.leaves
                     .push(Leaf::new(terminal_width, terminal_height, true, rng));
@@ -223,7 +222,7 @@ impl AnimationSystem for FallingLeaves {
     }
 
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
-        self.update(ctx.size.width, ctx.size.height, rng);
+        self.update(ctx.size.width, ctx.size.height, ctx.dt, rng);
     }
 
     fn render(
@@ -0,0 +1,172 @@
+//! A thin band of mist along the ground and dew glinting on the grass, for
+//! clear, calm and humid mornings around sunrise.
+
+use crate::animation::ramp::Ramp;
+use crate::animation::{AnimationSystem, FrameCommands, FrameContext, RenderLayer, TerminalSize};
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+
+use rand::{Rng, RngExt};
+use std::io;
+
+/// Rows of mist above the ground, lowest first, with the glyph each uses.
+const BAND: [char; 2] = ['~', '-'];
+/// Columns per frame the mist drifts by.
+const DRIFT_SPEED: f32 = 0.02;
+/// Chance per column and frame of a drop catching the light, at full mist.
+const DEW_RATE: f32 = 0.002;
+/// Frames a drop glints for.
+const DEW_FRAMES: f32 = 40.0;
+
+const MIST_COLOR: Color = Color::Rgb {
+    r: 200,
+    g: 205,
+    b: 215,
+};
+const DEW_COLOR: Color = Color::Rgb {
+    r: 210,
+    g: 235,
+    b: 255,
+};
+
+struct Dewdrop {
+    x: u16,
+    age: f32,
+}
+
+pub struct MorningMistSystem {
+    terminal_width: u16,
+    /// Easing towards the state's mist, so it burns off gradually.
+    level: Ramp,
+    drift: f32,
+    dew: Vec<Dewdrop>,
+}
+
+impl MorningMistSystem {
+    pub fn new(terminal_width: u16) -> Self {
+        Self {
+            terminal_width,
+            level: Ramp::new(0.0),
+            drift: 0.0,
+            dew: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, target: f32, terminal_width: u16, dt: f32, rng: &mut (impl Rng + ?Sized)) {
+        self.terminal_width = terminal_width;
+        self.level.set_target(target);
+        let level = self.level.update(dt);
+        self.drift += DRIFT_SPEED * dt;
+
+        for drop in &mut self.dew {
+            drop.age += dt;
+        }
+        self.dew
+            .retain(|drop| drop.age < DEW_FRAMES && drop.x < terminal_width);
+
+        for x in 0..terminal_width {
+            if rng.random::<f32>() < DEW_RATE * level * dt {
+                self.dew.push(Dewdrop { x, age: 0.0 });
+            }
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer, horizon_y: u16) -> io::Result<()> {
+        let level = self.level.current();
+        for (row, &ch) in BAND.iter().enumerate() {
+            let Some(y) = horizon_y.checked_sub(row as u16 + 1) else {
+                break;
+            };
+            for x in 0..self.terminal_width {
+                if is_misty(x, row, self.drift, level) {
+                    renderer.render_char(x, y, ch, MIST_COLOR)?;
+                }
+            }
+        }
+
+        for drop in &self.dew {
+            // A bright glint that fades to a speck
+            let ch = if drop.age < DEW_FRAMES / 3.0 {
+                '*'
+            } else {
+                '.'
+            };
+            renderer.render_char(drop.x, horizon_y, ch, DEW_COLOR)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether column `x` of band row `row` shows mist. Patches drift slowly
+/// and shrink as `level` falls; the upper row is always the thinner.
+fn is_misty(x: u16, row: usize, drift: f32, level: f32) -> bool {
+    let x = x as f32 - drift;
+    let patchiness = ((x * 0.21).sin() + (x * 0.057 + row as f32).sin()) / 4.0 + 0.5;
+    let thickness = level * if row == 0 { 0.8 } else { 0.45 };
+    patchiness < thickness
+}
+
+impl AnimationSystem for MorningMistSystem {
+    fn id(&self) -> &'static str {
+        "morning_mist"
+    }
+
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Foreground
+    }
+
+    /// Stays on while the mist burns off and the last drops fade.
+    fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
+        ctx.morning_mist > 0.0 || self.level.current() > 0.0 || !self.dew.is_empty()
+    }
+
+    fn on_resize(&mut self, size: TerminalSize) {
+        self.terminal_width = size.width;
+        self.dew.retain(|drop| drop.x < size.width);
+    }
+
+    fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
+        self.update(ctx.morning_mist, ctx.size.width, ctx.dt, rng);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        ctx: &FrameContext<'_>,
+    ) -> io::Result<()> {
+        MorningMistSystem::render(self, renderer, ctx.horizon_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coverage(row: usize, level: f32) -> usize {
+        (0..200).filter(|&x| is_misty(x, row, 0.0, level)).count()
+    }
+
+    #[test]
+    fn test_mist_thins_as_it_burns_off() {
+        assert_eq!(coverage(0, 0.0), 0);
+        assert!(coverage(0, 0.4) < coverage(0, 1.0));
+        assert!(coverage(1, 1.0) < coverage(0, 1.0));
+        assert!(coverage(0, 1.0) < 200);
+    }
+
+    #[test]
+    fn test_dew_gathers_then_fades() {
+        let mut mist = MorningMistSystem::new(100);
+        let mut rng = rand::rng();
+        for _ in 0..600 {
+            mist.update(1.0, 100, 1.0, &mut rng);
+        }
+        assert!(!mist.dew.is_empty());
+
+        for _ in 0..600 {
+            mist.update(0.0, 100, 1.0, &mut rng);
+        }
+        assert_eq!(mist.level.current(), 0.0);
+        assert!(mist.dew.is_empty());
+    }
+}
@@ -1,20 +1,29 @@
+pub mod ac_unit;
 pub mod airplanes;
+pub mod aurora;
 pub mod birds;
 pub mod chimney;
 pub mod clouds;
 pub mod fireflies;
+pub mod fireworks;
 pub mod fog;
+pub mod gusts;
 pub mod leaves;
+pub mod mist;
 pub mod moon;
+pub mod motes;
 pub mod raindrops;
+pub mod ramp;
 pub mod snow;
+pub mod snow_plow;
 pub mod stars;
 pub mod sunny;
 pub mod system;
 pub mod thunderstorm;
 
 pub use system::{
-    AnimationSystem, ChimneyPosition, FrameCommands, FrameContext, RenderLayer, TerminalSize, Wind,
+    AnimationSystem, ChimneyPosition, FrameCommands, FrameContext, HouseEmission, RenderLayer,
+    TerminalSize, Wind,
 };
 
 use crate::render::TerminalRenderer;
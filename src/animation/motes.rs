@@ -0,0 +1,236 @@
+//! Pollen and ash drifting through the air when the counts are high: sparse
+//! specks that ride the wind, unlike fog, which hides the view.
+
+use crate::animation::ramp::Ramp;
+use crate::animation::{
+    AnimationSystem, FrameCommands, FrameContext, RenderLayer, TerminalSize, Wind,
+};
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+
+use rand::{Rng, RngExt};
+use std::io;
+
+/// Columns per mote at full level.
+const COLUMNS_PER_MOTE: f32 = 6.0;
+/// Chance per frame of a missing mote drifting in, so they gather slowly.
+const SPAWN_RATE: f32 = 0.2;
+/// Rows per frame ash settles by.
+const ASH_FALL_SPEED: f32 = 0.015;
+/// Rows pollen bobs up and down by.
+const POLLEN_BOB: f32 = 0.6;
+/// Radians per frame of a mote's bob and twinkle.
+const PHASE_SPEED: f32 = 0.05;
+
+const POLLEN_COLOR: Color = Color::Rgb {
+    r: 232,
+    g: 200,
+    b: 64,
+};
+const ASH_COLOR: Color = Color::Rgb {
+    r: 150,
+    g: 145,
+    b: 140,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Pollen,
+    Ash,
+}
+
+struct Mote {
+    kind: Kind,
+    x: f32,
+    y: f32,
+    /// Own share of the drift, so motes don't move in step.
+    drift: f32,
+    phase: f32,
+}
+
+impl Mote {
+    fn glyph(&self) -> (char, Color) {
+        match self.kind {
+            Kind::Pollen if self.phase.sin() > 0.6 => ('°', POLLEN_COLOR),
+            Kind::Pollen => ('.', POLLEN_COLOR),
+            Kind::Ash if self.phase.cos() > 0.3 => (',', ASH_COLOR),
+            Kind::Ash => ('.', ASH_COLOR),
+        }
+    }
+}
+
+pub struct MoteSystem {
+    motes: Vec<Mote>,
+    pollen: Ramp,
+    ash: Ramp,
+    /// Columns per frame the wind carries motes by.
+    wind_x: f32,
+}
+
+impl MoteSystem {
+    pub fn new() -> Self {
+        Self {
+            motes: Vec::new(),
+            pollen: Ramp::new(0.0),
+            ash: Ramp::new(0.0),
+            wind_x: 0.05,
+        }
+    }
+
+    pub fn set_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
+        self.wind_x = -direction_deg.to_radians().sin() * speed_kmh / 40.0;
+    }
+
+    /// Eases towards `pollen` and `ash`, levels from 0 to 1 already scaled
+    /// by the particle density.
+    fn update(
+        &mut self,
+        pollen: f32,
+        ash: f32,
+        terminal_width: u16,
+        horizon_y: u16,
+        dt: f32,
+        rng: &mut (impl Rng + ?Sized),
+    ) {
+        self.pollen.set_target(pollen);
+        self.ash.set_target(ash);
+        let levels = [
+            (Kind::Pollen, self.pollen.update(dt)),
+            (Kind::Ash, self.ash.update(dt)),
+        ];
+
+        let width = terminal_width as f32;
+        let top = 1.0;
+        let bottom = horizon_y.saturating_sub(1) as f32;
+        for mote in &mut self.motes {
+            mote.phase += PHASE_SPEED * dt;
+            mote.x = (mote.x + (self.wind_x + mote.drift) * dt).rem_euclid(width.max(1.0));
+            match mote.kind {
+                Kind::Pollen => {
+                    let bob = mote.phase.cos() * POLLEN_BOB * PHASE_SPEED * dt;
+                    mote.y = (mote.y + bob).clamp(top, bottom.max(top));
+                }
+                Kind::Ash => {
+                    mote.y += ASH_FALL_SPEED * dt;
+                    // Settled ash is replaced by more from above
+                    if mote.y > bottom {
+                        mote.y = top;
+                    }
+                }
+            }
+        }
+
+        for (kind, level) in levels {
+            let target = (width / COLUMNS_PER_MOTE * level) as usize;
+            let count = self.motes.iter().filter(|mote| mote.kind == kind).count();
+            if count > target {
+                if let Some(i) = self.motes.iter().position(|mote| mote.kind == kind) {
+                    self.motes.swap_remove(i);
+                }
+            } else if count < target && bottom > top && rng.random::<f32>() < SPAWN_RATE * dt {
+                self.motes.push(Mote {
+                    kind,
+                    x: rng.random::<f32>() * width,
+                    y: top + rng.random::<f32>() * (bottom - top),
+                    drift: (rng.random::<f32>() - 0.5) * 0.04,
+                    phase: rng.random::<f32>() * std::f32::consts::TAU,
+                });
+            }
+        }
+    }
+
+    fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        for mote in &self.motes {
+            let (ch, color) = mote.glyph();
+            renderer.render_char(mote.x as u16, mote.y as u16, ch, color)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for MoteSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationSystem for MoteSystem {
+    fn id(&self) -> &'static str {
+        "motes"
+    }
+
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::Foreground
+    }
+
+    /// Stays on while the last motes thin out.
+    fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
+        ctx.state.air_quality.is_some() || !self.motes.is_empty()
+    }
+
+    fn on_resize(&mut self, size: TerminalSize) {
+        self.motes.retain(|mote| mote.x < size.width as f32);
+    }
+
+    fn on_wind(&mut self, wind: Wind) {
+        self.set_wind(wind.speed_kmh, wind.direction_deg);
+    }
+
+    fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
+        let air = ctx.state.air_quality;
+        // Rain and snow wash the pollen out of the air
+        let washed = ctx.conditions.is_raining || ctx.conditions.is_snowing;
+        let pollen = match air {
+            Some(air) if !washed => air.pollen_level(),
+            _ => 0.0,
+        };
+        let ash = air.map_or(0.0, |air| air.smoke_level());
+        self.update(
+            pollen * ctx.particle_density,
+            ash * ctx.particle_density,
+            ctx.size.width,
+            ctx.horizon_y,
+            ctx.dt,
+            rng,
+        );
+    }
+
+    fn render(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        _ctx: &FrameContext<'_>,
+    ) -> io::Result<()> {
+        MoteSystem::render(self, renderer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(motes: &MoteSystem, kind: Kind) -> usize {
+        motes.motes.iter().filter(|mote| mote.kind == kind).count()
+    }
+
+    #[test]
+    fn test_motes_gather_and_clear() {
+        let mut motes = MoteSystem::new();
+        let mut rng = rand::rng();
+        for _ in 0..600 {
+            motes.update(1.0, 0.5, 120, 20, 1.0, &mut rng);
+        }
+        assert_eq!(count(&motes, Kind::Pollen), 20);
+        assert_eq!(count(&motes, Kind::Ash), 10);
+        assert!(
+            motes
+                .motes
+                .iter()
+                .all(|mote| mote.y >= 1.0 && mote.y <= 19.0)
+        );
+
+        for _ in 0..600 {
+            motes.update(0.0, 0.0, 120, 20, 1.0, &mut rng);
+        }
+        assert!(motes.motes.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::animation::gusts::Gusts;
+use crate::animation::ramp::{Ramp, interpolate};
 use crate::animation::{
     AnimationSystem, FrameCommands, FrameContext, RenderLayer, TerminalSize, Wind,
 };
@@ -10,12 +12,30 @@ use std::collections::VecDeque;
 use std::io;
 
 const MAX_SPLASHES: usize = 100;
+/// Minutes before nowcast rain at which a few early drops start to fall.
+const PREFADE_MINUTES: f32 = 15.0;
+/// Share of the usual drops falling just before nowcast rain arrives.
+const PREFADE_MAX_DENSITY: f32 = 0.3;
+/// Fall speed of near and far drops by intensity level.
+const NEAR_SPEEDS: [(f32, f32); 4] = [(0.25, 0.4), (0.5, 0.7), (1.0, 0.9), (1.5, 1.8)];
+const FAR_SPEEDS: [(f32, f32); 4] = [(0.25, 0.2), (0.5, 0.4), (1.0, 0.6), (1.5, 1.2)];
+
+/// Drops per terminal column at each intensity.
+fn rain_level(intensity: RainIntensity) -> f32 {
+    match intensity {
+        RainIntensity::Drizzle => 0.25,
+        RainIntensity::Light => 0.5,
+        RainIntensity::Heavy => 1.0,
+        RainIntensity::Storm => 1.5,
+    }
+}
 
 struct Raindrop {
     x: f32,
     y: f32,
     speed_y: f32,
-    speed_x: f32,
+    /// Per-drop jitter added on top of the shared wind drift.
+    drift: f32,
     character: char,
     color: Color,
     z_index: u8,
@@ -25,8 +45,8 @@ struct Raindrop {
 struct Splash {
     x: u16,
     y: u16,
-    timer: u8,
-    max_timer: u8,
+    timer: f32,
+    max_timer: f32,
 }
 
 pub struct RaindropSystem {
@@ -36,17 +56,40 @@ pub struct RaindropSystem {
     terminal_width: u16,
     terminal_height: u16,
     intensity: RainIntensity,
+    /// Drop count and speed easing towards `intensity`'s.
+    level: Ramp,
     wind_x: f32,
+    has_wind_data: bool,
+    gusts: Gusts,
+    /// Multiplier on `wind_x` from the gust blowing this frame.
+    gust: f32,
+    /// Fraction of the intensity's drop count to keep falling; below 1.0
+    /// while rain is only on its way.
+    density: f32,
+    /// Fraction of a drop owed from earlier frames, so slow spawn rates
+    /// and short frames still add up to `spawn_rate` a frame.
+    spawn_carry: f32,
+}
+
+/// Horizontal-to-vertical speed ratio beyond which a streak is drawn slanted.
+const SLANT_THRESHOLD: f32 = 0.3;
+
+/// Picks a streak character matching the fall trajectory: `\` when drifting
+/// right, `/` when drifting left, otherwise the drop's own character.
+fn slant_char(speed_x: f32, speed_y: f32, upright: char) -> char {
+    let ratio = speed_x / speed_y.max(f32::EPSILON);
+    if ratio > SLANT_THRESHOLD {
+        '\\'
+    } else if ratio < -SLANT_THRESHOLD {
+        '/'
+    } else {
+        upright
+    }
 }
 
 impl RaindropSystem {
     pub fn new(terminal_width: u16, terminal_height: u16, intensity: RainIntensity) -> Self {
-        let drops_capacity = match intensity {
-            RainIntensity::Drizzle => (terminal_width / 4) as usize,
-            RainIntensity::Light => (terminal_width / 2) as usize,
-            RainIntensity::Heavy => terminal_width as usize,
-            RainIntensity::Storm => (terminal_width as f32 * 1.5) as usize,
-        };
+        let drops_capacity = (terminal_width as f32 * rain_level(intensity)) as usize;
 
         let mut system = Self {
             drops: Vec::with_capacity(drops_capacity),
@@ -55,7 +98,13 @@ impl RaindropSystem {
             terminal_width,
             terminal_height,
             intensity,
+            level: Ramp::new(rain_level(intensity)),
             wind_x: 0.0,
+            has_wind_data: false,
+            gusts: Gusts::new(),
+            gust: 1.0,
+            density: 1.0,
+            spawn_carry: 0.0,
         };
         let wind_dir = if rand::random::<bool>() { 1.0 } else { -1.0 };
         system.set_intensity_with_dir(intensity, wind_dir);
@@ -69,6 +118,11 @@ impl RaindropSystem {
 
     pub fn set_intensity_with_dir(&mut self, intensity: RainIntensity, direction_multiplier: f32) {
         self.intensity = intensity;
+        self.level.set_target(rain_level(intensity));
+        if self.has_wind_data {
+            // Real wind takes precedence over the per-intensity default drift.
+            return;
+        }
         let base_wind = match intensity {
             RainIntensity::Drizzle => 0.05,
             RainIntensity::Light => 0.1,
@@ -83,6 +137,7 @@ impl RaindropSystem {
         let direction_rad = direction_deg.to_radians();
         let x_component = -direction_rad.sin();
         self.wind_x = speed_factor * x_component;
+        self.has_wind_data = true;
     }
 
     fn spawn_drop(&mut self, rng: &mut (impl Rng + ?Sized)) {
@@ -90,9 +145,14 @@ impl RaindropSystem {
         let x = (rng.random::<u32>() % span) as f32 - (self.terminal_width as f32 * 0.5);
         let z_index = if rng.random::<bool>() { 1 } else { 0 };
 
-        let (speed_y, chars, color) = match self.intensity {
+        let speeds = if z_index == 1 {
+            &NEAR_SPEEDS
+        } else {
+            &FAR_SPEEDS
+        };
+        let speed_y = interpolate(speeds, self.level.current());
+        let (chars, color) = match self.intensity {
             RainIntensity::Drizzle => (
-                if z_index == 1 { 0.4 } else { 0.2 },
                 vec!['.', ','],
                 if z_index == 1 {
                     Color::Cyan
@@ -101,7 +161,6 @@ impl RaindropSystem {
                 },
             ),
             RainIntensity::Light => (
-                if z_index == 1 { 0.7 } else { 0.4 },
                 vec!['|', ':', '.'],
                 if z_index == 1 {
                     Color::White
@@ -110,8 +169,7 @@ impl RaindropSystem {
                 },
             ),
             RainIntensity::Heavy => (
-                if z_index == 1 { 0.9 } else { 0.6 }, // Slightly faster than Light
-                vec!['|', ':'],                       // Vertical density
+                vec!['|', ':'], // Vertical density
                 if z_index == 1 {
                     Color::Cyan
                 } else {
@@ -119,13 +177,7 @@ impl RaindropSystem {
                 },
             ),
             RainIntensity::Storm => (
-                if z_index == 1 { 1.8 } else { 1.2 },
-                // Use slant matching wind direction
-                if self.wind_x > 0.0 {
-                    vec!['\\']
-                } else {
-                    vec!['/']
-                },
+                vec!['|'],
                 if z_index == 1 {
                     Color::White
                 } else {
@@ -140,7 +192,7 @@ impl RaindropSystem {
             x,
             y: 0.0,
             speed_y: speed_y + (rng.random::<f32>() * 0.2),
-            speed_x: self.wind_x + (rng.random::<f32>() * 0.1 - 0.05),
+            drift: rng.random::<f32>() * 0.1 - 0.05,
             character: chars[char_idx],
             color,
             z_index,
@@ -151,17 +203,18 @@ impl RaindropSystem {
         &mut self,
         terminal_width: u16,
         terminal_height: u16,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
-        let target_count = match self.intensity {
-            RainIntensity::Drizzle => (terminal_width / 4) as usize,
-            RainIntensity::Light => (terminal_width / 2) as usize,
-            RainIntensity::Heavy => terminal_width as usize,
-            RainIntensity::Storm => (terminal_width as f32 * 1.5) as usize,
-        };
+        // Nothing on screen to ease away from
+        if self.drops.is_empty() {
+            self.level.snap();
+        }
+        let level = self.level.update(dt);
+        let target_count = (terminal_width as f32 * level * self.density) as usize;
 
         if self.drops.len() < target_count {
             let spawn_rate = match self.intensity {
@@ -169,9 +222,14 @@ impl RaindropSystem {
                 RainIntensity::Light => 2,
                 _ => 5,
             };
-            for _ in 0..spawn_rate {
+            self.spawn_carry += spawn_rate as f32 * dt;
+            let spawn_count = self.spawn_carry as usize;
+            self.spawn_carry -= spawn_count as f32;
+            for _ in 0..spawn_count {
                 self.spawn_drop(rng);
             }
+        } else {
+            self.spawn_carry = 0.0;
         }
 
         // Update drops
@@ -183,10 +241,12 @@ impl RaindropSystem {
         };
 
         let ground_y = terminal_height.saturating_sub(1);
+        self.gust = self.gusts.update(dt, rng);
+        let wind_x = self.wind_x * self.gust;
 
         self.drops.retain_mut(|drop| {
-            drop.y += drop.speed_y;
-            drop.x += drop.speed_x;
+            drop.y += drop.speed_y * dt;
+            drop.x += (wind_x + drop.drift) * dt;
 
             // Hit ground?
             if drop.y >= ground_y as f32 {
@@ -194,8 +254,8 @@ impl RaindropSystem {
                     new_splashes.push_back(Splash {
                         x: drop.x as u16,
                         y: ground_y,
-                        timer: 0,
-                        max_timer: 3,
+                        timer: 0.0,
+                        max_timer: 3.0,
                     });
                 }
                 return false; // Remove drop
@@ -216,7 +276,7 @@ impl RaindropSystem {
         }
 
         self.splashes.retain_mut(|splash| {
-            splash.timer += 1;
+            splash.timer += dt;
             splash.timer < splash.max_timer
         });
     }
@@ -229,18 +289,15 @@ impl RaindropSystem {
 
             if x >= 0 && x < self.terminal_width as i16 && y >= 0 && y < self.terminal_height as i16
             {
-                let ch = if self.intensity == RainIntensity::Storm
-                    || self.intensity == RainIntensity::Heavy
-                {
-                    if drop.speed_x > 0.5 {
-                        '\\'
-                    } else if drop.speed_x < -0.5 {
-                        '/'
-                    } else {
-                        drop.character
-                    }
-                } else {
+                // Drizzle stays as dots; streaks lean with the wind.
+                let ch = if self.intensity == RainIntensity::Drizzle {
                     drop.character
+                } else {
+                    slant_char(
+                        self.wind_x * self.gust + drop.drift,
+                        drop.speed_y,
+                        drop.character,
+                    )
                 };
                 renderer.render_char(x as u16, y as u16, ch, drop.color)?;
             }
@@ -249,7 +306,7 @@ impl RaindropSystem {
         // Render splashes
         for splash in &self.splashes {
             if splash.x < self.terminal_width && splash.y < self.terminal_height {
-                let ch = match splash.timer {
+                let ch = match splash.timer as u8 {
                     0 => '.',
                     1 => 'o',
                     2 => 'O',
@@ -263,6 +320,24 @@ impl RaindropSystem {
     }
 }
 
+impl RaindropSystem {
+    /// Grows from nothing to `PREFADE_MAX_DENSITY` over the last
+    /// `PREFADE_MINUTES` before the nowcast expects rain (not snow).
+    fn prefade_density(ctx: &FrameContext<'_>) -> f32 {
+        let warm = ctx
+            .state
+            .current_weather
+            .as_ref()
+            .is_some_and(|weather| weather.temperature > 0.0);
+        match ctx.state.precipitation_due_in() {
+            Some(minutes) if warm => {
+                (1.0 - minutes as f32 / PREFADE_MINUTES).max(0.0) * PREFADE_MAX_DENSITY
+            }
+            _ => 0.0,
+        }
+    }
+}
+
 impl AnimationSystem for RaindropSystem {
     fn id(&self) -> &'static str {
         "rain"
@@ -273,7 +348,9 @@ impl AnimationSystem for RaindropSystem {
     }
 
     fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
-        ctx.conditions.is_raining || ctx.conditions.is_thunderstorm
+        ctx.conditions.is_raining
+            || ctx.conditions.is_thunderstorm
+            || Self::prefade_density(ctx) > 0.0
     }
 
     fn on_resize(&mut self, size: TerminalSize) {
@@ -291,6 +368,7 @@ impl AnimationSystem for RaindropSystem {
 
     fn on_wind(&mut self, wind: Wind) {
         self.set_wind(wind.speed_kmh, wind.direction_deg);
+        self.gusts.set(wind.speed_kmh, wind.gust_kmh);
     }
 
     fn on_rain_intensity(&mut self, intensity: RainIntensity) {
@@ -298,7 +376,12 @@ impl AnimationSystem for RaindropSystem {
     }
 
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
-        self.update(ctx.size.width, ctx.size.height, rng);
+        self.density = if ctx.conditions.is_raining || ctx.conditions.is_thunderstorm {
+            1.0
+        } else {
+            Self::prefade_density(ctx)
+        } * ctx.particle_density;
+        self.update(ctx.size.width, ctx.size.height, ctx.dt, rng);
     }
 
     fn render(
@@ -309,3 +392,60 @@ impl AnimationSystem for RaindropSystem {
         RaindropSystem::render(self, renderer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slant_follows_drift_direction() {
+        assert_eq!(slant_char(0.5, 1.0, '|'), '\\');
+        assert_eq!(slant_char(-0.5, 1.0, '|'), '/');
+        assert_eq!(slant_char(0.1, 1.0, '|'), '|');
+    }
+
+    #[test]
+    fn test_wind_overrides_intensity_default() {
+        let mut rain = RaindropSystem::new(80, 24, RainIntensity::Light);
+        // Wind from the west (270°) pushes drops to the right.
+        rain.set_wind(40.0, 270.0);
+        rain.set_intensity(RainIntensity::Storm);
+        assert!((rain.wind_x - 1.0).abs() < 1e-4);
+
+        // Wind from the east pushes them to the left.
+        rain.set_wind(20.0, 90.0);
+        assert!((rain.wind_x + 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_intensity_change_ramps_drop_count() {
+        let mut rain = RaindropSystem::new(80, 24, RainIntensity::Drizzle);
+        let mut rng = rand::rng();
+        for _ in 0..60 {
+            rain.update(80, 24, 1.0, &mut rng);
+        }
+        let drizzle = rain.drops.len();
+        assert!(drizzle <= 20);
+
+        rain.set_intensity(RainIntensity::Storm);
+        rain.update(80, 24, 1.0, &mut rng);
+        assert!(rain.drops.len() <= drizzle + 5);
+        assert!(rain.level.current() < 0.3);
+
+        for _ in 0..150 {
+            rain.update(80, 24, 1.0, &mut rng);
+        }
+        assert!((rain.level.current() - 0.875).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_short_frames_spawn_at_the_spawn_rate() {
+        let mut rain = RaindropSystem::new(80, 24, RainIntensity::Drizzle);
+        let mut rng = rand::rng();
+        // A quarter frame each, a drizzle drop a frame: one drop in four
+        for _ in 0..8 {
+            rain.update(80, 24, 0.25, &mut rng);
+        }
+        assert!(rain.drops.len() <= 2);
+    }
+}
@@ -0,0 +1,98 @@
+//! Eases a weather system from one intensity to the next instead of
+//! snapping when a refresh changes it.
+
+/// Frames a change of intensity takes, about ten seconds at 30 fps.
+const RAMP_FRAMES: f32 = 300.0;
+
+/// A level moving linearly towards its target, so that any change takes
+/// `RAMP_FRAMES` however large it is.
+#[derive(Debug, Clone, Copy)]
+pub struct Ramp {
+    current: f32,
+    target: f32,
+    rate: f32,
+}
+
+impl Ramp {
+    pub fn new(level: f32) -> Self {
+        Self {
+            current: level,
+            target: level,
+            rate: 0.0,
+        }
+    }
+
+    pub fn set_target(&mut self, target: f32) {
+        if target != self.target {
+            self.target = target;
+            self.rate = (target - self.current).abs() / RAMP_FRAMES;
+        }
+    }
+
+    /// Jumps straight to the target, e.g. while nothing is on screen.
+    pub fn snap(&mut self) {
+        self.current = self.target;
+    }
+
+    /// Advances by `dt` frames and returns the new level.
+    pub fn update(&mut self, dt: f32) -> f32 {
+        let step = self.rate * dt;
+        self.current = if self.current < self.target {
+            (self.current + step).min(self.target)
+        } else {
+            (self.current - step).max(self.target)
+        };
+        self.current
+    }
+
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+}
+
+/// Piecewise-linear lookup in `(level, value)` pairs sorted by level,
+/// clamped to the first and last values.
+pub fn interpolate(table: &[(f32, f32)], level: f32) -> f32 {
+    let Some(&(first_level, first_value)) = table.first() else {
+        return 0.0;
+    };
+    if level <= first_level {
+        return first_value;
+    }
+    for pair in table.windows(2) {
+        let ((from_level, from_value), (to_level, to_value)) = (pair[0], pair[1]);
+        if level <= to_level {
+            let t = (level - from_level) / (to_level - from_level);
+            return from_value + (to_value - from_value) * t;
+        }
+    }
+    table[table.len() - 1].1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ramp_takes_the_same_time_for_any_change() {
+        let mut ramp = Ramp::new(0.25);
+        ramp.set_target(1.5);
+        assert!((ramp.update(RAMP_FRAMES / 2.0) - 0.875).abs() < 1e-4);
+        assert_eq!(ramp.update(RAMP_FRAMES), 1.5);
+
+        ramp.set_target(1.0);
+        ramp.update(RAMP_FRAMES / 2.0);
+        assert!((ramp.current() - 1.25).abs() < 1e-4);
+        ramp.snap();
+        assert_eq!(ramp.current(), 1.0);
+    }
+
+    #[test]
+    fn test_interpolate_between_and_beyond_points() {
+        let table = [(0.5, 1.0), (1.0, 2.0), (2.0, 6.0)];
+        assert_eq!(interpolate(&table, 0.0), 1.0);
+        assert_eq!(interpolate(&table, 0.75), 1.5);
+        assert_eq!(interpolate(&table, 1.5), 4.0);
+        assert_eq!(interpolate(&table, 3.0), 6.0);
+    }
+}
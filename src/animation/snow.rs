@@ -1,3 +1,5 @@
+use crate::animation::gusts::Gusts;
+use crate::animation::ramp::{Ramp, interpolate};
 use crate::animation::{
     AnimationSystem, FrameCommands, FrameContext, RenderLayer, TerminalSize, Wind,
 };
@@ -8,6 +10,28 @@ use crossterm::style::Color;
 use rand::{Rng, RngExt};
 use std::io;
 
+/// Fall speed of near and far flakes by intensity level.
+const NEAR_SPEEDS: [(f32, f32); 4] = [(0.25, 0.15), (0.5, 0.2), (1.0, 0.3), (2.5, 0.5)];
+const FAR_SPEEDS: [(f32, f32); 4] = [(0.25, 0.08), (0.5, 0.1), (1.0, 0.15), (2.5, 0.25)];
+
+/// Sideways drift of a blizzard, however the reported wind blows.
+const BLIZZARD_DRIFT: f32 = 1.2;
+/// Share of the screen, in percent, veiled by blowing snow in a blizzard.
+const WHITEOUT_PERCENT: f32 = 12.0;
+/// How fast the veil drifts with the wind, in columns per frame per unit of
+/// wind drift.
+const WHITEOUT_DRIFT: f32 = 2.0;
+
+/// Flakes per terminal column at each intensity.
+fn snow_level(intensity: SnowIntensity) -> f32 {
+    match intensity {
+        SnowIntensity::Light => 0.25,
+        SnowIntensity::Medium => 0.5,
+        SnowIntensity::Heavy => 1.0,
+        SnowIntensity::Blizzard => 2.5,
+    }
+}
+
 struct Snowflake {
     x: f32,
     y: f32,
@@ -23,23 +47,34 @@ pub struct SnowSystem {
     terminal_width: u16,
     terminal_height: u16,
     intensity: SnowIntensity,
+    /// Flake count and speed easing towards `intensity`'s.
+    level: Ramp,
     wind_x: f32,
+    gusts: Gusts,
+    /// How far the whiteout veil has drifted, in columns.
+    whiteout_offset: f32,
+    /// Share of the flakes to draw, from the frame context.
+    density: f32,
+    /// Fraction of a flake owed from earlier frames, so slow spawn rates
+    /// and short frames still add up to `spawn_rate` a frame.
+    spawn_carry: f32,
 }
 
 impl SnowSystem {
     pub fn new(terminal_width: u16, terminal_height: u16, intensity: SnowIntensity) -> Self {
-        let flakes_capacity = match intensity {
-            SnowIntensity::Light => (terminal_width / 4) as usize,
-            SnowIntensity::Medium => (terminal_width / 2) as usize,
-            SnowIntensity::Heavy => terminal_width as usize,
-        };
+        let flakes_capacity = (terminal_width as f32 * snow_level(intensity)) as usize;
 
         let mut system = Self {
             flakes: Vec::with_capacity(flakes_capacity),
             terminal_width,
             terminal_height,
             intensity,
+            level: Ramp::new(snow_level(intensity)),
             wind_x: 0.0,
+            gusts: Gusts::new(),
+            whiteout_offset: 0.0,
+            density: 1.0,
+            spawn_carry: 0.0,
         };
         // Initialize with some default wind
         let wind_dir = if rand::random::<bool>() { 0.2 } else { -0.2 };
@@ -55,10 +90,12 @@ impl SnowSystem {
 
     pub fn set_intensity_with_dir(&mut self, intensity: SnowIntensity, direction_multiplier: f32) {
         self.intensity = intensity;
+        self.level.set_target(snow_level(intensity));
         let base_wind = match intensity {
             SnowIntensity::Light => 0.05,
             SnowIntensity::Medium => 0.1,
             SnowIntensity::Heavy => 0.2,
+            SnowIntensity::Blizzard => BLIZZARD_DRIFT,
         };
         self.wind_x = base_wind * direction_multiplier;
     }
@@ -68,6 +105,10 @@ impl SnowSystem {
         let direction_rad = direction_deg.to_radians();
         let x_component = -direction_rad.sin();
         self.wind_x = speed_factor * x_component;
+        // A blizzard is driven sideways, even by a wind along the view
+        if self.intensity == SnowIntensity::Blizzard && self.wind_x.abs() < BLIZZARD_DRIFT {
+            self.wind_x = BLIZZARD_DRIFT.copysign(x_component);
+        }
     }
 
     fn spawn_flake(&mut self, rng: &mut (impl Rng + ?Sized)) {
@@ -77,10 +118,17 @@ impl SnowSystem {
 
         let z_index = if rng.random::<bool>() { 1 } else { 0 };
 
-        let (base_speed_y, chars) = match self.intensity {
-            SnowIntensity::Light => (if z_index == 1 { 0.15 } else { 0.08 }, vec!['.', '·']),
-            SnowIntensity::Medium => (if z_index == 1 { 0.2 } else { 0.1 }, vec!['.', '·', '*']),
-            SnowIntensity::Heavy => (if z_index == 1 { 0.3 } else { 0.15 }, vec!['*', '.', '·']),
+        let speeds = if z_index == 1 {
+            &NEAR_SPEEDS
+        } else {
+            &FAR_SPEEDS
+        };
+        let base_speed_y = interpolate(speeds, self.level.current());
+        let chars = match self.intensity {
+            SnowIntensity::Light => vec!['.', '·'],
+            SnowIntensity::Medium => vec!['.', '·', '*'],
+            SnowIntensity::Heavy => vec!['*', '.', '·'],
+            SnowIntensity::Blizzard => vec!['*', '·', '-', '*'],
         };
 
         let char_idx = (rng.random::<u32>() as usize) % chars.len();
@@ -104,36 +152,46 @@ impl SnowSystem {
         &mut self,
         terminal_width: u16,
         terminal_height: u16,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
 
-        let target_count = match self.intensity {
-            SnowIntensity::Light => (terminal_width / 4) as usize,
-            SnowIntensity::Medium => (terminal_width / 2) as usize,
-            SnowIntensity::Heavy => terminal_width as usize,
-        };
+        // Nothing on screen to ease away from
+        if self.flakes.is_empty() {
+            self.level.snap();
+        }
+        let target_count = (terminal_width as f32 * self.level.update(dt) * self.density) as usize;
 
         if self.flakes.len() < target_count {
             let spawn_rate = match self.intensity {
                 SnowIntensity::Light => 1,
                 SnowIntensity::Medium => 2,
                 SnowIntensity::Heavy => 4,
+                SnowIntensity::Blizzard => 10,
             };
-            for _ in 0..spawn_rate {
+            self.spawn_carry += spawn_rate as f32 * dt;
+            let spawn_count = self.spawn_carry as usize;
+            self.spawn_carry -= spawn_count as f32;
+            for _ in 0..spawn_count {
                 self.spawn_flake(rng);
             }
+        } else {
+            self.spawn_carry = 0.0;
         }
 
         let ground_y = terminal_height.saturating_sub(1);
+        // Flakes already falling get pushed along by a gust too
+        let gust_x = self.wind_x * (self.gusts.update(dt, rng) - 1.0);
+        self.whiteout_offset += (self.wind_x + gust_x) * WHITEOUT_DRIFT * dt;
 
         self.flakes.retain_mut(|flake| {
-            flake.y += flake.speed_y;
+            flake.y += flake.speed_y * dt;
 
             // Add horizontal sway
             let sway = (flake.y * 0.2 + flake.sway_offset).sin() * 0.05;
-            flake.x += flake.speed_x + sway;
+            flake.x += (flake.speed_x + gust_x + sway) * dt;
 
             // Hit ground or out of bounds
             if flake.y >= ground_y as f32 {
@@ -149,7 +207,36 @@ impl SnowSystem {
         });
     }
 
+    /// Whether blowing snow hides the cell at (`x`, `y`). The pattern is
+    /// fixed and drifts with the wind, so the veil streams across the scene
+    /// rather than flickering.
+    fn is_whited_out(&self, x: u16, y: u16, percent: f32) -> bool {
+        let column = (x as f32 - self.whiteout_offset).floor() as i64;
+        let hash = (column.wrapping_mul(73_856_093) ^ i64::from(y).wrapping_mul(19_349_663))
+            .rem_euclid(100);
+        (hash as f32) < percent
+    }
+
+    /// Share of the screen veiled, in percent: none up to heavy snow,
+    /// thickening as the flakes ramp up to a blizzard's.
+    fn whiteout_percent(&self) -> f32 {
+        let heavy = snow_level(SnowIntensity::Heavy);
+        let blizzard = snow_level(SnowIntensity::Blizzard);
+        ((self.level.current() - heavy) / (blizzard - heavy)).clamp(0.0, 1.0) * WHITEOUT_PERCENT
+    }
+
     pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let whiteout = self.whiteout_percent();
+        if whiteout > 0.0 {
+            for y in 0..self.terminal_height.saturating_sub(1) {
+                for x in 0..self.terminal_width {
+                    if self.is_whited_out(x, y, whiteout) {
+                        renderer.render_char(x, y, '░', Color::Grey)?;
+                    }
+                }
+            }
+        }
+
         for flake in &self.flakes {
             let x = flake.x as i16;
             let y = flake.y as i16;
@@ -189,6 +276,7 @@ impl AnimationSystem for SnowSystem {
 
     fn on_wind(&mut self, wind: Wind) {
         self.set_wind(wind.speed_kmh, wind.direction_deg);
+        self.gusts.set(wind.speed_kmh, wind.gust_kmh);
     }
 
     fn on_snow_intensity(&mut self, intensity: SnowIntensity) {
@@ -196,7 +284,8 @@ impl AnimationSystem for SnowSystem {
     }
 
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
-        self.update(ctx.size.width, ctx.size.height, rng);
+        self.density = ctx.particle_density;
+        self.update(ctx.size.width, ctx.size.height, ctx.dt, rng);
     }
 
     fn render(
@@ -0,0 +1,190 @@
+use crate::animation::{AnimationSystem, FrameCommands, FrameContext, RenderLayer, TerminalSize};
+use crate::render::TerminalRenderer;
+use crate::weather::SnowIntensity;
+use crossterm::style::Color;
+
+use rand::{Rng, RngExt};
+use std::io;
+
+const SNOW_PLOW_ART: &str = include_str!("assets/snow_plow.txt");
+
+/// Snow depth gained per frame while snowing, as a fraction of full cover.
+const LIGHT_ACCUMULATION: f32 = 0.000_07;
+const MEDIUM_ACCUMULATION: f32 = 0.000_14;
+const HEAVY_ACCUMULATION: f32 = 0.000_28;
+/// Depth lost per frame when it's above freezing and not snowing.
+const MELT_RATE: f32 = 0.000_2;
+/// The plow only turns out once the snow is this deep.
+const PLOW_DEPTH: f32 = 0.7;
+/// Chance per frame that the plow sets off once the snow is deep enough.
+const PLOW_CHANCE: f32 = 0.002;
+const PLOW_SPEED: f32 = 0.5;
+
+/// Snow settling along the ground, cleared now and then by a passing plow.
+pub struct SnowPlowSystem {
+    /// Fraction of the ground covered, 0.0 - 1.0.
+    depth: f32,
+    intensity: SnowIntensity,
+    plow_x: Option<f32>,
+    terminal_width: u16,
+}
+
+impl SnowPlowSystem {
+    pub fn new(terminal_width: u16) -> Self {
+        Self {
+            depth: 0.0,
+            intensity: SnowIntensity::Light,
+            plow_x: None,
+            terminal_width,
+        }
+    }
+
+    fn plow_width() -> f32 {
+        SNOW_PLOW_ART
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0) as f32
+    }
+
+    pub fn update(
+        &mut self,
+        is_snowing: bool,
+        temperature: Option<f64>,
+        dt: f32,
+        rng: &mut (impl Rng + ?Sized),
+    ) {
+        if is_snowing {
+            let rate = match self.intensity {
+                SnowIntensity::Light => LIGHT_ACCUMULATION,
+                SnowIntensity::Medium => MEDIUM_ACCUMULATION,
+                SnowIntensity::Heavy | SnowIntensity::Blizzard => HEAVY_ACCUMULATION,
+            };
+            self.depth = (self.depth + rate * dt).min(1.0);
+        } else if temperature.is_some_and(|t| t > 0.0) {
+            self.depth = (self.depth - MELT_RATE * dt).max(0.0);
+        }
+
+        match self.plow_x {
+            Some(x) if x >= self.terminal_width as f32 => {
+                // Road's clear once the plow has gone by
+                self.plow_x = None;
+                self.depth = 0.0;
+            }
+            Some(x) => self.plow_x = Some(x + PLOW_SPEED * dt),
+            None => {
+                if self.depth >= PLOW_DEPTH && rng.random::<f32>() < PLOW_CHANCE * dt {
+                    self.plow_x = Some(-Self::plow_width());
+                }
+            }
+        }
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer, ground_y: u16) -> io::Result<()> {
+        let threshold = (self.depth * 100.0) as u32;
+        // Everything behind the plow's blade has been cleared
+        let cleared_to = self
+            .plow_x
+            .map(|x| (x + Self::plow_width()).max(0.0) as u16)
+            .unwrap_or(0);
+
+        for x in cleared_to..self.terminal_width {
+            if cover_rand(x) < threshold {
+                let ch = if self.depth > 0.5 { '=' } else { '_' };
+                renderer.render_char(x, ground_y, ch, Color::White)?;
+            }
+        }
+
+        let Some(plow_x) = self.plow_x else {
+            return Ok(());
+        };
+        let line_count = SNOW_PLOW_ART.lines().count() as u16;
+        let top = (ground_y + 1).saturating_sub(line_count);
+        for (row, line) in SNOW_PLOW_ART.lines().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                let x = plow_x as i32 + col as i32;
+                if ch == ' ' || x < 0 || x >= self.terminal_width as i32 {
+                    continue;
+                }
+                renderer.render_char(x as u16, top + row as u16, ch, Color::Yellow)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn cover_rand(x: u16) -> u32 {
+    ((x as u32 ^ 0x2545_F491).wrapping_mul(0x9E37_79B9) >> 16) % 100
+}
+
+impl AnimationSystem for SnowPlowSystem {
+    fn id(&self) -> &'static str {
+        "snow_plow"
+    }
+
+    fn layer(&self) -> RenderLayer {
+        RenderLayer::PostScene
+    }
+
+    fn is_active(&self, ctx: &FrameContext<'_>) -> bool {
+        ctx.conditions.is_snowing || self.depth > 0.0 || self.plow_x.is_some()
+    }
+
+    fn on_resize(&mut self, size: TerminalSize) {
+        self.terminal_width = size.width;
+    }
+
+    fn on_snow_intensity(&mut self, intensity: SnowIntensity) {
+        self.intensity = intensity;
+    }
+
+    fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
+        self.terminal_width = ctx.size.width;
+        let temperature = ctx.state.current_weather.as_ref().map(|w| w.temperature);
+        self.update(ctx.conditions.is_snowing, temperature, ctx.dt, rng);
+    }
+
+    fn render(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        ctx: &FrameContext<'_>,
+    ) -> io::Result<()> {
+        SnowPlowSystem::render(self, renderer, ctx.horizon_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snow_builds_up_and_melts() {
+        let mut rng = rand::rng();
+        let mut system = SnowPlowSystem::new(80);
+        system.on_snow_intensity(SnowIntensity::Heavy);
+
+        system.update(true, Some(-5.0), 1000.0, &mut rng);
+        assert!(system.depth > 0.2);
+
+        let depth = system.depth;
+        system.update(false, Some(-5.0), 1000.0, &mut rng);
+        assert_eq!(system.depth, depth);
+
+        system.update(false, Some(4.0), 10_000.0, &mut rng);
+        assert_eq!(system.depth, 0.0);
+    }
+
+    #[test]
+    fn test_plow_clears_the_snow() {
+        let mut rng = rand::rng();
+        let mut system = SnowPlowSystem::new(80);
+        system.depth = 1.0;
+        system.plow_x = Some(79.0);
+
+        system.update(false, Some(-5.0), 4.0, &mut rng);
+        system.update(false, Some(-5.0), 1.0, &mut rng);
+        assert_eq!(system.plow_x, None);
+        assert_eq!(system.depth, 0.0);
+    }
+}
@@ -100,6 +100,7 @@ impl StarSystem {
         &mut self,
         terminal_width: u16,
         terminal_height: u16,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         if terminal_width == 0 || terminal_height == 0 {
@@ -122,19 +123,19 @@ impl StarSystem {
 
         // Twinkle
         for star in &mut self.stars {
-            star.phase += 0.05;
+            star.phase += 0.05 * dt;
             star.brightness = (star.phase.sin() + 1.0) / 2.0; // 0.0 to 1.0
         }
 
         // Shooting Star Logic
         if let Some(ref mut star) = self.shooting_star {
-            star.x += star.speed_x;
-            star.y += star.speed_y;
+            star.x += star.speed_x * dt;
+            star.y += star.speed_y * dt;
 
             if star.x < 0.0 || star.y as u16 >= terminal_height || star.length == 0 {
                 self.shooting_star = None;
             }
-        } else if rng.random::<f32>() < 0.005 {
+        } else if rng.random::<f32>() < 0.005 * dt {
             let half_width = (terminal_width / 2).max(1);
             let quarter_width = terminal_width / 4;
             let quarter_height = (terminal_height / 4).max(1);
@@ -227,7 +228,7 @@ impl AnimationSystem for StarSystem {
     }
 
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, _commands: &mut FrameCommands) {
-        self.update(ctx.size.width, ctx.size.height, rng);
+        self.update(ctx.size.width, ctx.size.height, ctx.dt, rng);
     }
 
     fn render(
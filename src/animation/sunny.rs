@@ -9,9 +9,16 @@ use crossterm::style::Color;
 use rand::Rng;
 
 use std::io;
-use std::time::{Duration, Instant};
-
-const FRAME_DELAY: Duration = Duration::from_millis(500);
+/// Nominal frames between sun animation frames (half a second at 30 FPS).
+const FRAME_DELAY: f32 = 15.0;
+/// UV index from which the sun is drawn fiercer (WHO "extreme").
+const EXTREME_UV_INDEX: f64 = 11.0;
+/// Sun color under an extreme UV index.
+const FIERCE_COLOR: Color = Color::Rgb {
+    r: 255,
+    g: 96,
+    b: 0,
+};
 
 const SUN_FRAMES: [&str; 2] = [
     include_str!("assets/sun_0.txt"),
@@ -20,21 +27,46 @@ const SUN_FRAMES: [&str; 2] = [
 
 pub struct SunnyAnimation {
     frames: Vec<Vec<String>>,
+    /// The same frames with starker rays, for an extreme UV index.
+    fierce_frames: Vec<Vec<String>>,
+    fierce: bool,
 }
 
 impl SunnyAnimation {
     pub fn new() -> Self {
-        let frames = SUN_FRAMES
+        let frames: Vec<Vec<String>> = SUN_FRAMES
             .iter()
             .map(|src| src.lines().map(|l| l.to_string()).collect())
             .collect();
-        Self { frames }
+        let fierce_frames = frames
+            .iter()
+            .map(|frame| {
+                frame
+                    .iter()
+                    .map(|line| line.replace('~', "✶").replace('-', "="))
+                    .collect()
+            })
+            .collect();
+        Self {
+            frames,
+            fierce_frames,
+            fierce: false,
+        }
+    }
+
+    pub fn set_fierce(&mut self, fierce: bool) {
+        self.fierce = fierce;
     }
 }
 
 impl Animation for SunnyAnimation {
     fn get_frame(&self, frame_number: usize) -> &[String] {
-        &self.frames[frame_number % self.frames.len()]
+        let frames = if self.fierce {
+            &self.fierce_frames
+        } else {
+            &self.frames
+        };
+        &frames[frame_number % frames.len()]
     }
 
     fn frame_count(&self) -> usize {
@@ -42,7 +74,11 @@ impl Animation for SunnyAnimation {
     }
 
     fn get_color(&self) -> Color {
-        Color::Yellow
+        if self.fierce {
+            FIERCE_COLOR
+        } else {
+            Color::Yellow
+        }
     }
 }
 
@@ -55,7 +91,7 @@ impl Default for SunnyAnimation {
 pub struct SunSystem {
     animation: SunnyAnimation,
     controller: AnimationController,
-    last_frame_time: Instant,
+    frame_elapsed: f32,
 }
 
 impl SunSystem {
@@ -63,7 +99,7 @@ impl SunSystem {
         Self {
             animation: SunnyAnimation::new(),
             controller: AnimationController::new(),
-            last_frame_time: Instant::now(),
+            frame_elapsed: 0.0,
         }
     }
 
@@ -139,13 +175,28 @@ impl AnimationSystem for SunSystem {
 
     fn update(
         &mut self,
-        _ctx: &FrameContext<'_>,
+        ctx: &FrameContext<'_>,
         _rng: &mut dyn Rng,
         _commands: &mut FrameCommands,
     ) {
-        if self.last_frame_time.elapsed() >= FRAME_DELAY {
+        let fierce = ctx
+            .state
+            .current_weather
+            .as_ref()
+            .and_then(|weather| weather.uv_index)
+            .is_some_and(|uv| uv >= EXTREME_UV_INDEX);
+        self.animation.set_fierce(fierce);
+
+        // An extreme sun also pulses twice as fast
+        let delay = if fierce {
+            FRAME_DELAY / 2.0
+        } else {
+            FRAME_DELAY
+        };
+        self.frame_elapsed += ctx.dt;
+        if self.frame_elapsed >= delay {
             self.controller.next_frame(&self.animation);
-            self.last_frame_time = Instant::now();
+            self.frame_elapsed = 0.0;
         }
     }
 
@@ -209,7 +260,7 @@ impl SunSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::animation::TerminalSize;
+    use crate::animation::{HouseEmission, TerminalSize};
     use crate::app_state::AppState;
     use crate::config::LocationDisplay;
     use crate::weather::types::CelestialEvents;
@@ -252,15 +303,11 @@ mod tests {
         let units = WeatherUnits::metric();
         let mut state = AppState::new(location, None, LocationDisplay::Coordinates, false, units);
         state.current_weather = Some(WeatherData {
-            condition: WeatherCondition::Clear,
             temperature: 20.0,
-            precipitation: 0.0,
             wind_speed: 5.0,
-            wind_direction: 0.0,
             sun,
-            moon_phase: None,
             timestamp: "2024-01-01T21:00:00Z".to_string(),
-            attribution: String::new(),
+            ..WeatherData::sample(WeatherCondition::Clear)
         });
         let conditions = WeatherConditions {
             sun,
@@ -272,11 +319,17 @@ mod tests {
                 width: 80,
                 height: 24,
             },
+            dt: 1.0,
             horizon_y: 18,
             conditions: &conditions,
             state: &state,
             show_leaves: false,
             chimney: None,
+            ac_unit: None,
+            tree: None,
+            emission: HouseEmission::Nothing,
+            morning_mist: 0.0,
+            particle_density: 1.0,
         };
 
         let y = SunSystem::resolved_sun_y(&ctx, 3);
@@ -294,15 +347,11 @@ mod tests {
         let units = WeatherUnits::metric();
         let mut state = AppState::new(location, None, LocationDisplay::Coordinates, false, units);
         state.current_weather = Some(WeatherData {
-            condition: WeatherCondition::Clear,
             temperature: 20.0,
-            precipitation: 0.0,
             wind_speed: 5.0,
-            wind_direction: 0.0,
             sun,
-            moon_phase: None,
             timestamp: "n/a".to_string(),
-            attribution: String::new(),
+            ..WeatherData::sample(WeatherCondition::Clear)
         });
         let conditions = WeatherConditions {
             sun,
@@ -314,14 +363,32 @@ mod tests {
                 width: 80,
                 height: 24,
             },
+            dt: 1.0,
             horizon_y: 18,
             conditions: &conditions,
             state: &state,
             show_leaves: false,
             chimney: None,
+            ac_unit: None,
+            tree: None,
+            emission: HouseEmission::Nothing,
+            morning_mist: 0.0,
+            particle_density: 1.0,
         };
 
         let y = SunSystem::resolved_sun_y(&ctx, 4);
         assert_eq!(y, 4);
     }
+
+    #[test]
+    fn fierce_sun_has_starker_rays() {
+        let mut animation = SunnyAnimation::new();
+        assert_eq!(animation.get_color(), Color::Yellow);
+
+        animation.set_fierce(true);
+        assert_eq!(animation.get_color(), FIERCE_COLOR);
+        let frame = animation.get_frame(0).join("\n");
+        assert!(frame.contains('✶'));
+        assert!(!frame.contains('~'));
+    }
 }
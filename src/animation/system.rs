@@ -21,20 +21,59 @@ pub struct TerminalSize {
 pub struct Wind {
     pub speed_kmh: f32,
     pub direction_deg: f32,
+    pub gust_kmh: Option<f32>,
+}
+
+/// Above this temperature (°C) nobody lights the fire.
+pub const COMFORT_THRESHOLD: f64 = 18.0;
+/// From this temperature (°C) the air conditioner runs.
+pub const HOT_THRESHOLD: f64 = 28.0;
+
+/// What the house is putting out into the air, driven by the outdoor
+/// temperature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HouseEmission {
+    ChimneySmoke,
+    AirConditioner,
+    Nothing,
+}
+
+impl HouseEmission {
+    /// Without weather data the fire is assumed lit.
+    pub fn for_temperature(celsius: Option<f64>) -> Self {
+        match celsius {
+            Some(t) if t >= HOT_THRESHOLD => Self::AirConditioner,
+            Some(t) if t > COMFORT_THRESHOLD => Self::Nothing,
+            _ => Self::ChimneySmoke,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct FrameCommands {
     pub flash_screen: bool,
+    /// Lightning hit the tree this frame.
+    pub tree_struck: bool,
 }
 
 pub struct FrameContext<'a> {
     pub size: TerminalSize,
+    /// Time since the previous frame in nominal frames (1.0 at 30 FPS), with
+    /// the animation time scale already applied. Motion should scale by it.
+    pub dt: f32,
     pub horizon_y: u16,
     pub conditions: &'a WeatherConditions,
     pub state: &'a AppState,
     pub show_leaves: bool,
     pub chimney: Option<ChimneyPosition>,
+    pub ac_unit: Option<ChimneyPosition>,
+    pub tree: Option<ChimneyPosition>,
+    pub emission: HouseEmission,
+    /// How thick the morning mist lies, from 0 to 1.
+    pub morning_mist: f32,
+    /// Share of the usual rain and snow to draw; below 1 when rendering
+    /// light, e.g. over SSH.
+    pub particle_density: f32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -62,3 +101,28 @@ pub trait AnimationSystem {
     fn render(&mut self, renderer: &mut TerminalRenderer, ctx: &FrameContext<'_>)
     -> io::Result<()>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emission_follows_temperature() {
+        assert_eq!(
+            HouseEmission::for_temperature(None),
+            HouseEmission::ChimneySmoke
+        );
+        assert_eq!(
+            HouseEmission::for_temperature(Some(-5.0)),
+            HouseEmission::ChimneySmoke
+        );
+        assert_eq!(
+            HouseEmission::for_temperature(Some(22.0)),
+            HouseEmission::Nothing
+        );
+        assert_eq!(
+            HouseEmission::for_temperature(Some(33.0)),
+            HouseEmission::AirConditioner
+        );
+    }
+}
@@ -1,5 +1,8 @@
-use crate::animation::{AnimationSystem, FrameCommands, FrameContext, RenderLayer, TerminalSize};
+use crate::animation::{
+    AnimationSystem, ChimneyPosition, FrameCommands, FrameContext, RenderLayer, TerminalSize,
+};
 use crate::render::TerminalRenderer;
+use crate::weather::severity::Severity;
 use crossterm::style::Color;
 
 use rand::{Rng, RngExt};
@@ -7,6 +10,34 @@ use std::collections::VecDeque;
 use std::io;
 
 const MAX_BOLTS: usize = 10;
+/// Chance that a bolt comes down on the tree instead of at random.
+const TREE_STRIKE_CHANCE: f32 = 0.01;
+/// Gaps between strikes for a storm overhead, and for one this far off
+/// (km) or further, against the usual gaps.
+const OVERHEAD_INTERVAL_SCALE: f32 = 0.5;
+const DISTANT_STORM_KM: f32 = 50.0;
+const DISTANT_INTERVAL_SCALE: f32 = 2.5;
+/// Gaps between strikes under storm watch, for a watch and a warning.
+const WATCH_INTERVAL_SCALE: f32 = 0.7;
+const WARNING_INTERVAL_SCALE: f32 = 0.4;
+
+/// How much longer than usual to wait between strikes for a storm
+/// `distance_km` away; unknown distances keep the usual pace.
+fn interval_scale(distance_km: Option<f64>) -> f32 {
+    distance_km.map_or(1.0, |km| {
+        let t = (km as f32 / DISTANT_STORM_KM).clamp(0.0, 1.0);
+        OVERHEAD_INTERVAL_SCALE + (DISTANT_INTERVAL_SCALE - OVERHEAD_INTERVAL_SCALE) * t
+    })
+}
+
+/// Strikes come faster the more severe the weather under storm watch.
+fn severity_scale(severity: Option<Severity>) -> f32 {
+    match severity {
+        None => 1.0,
+        Some(Severity::Watch) => WATCH_INTERVAL_SCALE,
+        Some(Severity::Warning) => WARNING_INTERVAL_SCALE,
+    }
+}
 
 #[derive(Clone, Copy, PartialEq)]
 enum LightningState {
@@ -19,18 +50,27 @@ enum LightningState {
 
 struct LightningBolt {
     segments: Vec<(u16, u16, char)>,
-    age: u8,
-    max_age: u8,
+    age: f32,
+    max_age: f32,
 }
 
 pub struct ThunderstormSystem {
     bolts: VecDeque<LightningBolt>,
     state: LightningState,
-    timer: u16,
+    timer: f32,
     terminal_width: u16,
     terminal_height: u16,
     flash_active: bool,
-    next_strike_in: u16,
+    next_strike_in: f32,
+    /// Stretches `next_strike_in` the further off the storm is.
+    interval_scale: f32,
+    /// Top of the tree, if the scene has one worth hitting.
+    tree: Option<ChimneyPosition>,
+    /// The bolt being formed is headed for the tree.
+    tree_targeted: bool,
+    /// Set on the frame a bolt hits the tree; it only happens once.
+    tree_hit: bool,
+    tree_struck: bool,
 }
 
 impl ThunderstormSystem {
@@ -38,14 +78,23 @@ impl ThunderstormSystem {
         Self {
             bolts: VecDeque::with_capacity(MAX_BOLTS),
             state: LightningState::Idle,
-            timer: 0,
+            timer: 0.0,
             terminal_width,
             terminal_height,
             flash_active: false,
-            next_strike_in: 60 + (rand::random::<u16>() % 120), // Random start delay
+            next_strike_in: (60 + (rand::random::<u16>() % 120)) as f32, // Random start delay
+            interval_scale: 1.0,
+            tree: None,
+            tree_targeted: false,
+            tree_hit: false,
+            tree_struck: false,
         }
     }
 
+    pub fn set_tree(&mut self, tree: Option<ChimneyPosition>) {
+        self.tree = tree;
+    }
+
     fn generate_bolt(&mut self, rng: &mut (impl Rng + ?Sized)) -> bool {
         if self.terminal_width < 12 || self.terminal_height < 8 {
             return false;
@@ -56,18 +105,38 @@ impl ThunderstormSystem {
             return false;
         }
 
-        let start_x = (rng.random::<u16>() % usable_width) + 5;
+        let target = self
+            .tree
+            .filter(|tree| !self.tree_struck && tree.y > 4)
+            .filter(|_| rng.random::<f32>() < TREE_STRIKE_CHANCE);
+        self.tree_targeted = target.is_some();
+
+        let start_x = match target {
+            Some(tree) => tree.x.clamp(5, usable_width + 4),
+            None => (rng.random::<u16>() % usable_width) + 5,
+        };
         let mut segments = Vec::new();
         let mut x = start_x as i16;
         let mut y = 2; // Start below top bar
 
         segments.push((x as u16, y as u16, '+')); // Start point
 
-        let y_end = self.terminal_height.saturating_sub(5) as i16;
+        let y_end = match target {
+            Some(tree) => tree.y as i16,
+            None => self.terminal_height.saturating_sub(5) as i16,
+        };
         let max_x = self.terminal_width.saturating_sub(3) as i16;
 
         while y < y_end {
-            let direction = (rng.random::<i8>() % 3) - 1; // -1, 0, 1
+            let mut direction = (rng.random::<i8>() % 3) - 1; // -1, 0, 1
+            if let Some(tree) = target {
+                // Wander, but never further than the remaining rows can make up
+                let remaining = y_end - y - 1;
+                let offset = x - tree.x as i16;
+                if (offset + direction as i16).abs() > remaining {
+                    direction = -offset.signum() as i8;
+                }
+            }
             x += direction as i16;
             y += 1;
 
@@ -108,8 +177,8 @@ impl ThunderstormSystem {
 
         self.bolts.push_back(LightningBolt {
             segments,
-            age: 0,
-            max_age: 10,
+            age: 0.0,
+            max_age: 10.0,
         });
 
         while self.bolts.len() > MAX_BOLTS {
@@ -123,6 +192,7 @@ impl ThunderstormSystem {
         &mut self,
         terminal_width: u16,
         terminal_height: u16,
+        dt: f32,
         rng: &mut (impl Rng + ?Sized),
     ) {
         self.terminal_width = terminal_width;
@@ -132,54 +202,59 @@ impl ThunderstormSystem {
             self.bolts.clear();
             self.flash_active = false;
             self.state = LightningState::Idle;
-            self.timer = 0;
-            self.next_strike_in = 60 + (rng.random::<u16>() % 120);
+            self.timer = 0.0;
+            self.next_strike_in = (60 + (rng.random::<u16>() % 120)) as f32;
             return;
         }
 
         match self.state {
             LightningState::Idle => {
                 self.flash_active = false;
-                if self.timer >= self.next_strike_in {
-                    self.timer = 0;
+                if self.timer >= self.next_strike_in * self.interval_scale {
+                    self.timer = 0.0;
 
                     if self.generate_bolt(rng) {
                         self.state = LightningState::Forming;
                     } else {
-                        self.next_strike_in = 30 + (rng.random::<u16>() % 200);
+                        self.next_strike_in = (30 + (rng.random::<u16>() % 200)) as f32;
                     }
                 } else {
-                    self.timer += 1;
+                    self.timer += dt;
                 }
             }
             LightningState::Forming => {
                 self.state = LightningState::Strike;
-                self.timer = 0;
+                self.timer = 0.0;
             }
             LightningState::Strike => {
+                if self.tree_targeted {
+                    self.tree_targeted = false;
+                    self.tree_struck = true;
+                    self.tree_hit = true;
+                }
                 self.flash_active = true;
                 self.state = LightningState::Flash;
-                self.timer = 0;
+                self.timer = 0.0;
             }
             LightningState::Flash => {
                 self.flash_active = false;
-                if self.timer > 2 {
+                if self.timer > 2.0 {
                     self.state = LightningState::Fading;
-                    self.timer = 0;
+                    self.timer = 0.0;
                 } else {
-                    self.timer += 1;
+                    self.timer += dt;
                 }
             }
             LightningState::Fading => {
                 self.bolts.retain_mut(|bolt| {
-                    bolt.age += 1;
+                    bolt.age += dt;
                     bolt.age < bolt.max_age
                 });
 
                 if self.bolts.is_empty() {
                     self.state = LightningState::Idle;
-                    self.timer = 0;
-                    self.next_strike_in = 30 + (rng.random::<u16>() % 200);
+                    self.timer = 0.0;
+                    self.next_strike_in = (30 + (rng.random::<u16>() % 200)) as f32;
                 }
             }
         }
@@ -222,13 +297,17 @@ impl AnimationSystem for ThunderstormSystem {
             self.bolts.clear();
             self.flash_active = false;
             self.state = LightningState::Idle;
-            self.timer = 0;
+            self.timer = 0.0;
         }
     }
 
     fn update(&mut self, ctx: &FrameContext<'_>, rng: &mut dyn Rng, commands: &mut FrameCommands) {
-        self.update(ctx.size.width, ctx.size.height, rng);
+        self.set_tree(ctx.tree);
+        self.interval_scale = interval_scale(ctx.state.storm.map(|storm| storm.distance_km))
+            * severity_scale(ctx.state.storm_watch_severity());
+        self.update(ctx.size.width, ctx.size.height, ctx.dt, rng);
         commands.flash_screen |= self.flash_active;
+        commands.tree_struck |= std::mem::take(&mut self.tree_hit);
     }
 
     fn render(
@@ -239,3 +318,44 @@ impl AnimationSystem for ThunderstormSystem {
         ThunderstormSystem::render(self, renderer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closer_storms_strike_more_often() {
+        assert_eq!(interval_scale(None), 1.0);
+        assert_eq!(interval_scale(Some(0.0)), OVERHEAD_INTERVAL_SCALE);
+        assert!(interval_scale(Some(10.0)) < interval_scale(Some(30.0)));
+        assert_eq!(interval_scale(Some(200.0)), DISTANT_INTERVAL_SCALE);
+    }
+
+    #[test]
+    fn test_warnings_strike_more_often_than_watches() {
+        assert_eq!(severity_scale(None), 1.0);
+        assert!(severity_scale(Some(Severity::Warning)) < severity_scale(Some(Severity::Watch)));
+        assert!(severity_scale(Some(Severity::Watch)) < 1.0);
+    }
+
+    #[test]
+    fn test_tree_strike_lands_on_the_tree() {
+        let mut rng = rand::rng();
+        let mut storm = ThunderstormSystem::new(120, 40);
+        let tree = ChimneyPosition { x: 30, y: 28 };
+        storm.set_tree(Some(tree));
+
+        // Strikes on the tree are rare; keep rolling until one comes along
+        while !storm.tree_targeted {
+            storm.bolts.clear();
+            assert!(storm.generate_bolt(&mut rng));
+        }
+
+        let bolt = storm.bolts.back().unwrap();
+        assert!(
+            bolt.segments
+                .iter()
+                .any(|&(x, y, _)| x == tree.x && y == tree.y)
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use crate::animation::{
-    AnimationSystem, ChimneyPosition, FrameCommands, FrameContext, RenderLayer, TerminalSize, Wind,
-    airplanes::AirplaneSystem, birds::BirdSystem, chimney::ChimneySmoke, clouds::CloudSystem,
-    fireflies::FireflySystem, fog::FogSystem, leaves::FallingLeaves, moon::MoonSystem,
-    raindrops::RaindropSystem, snow::SnowSystem, stars::StarSystem, sunny::SunSystem,
+    AnimationSystem, ChimneyPosition, FrameCommands, FrameContext, HouseEmission, RenderLayer,
+    TerminalSize, Wind, ac_unit::AirConditionerSystem, airplanes::AirplaneSystem,
+    aurora::AuroraSystem, birds::BirdSystem, chimney::ChimneySmoke, clouds::CloudSystem,
+    fireflies::FireflySystem, fireworks::FireworkSystem, fog::FogSystem, leaves::FallingLeaves,
+    mist::MorningMistSystem, moon::MoonSystem, motes::MoteSystem, raindrops::RaindropSystem,
+    snow::SnowSystem, snow_plow::SnowPlowSystem, stars::StarSystem, sunny::SunSystem,
     thunderstorm::ThunderstormSystem,
 };
 use crate::app_state::AppState;
@@ -11,10 +13,30 @@ use crate::scene::SceneLayout;
 use crate::weather::{FogIntensity, RainIntensity, SnowIntensity, WeatherConditions};
 use rand::Rng;
 use std::io;
+use std::time::{Duration, Instant};
+
+/// Frame length the per-frame speeds in each system were tuned against.
+const NOMINAL_FRAME: Duration = Duration::from_millis(1000 / 30);
+
+/// Largest step taken in one frame, so a stalled terminal or suspended
+/// process doesn't make everything jump across the screen on resume.
+const MAX_FRAME_DT: f32 = 5.0;
 
 pub struct AnimationManager {
     systems: Vec<Box<dyn AnimationSystem>>,
     show_leaves: bool,
+    time_scale: f32,
+    frame_dt: f32,
+    /// Seconds of animation played so far, at the time scale and not
+    /// counting time paused.
+    elapsed_secs: f64,
+    last_frame: Option<Instant>,
+    /// Advance one nominal frame per `begin_frame` regardless of wall time.
+    fixed_step: bool,
+    /// Systems are drawn where they are but not stepped.
+    paused: bool,
+    tree_struck: bool,
+    particle_density: f32,
 }
 
 impl AnimationManager {
@@ -22,6 +44,8 @@ impl AnimationManager {
         let systems: Vec<Box<dyn AnimationSystem>> = vec![
             // Background (code-defined order)
             Box::new(StarSystem::new(term_width, term_height)),
+            Box::new(AuroraSystem::new(term_width, term_height)),
+            Box::new(FireworkSystem::new()),
             Box::new(MoonSystem::new(term_width, term_height, None)),
             Box::new(FireflySystem::new(term_width, term_height)),
             Box::new(BirdSystem::new(term_width, term_height)),
@@ -30,6 +54,8 @@ impl AnimationManager {
             Box::new(AirplaneSystem::new(term_width, term_height)),
             // Post-scene
             Box::new(ChimneySmoke::new()),
+            Box::new(AirConditionerSystem::new()),
+            Box::new(SnowPlowSystem::new(term_width)),
             // Foreground
             Box::new(RaindropSystem::new(
                 term_width,
@@ -43,7 +69,9 @@ impl AnimationManager {
                 SnowIntensity::Light,
             )),
             Box::new(FogSystem::new(term_width, term_height, FogIntensity::Light)),
+            Box::new(MorningMistSystem::new(term_width)),
             Box::new(FallingLeaves::new(term_width, term_height)),
+            Box::new(MoteSystem::new()),
         ];
 
         debug_assert!(
@@ -57,7 +85,72 @@ impl AnimationManager {
         Self {
             systems,
             show_leaves,
+            time_scale: 1.0,
+            frame_dt: 1.0,
+            elapsed_secs: 0.0,
+            last_frame: None,
+            fixed_step: false,
+            paused: false,
+            tree_struck: false,
+            particle_density: 1.0,
+        }
+    }
+
+    /// Thins out rain and snow; 1.0 draws all of it.
+    pub fn set_particle_density(&mut self, density: f32) {
+        self.particle_density = density.clamp(0.0, 1.0);
+    }
+
+    /// Scales how fast animations play; 1.0 is real time.
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.time_scale = scale.max(0.0);
+    }
+
+    /// Steps every frame by exactly one nominal frame, for rendering frames
+    /// back to back without waiting, as `weathr preview` does.
+    pub fn set_fixed_step(&mut self, fixed: bool) {
+        self.fixed_step = fixed;
+    }
+
+    /// Freezes every system in place. Resuming continues from the same
+    /// state, without catching up on the time spent paused.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Measures the time since the previous frame. Call once per frame before
+    /// rendering any layer so every system advances by the same step.
+    pub fn begin_frame(&mut self) {
+        if self.paused {
+            self.last_frame = None;
+            self.frame_dt = 0.0;
+            return;
         }
+
+        let now = Instant::now();
+        let frames = match self.last_frame {
+            _ if self.fixed_step => 1.0,
+            Some(last) => (now.duration_since(last).as_secs_f32() / NOMINAL_FRAME.as_secs_f32())
+                .min(MAX_FRAME_DT),
+            None => 1.0,
+        };
+        self.last_frame = Some(now);
+        self.frame_dt = frames * self.time_scale;
+        self.elapsed_secs += (self.frame_dt * NOMINAL_FRAME.as_secs_f32()) as f64;
+    }
+
+    /// Seconds of animation played so far, for scenery that moves with it.
+    pub fn elapsed_secs(&self) -> f64 {
+        self.elapsed_secs
+    }
+
+    /// Whether lightning hit the tree since the last call.
+    pub fn take_tree_struck(&mut self) -> bool {
+        std::mem::take(&mut self.tree_struck)
     }
 
     pub fn on_resize(&mut self, width: u16, height: u16) {
@@ -85,10 +178,11 @@ impl AnimationManager {
         }
     }
 
-    pub fn update_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
+    pub fn update_wind(&mut self, speed_kmh: f32, direction_deg: f32, gust_kmh: Option<f32>) {
         let wind = Wind {
             speed_kmh,
             direction_deg,
+            gust_kmh,
         };
         for system in &mut self.systems {
             system.on_wind(wind);
@@ -110,17 +204,30 @@ impl AnimationManager {
         let chimney = layout
             .chimney_pos
             .map(|pos| ChimneyPosition { x: pos.x, y: pos.y });
+        let ac_unit = layout
+            .ac_unit_pos
+            .map(|pos| ChimneyPosition { x: pos.x, y: pos.y });
+        let tree = layout
+            .tree_pos
+            .map(|pos| ChimneyPosition { x: pos.x, y: pos.y });
+        let temperature = state.current_weather.as_ref().map(|w| w.temperature);
 
         FrameContext {
             size: TerminalSize {
                 width: layout.width,
                 height: layout.height,
             },
+            dt: self.frame_dt,
             horizon_y: layout.ground_y,
             conditions,
             state,
             show_leaves: self.show_leaves,
             chimney,
+            ac_unit,
+            tree,
+            emission: HouseEmission::for_temperature(temperature),
+            morning_mist: state.morning_mist(state.scheduler.now()),
+            particle_density: self.particle_density,
         }
     }
 
@@ -144,13 +251,16 @@ impl AnimationManager {
             if !system.is_active(ctx) {
                 continue;
             }
-            system.update(ctx, rng, &mut commands);
+            if !self.paused {
+                system.update(ctx, rng, &mut commands);
+            }
             system.render(renderer, ctx)?;
         }
 
         if commands.flash_screen {
             renderer.flash_screen()?;
         }
+        self.tree_struck |= commands.tree_struck;
 
         Ok(())
     }
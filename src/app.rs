@@ -1,30 +1,92 @@
 use crate::animation_manager::AnimationManager;
 use crate::app_state::AppState;
-use crate::config::{Config, Provider};
-use crate::error::WeatherError;
-use crate::render::TerminalRenderer;
+use crate::cache;
+use crate::config::{
+    Config, LocationDisplay, LocationProfile, SceneConfig, TemperatureBand, TemperatureColors,
+};
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::geolocation::{self, GeoLocation};
+use crate::history;
+use crate::http;
+use crate::render::{Banner, Footer, TerminalRenderer, display_width, is_ssh_session};
 use crate::scene::overlay::OverlayRegistry;
-use crate::scene::world::WorldScene;
+use crate::scene::season::Season;
+use crate::scene::world::{WorldScene, load_custom_houses};
 use crate::scene::{SceneContext, SceneRegistry};
+use crate::scheduler::Scheduler;
 use crate::theme::ThemeRegistry;
 
-use crate::weather::provider::WeatherProvider;
-use crate::weather::provider::met_office::{MetOfficeProvider, MetOfficeProviderConfig};
-use crate::weather::types::CelestialEvents;
+use crate::keymap::{Action, Keymap};
+use crate::notify::Notifier;
+use crate::weather::air_quality::{AirQuality, AirQualityForecast};
+use crate::weather::archive::{OpenMeteoArchive, YesterdayTemperatures};
+use crate::weather::aurora::{AURORA_MIN_LATITUDE, KpForecast};
+use crate::weather::comfort::Comfort;
+use crate::weather::marine::{MarineConditions, MarineForecast};
+use crate::weather::normals::{ClimateNormals, MonthlyNormals, month_name};
+use crate::weather::provider;
+use crate::weather::radar::{
+    INTENSITY_GLYPHS, OpenMeteoRadar, RADAR_COLUMNS, RADAR_ROWS, RadarFrame, RadarMap,
+    intensity_level,
+};
+use crate::weather::severity::Severity;
+use crate::weather::transition::Transition;
+use crate::weather::types::{
+    CelestialEvents, HourlyForecast, IconSet, PrecipitationUnit, TemperatureUnit,
+};
+use crate::weather::units::{format_snow, humidex, ms_to_kmh, wind_chill};
 use crate::weather::{
-    OpenMeteoProvider, WeatherClient, WeatherCondition, WeatherData, WeatherLocation,
+    WeatherClient, WeatherCondition, WeatherData, WeatherLocation, WeatherUnits, format_distance,
+    format_temperature, format_wind_speed,
 };
-use crossterm::event::{self, Event, KeyCode, KeyModifiers};
-use serde::Deserialize;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use crossterm::event::{self, Event};
+use crossterm::style::Color;
 use std::io;
-use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
 const REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+const LOCATION_REFRESH_INTERVAL: Duration = Duration::from_secs(900);
+/// How often to check whether the network is back while offline.
+const OFFLINE_PROBE_INTERVAL: Duration = Duration::from_secs(15);
+const TRANSITION_TICK: Duration = Duration::from_secs(1);
 const INPUT_POLL_FPS: u64 = 30;
 const FRAME_DURATION: Duration = Duration::from_millis(1000 / INPUT_POLL_FPS);
+/// Frame rate and share of the rain and snow when rendering light.
+const LIGHT_FPS: u64 = 10;
+const LIGHT_PARTICLE_DENSITY: f32 = 0.5;
 const DEFAULT_THEME_ID: &str = "default";
+const PAUSED_LABEL: &str = "Paused";
+/// Columns taken by one hour of the forecast strip.
+const FORECAST_COLUMN_WIDTH: usize = 7;
+/// The forecast strip starts below the HUD.
+const FORECAST_ROW: u16 = 3;
+/// Hours in the `c` comfort strip, each two columns wide.
+const COMFORT_HOURS: usize = 24;
+/// Width of the label column in the `d` detail panel.
+const DETAIL_LABEL_WIDTH: usize = 12;
+const FEET_PER_METRE: f64 = 3.28084;
+/// The radar map is fetched again after this long, or for a new location.
+const RADAR_MAX_AGE: Duration = Duration::from_secs(600);
+/// How long each quarter hour of the radar loop stays up.
+const RADAR_FRAME_TIME: Duration = Duration::from_millis(700);
+/// Extra frame times the latest radar frame is held before the loop restarts.
+const RADAR_HOLD_FRAMES: usize = 2;
+const RADAR_LEGEND: &str = "light ░▒▓█ heavy";
+/// The `g` graph reads the history file again after this long, picking up
+/// what was recorded since.
+const HISTORY_MAX_AGE: Duration = Duration::from_secs(300);
+/// Widest the `g` graph gets, in columns.
+const HISTORY_MAX_WIDTH: u16 = 72;
+
+/// Archived date range to play back instead of live data.
+#[derive(Clone, Copy, Debug)]
+pub struct Replay {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub speed: f64,
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct ThemeBindings {
@@ -87,11 +149,244 @@ fn resolve_theme_bindings(
     }
 }
 
-fn generate_offline_weather(rng: &mut impl rand::Rng) -> WeatherData {
-    use chrono::{Local, Timelike};
+/// Tracks the auto-detected location so a long-running session follows a
+/// laptop from one place to another.
+struct Roaming {
+    current: GeoLocation,
+    display: LocationDisplay,
+    language: String,
+}
+
+impl Roaming {
+    async fn check_moved(&mut self) -> Option<(WeatherLocation, Option<String>)> {
+        let moved_to = geolocation::detect_move(&self.current).await?;
+
+        let mut city_name = moved_to.city.clone();
+        if matches!(self.display, LocationDisplay::City | LocationDisplay::Mixed)
+            && let Some(name) =
+                geolocation::reverse_geocode(moved_to.latitude, moved_to.longitude, &self.language)
+                    .await
+        {
+            city_name = Some(name);
+        }
+
+        let location = WeatherLocation {
+            latitude: moved_to.latitude,
+            longitude: moved_to.longitude,
+            elevation: None,
+        };
+        self.current = moved_to;
+        Some((location, city_name))
+    }
+}
+
+fn simulated_weather(condition: WeatherCondition, night: bool) -> WeatherData {
+    WeatherData {
+        condition,
+        temperature: 20.0,
+        precipitation: if condition.is_raining() { 2.5 } else { 0.0 },
+        // m/s: about 45 km/h in storms, 10 km/h otherwise
+        wind_speed: if condition.is_thunderstorm() {
+            12.5
+        } else {
+            2.8
+        },
+        wind_direction: 225.0,
+        sun: CelestialEvents::from_bool(!night),
+        moon_phase: Some(0.5),
+        pressure: None,
+        disagreement: None,
+        humidity: None,
+        apparent_temperature: None,
+        uv_index: None,
+        source: String::new(),
+        nowcast: Vec::new(),
+        hourly: Vec::new(),
+        today: None,
+        details: Default::default(),
+        timestamp: "simulated".to_string(),
+        attribution: "".to_string(),
+    }
+}
+
+/// The three rows of the `f` forecast strip (hour, glyph and temperature,
+/// chance of precipitation), with as many hours as fit in `width` columns.
+fn forecast_strip(
+    hours: &[HourlyForecast],
+    units: WeatherUnits,
+    icons: IconSet,
+    width: u16,
+) -> [String; 3] {
+    let fit = (width as usize).saturating_sub(4) / FORECAST_COLUMN_WIDTH;
+    let mut rows: [String; 3] = Default::default();
+    for hour in hours.iter().take(fit) {
+        let (temperature, _) = format_temperature(hour.temperature, units.temperature);
+        let precipitation = hour
+            .precipitation_probability
+            .map(|p| format!("{:.0}%", p))
+            .unwrap_or_default();
+        let cells = [
+            hour.time.get(11..16).unwrap_or(&hour.time).to_string(),
+            format!("{} {:.0}°", hour.condition.icon(icons), temperature),
+            precipitation,
+        ];
+        for (row, cell) in rows.iter_mut().zip(cells) {
+            row.push_str(&cell);
+            let padding = FORECAST_COLUMN_WIDTH.saturating_sub(display_width(&cell));
+            row.push_str(&" ".repeat(padding));
+        }
+    }
+    rows.map(|row| row.trim_end().to_string())
+}
+
+/// How each of the coming hours feels, as many as fit two columns apiece,
+/// and a line marking every third hour under them.
+fn comfort_strip(hours: &[HourlyForecast], width: u16) -> (Vec<Comfort>, String) {
+    let fit = ((width as usize).saturating_sub(4) / 2).min(COMFORT_HOURS);
+    let hours = &hours[..hours.len().min(fit)];
+    let levels = hours
+        .iter()
+        .map(|hour| Comfort::of(hour.temperature, hour.humidity))
+        .collect();
+    let mut ticks = String::new();
+    for hour in hours.iter().step_by(3) {
+        let label = hour.time.get(11..13).unwrap_or("");
+        ticks.push_str(&format!("{:<6}", label));
+    }
+    (levels, ticks.trim_end().to_string())
+}
+
+fn comfort_color(comfort: Comfort) -> Color {
+    match comfort {
+        Comfort::Cold => Color::Blue,
+        Comfort::Cool => Color::Cyan,
+        Comfort::Pleasant => Color::Green,
+        Comfort::Sticky => Color::Yellow,
+        Comfort::Hot => Color::Rgb {
+            r: 255,
+            g: 165,
+            b: 0,
+        },
+        Comfort::Oppressive => Color::Red,
+    }
+}
+
+/// Rows of the `d` detail panel, label and value. Wind chill and humidex
+/// only appear where they apply; other readings the provider doesn't report
+/// show as a dash. Waves and tide are added by the sea, and today against
+/// the month's normal once the normals are in.
+fn detail_lines(
+    weather: &WeatherData,
+    marine: Option<&MarineConditions>,
+    normals: Option<&MonthlyNormals>,
+    units: WeatherUnits,
+) -> Vec<String> {
+    let details = &weather.details;
+    let temperature = |celsius: f64| {
+        let (value, unit) = format_temperature(celsius, units.temperature);
+        format!("{:.1}{}", value, unit)
+    };
+
+    let mut rows = vec![("Dew point", details.dew_point.map(temperature))];
+    if let Some(chill) = wind_chill(weather.temperature, weather.wind_speed) {
+        rows.push(("Wind chill", Some(temperature(chill))));
+    }
+    if let Some(index) = details
+        .dew_point
+        .and_then(|dew_point| humidex(weather.temperature, dew_point))
+    {
+        rows.push(("Humidex", Some(format!("{:.0}", index))));
+    }
+    rows.push((
+        "Gusts",
+        details.wind_gusts.map(|gusts| {
+            let (value, unit) = format_wind_speed(gusts, units.wind_speed);
+            format!("{:.1}{}", value, unit)
+        }),
+    ));
+    let clouds = details
+        .cloud_layers
+        .iter()
+        .map(|layer| format!("{} {:.0}%", layer.level.label(), layer.cover))
+        .collect::<Vec<_>>();
+    rows.push(("Clouds", (!clouds.is_empty()).then(|| clouds.join(" · "))));
+    rows.push((
+        "Visibility",
+        details.visibility.map(|metres| {
+            let (value, unit) = format_distance(metres, units.distance);
+            format!("{:.1} {}", value, unit)
+        }),
+    ));
+    if let Some(depth) = details.snow_depth {
+        let (value, unit) = format_snow(depth * 100.0, units.precipitation);
+        rows.push(("Snow depth", Some(format!("{:.0}{}", value, unit))));
+    }
+    let month = weather.timestamp.get(5..7).and_then(|m| m.parse().ok());
+    if let (Some(normals), Some(today), Some(month)) = (normals, weather.today, month)
+        && let Some(mut anomaly) = normals.anomaly(month, (today.min + today.max) / 2.0)
+    {
+        if units.temperature == TemperatureUnit::Fahrenheit {
+            anomaly *= 9.0 / 5.0;
+        }
+        let (_, unit) = format_temperature(0.0, units.temperature);
+        let side = if anomaly < 0.0 { "below" } else { "above" };
+        rows.push((
+            "Climate",
+            Some(format!(
+                "{:+.1}{} {} average for {}",
+                anomaly,
+                unit,
+                side,
+                month_name(month)
+            )),
+        ));
+    }
+    if let Some(marine) = marine {
+        let length = |metres: f64| {
+            if units.precipitation == PrecipitationUnit::Inch {
+                format!("{:+.1} ft", metres * FEET_PER_METRE)
+            } else {
+                format!("{:+.2} m", metres)
+            }
+        };
+        rows.push((
+            "Waves",
+            marine
+                .wave_height
+                .map(|height| length(height).trim_start_matches('+').to_string()),
+        ));
+        rows.push((
+            "Tide",
+            marine.tide.map(|tide| match marine.sea_level {
+                Some(level) => format!("{}, {}", tide.label(), length(level)),
+                None => tide.label().to_string(),
+            }),
+        ));
+    }
+
+    rows.into_iter()
+        .map(|(label, value)| {
+            format!(
+                "{:<width$}{}",
+                label,
+                value.as_deref().unwrap_or("—"),
+                width = DETAIL_LABEL_WIDTH
+            )
+        })
+        .collect()
+}
+
+/// Footer status while a provider is rate limiting us, e.g. "Rate limited,
+/// next update at 14:35".
+fn rate_limited_status(now: DateTime<FixedOffset>, delay: Duration) -> String {
+    let next = now + delay;
+    format!("Rate limited, next update at {}", next.format("%H:%M"))
+}
+
+fn generate_offline_weather(rng: &mut impl rand::Rng, now: DateTime<FixedOffset>) -> WeatherData {
+    use chrono::Timelike;
     use rand::RngExt;
 
-    let now = Local::now();
     let hour = now.hour();
     let is_day = (6..18).contains(&hour);
 
@@ -116,33 +411,282 @@ fn generate_offline_weather(rng: &mut impl rand::Rng) -> WeatherData {
         wind_direction: rng.random_range(0.0..360.0),
         sun: CelestialEvents::from_bool(is_day),
         moon_phase: Some(0.5),
+        pressure: None,
+        disagreement: None,
+        humidity: None,
+        apparent_temperature: None,
+        uv_index: None,
+        source: String::new(),
+        nowcast: Vec::new(),
+        hourly: Vec::new(),
+        today: None,
+        details: Default::default(),
         timestamp: now.format("%Y-%m-%dT%H:%M:%S").to_string(),
         attribution: "".to_string(),
     }
 }
 
+pub struct SimulatedTransition {
+    pub from: WeatherCondition,
+    pub to: WeatherCondition,
+    pub over: Duration,
+}
+
+/// What a session shows in place of live weather, and how.
+#[derive(Default)]
+pub struct SessionOptions {
+    pub simulate_condition: Option<String>,
+    pub simulate_transition: Option<SimulatedTransition>,
+    pub simulate_night: bool,
+    pub show_leaves: bool,
+    pub replay: Option<Replay>,
+}
+
+/// The `m` precipitation map: the last hour around the location, fetched in
+/// the background and looped.
+struct RadarPanel {
+    shown: bool,
+    opened_at: Instant,
+    map: Option<RadarMap>,
+    /// Where and when the last fetch was started.
+    requested: Option<(WeatherLocation, Instant)>,
+    error: Option<String>,
+    tx: mpsc::Sender<Result<RadarMap, WeatherError>>,
+    receiver: mpsc::Receiver<Result<RadarMap, WeatherError>>,
+}
+
+impl RadarPanel {
+    fn new() -> Self {
+        let (tx, receiver) = mpsc::channel(1);
+        Self {
+            shown: false,
+            opened_at: Instant::now(),
+            map: None,
+            requested: None,
+            error: None,
+            tx,
+            receiver,
+        }
+    }
+
+    fn toggle(&mut self) {
+        self.shown = !self.shown;
+        self.opened_at = Instant::now();
+    }
+
+    /// Starts a fetch unless the map is recent and for the same place.
+    fn request(&mut self, location: WeatherLocation) {
+        let due = self.requested.is_none_or(|(fetched_for, at)| {
+            fetched_for.latitude != location.latitude
+                || fetched_for.longitude != location.longitude
+                || at.elapsed() >= RADAR_MAX_AGE
+        });
+        if !due {
+            return;
+        }
+
+        self.requested = Some((location, Instant::now()));
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let result = OpenMeteoRadar::new().fetch(&location).await;
+            let _ = tx.send(result).await;
+        });
+    }
+
+    /// Takes in a finished fetch; true when it brought a new map.
+    fn receive(&mut self) -> bool {
+        match self.receiver.try_recv() {
+            Ok(Ok(map)) => {
+                self.map = Some(map);
+                self.error = None;
+                true
+            }
+            Ok(Err(error)) => {
+                self.error = Some(match error {
+                    WeatherError::Network(net_err) => net_err.user_friendly_message(),
+                    error => error.to_string(),
+                });
+                false
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// The frame to show `elapsed` after opening the panel.
+    fn frame(&self, elapsed: Duration) -> Option<&RadarFrame> {
+        let frames = &self.map.as_ref()?.frames;
+        let steps = frames.len() + RADAR_HOLD_FRAMES;
+        let step = (elapsed.as_millis() / RADAR_FRAME_TIME.as_millis()) as usize % steps;
+        frames.get(step.min(frames.len().saturating_sub(1)))
+    }
+}
+
+/// The `g` graph of the weather recorded here, read in the background.
+struct HistoryPanel {
+    shown: bool,
+    records: Option<Vec<history::Record>>,
+    /// Where and when the history was last read.
+    requested: Option<(WeatherLocation, Instant)>,
+    tx: mpsc::Sender<Vec<history::Record>>,
+    receiver: mpsc::Receiver<Vec<history::Record>>,
+}
+
+impl HistoryPanel {
+    fn new() -> Self {
+        let (tx, receiver) = mpsc::channel(1);
+        Self {
+            shown: false,
+            records: None,
+            requested: None,
+            tx,
+            receiver,
+        }
+    }
+
+    /// Reads the history unless it was read recently for the same place.
+    fn request(&mut self, location: WeatherLocation) {
+        let due = self.requested.is_none_or(|(read_for, at)| {
+            read_for.latitude != location.latitude
+                || read_for.longitude != location.longitude
+                || at.elapsed() >= HISTORY_MAX_AGE
+        });
+        if !due {
+            return;
+        }
+
+        self.requested = Some((location, Instant::now()));
+        let tx = self.tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let records = history::load(&location).unwrap_or_default();
+            let _ = tx.blocking_send(records);
+        });
+    }
+
+    fn receive(&mut self) {
+        if let Ok(records) = self.receiver.try_recv() {
+            self.records = Some(records);
+        }
+    }
+}
+
+/// Lines of the radar panel for one frame: the time, the map with the
+/// location marked `+` in the middle, and a legend. Each cell is two
+/// columns wide so the map looks about square.
+fn radar_lines(frame: &RadarFrame) -> Vec<Vec<(char, Color)>> {
+    let time = frame.time.split('T').nth(1).unwrap_or(&frame.time);
+    let mut lines = vec![
+        format!("Radar {}", time)
+            .chars()
+            .map(|c| (c, Color::Cyan))
+            .collect(),
+    ];
+
+    for row in 0..RADAR_ROWS {
+        let mut line = Vec::with_capacity(RADAR_COLUMNS * 2);
+        for column in 0..RADAR_COLUMNS {
+            let level = intensity_level(frame.cell(column, row));
+            let color = match level {
+                0 => Color::DarkGrey,
+                1 => Color::Green,
+                2 => Color::Yellow,
+                3 => Color::Rgb {
+                    r: 255,
+                    g: 165,
+                    b: 0,
+                },
+                _ => Color::Red,
+            };
+            let glyph = INTENSITY_GLYPHS[level];
+            let home = row == RADAR_ROWS / 2 && column == RADAR_COLUMNS / 2;
+            line.push(if home {
+                ('+', Color::White)
+            } else {
+                (glyph, color)
+            });
+            line.push((glyph, color));
+        }
+        lines.push(line);
+    }
+
+    lines.push(RADAR_LEGEND.chars().map(|c| (c, Color::DarkGrey)).collect());
+    lines
+}
+
 pub struct App {
     state: AppState,
     animations: AnimationManager,
     scenes: SceneRegistry,
     overlays: OverlayRegistry,
     themes: ThemeRegistry,
-    active_scene_id: &'static str,
+    active_scene_id: String,
     active_overlay_id: Option<&'static str>,
     weather_receiver: mpsc::Receiver<Result<WeatherData, WeatherError>>,
+    location_receiver: mpsc::Receiver<(WeatherLocation, Option<String>)>,
+    yesterday_receiver: mpsc::Receiver<Option<f64>>,
+    /// Tonight's highest Kp, for places far enough north or south.
+    aurora_receiver: mpsc::Receiver<Option<f64>>,
+    marine_receiver: mpsc::Receiver<Option<MarineConditions>>,
+    air_quality_receiver: mpsc::Receiver<Option<AirQuality>>,
+    normals_receiver: mpsc::Receiver<Option<MonthlyNormals>>,
+    /// Time to the next refresh, stretched to keep within the provider's
+    /// request budget, and a warning once the budget runs low.
+    schedule_receiver: mpsc::Receiver<(Duration, Option<String>)>,
+    /// Pressure readings saved by an earlier session for this location.
+    pressure_receiver: mpsc::Receiver<Vec<(String, f64)>>,
     hide_hud: bool,
+    /// Toggled with `f`: the hourly forecast strip under the HUD.
+    show_forecast: bool,
+    /// Toggled with `c`: how the coming hours feel, as colored blocks.
+    show_comfort: bool,
+    show_details: bool,
+    radar: RadarPanel,
+    history: HistoryPanel,
+    /// Whether fetches are recorded, for the `g` graph to say why it's empty.
+    record_history: bool,
+    footer: Footer,
+    banner: Banner,
+    temperature_colors: TemperatureColors,
+    profiles: Vec<LocationProfile>,
+    profile_index: Option<usize>,
+    /// The `[location]` the session started at, for `g h`.
+    home: (WeatherLocation, Option<String>),
+    base_units: WeatherUnits,
+    /// Tells the fetch task about a location picked with `l`; `None` when
+    /// not showing live weather.
+    switch_tx: Option<mpsc::Sender<(WeatherLocation, WeatherUnits)>>,
+    /// Asks the fetch task for an immediate refresh (`r`); `None` when not
+    /// showing live weather.
+    refresh_tx: Option<mpsc::Sender<()>>,
+    /// Time between automatic refreshes, stretched to fit the provider's
+    /// request budget.
+    refresh_interval: Duration,
+    keymap: Keymap,
+    /// Fewer frames, particles and escape sequences, e.g. over SSH.
+    light_rendering: bool,
+    /// `None` unless some desktop notification is turned on.
+    notifier: Option<Notifier>,
+    #[cfg(feature = "audio")]
+    ambience: Option<crate::audio::Ambience>,
+    /// Whether the terminal has focus; the ambience is muted while it hasn't.
+    #[cfg(feature = "audio")]
+    focused: bool,
 }
 
 impl App {
     pub fn new(
         config: &Config,
-        simulate_condition: Option<String>,
-        simulate_night: bool,
-        show_leaves: bool,
+        options: SessionOptions,
         term_width: u16,
         term_height: u16,
         themes: ThemeRegistry,
     ) -> Self {
+        let SessionOptions {
+            simulate_condition,
+            simulate_transition,
+            simulate_night,
+            show_leaves,
+            replay,
+        } = options;
         let location = WeatherLocation {
             latitude: config.location.latitude,
             longitude: config.location.longitude,
@@ -156,15 +700,82 @@ impl App {
             config.location.hide,
             config.units,
         );
+        state.beaufort = config.beaufort;
+        state.storm_watch = config.storm_watch;
+        state.events = config.events.events();
+        state.icons = config.icons;
         let mut animations = AnimationManager::new(term_width, term_height, show_leaves);
+        animations.set_time_scale(config.animation_speed.unwrap_or(1.0) as f32);
+        let light_rendering = config.rendering.is_light(is_ssh_session());
+        if light_rendering {
+            animations.set_particle_density(LIGHT_PARTICLE_DENSITY);
+        }
 
         let mut scenes = SceneRegistry::new();
-        scenes.register(Box::new(WorldScene::new(term_width, term_height)));
+        let world = |scene: &SceneConfig| {
+            WorldScene::new(
+                term_width,
+                term_height,
+                config.wind_sock,
+                config.bedtime(),
+                scene.clone(),
+            )
+        };
+        scenes.register(Box::new(world(&config.scene)));
+        if let Ok(dir) = Config::get_scenes_dir() {
+            for (name, house) in load_custom_houses(&dir) {
+                match house {
+                    Ok(house) => {
+                        scenes.register(Box::new(world(&config.scene).with_house(name, house)))
+                    }
+                    Err(e) if config.scene.name.as_ref() == Some(&name) => {
+                        eprintln!("Warning: scene '{}' can't be shown: {}", name, e);
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
 
         let overlays = OverlayRegistry::new();
         let bindings = resolve_theme_bindings(&themes, &scenes, &overlays);
+        let mut scene_id = bindings.scene_id.to_string();
+        if let Some(name) = &config.scene.name {
+            match scenes.get(name) {
+                Some(_) => scene_id = name.clone(),
+                None => eprintln!(
+                    "Warning: scene '{}' is not available. Using '{}'.",
+                    name, bindings.scene_id
+                ),
+            }
+        }
+
+        let live =
+            simulate_condition.is_none() && simulate_transition.is_none() && replay.is_none();
+        let provider_kind = config.provider.selected();
+        let refresh_interval =
+            REFRESH_INTERVAL.max(provider::min_refresh_interval(&config.provider));
+        let mut footer = if live {
+            Footer::new(
+                config.footer.position,
+                provider_kind.license_note(),
+                provider_kind.requires_attribution(),
+            )
+        } else {
+            Footer::new(config.footer.position, None, false)
+        };
+        footer.set_status("Awaiting weather data");
 
         let (tx, rx) = mpsc::channel(1);
+        let (location_tx, location_rx) = mpsc::channel(1);
+        let (yesterday_tx, yesterday_rx) = mpsc::channel(1);
+        let (aurora_tx, aurora_rx) = mpsc::channel(1);
+        let (marine_tx, marine_rx) = mpsc::channel(1);
+        let (air_quality_tx, air_quality_rx) = mpsc::channel(1);
+        let (normals_tx, normals_rx) = mpsc::channel(1);
+        let (pressure_tx, pressure_rx) = mpsc::channel(1);
+        let (schedule_tx, schedule_rx) = mpsc::channel(1);
+        let mut switch_tx = None;
+        let mut refresh_tx = None;
 
         if let Some(ref condition_str) = simulate_condition {
             let simulated_condition =
@@ -175,71 +786,250 @@ impl App {
                         WeatherCondition::Clear
                     });
 
-            let weather = WeatherData {
-                condition: simulated_condition,
-                temperature: 20.0,
-                precipitation: if simulated_condition.is_raining() {
-                    2.5
-                } else {
-                    0.0
-                },
-                wind_speed: if simulated_condition.is_thunderstorm() {
-                    45.0
-                } else {
-                    10.0
-                },
-                wind_direction: 225.0,
-                sun: CelestialEvents::from_bool(!simulate_night),
-                moon_phase: Some(0.5),
-                timestamp: "simulated".to_string(),
-                attribution: "".to_string(),
-            };
+            let weather = simulated_weather(simulated_condition, simulate_night);
 
             let rain_intensity = weather.condition.rain_intensity();
-            let snow_intensity = weather.condition.snow_intensity();
+            let snow_intensity = weather.snow_intensity();
             let wind_speed = weather.wind_speed;
             let wind_direction = weather.wind_direction;
+            let wind_gusts = weather.details.wind_gusts;
 
             state.update_weather(weather);
             animations.update_rain_intensity(rain_intensity);
             animations.update_snow_intensity(snow_intensity);
-            animations.update_wind(wind_speed as f32, wind_direction as f32);
-        } else {
-            let wanted_provider = config
-                .provider
-                .keys()
-                .next()
-                .cloned()
-                .unwrap_or(Provider::default());
-
-            let provider: Arc<dyn WeatherProvider> = match wanted_provider {
-                Provider::OpenMeteo => Arc::new(OpenMeteoProvider::new()),
-                Provider::MetOffice => {
-                    let provider_config = {
-                        if let Some(provider_config) = config.provider.get(&wanted_provider) {
-                            MetOfficeProviderConfig::deserialize(provider_config.clone()).unwrap()
-                        } else {
-                            MetOfficeProviderConfig::default()
-                        }
-                    };
-                    Arc::new(MetOfficeProvider::new(provider_config).unwrap())
-                }
-            };
-
-            let weather_client = WeatherClient::new(provider, REFRESH_INTERVAL);
-            let units = config.units;
+            animations.update_wind(
+                ms_to_kmh(wind_speed) as f32,
+                wind_direction as f32,
+                wind_gusts.map(|gusts| ms_to_kmh(gusts) as f32),
+            );
+        } else if let Some(sim) = simulate_transition {
+            let transition = Transition::new(
+                simulated_weather(sim.from, simulate_night),
+                simulated_weather(sim.to, simulate_night),
+            );
 
             tokio::spawn(async move {
+                let started = tokio::time::Instant::now();
                 loop {
-                    let result = weather_client
-                        .get_current_weather(&location, &units, wanted_provider)
-                        .await;
-                    if tx.send(result).await.is_err() {
-                        break;
+                    let progress = started.elapsed().as_secs_f64() / sim.over.as_secs_f64();
+                    let mut weather = transition.at(progress);
+                    weather.attribution = format!(
+                        "Transition {} -> {}: {:.0}%",
+                        sim.from.as_str(),
+                        sim.to.as_str(),
+                        progress.min(1.0) * 100.0
+                    );
+                    if tx.send(Ok(weather)).await.is_err() || progress >= 1.0 {
+                        return;
                     }
-                    tokio::time::sleep(REFRESH_INTERVAL).await;
+                    tokio::time::sleep(TRANSITION_TICK).await;
                 }
             });
+        } else if let Some(replay) = replay {
+            tokio::spawn(async move {
+                let archive = OpenMeteoArchive::new();
+                let frames = match archive
+                    .fetch_hourly(&location, replay.from, replay.to)
+                    .await
+                {
+                    Ok(frames) => frames,
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                };
+
+                let frame_interval = Duration::from_secs_f64(3600.0 / replay.speed);
+                loop {
+                    for mut frame in frames.iter().cloned() {
+                        frame.attribution = format!(
+                            "Replay {} @ {}x | {}",
+                            frame.timestamp.replace('T', " "),
+                            replay.speed,
+                            frame.attribution
+                        );
+                        if tx.send(Ok(frame)).await.is_err() {
+                            return;
+                        }
+                        tokio::time::sleep(frame_interval).await;
+                    }
+                }
+            });
+        } else {
+            match provider::from_config(&config.provider) {
+                Err(error) => {
+                    // Surfaces in the attribution line and falls back to offline weather
+                    let _ = tx.try_send(Err(error));
+                }
+                Ok((wanted_provider, provider)) => {
+                    let weather_client = WeatherClient::new(provider, refresh_interval);
+                    let units = config.units;
+                    let record_history = config.record_history;
+                    // Polling faster in a storm still keeps within the budget
+                    let storm_watch = config
+                        .storm_watch
+                        .then(|| provider::min_refresh_interval(&config.provider));
+                    let (profile_tx, mut switch_rx) = mpsc::channel(1);
+                    switch_tx = Some(profile_tx);
+                    let (manual_tx, mut refresh_rx) = mpsc::channel(1);
+                    refresh_tx = Some(manual_tx);
+                    let roaming = config.location.auto.then(|| Roaming {
+                        current: GeoLocation {
+                            latitude: location.latitude,
+                            longitude: location.longitude,
+                            city: config.location.city.clone(),
+                        },
+                        display: config.location.display,
+                        language: config.location.city_name_language.clone(),
+                    });
+
+                    tokio::spawn(async move {
+                        let mut location = location;
+                        let mut units = units;
+                        let mut roaming = roaming;
+                        let mut last_location_check = Instant::now();
+                        let mut was_offline = false;
+                        let mut yesterday = YesterdayTemperatures::new();
+                        let mut marine = MarineForecast::new();
+                        let mut air_quality = AirQualityForecast::new();
+                        let mut normals = ClimateNormals::new();
+                        let mut kp_forecast = KpForecast::new();
+                        let mut pressure_loaded_for = None;
+                        let mut scheduler = Scheduler::default();
+
+                        loop {
+                            // Sent ahead of the weather, so the saved readings come first
+                            let place = (location.latitude, location.longitude);
+                            if pressure_loaded_for != Some(place) {
+                                pressure_loaded_for = Some(place);
+                                if let Some(saved) =
+                                    cache::load_cached_pressure(place.0, place.1).await
+                                    && pressure_tx.send(saved).await.is_err()
+                                {
+                                    break;
+                                }
+                            }
+
+                            let result = weather_client
+                                .get_current_weather(&location, &units, wanted_provider)
+                                .await;
+                            if record_history
+                                && let Ok(weather) = &result
+                                && !weather_client.last_was_cached()
+                            {
+                                history::record(weather, &location).await;
+                            }
+                            // A fetch succeeding after failures usually means the network changed.
+                            let reconnected = was_offline && result.is_ok();
+                            was_offline = result.is_err();
+                            let unreachable = match &result {
+                                Err(WeatherError::Network(NetworkError::Offline { url })) => {
+                                    Some(url.clone())
+                                }
+                                _ => None,
+                            };
+                            let timestamp = result.as_ref().ok().map(|w| w.timestamp.clone());
+                            if let Ok(weather) = &result {
+                                scheduler = Scheduler::of(weather);
+                            }
+                            let storm_poll = storm_watch.and_then(|min_interval| {
+                                let severity = Severity::of(result.as_ref().ok()?)?;
+                                Some(severity.poll_interval().max(min_interval))
+                            });
+                            if tx.send(result).await.is_err() {
+                                break;
+                            }
+
+                            if let Some(timestamp) = timestamp {
+                                let temperature = yesterday.same_hour(&location, &timestamp).await;
+                                if yesterday_tx.send(temperature).await.is_err() {
+                                    break;
+                                }
+                                let sea = marine.at(&location, scheduler.now().naive_local()).await;
+                                if marine_tx.send(sea).await.is_err() {
+                                    break;
+                                }
+                                let air = air_quality.at(&location, &timestamp).await;
+                                if air_quality_tx.send(air).await.is_err() {
+                                    break;
+                                }
+                                if normals_tx.send(normals.at(&location).await).await.is_err() {
+                                    break;
+                                }
+                                let kp = if location.latitude.abs() >= AURORA_MIN_LATITUDE {
+                                    kp_forecast.tonight().await
+                                } else {
+                                    None
+                                };
+                                if aurora_tx.send(kp).await.is_err() {
+                                    break;
+                                }
+                            }
+
+                            if let Some(roaming) = roaming.as_mut()
+                                && (reconnected
+                                    || last_location_check.elapsed() >= LOCATION_REFRESH_INTERVAL)
+                            {
+                                last_location_check = Instant::now();
+                                if let Some((moved_to, city_name)) = roaming.check_moved().await {
+                                    location = moved_to;
+                                    weather_client.invalidate_cache().await;
+                                    if location_tx.send((location, city_name)).await.is_err() {
+                                        break;
+                                    }
+                                    continue;
+                                }
+                            }
+
+                            let delay = match storm_poll {
+                                Some(interval) => weather_client.refresh_delay_from(interval).await,
+                                None => weather_client.refresh_delay().await,
+                            };
+                            if unreachable.is_none()
+                                && schedule_tx
+                                    .send((delay, weather_client.budget_warning()))
+                                    .await
+                                    .is_err()
+                            {
+                                break;
+                            }
+
+                            let wait = async {
+                                match &unreachable {
+                                    // No provider requests until its host answers again,
+                                    // then refresh straight away
+                                    Some(url) => loop {
+                                        tokio::time::sleep(OFFLINE_PROBE_INTERVAL).await;
+                                        if http::is_reachable(url).await {
+                                            break;
+                                        }
+                                    },
+                                    None => tokio::time::sleep(delay).await,
+                                }
+                            };
+
+                            tokio::select! {
+                                _ = wait => {
+                                    // Sooner than the cached weather expires
+                                    if storm_poll.is_some() {
+                                        weather_client.invalidate_cache().await;
+                                    }
+                                }
+                                Some((switched_to, switched_units)) = switch_rx.recv() => {
+                                    location = switched_to;
+                                    units = switched_units;
+                                    // A picked location wins over the detected one
+                                    roaming = None;
+                                    weather_client.invalidate_cache().await;
+                                }
+                                Some(()) = refresh_rx.recv() => {
+                                    weather_client.invalidate_cache().await;
+                                }
+                            }
+                        }
+                    });
+                }
+            }
         }
 
         Self {
@@ -248,171 +1038,775 @@ impl App {
             scenes,
             overlays,
             themes,
-            active_scene_id: bindings.scene_id,
+            active_scene_id: scene_id,
             active_overlay_id: bindings.overlay_id,
             weather_receiver: rx,
+            location_receiver: location_rx,
+            yesterday_receiver: yesterday_rx,
+            aurora_receiver: aurora_rx,
+            marine_receiver: marine_rx,
+            air_quality_receiver: air_quality_rx,
+            normals_receiver: normals_rx,
+            pressure_receiver: pressure_rx,
+            schedule_receiver: schedule_rx,
             hide_hud: config.hide_hud,
+            show_forecast: false,
+            show_comfort: false,
+            show_details: false,
+            radar: RadarPanel::new(),
+            history: HistoryPanel::new(),
+            record_history: config.record_history,
+            footer,
+            banner: Banner::new(),
+            temperature_colors: config.temperature_colors,
+            profiles: config.locations.clone(),
+            profile_index: config.locations.iter().position(|profile| {
+                profile.latitude == config.location.latitude
+                    && profile.longitude == config.location.longitude
+            }),
+            home: (location, config.location.city.clone()),
+            base_units: config.units,
+            switch_tx,
+            refresh_tx,
+            refresh_interval,
+            keymap: Keymap::new(),
+            light_rendering,
+            notifier: (config.notifications.rain || config.notifications.alerts)
+                .then(|| Notifier::new(config.notifications)),
+            #[cfg(feature = "audio")]
+            ambience: if config.audio.enabled {
+                crate::audio::Ambience::new(config.audio)
+            } else {
+                None
+            },
+            #[cfg(feature = "audio")]
+            focused: true,
         }
     }
 
-    pub async fn run(&mut self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    /// The latest real (not simulated or replayed) weather, with where and
+    /// in which units it is shown.
+    pub fn live_weather(&self) -> Option<(&WeatherData, WeatherLocation, WeatherUnits)> {
+        if self.refresh_tx.is_none() || self.state.is_offline {
+            return None;
+        }
+        let weather = self.state.current_weather.as_ref()?;
+        Some((weather, self.state.location, self.state.units))
+    }
+
+    /// Moves to the next `[[locations]]` profile and refetches its weather.
+    fn cycle_location(&mut self) {
+        let Some(switch_tx) = &self.switch_tx else {
+            return;
+        };
+        if self.profiles.is_empty() {
+            return;
+        }
+
+        let index = self
+            .profile_index
+            .map_or(0, |index| (index + 1) % self.profiles.len());
+        let profile = &self.profiles[index];
+        let location = WeatherLocation {
+            latitude: profile.latitude,
+            longitude: profile.longitude,
+            elevation: None,
+        };
+        let units = profile.units.unwrap_or(self.base_units);
+
+        if switch_tx.try_send((location, units)).is_ok() {
+            self.profile_index = Some(index);
+            self.state.set_location(
+                location,
+                Some(profile.city.clone().unwrap_or_else(|| profile.name.clone())),
+            );
+            self.state.units = units;
+            self.footer
+                .set_status(format!("Switching to {}", profile.name));
+        }
+    }
+
+    /// Goes back to the `[location]` the session started at.
+    fn go_home(&mut self) {
+        let Some(switch_tx) = &self.switch_tx else {
+            return;
+        };
+        let (location, city) = self.home.clone();
+        if switch_tx.try_send((location, self.base_units)).is_ok() {
+            self.profile_index = None;
+            self.state.set_location(location, city);
+            self.state.units = self.base_units;
+            self.footer.set_status("Switching to home location");
+        }
+    }
+
+    /// Runs a key binding; `false` means quit.
+    pub fn apply(&mut self, action: Action) -> bool {
+        match action {
+            Action::Quit => return false,
+            Action::CycleLocation => self.cycle_location(),
+            Action::HomeLocation => self.go_home(),
+            Action::Refresh => self.refresh_now(),
+            Action::ToggleForecast => self.show_forecast = !self.show_forecast,
+            Action::ToggleComfort => self.show_comfort = !self.show_comfort,
+            Action::ToggleDetails => self.show_details = !self.show_details,
+            Action::ToggleRadar => self.radar.toggle(),
+            Action::ToggleHistory => self.history.shown = !self.history.shown,
+            Action::TogglePause => {
+                let paused = !self.animations.is_paused();
+                self.animations.set_paused(paused);
+                #[cfg(feature = "audio")]
+                self.sync_muting();
+            }
+        }
+        true
+    }
+
+    /// Silences the ambience while paused or in a background terminal.
+    #[cfg(feature = "audio")]
+    fn sync_muting(&mut self) {
+        let muted = self.animations.is_paused() || !self.focused;
+        if let Some(ambience) = &mut self.ambience {
+            ambience.set_muted(muted);
+        }
+    }
+
+    /// Starts the countdown to the fetch task's next automatic refresh.
+    fn schedule_next_refresh(&mut self) {
+        if self.refresh_tx.is_some() {
+            self.state
+                .set_next_refresh(Some(Instant::now() + self.refresh_interval));
+        }
+    }
+
+    /// Fetches fresh weather now and restarts the refresh countdown.
+    fn refresh_now(&mut self) {
+        let Some(refresh_tx) = &self.refresh_tx else {
+            return;
+        };
+        if refresh_tx.try_send(()).is_ok() {
+            self.state.set_next_refresh(None);
+            self.footer.set_status("Refreshing weather data");
+        }
+    }
+
+    /// Feels-like color for the HUD temperature, if enabled.
+    fn temperature_color(&self) -> Option<Color> {
+        let colors = self.temperature_colors;
+        let weather = self
+            .state
+            .current_weather
+            .as_ref()
+            .filter(|_| colors.enabled)?;
+        Some(match colors.band(weather.feels_like()) {
+            TemperatureBand::Cold => Color::Blue,
+            TemperatureBand::Mild => Color::Green,
+            TemperatureBand::Warm => Color::Rgb {
+                r: 255,
+                g: 165,
+                b: 0,
+            },
+            TemperatureBand::Hot => Color::Red,
+        })
+    }
+
+    fn render_hud(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let info = &self.state.cached_weather_info;
+        let (Some(temp_color), Some(span)) = (
+            self.temperature_color(),
+            self.state.cached_temperature_span.clone(),
+        ) else {
+            return renderer.render_line_colored(2, 1, info, Color::Cyan);
+        };
+
+        let mut x = 2;
+        for (text, color) in [
+            (&info[..span.start], Color::Cyan),
+            (&info[span.clone()], temp_color),
+            (&info[span.end..], Color::Cyan),
+        ] {
+            renderer.render_line_colored(x, 1, text, color)?;
+            x += display_width(text) as u16;
+        }
+        Ok(())
+    }
+
+    /// The hourly forecast strip, or a note when the provider has none.
+    fn render_forecast(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let hours = self
+            .state
+            .current_weather
+            .as_ref()
+            .map_or(&[][..], |weather| &weather.hourly);
+        if hours.is_empty() {
+            return renderer.render_line_colored(
+                2,
+                FORECAST_ROW,
+                "No hourly forecast from this provider",
+                Color::DarkGrey,
+            );
+        }
+
+        let (width, _) = renderer.get_size();
+        let rows = forecast_strip(hours, self.state.units, self.state.icons, width);
+        for (y, row) in (FORECAST_ROW..).zip(&rows) {
+            renderer.render_line_colored(2, y, row, Color::Cyan)?;
+        }
+        Ok(())
+    }
+
+    /// First row of the `c` comfort strip, below the forecast strip when
+    /// that is shown.
+    fn comfort_row(&self) -> u16 {
+        if self.show_forecast {
+            FORECAST_ROW + 4
+        } else {
+            FORECAST_ROW
+        }
+    }
+
+    /// First row of the detail panel, below whichever strips are shown.
+    fn details_row(&self) -> u16 {
+        if self.show_comfort {
+            self.comfort_row() + 4
+        } else {
+            self.comfort_row()
+        }
+    }
+
+    /// The comfort strip: a block per hour colored by how it feels, the
+    /// hours under it and a key to the colors that appear.
+    fn render_comfort(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let row = self.comfort_row();
+        let hours = self
+            .state
+            .current_weather
+            .as_ref()
+            .map_or(&[][..], |weather| &weather.hourly);
+        if hours.is_empty() {
+            return renderer.render_line_colored(
+                2,
+                row,
+                "No hourly forecast from this provider",
+                Color::DarkGrey,
+            );
+        }
+
+        let (width, _) = renderer.get_size();
+        let (levels, ticks) = comfort_strip(hours, width);
+        for (x, &level) in (2..).step_by(2).zip(&levels) {
+            renderer.render_line_colored(x, row, "██", comfort_color(level))?;
+        }
+        renderer.render_line_colored(2, row + 1, &ticks, Color::DarkGrey)?;
+
+        let mut key = levels.clone();
+        key.sort();
+        key.dedup();
+        let mut x = 2;
+        for level in key {
+            renderer.render_line_colored(x, row + 2, "■", comfort_color(level))?;
+            renderer.render_line_colored(x + 2, row + 2, level.label(), Color::Cyan)?;
+            x += level.label().len() as u16 + 4;
+        }
+        Ok(())
+    }
+
+    /// The detail panel, below the forecast and comfort strips when those
+    /// are shown too.
+    fn render_details(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let row = self.details_row();
+        let Some(weather) = self.state.current_weather.as_ref() else {
+            return renderer.render_line_colored(2, row, "Waiting for weather", Color::DarkGrey);
+        };
+
+        for (y, line) in (row..).zip(detail_lines(
+            weather,
+            self.state.marine.as_ref(),
+            self.state.normals.as_ref(),
+            self.state.units,
+        )) {
+            renderer.render_line_colored(2, y, &line, Color::Cyan)?;
+        }
+        Ok(())
+    }
+
+    /// The radar map at the top right, or why there is none yet.
+    fn render_radar(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let (width, _) = renderer.get_size();
+        let panel_width = (RADAR_COLUMNS * 2).max(RADAR_LEGEND.chars().count()) as u16;
+        let x = width.saturating_sub(panel_width + 2);
+
+        let Some(frame) = self.radar.frame(self.radar.opened_at.elapsed()) else {
+            let note = if self.live_weather().is_none() {
+                "Radar needs live weather"
+            } else if let Some(error) = &self.radar.error {
+                error
+            } else {
+                "Fetching radar"
+            };
+            let x = width.saturating_sub(display_width(note) as u16 + 2);
+            return renderer.render_line_colored(x, FORECAST_ROW, note, Color::DarkGrey);
+        };
+
+        for (y, line) in (FORECAST_ROW..).zip(radar_lines(frame)) {
+            for (dx, (ch, color)) in (0..).zip(line) {
+                renderer.render_char(x + dx, y, ch, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The `g` history graph, below the strips and detail panel when those
+    /// are shown.
+    fn render_history(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let mut row = self.details_row();
+        if self.show_details {
+            let details = self.state.current_weather.as_ref().map_or(1, |weather| {
+                detail_lines(
+                    weather,
+                    self.state.marine.as_ref(),
+                    self.state.normals.as_ref(),
+                    self.state.units,
+                )
+                .len()
+            });
+            row += details as u16 + 1;
+        }
+
+        let lines = match &self.history.records {
+            None => vec![("Reading history".to_string(), Color::DarkGrey)],
+            Some(records) if records.is_empty() && !self.record_history => vec![(
+                "No history: set record_history = true in config.toml".to_string(),
+                Color::DarkGrey,
+            )],
+            Some(records) => {
+                let (width, _) = renderer.get_size();
+                let width = width.saturating_sub(4).clamp(1, HISTORY_MAX_WIDTH);
+                history::graph_lines(
+                    records,
+                    self.state.scheduler.now(),
+                    width as usize,
+                    &self.state.units,
+                )
+            }
+        };
+        for (y, (line, color)) in (row..).zip(lines) {
+            renderer.render_line_colored(2, y, &line, color)?;
+        }
+        Ok(())
+    }
+
+    /// Registered scene ids, for `weathr preview --list`.
+    pub fn scene_ids(&self) -> Vec<&str> {
+        self.scenes.ids()
+    }
+
+    pub fn active_scene(&self) -> &str {
+        &self.active_scene_id
+    }
+
+    /// Shows `id` instead of the theme's scene; `false` if no such scene.
+    pub fn select_scene(&mut self, id: &str) -> bool {
+        if self.scenes.get(id).is_none() {
+            return false;
+        }
+        self.active_scene_id = id.to_string();
+        true
+    }
+
+    /// Takes in updates and draws one frame into `renderer` without
+    /// flushing it, for `weathr compare` to place beside another.
+    pub fn render_pane(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        rng: &mut impl rand::Rng,
+    ) -> io::Result<()> {
+        self.receive_updates(rng);
+        self.render_frame(renderer, rng)
+    }
+
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.animations.on_resize(width, height);
+    }
+
+    /// Renders `frames` frames back to back without drawing them, leaving
+    /// the last one in the renderer's buffer for `print_frame`.
+    pub fn render_preview(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        frames: u32,
+    ) -> io::Result<()> {
         let mut rng = rand::rng();
-        let mut attribution = "Awaiting weather data".to_string();
+        self.animations.set_fixed_step(true);
+        for _ in 0..frames.max(1) {
+            self.receive_updates(&mut rng);
+            self.render_frame(renderer, &mut rng)?;
+        }
+        Ok(())
+    }
 
-        loop {
-            match self.weather_receiver.try_recv() {
-                Ok(result) => match result {
-                    Ok(weather) => {
-                        let rain_intensity = weather.condition.rain_intensity();
-                        let snow_intensity = weather.condition.snow_intensity();
-                        let fog_intensity = weather.condition.fog_intensity();
-                        let wind_speed = weather.wind_speed;
-                        let wind_direction = weather.wind_direction;
-                        attribution = weather.attribution.clone();
-
-                        if let Some(moon_phase) = weather.moon_phase {
-                            self.animations.update_moon_phase(moon_phase);
+    /// Takes in weather, location, yesterday's temperature, the sea state,
+    /// climate normals, the aurora forecast, saved pressure readings, the refresh schedule
+    /// and the radar map sent by background tasks since the last frame.
+    fn receive_updates(&mut self, rng: &mut impl rand::Rng) {
+        if let Ok((location, city_name)) = self.location_receiver.try_recv() {
+            self.state.set_location(location, city_name);
+        }
+
+        if let Ok(temperature) = self.yesterday_receiver.try_recv() {
+            self.state.set_yesterday_temperature(temperature);
+        }
+
+        if let Ok(marine) = self.marine_receiver.try_recv() {
+            self.state.marine = marine;
+        }
+
+        if let Ok(air_quality) = self.air_quality_receiver.try_recv() {
+            self.state.air_quality = air_quality;
+        }
+
+        if let Ok(normals) = self.normals_receiver.try_recv() {
+            self.state.normals = normals;
+        }
+
+        if let Ok(kp) = self.aurora_receiver.try_recv() {
+            self.state.set_aurora_kp(kp);
+        }
+
+        if let Ok(saved) = self.pressure_receiver.try_recv() {
+            self.state.pressure_history.restore(&saved);
+        }
+
+        if self.radar.receive() {
+            let storm = self.radar.map.as_ref().and_then(RadarMap::storm);
+            self.state.set_storm(storm);
+        }
+        if self.notifier.is_some() && self.live_weather().is_some() {
+            let rain_due_in = self.state.precipitation_due_in();
+            let place = self.state.city_name.clone().unwrap_or_else(|| {
+                format!(
+                    "{:.2}, {:.2}",
+                    self.state.location.latitude, self.state.location.longitude
+                )
+            });
+            if let (Some(notifier), Some(weather)) =
+                (self.notifier.as_mut(), self.state.current_weather.as_ref())
+            {
+                notifier.check(weather, rain_due_in, &place);
+            }
+        }
+
+        self.history.receive();
+        if self.history.shown {
+            self.history.request(self.state.location);
+        }
+
+        // The radar also tracks how far off a thunderstorm is
+        if (self.radar.shown || self.state.weather_conditions.is_thunderstorm)
+            && let Some((_, location, _)) = self.live_weather()
+        {
+            self.radar.request(location);
+        }
+
+        match self.weather_receiver.try_recv() {
+            Ok(result) => match result {
+                Ok(weather) => {
+                    let rain_intensity = weather.condition.rain_intensity();
+                    let snow_intensity = weather.snow_intensity();
+                    let fog_intensity = weather.condition.fog_intensity();
+                    let wind_speed = weather.wind_speed;
+                    let wind_direction = weather.wind_direction;
+                    let wind_gusts = weather.details.wind_gusts;
+                    self.footer.clear();
+                    self.footer.set_attribution(weather.attribution.clone());
+                    self.schedule_next_refresh();
+                    if let Some(change) = self.state.condition_change(&weather) {
+                        self.banner.show(change);
+                    }
+
+                    if let Some(moon_phase) = weather.moon_phase {
+                        self.animations.update_moon_phase(moon_phase);
+                    }
+
+                    let has_pressure = weather.pressure.is_some();
+                    self.state.update_weather(weather);
+                    if self.refresh_tx.is_some() && has_pressure {
+                        let location = self.state.location;
+                        cache::save_pressure_cache(
+                            self.state.pressure_history.samples(),
+                            location.latitude,
+                            location.longitude,
+                        );
+                    }
+                    self.animations.update_rain_intensity(rain_intensity);
+                    self.animations.update_snow_intensity(snow_intensity);
+                    self.animations.update_fog_intensity(fog_intensity);
+                    self.animations.update_wind(
+                        ms_to_kmh(wind_speed) as f32,
+                        wind_direction as f32,
+                        wind_gusts.map(|gusts| ms_to_kmh(gusts) as f32),
+                    );
+                }
+                Err(WeatherError::Network(NetworkError::RateLimited {
+                    retry_after: Some(delay),
+                    ..
+                })) if self.state.current_weather.is_some() => {
+                    // The weather shown is still recent; just say when it updates
+                    self.state.set_next_refresh(Some(Instant::now() + delay));
+                    self.footer
+                        .set_status(rate_limited_status(self.state.scheduler.now(), delay));
+                }
+                Err(WeatherError::Data(error @ DataError::Implausible(_)))
+                    if self.state.current_weather.is_some() =>
+                {
+                    // The last good observation stays up
+                    self.schedule_next_refresh();
+                    self.footer.set_status(error.to_string());
+                }
+                Err(error) => {
+                    if matches!(error, WeatherError::Network(NetworkError::Offline { .. })) {
+                        // Refetched as soon as the network is back
+                        self.state.set_next_refresh(None);
+                    } else {
+                        self.schedule_next_refresh();
+                    }
+                    let status = match &error {
+                        WeatherError::Network(NetworkError::RateLimited {
+                            retry_after: Some(delay),
+                            ..
+                        }) => {
+                            self.state.set_next_refresh(Some(Instant::now() + *delay));
+                            rate_limited_status(self.state.scheduler.now(), *delay)
                         }
+                        WeatherError::Network(NetworkError::Offline { .. }) => {
+                            "No network connection, updating once it's back".to_string()
+                        }
+                        WeatherError::Network(net_err) => {
+                            format!("Provider failed with {}", net_err.user_friendly_message())
+                        }
+                        _ => format!("Provider failed with Failed to fetch weather: {}", error),
+                    };
 
-                        self.state.update_weather(weather);
+                    if self.state.current_weather.is_none() {
+                        self.footer.set_status(format!("{status} - Simulating"));
+                        let offline_weather =
+                            generate_offline_weather(rng, self.state.scheduler.now());
+                        let rain_intensity = offline_weather.condition.rain_intensity();
+                        let snow_intensity = offline_weather.snow_intensity();
+                        let fog_intensity = offline_weather.condition.fog_intensity();
+                        let wind_speed = offline_weather.wind_speed;
+                        let wind_direction = offline_weather.wind_direction;
+                        let wind_gusts = offline_weather.details.wind_gusts;
+
+                        self.state.update_weather(offline_weather);
+                        self.state.set_offline_mode(true);
                         self.animations.update_rain_intensity(rain_intensity);
                         self.animations.update_snow_intensity(snow_intensity);
                         self.animations.update_fog_intensity(fog_intensity);
-                        self.animations
-                            .update_wind(wind_speed as f32, wind_direction as f32);
-                    }
-                    Err(error) => {
-                        let error_msg = match &error {
-                            WeatherError::Network(net_err) => net_err.user_friendly_message(),
-                            _ => format!("Failed to fetch weather: {}", error),
-                        };
-
-                        if self.state.current_weather.is_none() {
-                            attribution = format!("Provider failed with {error_msg} - Simulating");
-                            let offline_weather = generate_offline_weather(&mut rng);
-                            let rain_intensity = offline_weather.condition.rain_intensity();
-                            let snow_intensity = offline_weather.condition.snow_intensity();
-                            let fog_intensity = offline_weather.condition.fog_intensity();
-                            let wind_speed = offline_weather.wind_speed;
-                            let wind_direction = offline_weather.wind_direction;
-
-                            self.state.update_weather(offline_weather);
-                            self.state.set_offline_mode(true);
-                            self.animations.update_rain_intensity(rain_intensity);
-                            self.animations.update_snow_intensity(snow_intensity);
-                            self.animations.update_fog_intensity(fog_intensity);
-                            self.animations
-                                .update_wind(wind_speed as f32, wind_direction as f32);
-                        } else {
-                            self.state.set_offline_mode(true);
-                            attribution = format!("Provider failed with {error_msg}");
-                        }
-                    }
-                },
-                Err(e) => {
-                    if e == mpsc::error::TryRecvError::Disconnected {
-                        attribution = "".to_string();
+                        self.animations.update_wind(
+                            ms_to_kmh(wind_speed) as f32,
+                            wind_direction as f32,
+                            wind_gusts.map(|gusts| ms_to_kmh(gusts) as f32),
+                        );
+                    } else {
+                        self.state.set_offline_mode(true);
+                        self.footer.set_status(status);
                     }
                 }
+            },
+            Err(e) => {
+                if e == mpsc::error::TryRecvError::Disconnected {
+                    self.footer.clear();
+                }
             }
+        }
 
-            renderer.clear()?;
-
-            let theme = self.themes.active();
-            let palette = &theme.palette;
+        // Sent after the fetch, so it has the last word on the countdown
+        if let Ok((delay, budget_warning)) = self.schedule_receiver.try_recv() {
+            self.state.set_next_refresh(Some(Instant::now() + delay));
+            if let Some(warning) = budget_warning {
+                self.footer.set_status(warning);
+            }
+        }
 
-            let (term_width, term_height) = renderer.get_size();
-            let scene = self
-                .scenes
-                .get_mut(self.active_scene_id)
-                .expect("active scene must be registered");
-            scene.update_size(term_width, term_height);
+        #[cfg(feature = "audio")]
+        if let Some(ambience) = &self.ambience {
+            ambience.update(&self.state);
+        }
+    }
 
-            let layout = scene.layout();
-            let ctx = SceneContext {
-                conditions: &self.state.weather_conditions,
-                palette,
-            };
+    fn render_frame(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        rng: &mut impl rand::Rng,
+    ) -> io::Result<()> {
+        renderer.clear()?;
+
+        let theme = self.themes.active();
+        let palette = &theme.palette;
+
+        let (term_width, term_height) = renderer.get_size();
+        let scene = self
+            .scenes
+            .get_mut(&self.active_scene_id)
+            .expect("active scene must be registered");
+        scene.update_size(term_width, term_height);
+
+        let layout = scene.layout();
+        self.animations.begin_frame();
+        let now = self.state.scheduler.now();
+        self.state.update_wetness(Instant::now());
+        self.state.update_daylight(self.animations.elapsed_secs());
+        self.state.holiday = self.state.events.at(now.naive_local());
+        let ctx = SceneContext {
+            conditions: &self.state.weather_conditions,
+            palette,
+            weather: self.state.current_weather.as_ref(),
+            units: self.state.units,
+            tree_charred: self.state.tree_charred,
+            marine: self.state.marine.as_ref(),
+            now,
+            season: Season::at(self.state.location.latitude, now.date_naive()),
+            wetness: self.state.wetness.level(),
+            daylight: self.state.daylight.level(),
+            storm_watch: self.state.storm_watch_severity(),
+            holiday: self.state.holiday,
+            animation_secs: self.animations.elapsed_secs(),
+        };
 
-            self.animations.render_background(
-                renderer,
-                &self.state.weather_conditions,
-                &self.state,
-                &layout,
-                &mut rng,
-            )?;
+        self.animations.render_background(
+            renderer,
+            &self.state.weather_conditions,
+            &self.state,
+            &layout,
+            rng,
+        )?;
 
-            scene.render(renderer, &ctx)?;
+        scene.render(renderer, &ctx)?;
 
-            if let Some(ov_id) = self.active_overlay_id {
-                if let Some(overlay) = self.overlays.get_mut(ov_id) {
-                    overlay.update_size(term_width, term_height);
-                    overlay.render(renderer, &ctx, &layout)?;
-                }
+        if let Some(ov_id) = self.active_overlay_id {
+            if let Some(overlay) = self.overlays.get_mut(ov_id) {
+                overlay.update_size(term_width, term_height);
+                overlay.render(renderer, &ctx, &layout)?;
             }
+        }
 
-            self.animations.render_chimney_smoke(
-                renderer,
-                &self.state.weather_conditions,
-                &self.state,
-                &layout,
-                &mut rng,
-            )?;
-
-            self.animations.render_foreground(
-                renderer,
-                &self.state.weather_conditions,
-                &self.state,
-                &layout,
-                &mut rng,
-            )?;
-
-            self.state.update_loading_animation();
-            self.state.update_cached_info();
-
-            if !self.hide_hud {
-                renderer.render_line_colored(
-                    2,
-                    1,
-                    &self.state.cached_weather_info,
-                    crossterm::style::Color::Cyan,
-                )?;
-            }
+        self.animations.render_chimney_smoke(
+            renderer,
+            &self.state.weather_conditions,
+            &self.state,
+            &layout,
+            rng,
+        )?;
+
+        self.animations.render_foreground(
+            renderer,
+            &self.state.weather_conditions,
+            &self.state,
+            &layout,
+            rng,
+        )?;
+        if self.animations.take_tree_struck() {
+            self.state.tree_charred = true;
+        }
 
-            let attribution_x = if term_width > attribution.len() as u16 {
-                term_width - attribution.len() as u16 - 2
-            } else {
-                0
+        self.state.update_loading_animation();
+        self.state.update_refresh_countdown();
+        self.state.update_sun_countdown();
+        self.state.update_storm_countdown();
+        self.state.update_cached_info();
+
+        let severity = self
+            .state
+            .current_weather
+            .as_ref()
+            .filter(|_| !self.state.is_offline)
+            .and_then(Severity::of);
+        if let Some(severity) = severity {
+            let color = match severity {
+                Severity::Watch => Color::Yellow,
+                Severity::Warning => Color::Red,
             };
-            let attribution_y = if term_height > 0 { term_height - 1 } else { 0 };
-            renderer.render_line_colored(
-                attribution_x,
-                attribution_y,
-                &attribution,
-                crossterm::style::Color::DarkGrey,
-            )?;
+            renderer.render_border(color)?;
+        }
+
+        if !self.hide_hud {
+            self.render_hud(renderer)?;
+        }
+        if self.show_forecast {
+            self.render_forecast(renderer)?;
+        }
+        if self.show_comfort {
+            self.render_comfort(renderer)?;
+        }
+        if self.show_details {
+            self.render_details(renderer)?;
+        }
+        if self.radar.shown {
+            self.render_radar(renderer)?;
+        }
+        if self.history.shown {
+            self.render_history(renderer)?;
+        }
+
+        if self.animations.is_paused() {
+            let x = term_width.saturating_sub(PAUSED_LABEL.len() as u16 + 2);
+            renderer.render_line_colored(x, 1, PAUSED_LABEL, Color::Yellow)?;
+        }
+
+        self.banner.render(renderer)?;
+        self.footer.render(renderer)?;
+
+        Ok(())
+    }
+
+    /// Sets `renderer` up for the rendering profile and returns the time
+    /// between frames.
+    pub fn prepare_renderer(&self, renderer: &mut TerminalRenderer) -> Duration {
+        renderer.set_bridge_gaps(self.light_rendering);
+        if self.light_rendering {
+            Duration::from_millis(1000 / LIGHT_FPS)
+        } else {
+            FRAME_DURATION
+        }
+    }
 
+    pub async fn run(&mut self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let mut rng = rand::rng();
+        let frame_duration = self.prepare_renderer(renderer);
+
+        loop {
+            self.receive_updates(&mut rng);
+            self.render_frame(renderer, &mut rng)?;
             renderer.flush()?;
 
-            if event::poll(FRAME_DURATION)? {
+            if event::poll(frame_duration)? {
                 match event::read()? {
                     Event::Resize(width, height) => {
                         renderer.manual_resize(width, height)?;
                         let (new_width, new_height) = renderer.get_size();
                         self.animations.on_resize(new_width, new_height);
                     }
-                    Event::Key(key_event) => match key_event.code {
-                        KeyCode::Char('q') | KeyCode::Char('Q') => break,
-                        KeyCode::Char('c')
-                            if key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+                    Event::Key(key_event) => {
+                        if let Some(action) = self.keymap.handle(key_event)
+                            && !self.apply(action)
                         {
                             break;
                         }
-                        _ => {}
-                    },
+                    }
+                    #[cfg(feature = "audio")]
+                    focus @ (Event::FocusGained | Event::FocusLost) => {
+                        self.focused = focus == Event::FocusGained;
+                        self.sync_muting();
+                    }
                     _ => {}
                 }
             }
+            if let Some(action) = self.keymap.tick()
+                && !self.apply(action)
+            {
+                break;
+            }
         }
 
         Ok(())
@@ -427,6 +1821,9 @@ mod tests {
     use crate::scene::{Scene, SceneContext, SceneLayout};
     use crate::theme::catalogue::DEFAULT_PALETTE;
     use crate::theme::{Theme, ThemeRegistry};
+    use crate::weather::marine::Tide;
+    use crate::weather::types::TemperatureRange;
+    use crate::weather::types::{CloudLayer, CloudLevel, WeatherDetails};
     use std::io;
 
     struct TestScene {
@@ -440,7 +1837,7 @@ mod tests {
     }
 
     impl Scene for TestScene {
-        fn id(&self) -> &'static str {
+        fn id(&self) -> &str {
             self.id
         }
 
@@ -458,6 +1855,8 @@ mod tests {
             SceneLayout {
                 ground_y: 0,
                 chimney_pos: None,
+                ac_unit_pos: None,
+                tree_pos: None,
                 width: 0,
                 height: 0,
             }
@@ -559,4 +1958,163 @@ mod tests {
         assert_eq!(bindings.theme_id, "overlay");
         assert_eq!(bindings.overlay_id, Some("hud"));
     }
+
+    #[test]
+    fn forecast_strip_fits_hours_to_width() {
+        let hour = |time: &str, condition, temperature, chance| HourlyForecast {
+            time: time.to_string(),
+            condition,
+            temperature,
+            precipitation_probability: chance,
+            humidity: None,
+        };
+        let hours = [
+            hour("2024-01-01T13:00", WeatherCondition::Clear, 20.4, Some(0.0)),
+            hour("2024-01-01T14:00", WeatherCondition::Rain, 18.0, Some(65.0)),
+            hour("2024-01-01T15:00", WeatherCondition::Rain, 17.0, None),
+        ];
+
+        let rows = forecast_strip(&hours, WeatherUnits::metric(), IconSet::Unicode, 20);
+        assert_eq!(rows[0], "13:00  14:00");
+        assert_eq!(rows[1], "☼ 20°  ☂ 18°");
+        assert_eq!(rows[2], "0%     65%");
+
+        let rows = forecast_strip(&hours, WeatherUnits::imperial(), IconSet::Unicode, 80);
+        assert_eq!(rows[1], "☼ 69°  ☂ 64°  ☂ 63°");
+
+        let rows = forecast_strip(&hours, WeatherUnits::metric(), IconSet::Ascii, 80);
+        assert_eq!(rows[1], "O 20°  / 18°  / 17°");
+        let rows = forecast_strip(&hours, WeatherUnits::metric(), IconSet::Nerd, 80);
+        assert_eq!(rows[1], "\u{e30d} 20°  \u{e318} 18°  \u{e318} 17°");
+    }
+
+    #[test]
+    fn comfort_strip_marks_every_third_hour() {
+        let hours: Vec<_> = (0..30)
+            .map(|i| HourlyForecast {
+                time: format!("2024-07-01T{:02}:00", (20 + i) % 24),
+                condition: WeatherCondition::Clear,
+                temperature: if i < 4 { 26.0 } else { 20.0 },
+                precipitation_probability: None,
+                humidity: Some(if i < 4 { 80.0 } else { 50.0 }),
+            })
+            .collect();
+
+        let (levels, ticks) = comfort_strip(&hours, 20);
+        // Muggy until midnight, then pleasant
+        assert_eq!(levels[..4], [Comfort::Oppressive; 4]);
+        assert_eq!(levels[4..], [Comfort::Pleasant; 4]);
+        assert_eq!(ticks, "20    23    02");
+
+        // Never more than a day, however wide
+        let (levels, _) = comfort_strip(&hours, 200);
+        assert_eq!(levels.len(), COMFORT_HOURS);
+    }
+
+    #[test]
+    fn detail_lines_show_what_applies() {
+        let mut weather = generate_offline_weather(&mut rand::rng(), Scheduler::default().now());
+        weather.temperature = -5.0;
+        weather.wind_speed = 8.0;
+        weather.details = WeatherDetails {
+            dew_point: Some(-9.0),
+            wind_gusts: Some(15.0),
+            visibility: None,
+            cloud_layers: vec![CloudLayer {
+                level: CloudLevel::Low,
+                cover: 75.0,
+            }],
+            ..WeatherDetails::default()
+        };
+
+        let lines = detail_lines(&weather, None, None, WeatherUnits::metric());
+        assert_eq!(
+            lines,
+            [
+                "Dew point   -9.0°C",
+                "Wind chill  -12.8°C",
+                "Gusts       54.0km/h",
+                "Clouds      low 75%",
+                "Visibility  —",
+            ]
+        );
+
+        weather.temperature = 30.0;
+        weather.details.dew_point = Some(15.0);
+        weather.details.visibility = Some(16093.44);
+        weather.details.snow_depth = Some(0.32);
+        let marine = MarineConditions {
+            wave_height: Some(1.2),
+            sea_level: Some(-0.3),
+            tide: Some(Tide::Falling),
+            tide_fraction: Some(0.25),
+        };
+        let lines = detail_lines(&weather, Some(&marine), None, WeatherUnits::imperial());
+        assert_eq!(lines[1], "Humidex     34");
+        assert_eq!(lines[4], "Visibility  10.0 mi");
+        assert_eq!(lines[5], "Snow depth  13in");
+        assert_eq!(lines[6], "Waves       3.9 ft");
+        assert_eq!(lines[7], "Tide        falling, -1.0 ft");
+    }
+
+    #[test]
+    fn detail_lines_compare_today_with_the_normal() {
+        let mut weather = generate_offline_weather(&mut rand::rng(), Scheduler::default().now());
+        weather.timestamp = "2026-02-10T14:00".to_string();
+        weather.today = Some(TemperatureRange {
+            min: 2.0,
+            max: 10.4,
+        });
+        let mut normals = MonthlyNormals { means: [0.0; 12] };
+        normals.means[1] = 3.0;
+
+        let climate = |weather: &WeatherData, normals: &MonthlyNormals, units| {
+            detail_lines(weather, None, Some(normals), units)
+                .into_iter()
+                .find(|line| line.starts_with("Climate"))
+        };
+        assert_eq!(
+            climate(&weather, &normals, WeatherUnits::metric()).as_deref(),
+            Some("Climate     +3.2°C above average for February")
+        );
+        normals.means[1] = 7.0;
+        assert_eq!(
+            climate(&weather, &normals, WeatherUnits::imperial()).as_deref(),
+            Some("Climate     -1.4°F below average for February")
+        );
+
+        weather.today = None;
+        assert_eq!(climate(&weather, &normals, WeatherUnits::metric()), None);
+    }
+
+    #[test]
+    fn radar_loops_and_holds_the_latest_frame() {
+        let frame = |time: &str, rain: f64| RadarFrame {
+            time: time.to_string(),
+            cells: vec![rain; RADAR_COLUMNS * RADAR_ROWS],
+            thunder: Vec::new(),
+        };
+        let mut panel = RadarPanel::new();
+        assert!(panel.frame(Duration::ZERO).is_none());
+        panel.map = Some(RadarMap {
+            frames: vec![
+                frame("2026-10-16T14:00", 0.0),
+                frame("2026-10-16T14:15", 2.0),
+            ],
+        });
+
+        let time_at = |ms| panel.frame(Duration::from_millis(ms)).unwrap().time.clone();
+        assert_eq!(time_at(0), "2026-10-16T14:00");
+        assert_eq!(time_at(700), "2026-10-16T14:15");
+        assert_eq!(time_at(2100), "2026-10-16T14:15");
+        assert_eq!(time_at(2800), "2026-10-16T14:00");
+
+        let lines = radar_lines(panel.frame(Duration::from_millis(700)).unwrap());
+        let text = |line: &[(char, Color)]| line.iter().map(|(c, _)| c).collect::<String>();
+        assert_eq!(text(&lines[0]), "Radar 14:15");
+        assert_eq!(text(&lines[1]), "▒".repeat(RADAR_COLUMNS * 2));
+        let middle = text(&lines[1 + RADAR_ROWS / 2]);
+        assert_eq!(middle.chars().nth(RADAR_COLUMNS / 2 * 2), Some('+'));
+        assert_eq!(text(&lines[RADAR_ROWS + 1]), RADAR_LEGEND);
+    }
 }
@@ -1,22 +1,98 @@
 use crate::config::LocationDisplay;
+use crate::scene::daylight::Daylight;
+use crate::scene::events::{Events, Holiday};
+use crate::scene::wetness::Wetness;
+use crate::scheduler::Scheduler;
+use crate::weather::air_quality::AirQuality;
+use crate::weather::aurora::kp_needed;
+use crate::weather::marine::MarineConditions;
+use crate::weather::normals::MonthlyNormals;
+use crate::weather::pressure::PressureHistory;
+use crate::weather::radar::Storm;
+use crate::weather::severity::{self, Severity};
+use crate::weather::types::{Disagreement, IconSet, TemperatureUnit};
+use crate::weather::units::format_snow;
 use crate::weather::{
-    WeatherCondition, WeatherConditions, WeatherData, WeatherLocation, WeatherUnits,
-    format_precipitation, format_temperature, format_wind_speed,
+    SnowIntensity, WeatherCondition, WeatherConditions, WeatherData, WeatherLocation, WeatherUnits,
+    astro, beaufort_description, format_distance, format_precipitation, format_temperature,
+    format_wind_speed,
 };
+use chrono::{DateTime, FixedOffset, NaiveDateTime, NaiveTime, TimeDelta};
+use std::ops::Range;
 use std::time::Instant;
 
+/// Length of a nowcast step.
+const NOWCAST_STEP_MINUTES: u64 = 15;
+/// Nowcast rate (mm/h) from which a step counts as wet.
+const NOWCAST_WET_RATE: f64 = 0.1;
+/// Nowcast steps with a lower chance of precipitation are ignored.
+const NOWCAST_LIKELY_PERCENT: f64 = 50.0;
+
+/// Relative humidity (%) from which clear mornings turn misty.
+const MIST_HUMIDITY: f64 = 85.0;
+/// Wind (m/s) above which the mist is stirred away.
+const MIST_MAX_WIND: f64 = 2.0;
+/// The mist gathers this long before sunrise,
+const MIST_BEFORE_SUNRISE_MINUTES: i64 = 60;
+/// lies thickest until this long after it,
+const MIST_THICK_MINUTES: i64 = 30;
+/// and is gone this long after that.
+const MIST_BURN_OFF_MINUTES: i64 = 120;
+
 pub struct AppState {
     pub current_weather: Option<WeatherData>,
     pub is_offline: bool,
     pub weather_conditions: WeatherConditions,
     pub loading_state: LoadingState,
     pub cached_weather_info: String,
+    /// Byte range of the temperature value within `cached_weather_info`.
+    pub cached_temperature_span: Option<Range<usize>>,
     pub weather_info_needs_update: bool,
     pub location: WeatherLocation,
     pub city_name: Option<String>,
+    /// The clock at `location`.
+    pub scheduler: Scheduler,
     pub location_display: LocationDisplay,
     pub hide_location: bool,
     pub units: WeatherUnits,
+    /// Temperature (°C) at the same hour yesterday, when known.
+    pub yesterday_temperature: Option<f64>,
+    /// Name the wind's Beaufort force after its speed.
+    pub beaufort: bool,
+    /// Nerd Font icons also go before the condition.
+    pub icons: IconSet,
+    /// Count down to the end of severe weather and darken the scene for it.
+    pub storm_watch: bool,
+    /// Dates the yard is decorated for,
+    pub events: Events,
+    /// and what's up for today.
+    pub holiday: Holiday,
+    /// Waves and tide, for a place by the sea.
+    pub marine: Option<MarineConditions>,
+    /// Pollen and pollution, drawn as motes in the air.
+    pub air_quality: Option<AirQuality>,
+    /// This place's monthly climate normals, for the detail panel.
+    pub normals: Option<MonthlyNormals>,
+    /// The highest Kp forecast for the coming night, at high latitudes.
+    pub aurora_kp: Option<f64>,
+    /// The nearest thunderstorm on the radar, while there is one.
+    pub storm: Option<Storm>,
+    /// Recent pressure readings for the barometer tendency.
+    pub pressure_history: PressureHistory,
+    /// When the next automatic fetch is due, for live weather.
+    pub next_refresh: Option<Instant>,
+    /// Whole seconds until `next_refresh` as last shown in the HUD.
+    refresh_countdown: Option<u64>,
+    /// Lightning hit the tree this session.
+    pub tree_charred: bool,
+    /// How wet the rain has left the ground.
+    pub wetness: Wetness,
+    /// How far day has faded into night, or back.
+    pub daylight: Daylight,
+    /// Minutes to the next sunrise or sunset as last shown in the HUD.
+    sun_countdown: Option<i64>,
+    /// Minutes until severe weather eases as last shown in the HUD.
+    storm_countdown: Option<i64>,
 }
 
 impl AppState {
@@ -33,12 +109,33 @@ impl AppState {
             weather_conditions: WeatherConditions::default(),
             loading_state: LoadingState::new(),
             cached_weather_info: String::new(),
+            cached_temperature_span: None,
             weather_info_needs_update: true,
             location,
             city_name,
+            scheduler: Scheduler::default(),
             location_display,
             hide_location,
             units,
+            yesterday_temperature: None,
+            beaufort: false,
+            icons: IconSet::default(),
+            storm_watch: false,
+            events: Events::default(),
+            holiday: Holiday::default(),
+            storm: None,
+            aurora_kp: None,
+            marine: None,
+            air_quality: None,
+            normals: None,
+            pressure_history: PressureHistory::new(),
+            next_refresh: None,
+            refresh_countdown: None,
+            tree_charred: false,
+            wetness: Wetness::default(),
+            daylight: Daylight::default(),
+            sun_countdown: None,
+            storm_countdown: None,
         }
     }
 
@@ -50,12 +147,248 @@ impl AppState {
         self.weather_conditions.is_cloudy = weather.condition.is_cloudy();
         self.weather_conditions.is_foggy = weather.condition.is_foggy();
         self.weather_conditions.sun = weather.sun;
+        if let Some(pressure) = weather.pressure {
+            self.pressure_history.record(&weather.timestamp, pressure);
+        }
+        if let Some(zone) = &weather.details.timezone {
+            self.scheduler = Scheduler::for_zone(zone);
+        }
 
         self.current_weather = Some(weather);
         self.is_offline = false;
         self.weather_info_needs_update = true;
     }
 
+    /// Banner text for a refresh that changes the condition, e.g.
+    /// "Rain starting — 2.5 mm/h". `None` for the first reading or when
+    /// replacing simulated offline weather.
+    pub fn condition_change(&self, next: &WeatherData) -> Option<String> {
+        let previous = self.current_weather.as_ref().filter(|_| !self.is_offline)?;
+        if previous.condition == next.condition {
+            return None;
+        }
+
+        let precipitating = |condition: WeatherCondition| {
+            condition.is_raining() || condition.is_snowing() || condition.is_thunderstorm()
+        };
+
+        Some(if precipitating(next.condition) {
+            let (amount, unit) = format_precipitation(next.precipitation, self.units.precipitation);
+            if amount > 0.0 {
+                format!(
+                    "{} starting — {:.1} {}/h",
+                    next.condition.label(),
+                    amount,
+                    unit
+                )
+            } else {
+                format!("{} starting", next.condition.label())
+            }
+        } else if precipitating(previous.condition) {
+            format!(
+                "{} ending — now {}",
+                previous.condition.label(),
+                next.condition.label()
+            )
+        } else {
+            format!(
+                "{} → {}",
+                previous.condition.label(),
+                next.condition.label()
+            )
+        })
+    }
+
+    pub fn set_location(&mut self, location: WeatherLocation, city_name: Option<String>) {
+        self.location = location;
+        self.city_name = city_name;
+        self.scheduler = Scheduler::default();
+        self.yesterday_temperature = None;
+        self.marine = None;
+        self.wetness = Wetness::default();
+        self.air_quality = None;
+        self.normals = None;
+        self.storm = None;
+        self.aurora_kp = None;
+        self.pressure_history.clear();
+        self.weather_info_needs_update = true;
+    }
+
+    pub fn set_aurora_kp(&mut self, kp: Option<f64>) {
+        if kp != self.aurora_kp {
+            self.aurora_kp = kp;
+            self.weather_info_needs_update = true;
+        }
+    }
+
+    /// Tonight's Kp when it's dark and the aurora reaches this far from
+    /// the pole.
+    fn aurora_kp_tonight(&self) -> Option<f64> {
+        self.aurora_kp.filter(|&kp| {
+            !self.weather_conditions.sun.is_day && kp >= kp_needed(self.location.latitude)
+        })
+    }
+
+    /// " | Aurora possible tonight (Kp 6)".
+    fn aurora_text(&self) -> String {
+        self.aurora_kp_tonight()
+            .map(|kp| format!(" | Aurora possible tonight (Kp {:.0})", kp))
+            .unwrap_or_default()
+    }
+
+    /// How bright the aurora is drawn, from 0 (not at all) to 1: brighter
+    /// the more the Kp exceeds what it takes to reach here, and only under
+    /// a mostly clear sky.
+    pub fn aurora_strength(&self) -> f32 {
+        let Some(kp) = self.aurora_kp_tonight() else {
+            return 0.0;
+        };
+        let clear = self.current_weather.as_ref().is_some_and(|weather| {
+            matches!(
+                weather.condition,
+                WeatherCondition::Clear
+                    | WeatherCondition::MainlyClear
+                    | WeatherCondition::PartlyCloudy
+            )
+        });
+        if !clear {
+            return 0.0;
+        }
+        let excess = kp - kp_needed(self.location.latitude);
+        ((excess as f32 + 1.0) / 4.0).clamp(0.25, 1.0)
+    }
+
+    pub fn set_storm(&mut self, storm: Option<Storm>) {
+        if storm != self.storm {
+            self.storm = storm;
+            self.weather_info_needs_update = true;
+        }
+    }
+
+    /// " | Storm ~8 km away, approaching" in a thunderstorm, once the radar
+    /// has found it.
+    fn storm_text(&self) -> String {
+        let Some(storm) = self
+            .storm
+            .filter(|_| self.weather_conditions.is_thunderstorm)
+        else {
+            return String::new();
+        };
+        if storm.distance_km < 1.0 {
+            return " | Storm overhead".to_string();
+        }
+
+        let (distance, unit) = format_distance(storm.distance_km * 1000.0, self.units.distance);
+        let movement = storm
+            .movement
+            .map(|movement| format!(", {}", movement.label()))
+            .unwrap_or_default();
+        format!(" | Storm ~{:.0} {} away{}", distance, unit, movement)
+    }
+
+    /// " | Snow: 1.5cm/h, 32cm deep" while it snows, with what the provider
+    /// reports of both; "Blizzard" instead of "Snow" when it is one.
+    fn snow_text(&self, weather: &WeatherData) -> String {
+        if !self.weather_conditions.is_snowing {
+            return String::new();
+        }
+        let amount = |cm: f64, precision: usize| {
+            let (value, unit) = format_snow(cm, self.units.precipitation);
+            format!("{:.*}{}", precision, value, unit)
+        };
+        let figures: Vec<String> = [
+            weather
+                .details
+                .snowfall
+                .map(|cm| format!("{}/h", amount(cm, 1))),
+            weather
+                .details
+                .snow_depth
+                .map(|metres| format!("{} deep", amount(metres * 100.0, 0))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let blizzard = weather.snow_intensity() == SnowIntensity::Blizzard;
+        match (blizzard, figures.is_empty()) {
+            (false, true) => String::new(),
+            (true, true) => " | Blizzard".to_string(),
+            (blizzard, false) => format!(
+                " | {}: {}",
+                if blizzard { "Blizzard" } else { "Snow" },
+                figures.join(", ")
+            ),
+        }
+    }
+
+    pub fn set_yesterday_temperature(&mut self, temperature: Option<f64>) {
+        self.yesterday_temperature = temperature;
+        self.weather_info_needs_update = true;
+    }
+
+    /// " ▲3° vs yesterday" style suffix for the HUD temperature.
+    fn yesterday_delta(&self, temperature: f64) -> String {
+        let Some(yesterday) = self.yesterday_temperature else {
+            return String::new();
+        };
+
+        let mut delta = temperature - yesterday;
+        if self.units.temperature == TemperatureUnit::Fahrenheit {
+            delta *= 9.0 / 5.0;
+        }
+        let arrow = if delta.abs() < 0.5 {
+            "="
+        } else if delta > 0.0 {
+            "▲"
+        } else {
+            "▼"
+        };
+        format!(" {}{:.0}° vs yesterday", arrow, delta.abs())
+    }
+
+    /// " (4°/15°)": today's forecast low and high, when the provider has
+    /// them.
+    fn today_range_text(&self, weather: &WeatherData) -> String {
+        let Some(today) = weather.today else {
+            return String::new();
+        };
+        let (min, _) = format_temperature(today.min, self.units.temperature);
+        let (max, _) = format_temperature(today.max, self.units.temperature);
+        format!(" ({:.0}°/{:.0}°)", min, max)
+    }
+
+    /// " (feels -4.2°C)" when the feels-like temperature is a degree or more
+    /// off the air temperature.
+    fn feels_like_text(&self, weather: &WeatherData) -> String {
+        let feels_like = weather.feels_like();
+        if (feels_like - weather.temperature).abs() < 1.0 {
+            return String::new();
+        }
+        let (value, unit) = format_temperature(feels_like, self.units.temperature);
+        format!(" (feels {:.1}{})", value, unit)
+    }
+
+    /// " | UV: 7" in daylight, when the provider reports it.
+    fn uv_text(&self, uv_index: Option<f64>) -> String {
+        match uv_index {
+            Some(uv) if self.weather_conditions.sun.is_day => format!(" | UV: {:.0}", uv),
+            _ => String::new(),
+        }
+    }
+
+    /// " | OM 14:00": which provider the reading is from and when it was
+    /// observed, left out for simulated weather.
+    fn source_text(weather: &WeatherData) -> String {
+        if weather.source.is_empty() {
+            return String::new();
+        }
+        match weather.timestamp.get(11..16) {
+            Some(time) => format!(" | {} {}", weather.source, time),
+            None => format!(" | {}", weather.source),
+        }
+    }
+
     pub fn set_offline_mode(&mut self, offline: bool) {
         self.is_offline = offline;
         self.weather_info_needs_update = true;
@@ -68,24 +401,257 @@ impl AppState {
         }
     }
 
+    pub fn set_next_refresh(&mut self, next_refresh: Option<Instant>) {
+        self.next_refresh = next_refresh;
+        self.update_refresh_countdown();
+    }
+
+    /// Marks the HUD for redraw when the countdown ticks to another second.
+    pub fn update_refresh_countdown(&mut self) {
+        let countdown = self
+            .next_refresh
+            .map(|at| at.saturating_duration_since(Instant::now()).as_secs());
+        if countdown != self.refresh_countdown {
+            self.refresh_countdown = countdown;
+            self.weather_info_needs_update = true;
+        }
+    }
+
+    /// Marks the HUD for redraw when the sunrise/sunset countdown ticks to
+    /// another minute.
+    pub fn update_sun_countdown(&mut self) {
+        let countdown = self
+            .next_sun_event(self.scheduler.now())
+            .map(|(_, _, until)| until.num_minutes());
+        if countdown != self.sun_countdown {
+            self.sun_countdown = countdown;
+            self.weather_info_needs_update = true;
+        }
+    }
+
+    pub fn update_storm_countdown(&mut self) {
+        let countdown = self.current_weather.as_ref().and_then(|weather| {
+            self.storm_easing(weather, self.scheduler.now())
+                .map(|(_, until)| until.num_minutes())
+        });
+        if countdown != self.storm_countdown {
+            self.storm_countdown = countdown;
+            self.weather_info_needs_update = true;
+        }
+    }
+
+    /// Severe weather, while storm watch is on and the weather is live.
+    pub fn storm_watch_severity(&self) -> Option<Severity> {
+        if !self.storm_watch || self.is_offline {
+            return None;
+        }
+        self.current_weather.as_ref().and_then(Severity::of)
+    }
+
+    /// When the severe weather in storm watch is forecast to ease, with how
+    /// long until then.
+    fn storm_easing(
+        &self,
+        weather: &WeatherData,
+        now: DateTime<FixedOffset>,
+    ) -> Option<(NaiveTime, TimeDelta)> {
+        self.storm_watch_severity()?;
+        let easing = severity::easing_at(weather)?;
+        let until = self.scheduler.at(easing.date(), easing.time()) - now;
+        (until > TimeDelta::zero()).then_some((easing.time(), until))
+    }
+
+    /// " | Storm watch: warning until ~17:00 (in 1h 40m)" in severe weather
+    /// with storm watch on; without the time when the forecast doesn't say.
+    fn storm_watch_text(&self, weather: &WeatherData, now: DateTime<FixedOffset>) -> String {
+        let Some(severity) = self.storm_watch_severity() else {
+            return String::new();
+        };
+        match self.storm_easing(weather, now) {
+            Some((at, until)) => format!(
+                " | Storm watch: {} until ~{} (in {})",
+                severity.label(),
+                at.format("%H:%M"),
+                format_minutes(until.num_minutes())
+            ),
+            None => format!(" | Storm watch: {}", severity.label()),
+        }
+    }
+
+    /// The next sunrise or sunset after `now`, with how long until it. Uses
+    /// the provider's times when it sent them and computes them otherwise;
+    /// `None` during polar day or night.
+    fn next_sun_event(
+        &self,
+        now: DateTime<FixedOffset>,
+    ) -> Option<(&'static str, NaiveTime, TimeDelta)> {
+        let (rise, set) = self.sun_times(now)?;
+
+        let time = now.time();
+        // After sunset, tomorrow's sunrise is close enough to today's
+        let (label, at) = if time < rise || time >= set {
+            ("Sunrise", rise)
+        } else {
+            ("Sunset", set)
+        };
+        Some((label, at, self.scheduler.until(now, at)))
+    }
+
+    /// Today's sunrise and sunset, from the provider or computed.
+    fn sun_times(&self, now: DateTime<FixedOffset>) -> Option<(NaiveTime, NaiveTime)> {
+        let sun = self.weather_conditions.sun;
+        match (sun.rise, sun.set) {
+            (Some(rise), Some(set)) => Some((rise, set)),
+            _ => {
+                let events =
+                    astro::celestial_events(self.location.latitude, self.location.longitude, now);
+                Some((events.rise?, events.set?))
+            }
+        }
+    }
+
+    /// How thick the morning mist lies, from 0 to 1. On a clear, calm and
+    /// humid morning it gathers before sunrise and burns off as the sun
+    /// climbs.
+    pub fn morning_mist(&self, now: DateTime<FixedOffset>) -> f32 {
+        let Some(weather) = &self.current_weather else {
+            return 0.0;
+        };
+        let clear = matches!(
+            weather.condition,
+            WeatherCondition::Clear | WeatherCondition::MainlyClear
+        );
+        let humid = weather.humidity.is_some_and(|h| h >= MIST_HUMIDITY);
+        if !clear
+            || !humid
+            || weather.wind_speed > MIST_MAX_WIND
+            || self.weather_conditions.is_foggy
+        {
+            return 0.0;
+        }
+        let Some((rise, _)) = self.sun_times(now) else {
+            return 0.0;
+        };
+
+        let minutes = now.time().signed_duration_since(rise).num_minutes();
+        if minutes < -MIST_BEFORE_SUNRISE_MINUTES {
+            0.0
+        } else if minutes <= MIST_THICK_MINUTES {
+            1.0
+        } else {
+            let burned = (minutes - MIST_THICK_MINUTES) as f32 / MIST_BURN_OFF_MINUTES as f32;
+            (1.0 - burned).max(0.0)
+        }
+    }
+
+    /// " | Sunset 18:42 (in 1h 12m)", left out for simulated weather.
+    fn sun_text(&self, weather: &WeatherData, now: DateTime<FixedOffset>) -> String {
+        if weather.source.is_empty() {
+            return String::new();
+        }
+        let Some((label, at, until)) = self.next_sun_event(now) else {
+            return String::new();
+        };
+        format!(
+            " | {} {} (in {})",
+            label,
+            at.format("%H:%M"),
+            format_minutes(until.num_minutes())
+        )
+    }
+
+    /// Minutes until the nowcast expects rain or snow to start, while it is
+    /// still dry.
+    pub fn precipitation_due_in(&self) -> Option<u32> {
+        self.precipitation_due_at(self.scheduler.now())
+    }
+
+    /// The countdown as of `now`, from the nowcast's own step times, which
+    /// start at the observation time, so cached or late data counts down no
+    /// further than it should.
+    fn precipitation_due_at(&self, now: DateTime<FixedOffset>) -> Option<u32> {
+        let weather = self.current_weather.as_ref()?;
+        if self.is_offline || weather.condition.is_raining() || weather.condition.is_snowing() {
+            return None;
+        }
+        let observed =
+            NaiveDateTime::parse_from_str(weather.timestamp.get(..16)?, "%Y-%m-%dT%H:%M").ok()?;
+        let observed = self.scheduler.at(observed.date(), observed.time());
+        let elapsed = u64::try_from((now - observed).num_seconds()).unwrap_or(0);
+        weather
+            .nowcast
+            .iter()
+            .filter(|step| (step.minutes_ahead as u64 + NOWCAST_STEP_MINUTES) * 60 > elapsed)
+            .find(|step| {
+                step.precipitation >= NOWCAST_WET_RATE
+                    && step.probability.is_none_or(|p| p >= NOWCAST_LIKELY_PERCENT)
+            })
+            .map(|step| ((step.minutes_ahead as u64 * 60).saturating_sub(elapsed) / 60) as u32)
+    }
+
+    /// " | Rain in ~23 min" from the nowcast.
+    fn nowcast_text(&self, weather: &WeatherData, now: DateTime<FixedOffset>) -> String {
+        let Some(minutes) = self.precipitation_due_at(now) else {
+            return String::new();
+        };
+        let kind = if weather.temperature <= 0.0 {
+            "Snow"
+        } else {
+            "Rain"
+        };
+        if minutes == 0 {
+            format!(" | {} starting", kind)
+        } else {
+            format!(" | {} in ~{} min", kind, minutes)
+        }
+    }
+
+    /// " | Pressure: 1012hPa ↘ Storm possible" style HUD segment.
+    fn pressure_text(&self, pressure: Option<f64>) -> String {
+        let Some(pressure) = pressure else {
+            return String::new();
+        };
+
+        let mut text = format!(" | Pressure: {:.0}hPa", pressure);
+        if let Some(tendency) = self.pressure_history.tendency() {
+            text.push(' ');
+            text.push_str(tendency.glyph());
+        }
+        if self.pressure_history.storm_possible() {
+            text.push_str(" Storm possible");
+        }
+        text
+    }
+
+    /// " (fresh breeze)" when enabled.
+    fn beaufort_text(&self, wind_ms: f64) -> String {
+        if self.beaufort {
+            format!(" ({})", beaufort_description(wind_ms))
+        } else {
+            String::new()
+        }
+    }
+
+    /// " | Providers disagree (4° apart)" flag for consensus mode.
+    fn disagreement_text(&self, disagreement: Option<Disagreement>) -> String {
+        let Some(disagreement) = disagreement else {
+            return String::new();
+        };
+
+        let mut spread = disagreement.temperature_spread;
+        if self.units.temperature == TemperatureUnit::Fahrenheit {
+            spread *= 9.0 / 5.0;
+        }
+        let mut details = vec![format!("{:.0}° apart", spread)];
+        if disagreement.precipitation {
+            details.push("rain or not".to_string());
+        }
+        format!(" | Providers disagree ({})", details.join(", "))
+    }
+
     pub fn get_condition_text(&self) -> &str {
         if let Some(ref weather) = self.current_weather {
-            match weather.condition {
-                WeatherCondition::Clear => "Clear",
-                WeatherCondition::Cloudy => "Cloudy",
-                WeatherCondition::PartlyCloudy => "Partly Cloudy",
-                WeatherCondition::Overcast => "Overcast",
-                WeatherCondition::Fog => "Fog",
-                WeatherCondition::Drizzle => "Drizzle",
-                WeatherCondition::FreezingRain => "Freezing Rain",
-                WeatherCondition::Rain => "Rain",
-                WeatherCondition::Snow => "Snow",
-                WeatherCondition::SnowGrains => "Snow Grains",
-                WeatherCondition::RainShowers => "Rain Showers",
-                WeatherCondition::SnowShowers => "Snow Showers",
-                WeatherCondition::Thunderstorm => "Thunderstorm",
-                WeatherCondition::ThunderstormHail => "Thunderstorm with Hail",
-            }
+            weather.condition.label()
         } else {
             "Loading"
         }
@@ -124,29 +690,65 @@ impl AppState {
             format!(" | Location: {}", label)
         };
 
-        self.cached_weather_info = if let Some(ref weather) = self.current_weather {
+        if let Some(ref weather) = self.current_weather {
             let (temp, temp_unit) = format_temperature(weather.temperature, self.units.temperature);
             let (wind, wind_unit) = format_wind_speed(weather.wind_speed, self.units.wind_speed);
             let (precip, precip_unit) =
                 format_precipitation(weather.precipitation, self.units.precipitation);
 
-            let offline_indicator = if self.is_offline { "OFFLINE | " } else { "" };
+            // Real weather kept from before the network went is labelled as such
+            let offline_indicator = match (self.is_offline, weather.source.is_empty()) {
+                (false, _) => "",
+                (true, true) => "OFFLINE | ",
+                (true, false) => "OFFLINE, CACHED | ",
+            };
 
-            format!(
-                "{}Weather: {} | Temp: {:.1}{} | Wind: {:.1}{} | Precip: {:.1}{}{} | Press 'q' to quit",
+            let icon = match self.icons {
+                IconSet::Nerd => format!("{} ", weather.condition.icon(IconSet::Nerd)),
+                IconSet::Unicode | IconSet::Ascii => String::new(),
+            };
+            let prefix = format!(
+                "{}Weather: {}{} | Temp: ",
                 offline_indicator,
-                self.get_condition_text(),
-                temp,
-                temp_unit,
+                icon,
+                self.get_condition_text()
+            );
+            let temperature = format!("{:.1}{}", temp, temp_unit);
+            self.cached_temperature_span = Some(prefix.len()..prefix.len() + temperature.len());
+            let next_update = self
+                .refresh_countdown
+                .map(|secs| format!(" | Next update in {}", format_countdown(secs)))
+                .unwrap_or_default();
+            self.cached_weather_info = format!(
+                "{}{}{}{}{} | Wind: {:.1}{}{} | Precip: {:.1}{}{}{}{}{}{}{}{}{}{}{}{}{} | Press 'q' to quit",
+                prefix,
+                temperature,
+                self.today_range_text(weather),
+                self.feels_like_text(weather),
+                self.yesterday_delta(weather.temperature),
                 wind,
                 wind_unit,
+                self.beaufort_text(weather.wind_speed),
                 precip,
                 precip_unit,
-                location_str
-            )
+                self.nowcast_text(weather, self.scheduler.now()),
+                self.snow_text(weather),
+                self.storm_text(),
+                self.storm_watch_text(weather, self.scheduler.now()),
+                self.uv_text(weather.uv_index),
+                self.aurora_text(),
+                self.sun_text(weather, self.scheduler.now()),
+                self.pressure_text(weather.pressure),
+                self.disagreement_text(weather.disagreement),
+                Self::source_text(weather),
+                location_str,
+                next_update
+            );
         } else {
-            format!("Weather: Loading... {}", self.loading_state.current_char())
-        };
+            self.cached_temperature_span = None;
+            self.cached_weather_info =
+                format!("Weather: Loading... {}", self.loading_state.current_char());
+        }
 
         self.weather_info_needs_update = false;
     }
@@ -159,13 +761,38 @@ impl AppState {
         if let Some(ref weather) = self.current_weather {
             matches!(
                 weather.condition,
-                WeatherCondition::Clear | WeatherCondition::PartlyCloudy | WeatherCondition::Cloudy
+                WeatherCondition::Clear
+                    | WeatherCondition::MainlyClear
+                    | WeatherCondition::PartlyCloudy
+                    | WeatherCondition::Cloudy
             )
         } else {
             false
         }
     }
 
+    /// Soaks or dries the ground up to `now` by whether it's raining, from
+    /// the first weather on.
+    pub fn update_wetness(&mut self, now: Instant) {
+        let Some(weather) = &self.current_weather else {
+            return;
+        };
+        let conditions = &self.weather_conditions;
+        let raining =
+            (conditions.is_raining || conditions.is_thunderstorm) && !conditions.is_snowing;
+        let rain = raining.then(|| weather.condition.rain_intensity());
+        self.wetness.update(rain, now);
+    }
+
+    /// Fades the lighting towards day or night up to `secs` of animation,
+    /// from the first weather on.
+    pub fn update_daylight(&mut self, secs: f64) {
+        if self.current_weather.is_some() {
+            self.daylight
+                .update(self.weather_conditions.sun.is_day, secs);
+        }
+    }
+
     pub fn should_show_fireflies(&self) -> bool {
         if self.weather_conditions.sun.is_day {
             return false;
@@ -175,7 +802,9 @@ impl AppState {
             let is_warm = weather.temperature > 15.0;
             let is_clear_night = matches!(
                 weather.condition,
-                WeatherCondition::Clear | WeatherCondition::PartlyCloudy
+                WeatherCondition::Clear
+                    | WeatherCondition::MainlyClear
+                    | WeatherCondition::PartlyCloudy
             );
             is_warm
                 && is_clear_night
@@ -188,6 +817,20 @@ impl AppState {
     }
 }
 
+/// "3:42" for 222 seconds.
+/// "1h 12m", or "12m" under the hour.
+fn format_minutes(minutes: i64) -> String {
+    if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn format_countdown(secs: u64) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
 pub struct LoadingState {
     pub frame: usize,
     pub last_update: Instant,
@@ -227,9 +870,12 @@ impl Default for LoadingState {
 mod tests {
     use super::*;
     use crate::config::LocationDisplay;
+    use crate::weather::radar::StormMovement;
     use crate::weather::types::{
-        CelestialEvents, PrecipitationUnit, TemperatureUnit, WindSpeedUnit,
+        CelestialEvents, DistanceUnit, HourlyForecast, NowcastStep, PrecipitationUnit,
+        TemperatureRange, TemperatureUnit, WindSpeedUnit,
     };
+    use chrono::NaiveDate;
 
     fn create_app_state(lat: f64, lon: f64) -> AppState {
         create_app_state_full(lat, lon, None, LocationDisplay::Coordinates)
@@ -250,19 +896,16 @@ mod tests {
             temperature: TemperatureUnit::Celsius,
             wind_speed: WindSpeedUnit::Kmh,
             precipitation: PrecipitationUnit::Mm,
+            distance: DistanceUnit::Km,
         };
         let mut app = AppState::new(location, city, display, false, units);
 
         let weather = WeatherData {
-            condition: WeatherCondition::Clear,
             temperature: 20.0,
-            precipitation: 0.0,
             wind_speed: 10.0,
-            wind_direction: 0.0,
             moon_phase: Some(0.5),
             timestamp: "2024-01-01T12:00:00Z".to_string(),
-            attribution: "".to_string(),
-            sun: CelestialEvents::from_bool(true),
+            ..WeatherData::sample(WeatherCondition::Clear)
         };
         app.update_weather(weather);
 
@@ -335,6 +978,510 @@ mod tests {
         assert!(app.cached_weather_info.contains("0.00°E"));
     }
 
+    #[test]
+    fn test_temperature_span() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+
+        let span = app.cached_temperature_span.clone().unwrap();
+        assert_eq!(&app.cached_weather_info[span], "20.0°C");
+    }
+
+    #[test]
+    fn test_condition_change() {
+        let mut app = create_app_state(0.0, 0.0);
+        let mut next = app.current_weather.clone().unwrap();
+        assert_eq!(app.condition_change(&next), None);
+
+        next.condition = WeatherCondition::Rain;
+        next.precipitation = 2.5;
+        assert_eq!(
+            app.condition_change(&next).as_deref(),
+            Some("Rain starting — 2.5 mm/h")
+        );
+
+        app.update_weather(next.clone());
+        next.condition = WeatherCondition::Overcast;
+        assert_eq!(
+            app.condition_change(&next).as_deref(),
+            Some("Rain ending — now Overcast")
+        );
+
+        app.set_offline_mode(true);
+        assert_eq!(app.condition_change(&next), None);
+    }
+
+    #[test]
+    fn test_pressure_tendency_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("Pressure"));
+
+        let mut weather = app.current_weather.clone().unwrap();
+        for (time, hpa) in [("12:00", 1004.0), ("13:00", 1000.0), ("14:00", 996.0)] {
+            weather.timestamp = format!("2024-01-01T{}", time);
+            weather.pressure = Some(hpa);
+            app.update_weather(weather.clone());
+        }
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains("Pressure: 996hPa ⬇ Storm possible")
+        );
+    }
+
+    #[test]
+    fn test_disagreement_flag() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("disagree"));
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.disagreement = Some(Disagreement {
+            temperature_spread: 4.0,
+            precipitation: true,
+        });
+        app.update_weather(weather);
+        app.units.temperature = TemperatureUnit::Fahrenheit;
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains("Providers disagree (7° apart, rain or not)")
+        );
+    }
+
+    #[test]
+    fn test_refresh_countdown() {
+        assert_eq!(format_countdown(222), "3:42");
+        assert_eq!(format_countdown(5), "0:05");
+
+        let mut app = create_app_state(0.0, 0.0);
+        app.set_next_refresh(Some(Instant::now() + std::time::Duration::from_secs(300)));
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("Next update in 4:5"));
+
+        app.set_next_refresh(None);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("Next update"));
+    }
+
+    #[test]
+    fn test_offline_weather_is_labelled_cached() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.set_offline_mode(true);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.starts_with("OFFLINE | "));
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.source = "OM".to_string();
+        app.update_weather(weather);
+        app.set_offline_mode(true);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.starts_with("OFFLINE, CACHED | "));
+    }
+
+    #[test]
+    fn test_aurora_at_night() {
+        let mut app = create_app_state(60.0, 25.0);
+        app.set_aurora_kp(Some(6.0));
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("Aurora"));
+        assert_eq!(app.aurora_strength(), 0.0);
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.sun = CelestialEvents::from_bool(false);
+        app.update_weather(weather.clone());
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains(" | Aurora possible tonight (Kp 6)")
+        );
+        assert!(app.aurora_strength() > 0.5);
+
+        // Not enough to reach 60°
+        app.set_aurora_kp(Some(2.0));
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("Aurora"));
+
+        app.set_aurora_kp(Some(6.0));
+        weather.condition = WeatherCondition::Overcast;
+        app.update_weather(weather);
+        assert_eq!(app.aurora_strength(), 0.0);
+    }
+
+    #[test]
+    fn test_storm_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.set_storm(Some(Storm {
+            distance_km: 8.3,
+            movement: Some(StormMovement::Approaching),
+        }));
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("Storm ~"));
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.condition = WeatherCondition::Thunderstorm;
+        app.update_weather(weather.clone());
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains(" | Storm ~8 km away, approaching")
+        );
+
+        app.units.distance = DistanceUnit::Mi;
+        app.set_storm(Some(Storm {
+            distance_km: 33.4,
+            movement: None,
+        }));
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains(" | Storm ~21 mi away |"));
+
+        app.set_storm(Some(Storm {
+            distance_km: 0.0,
+            movement: Some(StormMovement::Stationary),
+        }));
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains(" | Storm overhead"));
+    }
+
+    #[test]
+    fn test_storm_watch_counts_down_to_easing() {
+        let mut app = create_app_state(0.0, 0.0);
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.condition = WeatherCondition::ThunderstormHail;
+        weather.timestamp = "2024-06-01T15:20".to_string();
+        weather.hourly = [
+            ("2024-06-01T16:00", WeatherCondition::Thunderstorm),
+            ("2024-06-01T17:00", WeatherCondition::Rain),
+        ]
+        .into_iter()
+        .map(|(time, condition)| HourlyForecast {
+            time: time.to_string(),
+            condition,
+            temperature: 15.0,
+            precipitation_probability: None,
+            humidity: None,
+        })
+        .collect();
+        app.update_weather(weather.clone());
+        let now = app.scheduler.at(
+            NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            NaiveTime::from_hms_opt(15, 20, 0).unwrap(),
+        );
+
+        assert_eq!(app.storm_watch_text(&weather, now), "");
+        app.storm_watch = true;
+        assert_eq!(
+            app.storm_watch_text(&weather, now),
+            " | Storm watch: warning until ~17:00 (in 1h 40m)"
+        );
+
+        weather.hourly.pop();
+        assert_eq!(
+            app.storm_watch_text(&weather, now),
+            " | Storm watch: warning"
+        );
+
+        app.is_offline = true;
+        assert_eq!(app.storm_watch_severity(), None);
+    }
+
+    #[test]
+    fn test_snow_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.details.snowfall = Some(0.4);
+        weather.details.snow_depth = Some(0.32);
+        app.update_weather(weather.clone());
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("Snow:"));
+
+        weather.condition = WeatherCondition::HeavySnow;
+        weather.wind_speed = 5.0;
+        app.update_weather(weather.clone());
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains(" | Snow: 0.4cm/h, 32cm deep")
+        );
+
+        // Heavier snow in a gale is a blizzard
+        weather.details.snowfall = Some(2.0);
+        weather.details.wind_gusts = Some(20.0);
+        app.update_weather(weather.clone());
+        app.update_cached_info();
+        assert_eq!(weather.snow_intensity(), SnowIntensity::Blizzard);
+        assert!(
+            app.cached_weather_info
+                .contains(" | Blizzard: 2.0cm/h, 32cm deep")
+        );
+
+        app.units.precipitation = PrecipitationUnit::Inch;
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains(" | Blizzard: 0.8in/h, 13in deep")
+        );
+    }
+
+    #[test]
+    fn test_beaufort_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("Wind: 36.0km/h | "));
+
+        app.beaufort = true;
+        let weather = app.current_weather.clone().unwrap();
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .contains("Wind: 36.0km/h (fresh breeze) | ")
+        );
+    }
+
+    #[test]
+    fn test_nerd_icon_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        let weather = app.current_weather.clone().unwrap();
+        let condition = weather.condition;
+        app.update_cached_info();
+        assert!(
+            app.cached_weather_info
+                .starts_with(&format!("Weather: {} |", condition.label()))
+        );
+
+        app.icons = IconSet::Nerd;
+        app.update_weather(weather);
+        app.update_cached_info();
+        let prefix = format!(
+            "Weather: {} {} | Temp: ",
+            condition.icon(IconSet::Nerd),
+            condition.label()
+        );
+        assert!(app.cached_weather_info.starts_with(&prefix));
+        // The temperature span still lines up after the wider prefix
+        let span = app.cached_temperature_span.clone().unwrap();
+        assert_eq!(span.start, prefix.len());
+    }
+
+    #[test]
+    fn test_yesterday_delta() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("yesterday"));
+
+        app.set_yesterday_temperature(Some(17.2));
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("20.0°C ▲3° vs yesterday"));
+
+        app.units.temperature = TemperatureUnit::Fahrenheit;
+        app.set_yesterday_temperature(Some(21.0));
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("▼2° vs yesterday"));
+
+        app.set_yesterday_temperature(Some(20.1));
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("=0° vs yesterday"));
+
+        app.set_location(app.location, None);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("yesterday"));
+    }
+
+    #[test]
+    fn test_today_range_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("°/"));
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.today = Some(TemperatureRange {
+            min: 4.2,
+            max: 14.6,
+        });
+        app.update_weather(weather.clone());
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("Temp: 20.0°C (4°/15°)"));
+
+        app.units.temperature = TemperatureUnit::Fahrenheit;
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("(40°/58°)"));
+    }
+
+    #[test]
+    fn test_feels_like_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("feels"));
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.temperature = -10.0;
+        app.update_weather(weather.clone());
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("-10.0°C (feels -20.3°C)"));
+
+        weather.apparent_temperature = Some(-12.0);
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("(feels -12.0°C)"));
+    }
+
+    #[test]
+    fn test_uv_index_in_daylight_only() {
+        let mut app = create_app_state(0.0, 0.0);
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.uv_index = Some(7.2);
+        app.update_weather(weather.clone());
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains("Precip: 0.0mm | UV: 7 |"));
+
+        weather.sun = CelestialEvents::from_bool(false);
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("UV"));
+    }
+
+    #[test]
+    fn test_nowcast_countdown() {
+        let mut app = create_app_state(0.0, 0.0);
+        let mut weather = app.current_weather.clone().unwrap();
+        let step = |minutes_ahead, precipitation, probability| NowcastStep {
+            minutes_ahead,
+            precipitation,
+            probability,
+        };
+        weather.nowcast = vec![
+            step(0, 0.0, None),
+            step(15, 0.8, Some(30.0)),
+            step(30, 1.2, Some(70.0)),
+        ];
+        weather.timestamp = "2024-06-01T15:00".to_string();
+        app.update_weather(weather.clone());
+        let scheduler = app.scheduler;
+        let at = |h, m| {
+            scheduler.at(
+                NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+                NaiveTime::from_hms_opt(h, m, 0).unwrap(),
+            )
+        };
+        assert_eq!(app.precipitation_due_at(at(15, 0)), Some(30));
+        assert_eq!(app.nowcast_text(&weather, at(15, 0)), " | Rain in ~30 min");
+        // Data that arrives (or is read from the cache) late counts on
+        // from the observation time
+        assert_eq!(app.precipitation_due_at(at(15, 20)), Some(10));
+        assert_eq!(app.precipitation_due_at(at(15, 40)), Some(0));
+        assert_eq!(app.precipitation_due_at(at(15, 50)), None);
+
+        weather.condition = WeatherCondition::Rain;
+        app.update_weather(weather);
+        assert_eq!(app.precipitation_due_at(at(15, 0)), None);
+    }
+
+    #[test]
+    fn test_sun_countdown() {
+        let mut app = create_app_state(0.0, 0.0);
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.source = "OM".to_string();
+        weather.sun = CelestialEvents {
+            rise: NaiveTime::from_hms_opt(6, 10, 0),
+            set: NaiveTime::from_hms_opt(18, 42, 0),
+            ..CelestialEvents::from_bool(true)
+        };
+        app.update_weather(weather.clone());
+        let scheduler = app.scheduler;
+        let at = |h, m| {
+            scheduler.at(
+                NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+                NaiveTime::from_hms_opt(h, m, 0).unwrap(),
+            )
+        };
+
+        assert_eq!(
+            app.sun_text(&weather, at(17, 30)),
+            " | Sunset 18:42 (in 1h 12m)"
+        );
+        assert_eq!(
+            app.sun_text(&weather, at(18, 30)),
+            " | Sunset 18:42 (in 12m)"
+        );
+        assert_eq!(
+            app.sun_text(&weather, at(22, 0)),
+            " | Sunrise 06:10 (in 8h 10m)"
+        );
+        assert_eq!(
+            app.sun_text(&weather, at(5, 0)),
+            " | Sunrise 06:10 (in 1h 10m)"
+        );
+
+        // The clocks go forward overnight in Berlin
+        weather.details.timezone = Some("Europe/Berlin".to_string());
+        app.update_weather(weather.clone());
+        let night = app.scheduler.at(
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+            NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+        );
+        assert_eq!(
+            app.sun_text(&weather, night),
+            " | Sunrise 06:10 (in 4h 10m)"
+        );
+
+        weather.source = String::new();
+        assert_eq!(app.sun_text(&weather, at(17, 30)), "");
+    }
+
+    #[test]
+    fn test_morning_mist() {
+        let mut app = create_app_state(0.0, 0.0);
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.sun = CelestialEvents {
+            rise: NaiveTime::from_hms_opt(6, 0, 0),
+            set: NaiveTime::from_hms_opt(18, 0, 0),
+            ..CelestialEvents::from_bool(true)
+        };
+        weather.wind_speed = 1.0;
+        weather.humidity = Some(95.0);
+        app.update_weather(weather.clone());
+        let scheduler = app.scheduler;
+        let at = |h, m| {
+            scheduler.at(
+                NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+                NaiveTime::from_hms_opt(h, m, 0).unwrap(),
+            )
+        };
+
+        assert_eq!(app.morning_mist(at(4, 30)), 0.0);
+        assert_eq!(app.morning_mist(at(5, 30)), 1.0);
+        assert_eq!(app.morning_mist(at(7, 30)), 0.5);
+        assert_eq!(app.morning_mist(at(9, 0)), 0.0);
+        assert_eq!(app.morning_mist(at(15, 0)), 0.0);
+
+        weather.wind_speed = 6.0;
+        app.update_weather(weather.clone());
+        assert_eq!(app.morning_mist(at(6, 0)), 0.0);
+
+        weather.wind_speed = 1.0;
+        weather.humidity = Some(60.0);
+        app.update_weather(weather);
+        assert_eq!(app.morning_mist(at(6, 0)), 0.0);
+    }
+
+    #[test]
+    fn test_source_in_hud() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_cached_info();
+        assert!(!app.cached_weather_info.contains("| OM"));
+
+        let mut weather = app.current_weather.clone().unwrap();
+        weather.source = "OM".to_string();
+        weather.timestamp = "2024-01-01T14:00".to_string();
+        app.update_weather(weather);
+        app.update_cached_info();
+        assert!(app.cached_weather_info.contains(" | OM 14:00 | Location"));
+    }
+
     #[test]
     fn test_display_coordinates_mode() {
         let mut app = create_app_state_full(
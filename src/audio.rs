@@ -0,0 +1,149 @@
+use crate::app_state::AppState;
+use crate::config::AudioConfig;
+use rodio::source::noise::{Brownian, Pink};
+use rodio::{OutputStream, OutputStreamBuilder, Sink, Source};
+
+const SAMPLE_RATE: u32 = 44_100;
+/// Wind speed (m/s) at which the wind layer reaches its configured volume.
+const FULL_WIND_MS: f64 = 15.0;
+
+/// Volume of each ambience layer, before muting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Mix {
+    rain: f32,
+    wind: f32,
+    thunder: f32,
+}
+
+impl Mix {
+    fn for_state(config: &AudioConfig, state: &AppState) -> Self {
+        let Some(weather) = &state.current_weather else {
+            return Self {
+                rain: 0.0,
+                wind: 0.0,
+                thunder: 0.0,
+            };
+        };
+        let conditions = &state.weather_conditions;
+        let wet = conditions.is_raining || conditions.is_thunderstorm;
+
+        Self {
+            rain: if wet { config.rain } else { 0.0 },
+            wind: config.wind * (weather.wind_speed / FULL_WIND_MS).clamp(0.0, 1.0) as f32,
+            thunder: if conditions.is_thunderstorm {
+                config.thunder
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Looping background sound synthesized from filtered noise, one sink per
+/// layer so each can fade independently.
+pub struct Ambience {
+    // Dropping the stream stops playback
+    _stream: OutputStream,
+    rain: Sink,
+    wind: Sink,
+    thunder: Sink,
+    config: AudioConfig,
+    muted: bool,
+}
+
+impl Ambience {
+    /// Opens the default output device, or returns `None` if there isn't one.
+    pub fn new(config: AudioConfig) -> Option<Self> {
+        let mut stream = OutputStreamBuilder::open_default_stream().ok()?;
+        stream.log_on_drop(false);
+
+        let rain = Sink::connect_new(stream.mixer());
+        rain.append(Pink::new(SAMPLE_RATE).low_pass(4_000).amplify(0.4));
+
+        let wind = Sink::connect_new(stream.mixer());
+        wind.append(Brownian::new(SAMPLE_RATE).low_pass(500).amplify(0.2));
+
+        let thunder = Sink::connect_new(stream.mixer());
+        thunder.append(Brownian::new(SAMPLE_RATE).low_pass(120).amplify(0.5));
+
+        let ambience = Self {
+            _stream: stream,
+            rain,
+            wind,
+            thunder,
+            config,
+            muted: false,
+        };
+        ambience.apply(Mix {
+            rain: 0.0,
+            wind: 0.0,
+            thunder: 0.0,
+        });
+        Some(ambience)
+    }
+
+    /// Matches the layers to the weather currently on screen.
+    pub fn update(&self, state: &AppState) {
+        if !self.muted {
+            self.apply(Mix::for_state(&self.config, state));
+        }
+    }
+
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+        for sink in [&self.rain, &self.wind, &self.thunder] {
+            if muted {
+                sink.pause();
+            } else {
+                sink.play();
+            }
+        }
+    }
+
+    fn apply(&self, mix: Mix) {
+        self.rain.set_volume(mix.rain);
+        self.wind.set_volume(mix.wind);
+        self.thunder.set_volume(mix.thunder);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LocationDisplay;
+    use crate::weather::{WeatherCondition, WeatherData, WeatherLocation, WeatherUnits};
+
+    fn state_with(condition: WeatherCondition, wind_speed: f64) -> AppState {
+        let location = WeatherLocation {
+            latitude: 0.0,
+            longitude: 0.0,
+            elevation: None,
+        };
+        let mut state = AppState::new(
+            location,
+            None,
+            LocationDisplay::Coordinates,
+            false,
+            WeatherUnits::metric(),
+        );
+        state.update_weather(WeatherData {
+            wind_speed,
+            ..WeatherData::sample(condition)
+        });
+        state
+    }
+
+    #[test]
+    fn test_mix_follows_condition() {
+        let config = AudioConfig::default();
+
+        let clear = Mix::for_state(&config, &state_with(WeatherCondition::Clear, 0.0));
+        assert_eq!(clear.rain, 0.0);
+        assert_eq!(clear.wind, 0.0);
+
+        let storm = Mix::for_state(&config, &state_with(WeatherCondition::Thunderstorm, 30.0));
+        assert_eq!(storm.rain, config.rain);
+        assert_eq!(storm.wind, config.wind);
+        assert_eq!(storm.thunder, config.thunder);
+    }
+}
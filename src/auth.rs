@@ -0,0 +1,65 @@
+use crate::cli::AuthAction;
+use crate::config::Provider;
+use crate::error::ConfigError;
+use crate::secrets;
+#[cfg(feature = "keyring")]
+use std::io::IsTerminal;
+use std::io::{self, BufRead};
+
+/// Runs `weathr auth ...`.
+pub fn run(action: AuthAction) -> Result<(), ConfigError> {
+    match action {
+        AuthAction::Set { provider } => {
+            let provider = keyed_provider(&provider)?;
+            secrets::ensure_available(provider)?;
+            let key = read_key(provider)?;
+            if key.is_empty() {
+                return Err(ConfigError::InvalidAPIKey("no key entered".to_string()));
+            }
+            secrets::set_api_key(provider, &key)?;
+            println!(
+                "Stored the {} API key in the system keyring. You can remove api_key from config.toml.",
+                provider.name()
+            );
+        }
+        AuthAction::Remove { provider } => {
+            let provider = keyed_provider(&provider)?;
+            if secrets::delete_api_key(provider)? {
+                println!("Removed the {} API key.", provider.name());
+            } else {
+                println!("No {} API key was stored.", provider.name());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// clap only offers providers that take a key, so this is a formality.
+fn keyed_provider(name: &str) -> Result<Provider, ConfigError> {
+    Provider::from_name(name)
+        .filter(Provider::requires_api_key)
+        .ok_or_else(|| ConfigError::InvalidAPIKey(format!("{name} does not use an API key")))
+}
+
+/// Prompts without echo on a terminal; otherwise reads the first line of
+/// stdin, e.g. `pass weather/met-office | weathr auth set met_office`.
+fn read_key(provider: Provider) -> Result<String, ConfigError> {
+    let read_error = |e: io::Error| ConfigError::Keyring {
+        provider: provider.name(),
+        reason: e.to_string(),
+    };
+
+    #[cfg(feature = "keyring")]
+    if io::stdin().is_terminal() {
+        let key = rpassword::prompt_password(format!("{} API key: ", provider.name()))
+            .map_err(read_error)?;
+        return Ok(key.trim().to_string());
+    }
+
+    let mut line = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(read_error)?;
+    Ok(line.trim().to_string())
+}
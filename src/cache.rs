@@ -1,7 +1,10 @@
 use crate::weather::WeatherData;
+use crate::weather::normals::MonthlyNormals;
+use crate::weather::provider::budget::RequestCount;
 use crate::{config::Provider, geolocation::GeoLocation};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
 const LOCATION_CACHE_DURATION_SECS: u64 = 86400;
@@ -21,7 +24,7 @@ struct WeatherCache {
     provider: Provider,
 }
 
-fn get_cache_dir() -> Option<PathBuf> {
+pub fn get_cache_dir() -> Option<PathBuf> {
     Some(dirs::cache_dir()?.join("weathr"))
 }
 
@@ -32,7 +35,8 @@ fn current_timestamp() -> u64 {
         .unwrap_or(0)
 }
 
-fn make_location_key(latitude: f64, longitude: f64) -> String {
+/// Places within about a kilometre share cached data.
+pub fn make_location_key(latitude: f64, longitude: f64) -> String {
     format!("{:.2},{:.2}", latitude, longitude)
 }
 
@@ -160,3 +164,100 @@ pub fn save_weather_cache(
         }
     });
 }
+
+#[derive(Serialize, Deserialize)]
+struct PressureCache {
+    /// ("YYYY-MM-DDTHH:MM", hPa) readings, oldest first.
+    samples: Vec<(String, f64)>,
+    location_key: String,
+}
+
+/// Pressure readings saved for this location by an earlier session, for the
+/// barometer tendency. Readings too old to matter are dropped as new ones
+/// come in.
+pub async fn load_cached_pressure(latitude: f64, longitude: f64) -> Option<Vec<(String, f64)>> {
+    let cache_path = get_cache_dir()?.join("pressure.json");
+    let contents = fs::read_to_string(&cache_path).await.ok()?;
+    let cache: PressureCache = serde_json::from_str(&contents).ok()?;
+
+    (cache.location_key == make_location_key(latitude, longitude)).then_some(cache.samples)
+}
+
+pub fn save_pressure_cache(samples: Vec<(String, f64)>, latitude: f64, longitude: f64) {
+    tokio::spawn(async move {
+        if let Some(cache_dir) = get_cache_dir() {
+            let _ = fs::create_dir_all(&cache_dir).await;
+
+            let cache = PressureCache {
+                samples,
+                location_key: make_location_key(latitude, longitude),
+            };
+
+            if let Ok(json) = serde_json::to_string(&cache) {
+                let _ = fs::write(cache_dir.join("pressure.json"), json).await;
+            }
+        }
+    });
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct NormalsCache {
+    /// By location key, for every place shown so far.
+    places: BTreeMap<String, MonthlyNormals>,
+}
+
+async fn read_normals_cache(path: &Path) -> NormalsCache {
+    let contents = fs::read_to_string(path).await.unwrap_or_default();
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+/// Climate normals fetched for this location by an earlier session; kept
+/// for good, since they don't change.
+pub async fn load_cached_normals(latitude: f64, longitude: f64) -> Option<MonthlyNormals> {
+    let cache_path = get_cache_dir()?.join("normals.json");
+    let cache = read_normals_cache(&cache_path).await;
+    cache
+        .places
+        .get(&make_location_key(latitude, longitude))
+        .copied()
+}
+
+/// Adds this location's normals to those saved for other places.
+pub fn save_normals_cache(normals: MonthlyNormals, latitude: f64, longitude: f64) {
+    tokio::spawn(async move {
+        if let Some(cache_dir) = get_cache_dir() {
+            let _ = fs::create_dir_all(&cache_dir).await;
+
+            let cache_path = cache_dir.join("normals.json");
+            let mut cache = read_normals_cache(&cache_path).await;
+            cache
+                .places
+                .insert(make_location_key(latitude, longitude), normals);
+
+            if let Ok(json) = serde_json::to_string(&cache) {
+                let _ = fs::write(cache_path, json).await;
+            }
+        }
+    });
+}
+
+/// Requests made to `provider` so far, saved by this or an earlier session
+/// so its daily budget holds across restarts.
+pub async fn load_request_count(provider: Provider) -> Option<RequestCount> {
+    let cache_path = get_cache_dir()?.join(format!("requests-{}.json", provider.name()));
+    let contents = fs::read_to_string(&cache_path).await.ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+pub fn save_request_count(provider: Provider, count: RequestCount) {
+    tokio::spawn(async move {
+        if let Some(cache_dir) = get_cache_dir() {
+            let _ = fs::create_dir_all(&cache_dir).await;
+
+            if let Ok(json) = serde_json::to_string(&count) {
+                let path = cache_dir.join(format!("requests-{}.json", provider.name()));
+                let _ = fs::write(path, json).await;
+            }
+        }
+    });
+}
@@ -1,7 +1,11 @@
-use clap::Parser;
+use chrono::{NaiveDate, NaiveTime};
 use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Args, Parser, Subcommand};
 use clap_complete::Shell;
+use std::path::PathBuf;
+use std::time::Duration;
 
+use crate::config::Provider;
 use crate::weather::WeatherCondition;
 
 const LONG_VERSION: &str = concat!(
@@ -28,9 +32,26 @@ fn simulate_parser() -> PossibleValuesParser {
     )
 }
 
+/// Providers that take an API key, by config name.
+fn keyed_provider_parser() -> PossibleValuesParser {
+    PossibleValuesParser::new(
+        Provider::ALL
+            .iter()
+            .filter(|p| p.requires_api_key())
+            .map(|p| PossibleValue::new(p.name())),
+    )
+}
+
 #[derive(Parser)]
 #[command(version, long_version = LONG_VERSION, about = ABOUT, long_about = None)]
 pub struct Cli {
+    #[arg(
+        value_name = "LOCATION",
+        conflicts_with_all = ["profile", "auto_location"],
+        help = "Saved location to show, by name (see `weathr locations`)"
+    )]
+    pub location: Option<String>,
+
     #[arg(
         short,
         long,
@@ -40,6 +61,25 @@ pub struct Cli {
     )]
     pub simulate: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "FROM:TO",
+        value_parser = parse_transition,
+        conflicts_with = "simulate",
+        help = "Simulate a gradual change between two conditions, e.g. rain:clear"
+    )]
+    pub simulate_transition: Option<(WeatherCondition, WeatherCondition)>,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        default_value = "60s",
+        value_parser = parse_duration,
+        requires = "simulate_transition",
+        help = "How long --simulate-transition takes (e.g. 30s, 2m)"
+    )]
+    pub over: Duration,
+
     #[arg(
         short,
         long,
@@ -50,9 +90,40 @@ pub struct Cli {
     #[arg(short, long, help = "Enable falling autumn leaves")]
     pub leaves: bool,
 
+    #[arg(long, help = "Show a wind sock indicating wind speed and direction")]
+    pub wind_sock: bool,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Scene to show: world or a custom scene (see `weathr preview --list`)"
+    )]
+    pub scene: Option<String>,
+
+    #[arg(
+        long,
+        help = "Fetch every minute or two during severe weather, with a countdown to when it eases"
+    )]
+    pub storm_watch: bool,
+
     #[arg(long, help = "Auto-detect location via IP (uses ipinfo.io)")]
     pub auto_location: bool,
 
+    #[arg(
+        long,
+        conflicts_with_all = ["location", "profile", "auto_location"],
+        help = "Use coordinates, a map link or a place name copied to the clipboard"
+    )]
+    pub from_clipboard: bool,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with = "auto_location",
+        help = "Start at a saved [[locations]] profile, with its units"
+    )]
+    pub profile: Option<String>,
+
     #[arg(long, help = "Hide location coordinates in UI")]
     pub hide_location: bool,
 
@@ -76,8 +147,351 @@ pub struct Cli {
     #[arg(long, help = "Run silently (suppress non-error output)")]
     pub silent: bool,
 
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        help = "Quit automatically after this long (e.g. 5s, 2m)"
+    )]
+    pub duration: Option<Duration>,
+
+    #[arg(
+        long,
+        help = "Print a summary of the weather and the next 12 hours on exit"
+    )]
+    pub summary: bool,
+
+    #[arg(
+        long,
+        help = "Skip TLS certificate verification (prefer [network] ca_bundle)"
+    )]
+    pub insecure: bool,
+
     #[arg(long, value_name = "SHELL", value_enum)]
     pub completions: Option<Shell>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    #[command(about = "Replay archived weather for the current location as a time-lapse")]
+    Replay(ReplayArgs),
+
+    #[command(about = "Print a timestamped line to stdout whenever the weather changes")]
+    Watch(WatchArgs),
+
+    #[command(about = "List, add or remove saved locations ([[locations]] in config.toml)")]
+    Locations(LocationsArgs),
+
+    #[command(about = "Keep provider API keys in the system keyring instead of config.toml")]
+    Auth(AuthArgs),
+
+    #[command(about = "Print a single frame of a scene and weather condition, then exit")]
+    Preview(PreviewArgs),
+
+    #[command(about = "Show two places side by side, e.g. `weathr compare london \"new york\"`")]
+    Compare(CompareArgs),
+
+    #[command(
+        about = "Print this month as a calendar with each day's weather and low/high, then exit"
+    )]
+    Month,
+
+    #[command(about = "Print the weather recorded with record_history, a line a day")]
+    History(HistoryArgs),
+
+    #[command(
+        about = "Print the forecast along a GPX or KML route, e.g. `weathr route trip.gpx --depart 08:00 --speed 80`"
+    )]
+    Route(RouteArgs),
+}
+
+#[derive(Args)]
+pub struct ReplayArgs {
+    #[arg(long, value_name = "DATE", help = "First day to replay (YYYY-MM-DD)")]
+    pub from: NaiveDate,
+
+    #[arg(
+        long,
+        value_name = "DATE",
+        help = "Last day to replay (defaults to one week after --from)"
+    )]
+    pub to: Option<NaiveDate>,
+
+    #[arg(
+        long,
+        value_name = "FACTOR",
+        default_value = "600x",
+        value_parser = parse_speed,
+        help = "Time-lapse factor, e.g. 600x plays one hour in six seconds"
+    )]
+    pub speed: f64,
+}
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    #[arg(
+        long,
+        value_name = "DAYS",
+        default_value_t = 7,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "How many days back to show, today included"
+    )]
+    pub days: u32,
+}
+
+#[derive(Args)]
+pub struct RouteArgs {
+    #[arg(value_name = "FILE", help = "GPX track or route, or KML path")]
+    pub path: PathBuf,
+
+    #[arg(
+        long,
+        value_name = "HH:MM",
+        value_parser = parse_clock_time,
+        help = "When you set off (defaults to now; tomorrow if the time has passed)"
+    )]
+    pub depart: Option<NaiveTime>,
+
+    #[arg(
+        long,
+        value_name = "SPEED",
+        value_parser = parse_positive,
+        help = "Average speed in km/h, or mph with miles as the distance unit"
+    )]
+    pub speed: f64,
+
+    #[arg(
+        long,
+        value_name = "DISTANCE",
+        default_value_t = 20.0,
+        value_parser = parse_positive,
+        help = "Distance between forecasts, in km or miles"
+    )]
+    pub every: f64,
+}
+
+#[derive(Args)]
+pub struct WatchArgs {
+    #[arg(
+        long,
+        value_name = "DEGREES",
+        default_value_t = 2.0,
+        help = "Temperature bucket size in display units; a line is printed when the bucket changes"
+    )]
+    pub bucket: f64,
+
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 300,
+        help = "Seconds between weather checks"
+    )]
+    pub interval: u64,
+
+    #[arg(long, help = "Print the current weather once and exit")]
+    pub once: bool,
+}
+
+#[derive(Args)]
+pub struct LocationsArgs {
+    #[command(subcommand)]
+    pub action: Option<LocationsAction>,
+}
+
+#[derive(Subcommand)]
+pub enum LocationsAction {
+    #[command(about = "List saved locations (the default)")]
+    List,
+
+    #[command(
+        about = "Save a location under a name, e.g. `weathr locations add mum --place \"Leeds, UK\"`"
+    )]
+    Add(AddLocationArgs),
+
+    #[command(about = "Remove a saved location")]
+    Remove {
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+}
+
+#[derive(Args)]
+pub struct AddLocationArgs {
+    #[arg(
+        value_name = "NAME",
+        help = "Name to use with `weathr NAME` or --profile"
+    )]
+    pub name: String,
+
+    #[arg(
+        long,
+        allow_negative_numbers = true,
+        requires = "lon",
+        conflicts_with = "place",
+        help = "Latitude (-90 to 90)"
+    )]
+    pub lat: Option<f64>,
+
+    #[arg(
+        long,
+        allow_negative_numbers = true,
+        requires = "lat",
+        help = "Longitude (-180 to 180)"
+    )]
+    pub lon: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "QUERY",
+        conflicts_with = "lon",
+        help = "Look up coordinates by place name (once, via Nominatim)"
+    )]
+    pub place: Option<String>,
+
+    #[arg(long, help = "City name to show in the HUD")]
+    pub city: Option<String>,
+}
+
+#[derive(Args)]
+pub struct PreviewArgs {
+    #[arg(
+        long,
+        value_name = "ID",
+        help = "Scene to show (see --list; defaults to the theme's)"
+    )]
+    pub scene: Option<String>,
+
+    #[arg(
+        short,
+        long,
+        value_name = "CONDITION",
+        default_value = "clear",
+        value_parser = simulate_parser(),
+        help = "Weather condition to show"
+    )]
+    pub condition: String,
+
+    #[arg(short, long, help = "Show the scene at night")]
+    pub night: bool,
+
+    #[arg(short, long, help = "Add falling autumn leaves")]
+    pub leaves: bool,
+
+    #[arg(
+        long,
+        value_name = "COLUMNS",
+        help = "Frame width (defaults to the terminal's, or 80)"
+    )]
+    pub width: Option<u16>,
+
+    #[arg(
+        long,
+        value_name = "ROWS",
+        help = "Frame height (defaults to the terminal's, or 24)"
+    )]
+    pub height: Option<u16>,
+
+    #[arg(long, help = "List scenes and weather conditions instead")]
+    pub list: bool,
+}
+
+#[derive(Args)]
+pub struct CompareArgs {
+    #[arg(
+        value_name = "PLACE",
+        help = "Place on the left, looked up via Nominatim"
+    )]
+    pub left: String,
+
+    #[arg(value_name = "PLACE", help = "Place on the right")]
+    pub right: String,
+}
+
+#[derive(Args)]
+pub struct AuthArgs {
+    #[command(subcommand)]
+    pub action: AuthAction,
+}
+
+#[derive(Subcommand)]
+pub enum AuthAction {
+    #[command(about = "Store an API key, e.g. `weathr auth set met_office` (prompts for it)")]
+    Set {
+        #[arg(value_name = "PROVIDER", value_parser = keyed_provider_parser())]
+        provider: String,
+    },
+
+    #[command(about = "Remove a stored API key")]
+    Remove {
+        #[arg(value_name = "PROVIDER", value_parser = keyed_provider_parser())]
+        provider: String,
+    },
+}
+
+pub fn parse_speed(value: &str) -> Result<f64, String> {
+    let trimmed = value.trim();
+    let number = trimmed
+        .strip_suffix(['x', 'X'])
+        .unwrap_or(trimmed)
+        .parse::<f64>()
+        .map_err(|_| format!("invalid speed '{}' (expected e.g. 600x)", value))?;
+
+    if !number.is_finite() || number <= 0.0 {
+        return Err(format!("speed must be greater than zero, got '{}'", value));
+    }
+
+    Ok(number)
+}
+
+pub fn parse_clock_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| format!("invalid time '{}' (expected HH:MM)", value))
+}
+
+pub fn parse_positive(value: &str) -> Result<f64, String> {
+    let number = value
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("invalid number '{}'", value))?;
+
+    if !number.is_finite() || number <= 0.0 {
+        return Err(format!("must be greater than zero, got '{}'", value));
+    }
+
+    Ok(number)
+}
+
+pub fn parse_transition(value: &str) -> Result<(WeatherCondition, WeatherCondition), String> {
+    let (from, to) = value
+        .split_once(':')
+        .ok_or_else(|| format!("invalid transition '{}' (expected FROM:TO)", value))?;
+    Ok((from.parse()?, to.parse()?))
+}
+
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let trimmed = value.trim();
+    let (number, scale) = if let Some(minutes) = trimmed.strip_suffix('m') {
+        (minutes, 60.0)
+    } else {
+        (trimmed.strip_suffix('s').unwrap_or(trimmed), 1.0)
+    };
+
+    let seconds = number
+        .parse::<f64>()
+        .map_err(|_| format!("invalid duration '{}' (expected e.g. 60s or 2m)", value))?
+        * scale;
+
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return Err(format!(
+            "duration must be greater than zero, got '{}'",
+            value
+        ));
+    }
+
+    Ok(Duration::from_secs_f64(seconds))
 }
 
 pub fn extract_simulate_missing_value(err: clap::Error) -> clap::Error {
@@ -112,4 +526,5 @@ pub fn print_simulate_help() {
     eprintln!("  weathr --simulate rain");
     eprintln!("  weathr --simulate snow --night");
     eprintln!("  weathr -s thunderstorm -n");
+    eprintln!("  weathr --simulate-transition rain:clear --over 60s");
 }
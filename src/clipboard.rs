@@ -0,0 +1,194 @@
+//! `--from-clipboard`: coordinates or a place name copied from a maps app,
+//! read with the system's clipboard tool. Place names are looked up the same
+//! way as for `weathr compare`.
+
+use crate::error::GeolocationError;
+use crate::geolocation::{self, GeoLocation};
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Longer text than this wasn't copied as a place name.
+const MAX_PLACE_LEN: usize = 200;
+
+/// Clipboard readers to try in turn, as program and arguments.
+#[cfg(target_os = "macos")]
+const TOOLS: &[&[&str]] = &[&["pbpaste"]];
+
+#[cfg(windows)]
+const TOOLS: &[&[&str]] = &[&["powershell", "-NoProfile", "-Command", "Get-Clipboard"]];
+
+#[cfg(not(any(target_os = "macos", windows)))]
+const TOOLS: &[&[&str]] = &[
+    &["wl-paste", "--no-newline"],
+    &["xclip", "-selection", "clipboard", "-o"],
+    &["xsel", "--clipboard", "--output"],
+];
+
+#[derive(Debug, PartialEq)]
+enum Clipped {
+    Coordinates(f64, f64),
+    Place(String),
+}
+
+/// The location in the clipboard, looked up when it's a place name.
+pub async fn location() -> Result<GeoLocation, GeolocationError> {
+    let Some(text) = read().await else {
+        let tools: Vec<&str> = TOOLS.iter().map(|tool| tool[0]).collect();
+        return Err(GeolocationError::Clipboard(format!(
+            "it's empty or can't be read (tried {})",
+            tools.join(", ")
+        )));
+    };
+    match parse(&text) {
+        Some(Clipped::Coordinates(latitude, longitude)) => Ok(GeoLocation {
+            latitude,
+            longitude,
+            city: None,
+        }),
+        Some(Clipped::Place(place)) => geolocation::geocode(&place).await,
+        None => Err(GeolocationError::Clipboard(
+            "it holds neither coordinates nor a place name".to_string(),
+        )),
+    }
+}
+
+/// The clipboard's text from the first tool that reads any.
+async fn read() -> Option<String> {
+    for tool in TOOLS {
+        let output = Command::new(tool[0])
+            .args(&tool[1..])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .await;
+        let Ok(output) = output else {
+            continue;
+        };
+        let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !text.is_empty() {
+            return Some(text);
+        }
+    }
+    None
+}
+
+/// Coordinates as written or in a map link, else the first line as a
+/// place name.
+fn parse(text: &str) -> Option<Clipped> {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let coordinates = if line.contains("://") {
+        link_coordinates(line)
+    } else {
+        coordinates(line)
+    };
+    if let Some((latitude, longitude)) = coordinates {
+        return Some(Clipped::Coordinates(latitude, longitude));
+    }
+    (!line.contains("://") && line.len() <= MAX_PLACE_LEN).then(|| Clipped::Place(line.to_string()))
+}
+
+/// "52.52, 13.41", "52.52 13.41" or "52.5200° N, 13.4050° W".
+fn coordinates(text: &str) -> Option<(f64, f64)> {
+    let mut values = Vec::new();
+    for token in text
+        .split(|c: char| c == ',' || c == '°' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+    {
+        let (number, hemisphere) = match token.char_indices().last()? {
+            (i, c @ ('N' | 'S' | 'E' | 'W')) => (&token[..i], Some(c)),
+            _ => (token, None),
+        };
+        if !number.is_empty() {
+            values.push((number.parse::<f64>().ok()?, None));
+        }
+        if let Some(hemisphere) = hemisphere {
+            values.last_mut()?.1 = Some(hemisphere);
+        }
+    }
+    let [first, second] = values[..] else {
+        return None;
+    };
+    let signed = |(value, hemisphere): (f64, Option<char>)| match hemisphere {
+        Some('S' | 'W') => -value.abs(),
+        _ => value,
+    };
+    // Longitude first only when the hemispheres say so
+    let (latitude, longitude) = match (first.1, second.1) {
+        (Some('E' | 'W'), Some('N' | 'S')) => (signed(second), signed(first)),
+        _ => (signed(first), signed(second)),
+    };
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+        .then_some((latitude, longitude))
+}
+
+/// The place a Google Maps ("/@52.52,13.41,15z", "?q=52.52,13.41") or
+/// OpenStreetMap ("#map=15/52.52/13.41") link points at.
+fn link_coordinates(link: &str) -> Option<(f64, f64)> {
+    let link = link.replace("%2C", ",").replace("%2c", ",");
+    if let Some((_, at)) = link.split_once("/@") {
+        let mut parts = at.split(',');
+        return coordinates(&format!("{},{}", parts.next()?, parts.next()?));
+    }
+    if let Some((_, map)) = link.split_once("map=") {
+        let mut parts = map.split(['/', '&', '#']).skip(1);
+        return coordinates(&format!("{},{}", parts.next()?, parts.next()?));
+    }
+    ["q=", "query=", "ll="].iter().find_map(|key| {
+        let (_, value) = link.split_once(key)?;
+        coordinates(value.split(['&', '#']).next()?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_written_coordinates() {
+        assert_eq!(
+            parse("52.52, 13.41"),
+            Some(Clipped::Coordinates(52.52, 13.41))
+        );
+        assert_eq!(
+            parse("  -33.8688 151.2093\n"),
+            Some(Clipped::Coordinates(-33.8688, 151.2093))
+        );
+        assert_eq!(
+            parse("40.7128° N, 74.0060° W"),
+            Some(Clipped::Coordinates(40.7128, -74.006))
+        );
+        assert_eq!(
+            parse("74.0060W 40.7128N"),
+            Some(Clipped::Coordinates(40.7128, -74.006))
+        );
+        // Out of range, so not coordinates
+        assert_eq!(coordinates("120.0, 13.41"), None);
+    }
+
+    #[test]
+    fn test_map_links() {
+        assert_eq!(
+            parse("https://www.google.com/maps/@48.8584,2.2945,17z"),
+            Some(Clipped::Coordinates(48.8584, 2.2945))
+        );
+        assert_eq!(
+            parse("https://maps.google.com/?q=35.6762%2C139.6503"),
+            Some(Clipped::Coordinates(35.6762, 139.6503))
+        );
+        assert_eq!(
+            parse("https://www.openstreetmap.org/#map=15/52.5200/13.4050&layers=C"),
+            Some(Clipped::Coordinates(52.52, 13.405))
+        );
+        assert_eq!(parse("https://example.com/weather"), None);
+    }
+
+    #[test]
+    fn test_place_names() {
+        assert_eq!(
+            parse("Leeds, UK\nsecond line"),
+            Some(Clipped::Place("Leeds, UK".to_string()))
+        );
+        assert_eq!(parse(" \n "), None);
+        assert_eq!(parse(&"x".repeat(MAX_PLACE_LEN + 1)), None);
+    }
+}
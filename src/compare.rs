@@ -0,0 +1,307 @@
+//! `weathr compare`: two places side by side, each in its own half of the
+//! screen with its own weather, under a line comparing them.
+
+use crate::app::{App, SessionOptions};
+use crate::config::{Config, NotificationsConfig};
+use crate::error::GeolocationError;
+use crate::geolocation::{self, GeoLocation};
+use crate::keymap::{Action, Keymap};
+use crate::render::TerminalRenderer;
+use crate::scheduler::Scheduler;
+use crate::theme::ThemeRegistry;
+use crate::weather::units::format_temperature;
+use crate::weather::{WeatherData, WeatherUnits};
+use chrono::{DateTime, Utc};
+use crossterm::event::{self, Event};
+use crossterm::style::Color;
+use std::io;
+
+/// Rows above the panes, for the comparison.
+const HUD_ROWS: u16 = 1;
+
+/// A place as looked up, with the name to show for it.
+pub struct Place {
+    pub name: String,
+    pub location: GeoLocation,
+}
+
+/// Looks both places up, one after the other as Nominatim asks.
+pub async fn locate(left: &str, right: &str) -> Result<[Place; 2], GeolocationError> {
+    Ok([lookup(left).await?, lookup(right).await?])
+}
+
+async fn lookup(query: &str) -> Result<Place, GeolocationError> {
+    let location = geolocation::geocode(query).await?;
+    Ok(Place {
+        name: location.city.clone().unwrap_or_else(|| query.to_string()),
+        location,
+    })
+}
+
+struct Pane {
+    name: String,
+    app: App,
+    renderer: TerminalRenderer,
+}
+
+/// Two live views sharing one screen. Each fetches its own weather in the
+/// background, so both places update at once.
+pub struct Compare {
+    panes: [Pane; 2],
+    units: WeatherUnits,
+    keymap: Keymap,
+}
+
+impl Compare {
+    pub fn new(config: &Config, places: [Place; 2], width: u16, height: u16) -> Self {
+        let sizes = pane_sizes(width, height);
+        let mut first = true;
+        let panes = places.map(|place| {
+            let mut config = config.clone();
+            config.location.latitude = place.location.latitude;
+            config.location.longitude = place.location.longitude;
+            config.location.city = Some(place.name.clone());
+            config.location.auto = false;
+            // Too narrow for the full HUD; the comparison line stands in
+            config.hide_hud = true;
+            // Sound and notifications from the first pane only, so they
+            // don't come twice
+            if !std::mem::take(&mut first) {
+                config.audio.enabled = false;
+                config.notifications = NotificationsConfig::default();
+            }
+
+            let mut themes = ThemeRegistry::new();
+            let _ = themes.set_active(config.normalized_theme());
+            let (pane_width, pane_height) = sizes.1;
+            Pane {
+                name: place.name,
+                app: App::new(
+                    &config,
+                    SessionOptions::default(),
+                    pane_width,
+                    pane_height,
+                    themes,
+                ),
+                renderer: TerminalRenderer::pane(pane_width, pane_height),
+            }
+        });
+
+        Self {
+            panes,
+            units: config.units,
+            keymap: Keymap::new(),
+        }
+    }
+
+    fn resize(&mut self, width: u16, height: u16) {
+        let (_, (pane_width, pane_height)) = pane_sizes(width, height);
+        for pane in &mut self.panes {
+            pane.renderer = TerminalRenderer::pane(pane_width, pane_height);
+            pane.app.resize(pane_width, pane_height);
+        }
+    }
+
+    fn render_frame(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        rng: &mut impl rand::Rng,
+    ) -> io::Result<()> {
+        renderer.clear()?;
+        let (width, height) = renderer.get_size();
+        let (divider, _) = pane_sizes(width, height);
+
+        for (pane, x) in self.panes.iter_mut().zip([0, divider + 1]) {
+            pane.app.render_pane(&mut pane.renderer, rng)?;
+            renderer.draw_pane(&pane.renderer, x, HUD_ROWS);
+        }
+        for y in HUD_ROWS..height {
+            renderer.render_char(divider, y, '│', Color::DarkGrey)?;
+        }
+
+        let [left, right] = &self.panes;
+        let text = comparison_text(
+            [
+                (
+                    &left.name,
+                    left.app.live_weather().map(|(weather, ..)| weather),
+                ),
+                (
+                    &right.name,
+                    right.app.live_weather().map(|(weather, ..)| weather),
+                ),
+            ],
+            self.units,
+            Utc::now(),
+        );
+        renderer.render_line_colored(0, 0, &text, Color::Cyan)
+    }
+
+    pub async fn run(&mut self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let mut rng = rand::rng();
+        let frame_duration = self.panes[0].app.prepare_renderer(renderer);
+
+        loop {
+            self.render_frame(renderer, &mut rng)?;
+            renderer.flush()?;
+
+            if event::poll(frame_duration)? {
+                match event::read()? {
+                    Event::Resize(width, height) => {
+                        renderer.manual_resize(width, height)?;
+                        let (width, height) = renderer.get_size();
+                        self.resize(width, height);
+                    }
+                    Event::Key(key_event) => match self.keymap.handle(key_event) {
+                        Some(Action::Quit) => break,
+                        // Either place on its own makes no sense here
+                        Some(action @ (Action::Refresh | Action::TogglePause)) => {
+                            for pane in &mut self.panes {
+                                pane.app.apply(action);
+                            }
+                        }
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The divider's column, and the size of each pane either side of it below
+/// the comparison line.
+fn pane_sizes(width: u16, height: u16) -> (u16, (u16, u16)) {
+    let pane_width = width.saturating_sub(1) / 2;
+    (pane_width, (pane_width, height.saturating_sub(HUD_ROWS)))
+}
+
+/// "London: Rain 12.3°C | New York: Clear 18.1°C | New York 5.8°C warmer,
+/// 5h behind". The time difference is between the places' clocks `at`.
+fn comparison_text(
+    places: [(&str, Option<&WeatherData>); 2],
+    units: WeatherUnits,
+    at: DateTime<Utc>,
+) -> String {
+    let mut parts: Vec<String> = places
+        .iter()
+        .map(|(name, weather)| match weather {
+            Some(weather) => {
+                let (temp, unit) = format_temperature(weather.temperature, units.temperature);
+                format!(
+                    "{}: {} {:.1}{}",
+                    name,
+                    weather.condition.label(),
+                    temp,
+                    unit
+                )
+            }
+            None => format!("{}: Loading", name),
+        })
+        .collect();
+
+    if let [(_, Some(left)), (right_name, Some(right))] = places {
+        let (left_temp, unit) = format_temperature(left.temperature, units.temperature);
+        let (right_temp, _) = format_temperature(right.temperature, units.temperature);
+        let difference = right_temp - left_temp;
+        let mut comparison = if difference.abs() < 0.05 {
+            format!("{} same temperature", right_name)
+        } else {
+            format!(
+                "{} {:.1}{} {}",
+                right_name,
+                difference.abs(),
+                unit,
+                if difference > 0.0 { "warmer" } else { "colder" }
+            )
+        };
+        if let Some(hours) = hours_apart(left, right, at) {
+            comparison.push_str(&match hours {
+                0.0 => ", same time".to_string(),
+                h if h > 0.0 => format!(", {}h ahead", h),
+                h => format!(", {}h behind", -h),
+            });
+        }
+        parts.push(comparison);
+    }
+
+    parts.push("Press 'q' to quit".to_string());
+    parts.join(" | ")
+}
+
+/// How many hours `right`'s clock is ahead of `left`'s `at` that moment,
+/// from the time zones they came with.
+fn hours_apart(left: &WeatherData, right: &WeatherData, at: DateTime<Utc>) -> Option<f64> {
+    let offset = |weather: &WeatherData| {
+        weather.details.timezone.as_ref()?;
+        Some(Scheduler::of(weather).local(at).offset().local_minus_utc())
+    };
+    Some(f64::from(offset(right)? - offset(left)?) / 3600.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+    use crate::weather::types::WeatherDetails;
+    use chrono::TimeZone;
+
+    fn noon() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()
+    }
+
+    fn weather(condition: WeatherCondition, temperature: f64, timezone: &str) -> WeatherData {
+        WeatherData {
+            temperature,
+            source: "OM".to_string(),
+            details: WeatherDetails {
+                timezone: Some(timezone.to_string()),
+                ..Default::default()
+            },
+            timestamp: "2026-10-16T14:00".to_string(),
+            ..WeatherData::sample(condition)
+        }
+    }
+
+    #[test]
+    fn test_comparison_line() {
+        let london = weather(WeatherCondition::Rain, 12.3, "Europe/London");
+        let new_york = weather(WeatherCondition::Clear, 18.1, "America/New_York");
+        let text = comparison_text(
+            [("London", Some(&london)), ("New York", Some(&new_york))],
+            WeatherUnits::metric(),
+            noon(),
+        );
+        assert!(text.starts_with("London: Rain 12.3°C | New York: Clear 18.1°C | "));
+        assert!(text.contains("New York 5.8°C warmer, 5h behind"));
+
+        let text = comparison_text(
+            [("London", Some(&london)), ("New York", None)],
+            WeatherUnits::metric(),
+            noon(),
+        );
+        assert_eq!(
+            text,
+            "London: Rain 12.3°C | New York: Loading | Press 'q' to quit"
+        );
+    }
+
+    #[test]
+    fn test_hours_apart_by_time_zone() {
+        let weather = |timezone| weather(WeatherCondition::Clear, 10.0, timezone);
+        let london = weather("Europe/London");
+        let kathmandu = weather("Asia/Kathmandu");
+        assert_eq!(hours_apart(&london, &kathmandu, noon()), Some(4.75));
+
+        // A fortnight on, London's clocks have gone back and New York's haven't
+        let later = noon() + chrono::Duration::days(14);
+        let new_york = weather("America/New_York");
+        assert_eq!(hours_apart(&london, &new_york, later), Some(-4.0));
+
+        let mut unknown = weather("UTC");
+        unknown.details.timezone = None;
+        assert_eq!(hours_apart(&london, &unknown, noon()), None);
+    }
+}
@@ -1,16 +1,24 @@
+use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use toml::Table;
 
 use crate::error::ConfigError;
-use crate::weather::types::WeatherUnits;
+use crate::scene::events::{Event, Events};
+use crate::secrets;
+use crate::weather::types::{IconSet, WeatherUnits};
 
 pub const ENV_LATITUDE: &str = "WEATHR_LATITUDE";
 pub const ENV_LONGITUDE: &str = "WEATHR_LONGITUDE";
 pub const DEFAULT_THEME: &str = "default";
+pub const DEFAULT_BEDTIME: &str = "22:30";
+/// Beyond this the ground detail covers every cell anyway.
+const MAX_GROUND_DENSITY: f64 = 4.0;
+/// How deep `include`s may nest, which also stops a file including itself.
+const MAX_INCLUDE_DEPTH: usize = 8;
 
 #[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -31,10 +39,181 @@ pub struct Config {
     pub units: WeatherUnits,
     #[serde(default)]
     pub silent: bool,
+    /// Print a plain-text weather summary after quitting.
     #[serde(default)]
-    pub provider: HashMap<Provider, Table>,
+    pub summary_on_exit: bool,
+    /// Append every fetch to `history.csv` in the cache dir, for
+    /// `weathr history`.
+    #[serde(default)]
+    pub record_history: bool,
+    #[serde(default)]
+    pub provider: ProviderConfig,
     #[serde(default = "default_theme")]
     pub theme: String,
+    /// Animation time scale; `None` plays in real time.
+    #[serde(default)]
+    pub animation_speed: Option<f64>,
+    /// Show a wind sock in the yard.
+    #[serde(default)]
+    pub wind_sock: bool,
+    /// Name the wind's Beaufort force in the HUD, e.g. "fresh breeze".
+    #[serde(default)]
+    pub beaufort: bool,
+    /// In severe weather, fetch every minute or two, count down to when it
+    /// eases and darken the scene.
+    #[serde(default)]
+    pub storm_watch: bool,
+    /// Symbols for conditions in the forecast strip, `weathr month` and,
+    /// with Nerd Font icons, the HUD and `watch` lines.
+    #[serde(default)]
+    pub icons: IconSet,
+    /// How much to draw per second; lighter over SSH by default.
+    #[serde(default)]
+    pub rendering: Rendering,
+    /// Time ("HH:MM") around which the house lights go out.
+    #[serde(default)]
+    pub bedtime: Option<String>,
+    #[serde(default)]
+    pub audio: AudioConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub footer: FooterConfig,
+    #[serde(default)]
+    pub temperature_colors: TemperatureColors,
+    /// Saved places, picked with `--profile` or cycled with `l`.
+    #[serde(default)]
+    pub locations: Vec<LocationProfile>,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub scene: SceneConfig,
+    #[serde(default)]
+    pub events: EventsConfig,
+}
+
+/// Units for the system locale, by the usual POSIX precedence of the
+/// locale variables; metric when none is set.
+fn locale_units() -> WeatherUnits {
+    ["LC_ALL", "LC_MEASUREMENT", "LANG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+        .map_or_else(WeatherUnits::default, |locale| {
+            WeatherUnits::for_locale(&locale)
+        })
+}
+
+/// Reads the TOML at `path` on top of the files it lists in `include`, in
+/// order: each overrides the ones before it, and `path` overrides them all.
+/// Relative paths are from the including file's directory.
+fn read_with_includes(path: &Path, depth: usize) -> Result<toml::Value, ConfigError> {
+    let content = fs::read_to_string(path).map_err(|e| ConfigError::ReadError {
+        path: path.display().to_string(),
+        source: e,
+    })?;
+    let mut value: toml::Value = toml::from_str(&content).map_err(ConfigError::ParseError)?;
+    if secrets::holds_api_key(&value) {
+        secrets::warn_if_world_readable(path);
+    }
+
+    let invalid = |reason: &str| ConfigError::InvalidInclude {
+        path: path.display().to_string(),
+        reason: reason.to_string(),
+    };
+    let includes = match value
+        .as_table_mut()
+        .and_then(|table| table.remove("include"))
+    {
+        None => return Ok(value),
+        Some(toml::Value::Array(includes)) => includes,
+        Some(_) => return Err(invalid("expected a list of file paths")),
+    };
+    if depth >= MAX_INCLUDE_DEPTH {
+        return Err(invalid(
+            "includes nested too deeply (does a file include itself?)",
+        ));
+    }
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut merged = toml::Value::Table(Table::new());
+    for include in includes {
+        let include = include
+            .as_str()
+            .ok_or_else(|| invalid("expected a list of file paths"))?;
+        merge_config(
+            &mut merged,
+            read_with_includes(&dir.join(include), depth + 1)?,
+        );
+    }
+    merge_config(&mut merged, value);
+    Ok(merged)
+}
+
+/// `path` and the files it includes, directly or not, in the order they're
+/// read. Files that can't be read are left out.
+pub fn config_files(path: &Path) -> Vec<PathBuf> {
+    fn collect(path: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+        if depth > MAX_INCLUDE_DEPTH || files.iter().any(|file| file == path) {
+            return;
+        }
+        let Some(value) = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+        else {
+            return;
+        };
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let includes = value.get("include").and_then(toml::Value::as_array);
+        for include in includes
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_str)
+        {
+            collect(&dir.join(include), depth + 1, files);
+        }
+        files.push(path.to_path_buf());
+    }
+
+    let mut files = Vec::new();
+    collect(path, 0, &mut files);
+    files
+}
+
+/// As `merge_toml`, but `[[locations]]` profiles from every file are kept,
+/// so those saved in the main file don't hide those in an include.
+fn merge_config(base: &mut toml::Value, mut overlay: toml::Value) {
+    let profiles = overlay
+        .as_table_mut()
+        .and_then(|table| table.remove("locations"));
+    merge_toml(base, overlay);
+    let Some(profiles) = profiles else {
+        return;
+    };
+    if let (Some(toml::Value::Array(kept)), toml::Value::Array(added)) =
+        (base.get_mut("locations"), &profiles)
+    {
+        kept.extend(added.iter().cloned());
+    } else if let Some(table) = base.as_table_mut() {
+        table.insert("locations".to_string(), profiles);
+    }
+}
+
+/// Tables merge key by key; anything else in `overlay`, arrays included,
+/// replaces what `base` had.
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
 }
 
 fn default_theme() -> String {
@@ -44,8 +223,453 @@ fn default_theme() -> String {
 #[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Copy)]
 pub enum Provider {
     #[default]
+    #[serde(alias = "open_meteo")]
     OpenMeteo,
+    #[serde(alias = "met_office")]
     MetOffice,
+    #[serde(alias = "open_weather_map")]
+    OpenWeatherMap,
+    #[serde(alias = "met_norway")]
+    MetNorway,
+    /// Observations from the nearest airport's METAR.
+    #[serde(alias = "metar")]
+    Metar,
+    /// Deutscher Wetterdienst observations via Bright Sky.
+    #[serde(alias = "dwd")]
+    Dwd,
+    /// JSON printed by a user-supplied command, e.g. a home weather station.
+    #[serde(alias = "exec")]
+    Exec,
+    /// A local Ecowitt-compatible weather station, with another provider
+    /// filling in the sky.
+    #[serde(alias = "station")]
+    Station,
+}
+
+impl Provider {
+    pub const ALL: [Provider; 8] = [
+        Provider::OpenMeteo,
+        Provider::MetOffice,
+        Provider::OpenWeatherMap,
+        Provider::MetNorway,
+        Provider::Metar,
+        Provider::Dwd,
+        Provider::Exec,
+        Provider::Station,
+    ];
+
+    /// Looks a provider up by its config name, e.g. "met_office".
+    pub fn from_name(name: &str) -> Option<Provider> {
+        Self::ALL
+            .into_iter()
+            .find(|provider| provider.name() == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::OpenMeteo => "open_meteo",
+            Provider::MetOffice => "met_office",
+            Provider::OpenWeatherMap => "open_weather_map",
+            Provider::MetNorway => "met_norway",
+            Provider::Metar => "metar",
+            Provider::Dwd => "dwd",
+            Provider::Exec => "exec",
+            Provider::Station => "station",
+        }
+    }
+
+    /// Short tag for the HUD, so it's clear at a glance where the data is
+    /// from.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Provider::OpenMeteo => "OM",
+            Provider::MetOffice => "MetO",
+            Provider::OpenWeatherMap => "OWM",
+            Provider::MetNorway => "MetNo",
+            Provider::Metar => "METAR",
+            Provider::Dwd => "DWD",
+            Provider::Exec => "Exec",
+            Provider::Station => "Stn",
+        }
+    }
+
+    /// License of the provider's data, shown in the footer.
+    pub fn license_note(&self) -> Option<&'static str> {
+        match self {
+            Provider::OpenMeteo => Some("Weather data by Open-Meteo.com (CC BY 4.0)"),
+            Provider::MetOffice => None,
+            Provider::OpenWeatherMap => Some("OpenWeather data licensed under CC BY-SA 4.0"),
+            Provider::MetNorway => Some("MET Norway data licensed under CC BY 4.0"),
+            // US government data, public domain
+            Provider::Metar => None,
+            Provider::Dwd => Some("DWD data licensed under CC BY 4.0"),
+            Provider::Exec | Provider::Station => None,
+        }
+    }
+
+    /// Whether the provider needs an API key, which `weathr auth set` can
+    /// keep in the system keyring.
+    pub fn requires_api_key(&self) -> bool {
+        matches!(self, Provider::MetOffice | Provider::OpenWeatherMap)
+    }
+
+    /// Requests a day allowed on the provider's free tier, if it has a
+    /// fixed quota.
+    pub fn daily_request_limit(&self) -> Option<u32> {
+        match self {
+            Provider::OpenMeteo => Some(10_000),
+            Provider::MetOffice => Some(360),
+            Provider::OpenWeatherMap => Some(1_000),
+            Provider::MetNorway
+            | Provider::Metar
+            | Provider::Dwd
+            | Provider::Exec
+            | Provider::Station => None,
+        }
+    }
+
+    /// Whether the provider's terms require attribution on screen, in which
+    /// case it stays visible even with a hidden footer.
+    pub fn requires_attribution(&self) -> bool {
+        match self {
+            Provider::OpenMeteo | Provider::Metar | Provider::Exec | Provider::Station => false,
+            Provider::MetOffice
+            | Provider::OpenWeatherMap
+            | Provider::MetNorway
+            | Provider::Dwd => true,
+        }
+    }
+}
+
+/// The `[provider]` section: which provider to use, plus one optional
+/// sub-table of options per provider, e.g. `[provider.met_office]`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProviderConfig {
+    #[serde(default)]
+    pub name: Option<Provider>,
+    /// Fall back to Open-Meteo, then wttr.in, when the provider fails.
+    #[serde(default = "default_fallback")]
+    pub fallback: bool,
+    /// Two or more providers to query together and blend.
+    #[serde(default)]
+    pub consensus: Vec<Provider>,
+    #[serde(flatten)]
+    pub options: HashMap<Provider, Table>,
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            name: None,
+            fallback: default_fallback(),
+            consensus: Vec::new(),
+            options: HashMap::new(),
+        }
+    }
+}
+
+fn default_fallback() -> bool {
+    true
+}
+
+impl ProviderConfig {
+    /// The explicitly named provider, else the only one with options, else
+    /// Open-Meteo.
+    pub fn selected(&self) -> Provider {
+        if let Some(name) = self.name {
+            return name;
+        }
+        match self.options.keys().collect::<Vec<_>>().as_slice() {
+            [only] => **only,
+            _ => Provider::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Rendering {
+    /// Light over SSH, full otherwise.
+    #[default]
+    Auto,
+    Full,
+    /// Fewer frames, fewer particles and fewer escape sequences, for slow
+    /// links.
+    Light,
+}
+
+impl Rendering {
+    pub fn is_light(self, over_ssh: bool) -> bool {
+        match self {
+            Self::Auto => over_ssh,
+            Self::Full => false,
+            Self::Light => true,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FooterPosition {
+    #[default]
+    BottomRight,
+    BottomCenter,
+    /// Hides the footer unless the provider requires attribution.
+    Hidden,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(default)]
+pub struct FooterConfig {
+    pub position: FooterPosition,
+}
+
+/// HTTP settings shared by every request. The standard `HTTPS_PROXY`,
+/// `HTTP_PROXY` and `NO_PROXY` variables are honored without any of these.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Proxy for all requests, overriding the environment.
+    pub proxy: Option<String>,
+    /// PEM file of extra root certificates, e.g. a corporate TLS inspection CA.
+    pub ca_bundle: Option<PathBuf>,
+    /// Skip certificate verification entirely. A last resort.
+    pub insecure: bool,
+}
+
+/// Feels-like temperature bands, in °C, for coloring the HUD temperature.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct TemperatureColors {
+    pub enabled: bool,
+    /// Below this it is cold (blue).
+    pub cool: f64,
+    /// From this it is warm (orange); in between is mild (green).
+    pub warm: f64,
+    /// From this it is hot (red).
+    pub hot: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureBand {
+    Cold,
+    Mild,
+    Warm,
+    Hot,
+}
+
+impl TemperatureColors {
+    pub fn band(&self, feels_like: f64) -> TemperatureBand {
+        if feels_like >= self.hot {
+            TemperatureBand::Hot
+        } else if feels_like >= self.warm {
+            TemperatureBand::Warm
+        } else if feels_like >= self.cool {
+            TemperatureBand::Mild
+        } else {
+            TemperatureBand::Cold
+        }
+    }
+}
+
+impl Default for TemperatureColors {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cool: 10.0,
+            warm: 24.0,
+            hot: 32.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct SceneConfig {
+    /// The scene to show instead of the theme's: "world" or a house of the
+    /// user's own from the scenes dir. `scene = "myhouse"` is short for
+    /// this, and `--scene` overrides it.
+    pub name: Option<String>,
+    pub decorations: DecorationsConfig,
+    pub ground: GroundConfig,
+    pub commute: CommuteConfig,
+}
+
+/// Someone leaves the house in the morning and comes home in the evening,
+/// when turned on.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct CommuteConfig {
+    pub enabled: bool,
+    #[serde(deserialize_with = "deserialize_clock_time")]
+    pub leave: NaiveTime,
+    #[serde(deserialize_with = "deserialize_clock_time")]
+    pub home: NaiveTime,
+}
+
+impl Default for CommuteConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            leave: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            home: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        }
+    }
+}
+
+/// A time of day written "HH:MM".
+fn deserialize_clock_time<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<NaiveTime, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    NaiveTime::parse_from_str(text.trim(), "%H:%M")
+        .map_err(|_| serde::de::Error::custom(format!("invalid time {:?} (expected HH:MM)", text)))
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GroundTexture {
+    /// Snow when it is snowing or freezing, grass otherwise.
+    #[default]
+    Auto,
+    Grass,
+    Pavement,
+    Sand,
+    Snowfield,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct GroundConfig {
+    pub texture: GroundTexture,
+    /// How thickly flowers, pebbles and cracks are scattered; 1.0 is the
+    /// usual amount, 0.0 a plain surface.
+    pub density: f64,
+    /// Draw the sea along the right edge where the location is coastal.
+    pub shoreline: bool,
+}
+
+impl Default for GroundConfig {
+    fn default() -> Self {
+        Self {
+            texture: GroundTexture::Auto,
+            density: 1.0,
+            shoreline: true,
+        }
+    }
+}
+
+/// Which yard decorations to draw, and how far to move them.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct DecorationsConfig {
+    pub tree: bool,
+    pub fence: bool,
+    pub mailbox: bool,
+    pub street_lamps: bool,
+    pub road_sign: bool,
+    /// Only drawn on terminals wider than 120 columns.
+    pub pine_tree: bool,
+    pub offsets: DecorationOffsets,
+}
+
+impl Default for DecorationsConfig {
+    fn default() -> Self {
+        Self {
+            tree: true,
+            fence: true,
+            mailbox: true,
+            street_lamps: true,
+            road_sign: true,
+            pine_tree: true,
+            offsets: DecorationOffsets::default(),
+        }
+    }
+}
+
+/// Columns to shift each decoration by; negative moves it left.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct DecorationOffsets {
+    pub tree: i16,
+    pub fence: i16,
+    pub mailbox: i16,
+    pub street_lamps: i16,
+    pub road_sign: i16,
+    pub pine_tree: i16,
+}
+
+/// Ambient sound settings. Playback needs the `audio` cargo feature.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct AudioConfig {
+    pub enabled: bool,
+    /// Volume of the rain layer (0.0 - 1.0).
+    pub rain: f32,
+    /// Volume of the wind layer at strong wind (0.0 - 1.0).
+    pub wind: f32,
+    /// Volume of the thunder rumble (0.0 - 1.0).
+    pub thunder: f32,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rain: 0.5,
+            wind: 0.4,
+            thunder: 0.6,
+        }
+    }
+}
+
+/// Desktop notifications, each kind off unless turned on.
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    /// Rain or snow due within 30 minutes by the nowcast.
+    pub rain: bool,
+    /// The weather turning severe, as the yellow or red border shows.
+    pub alerts: bool,
+}
+
+/// Dates the yard is decorated for.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct EventsConfig {
+    /// String lights in late December, a pumpkin in late October and
+    /// fireworks at midnight on New Year's Eve.
+    pub builtin: bool,
+    /// The user's own, on top of those.
+    pub dates: Vec<Event>,
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            builtin: true,
+            dates: Vec::new(),
+        }
+    }
+}
+
+impl EventsConfig {
+    pub fn events(&self) -> Events {
+        Events::new(self.builtin, &self.dates)
+    }
+}
+
+/// A `[[locations]]` entry. `units` replaces the top-level `[units]` while
+/// this location is shown.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LocationProfile {
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(default)]
+    pub city: Option<String>,
+    #[serde(default)]
+    pub units: Option<WeatherUnits>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -108,11 +732,12 @@ impl Config {
                 );
             }
             let mut config = default;
+            config.units = locale_units();
             config.apply_env_overrides()?;
             return Ok(config);
         }
 
-        let mut config = Self::load_from_path(&config_path)?;
+        let mut config = Self::load_from_path(&config_path, locale_units())?;
         config.apply_env_overrides()?;
         config.validate()?;
         Ok(config)
@@ -155,6 +780,72 @@ impl Config {
             return Err(ConfigError::InvalidLongitude(self.location.longitude));
         }
 
+        if let Some(speed) = self.animation_speed
+            && !(speed.is_finite() && speed > 0.0)
+        {
+            return Err(ConfigError::InvalidAnimationSpeed(speed));
+        }
+
+        if let Some(bedtime) = &self.bedtime
+            && NaiveTime::parse_from_str(bedtime.trim(), "%H:%M").is_err()
+        {
+            return Err(ConfigError::InvalidBedtime(bedtime.clone()));
+        }
+
+        for profile in &self.locations {
+            if !(-90.0..=90.0).contains(&profile.latitude) {
+                return Err(ConfigError::InvalidLatitude(profile.latitude));
+            }
+            if !(-180.0..=180.0).contains(&profile.longitude) {
+                return Err(ConfigError::InvalidLongitude(profile.longitude));
+            }
+        }
+
+        let density = self.scene.ground.density;
+        if !(0.0..=MAX_GROUND_DENSITY).contains(&density) {
+            return Err(ConfigError::InvalidGroundDensity(density));
+        }
+
+        let colors = self.temperature_colors;
+        if !(colors.cool < colors.warm && colors.warm < colors.hot) {
+            return Err(ConfigError::InvalidTemperatureColors {
+                cool: colors.cool,
+                warm: colors.warm,
+                hot: colors.hot,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn bedtime(&self) -> NaiveTime {
+        self.bedtime
+            .as_deref()
+            .and_then(|bedtime| NaiveTime::parse_from_str(bedtime.trim(), "%H:%M").ok())
+            .unwrap_or_else(|| NaiveTime::parse_from_str(DEFAULT_BEDTIME, "%H:%M").unwrap())
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&LocationProfile> {
+        self.locations
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Switches the configured location (and units, if the profile sets
+    /// them) to the named `[[locations]]` profile.
+    pub fn apply_profile(&mut self, name: &str) -> Result<(), ConfigError> {
+        let profile = self
+            .profile(name)
+            .cloned()
+            .ok_or_else(|| ConfigError::UnknownProfile(name.to_string()))?;
+
+        self.location.latitude = profile.latitude;
+        self.location.longitude = profile.longitude;
+        self.location.city = profile.city;
+        self.location.auto = false;
+        if let Some(units) = profile.units {
+            self.units = units;
+        }
         Ok(())
     }
 
@@ -167,13 +858,18 @@ impl Config {
         }
     }
 
-    pub fn load_from_path(path: &PathBuf) -> Result<Self, ConfigError> {
-        let content = fs::read_to_string(path).map_err(|e| ConfigError::ReadError {
-            path: path.display().to_string(),
-            source: e,
-        })?;
-
-        let value: toml::Value = toml::from_str(&content).map_err(ConfigError::ParseError)?;
+    /// Reads `path`, taking any unit `[units]` leaves out from
+    /// `default_units`.
+    pub fn load_from_path(path: &Path, default_units: WeatherUnits) -> Result<Self, ConfigError> {
+        let mut value = read_with_includes(path, 0)?;
+
+        if let Some(scene) = value.get_mut("scene")
+            && let Some(name) = scene.as_str()
+        {
+            let mut table = toml::Table::new();
+            table.insert("name".to_string(), name.into());
+            *scene = table.into();
+        }
 
         if let Some(loc) = value.get("location") {
             let has_lat = loc.get("latitude").is_some();
@@ -189,21 +885,55 @@ impl Config {
             }
         }
 
-        toml::Value::try_into(value).map_err(ConfigError::ParseError)
+        // A preset such as `units = "uk"` sets every unit
+        let units_set = |key: &str| {
+            value
+                .get("units")
+                .is_some_and(|units| units.is_str() || units.get(key).is_some())
+        };
+        let (temperature_set, wind_speed_set, precipitation_set, distance_set) = (
+            units_set("temperature"),
+            units_set("wind_speed"),
+            units_set("precipitation"),
+            units_set("distance"),
+        );
+
+        let mut config: Self = toml::Value::try_into(value).map_err(ConfigError::ParseError)?;
+        if !temperature_set {
+            config.units.temperature = default_units.temperature;
+        }
+        if !wind_speed_set {
+            config.units.wind_speed = default_units.wind_speed;
+        }
+        if !precipitation_set {
+            config.units.precipitation = default_units.precipitation;
+        }
+        // Left out, it follows a precipitation unit that was set
+        if !distance_set && !precipitation_set {
+            config.units.distance = default_units.distance;
+        }
+        Ok(config)
     }
 
-    fn get_config_path() -> Result<PathBuf, ConfigError> {
+    pub fn get_config_path() -> Result<PathBuf, ConfigError> {
         let config_dir = dirs::config_dir()
             .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
             .ok_or(ConfigError::NoConfigDir)?;
 
         Ok(config_dir.join("weathr").join("config.toml"))
     }
+
+    /// Where custom scenes live, one directory each.
+    pub fn get_scenes_dir() -> Result<PathBuf, ConfigError> {
+        let config_path = Self::get_config_path()?;
+        Ok(config_path.with_file_name("scenes"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::weather::types::TemperatureUnit;
     use std::sync::Mutex;
 
     static ENV_MUTEX: Mutex<()> = Mutex::new(());
@@ -243,7 +973,7 @@ longitude = -74.0060
         let test_config_path = temp_dir.join("weathr_test_config.toml");
         fs::write(&test_config_path, toml_content).unwrap();
 
-        let config = Config::load_from_path(&test_config_path).unwrap();
+        let config = Config::load_from_path(&test_config_path, WeatherUnits::default()).unwrap();
         assert_eq!(config.location.latitude, 40.7128);
         assert_eq!(config.location.longitude, -74.0060);
 
@@ -253,7 +983,7 @@ longitude = -74.0060
     #[test]
     fn test_config_load_from_path_file_not_found() {
         let nonexistent_path = PathBuf::from("/tmp/nonexistent_weathr_config_12345.toml");
-        let result = Config::load_from_path(&nonexistent_path);
+        let result = Config::load_from_path(&nonexistent_path, WeatherUnits::default());
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().kind(), "ReadError");
     }
@@ -265,13 +995,83 @@ longitude = -74.0060
         let test_config_path = temp_dir.join("weathr_test_invalid.toml");
         fs::write(&test_config_path, toml_content).unwrap();
 
-        let result = Config::load_from_path(&test_config_path);
+        let result = Config::load_from_path(&test_config_path, WeatherUnits::default());
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().kind(), "ParseError");
 
         fs::remove_file(test_config_path).ok();
     }
 
+    #[test]
+    fn test_includes_are_overridden_in_order() {
+        let dir = std::env::temp_dir().join("weathr_test_includes");
+        fs::create_dir_all(dir.join("themes")).unwrap();
+        let theme = r#"
+theme = "nord"
+hide_hud = true
+
+[location]
+latitude = 1.0
+longitude = 2.0
+"#;
+        let secrets = r#"
+[location]
+longitude = 3.0
+
+[provider.open_weather_map]
+api_key = "secret"
+"#;
+        let main = r#"
+include = ["themes/nord.toml", "secrets.toml"]
+hide_hud = false
+"#;
+        fs::write(dir.join("themes/nord.toml"), theme).unwrap();
+        fs::write(dir.join("secrets.toml"), secrets).unwrap();
+        let config_path = dir.join("config.toml");
+        fs::write(&config_path, main).unwrap();
+
+        let config = Config::load_from_path(&config_path, WeatherUnits::default()).unwrap();
+        assert_eq!(config.theme, "nord");
+        // The including file has the last word
+        assert!(!config.hide_hud);
+        // Tables merge key by key
+        assert_eq!(config.location.latitude, 1.0);
+        assert_eq!(config.location.longitude, 3.0);
+        assert_eq!(config.provider.selected(), Provider::OpenWeatherMap);
+
+        // Saved locations add up rather than replace each other
+        fs::write(
+            dir.join("secrets.toml"),
+            "[[locations]]\nname = \"mum\"\nlatitude = 53.8\nlongitude = -1.55\n",
+        )
+        .unwrap();
+        fs::write(
+            &config_path,
+            format!(
+                "{}\n[[locations]]\nname = \"work\"\nlatitude = 51.5\nlongitude = -0.12\n",
+                main
+            ),
+        )
+        .unwrap();
+        let config = Config::load_from_path(&config_path, WeatherUnits::default()).unwrap();
+        let names: Vec<&str> = config.locations.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["mum", "work"]);
+        assert_eq!(
+            config_files(&config_path),
+            [
+                dir.join("themes/nord.toml"),
+                dir.join("secrets.toml"),
+                config_path.clone()
+            ]
+        );
+
+        fs::write(dir.join("secrets.toml"), r#"include = ["config.toml"]"#).unwrap();
+        let result = Config::load_from_path(&config_path, WeatherUnits::default());
+        assert_eq!(result.unwrap_err().kind(), "InvalidInclude");
+
+        fs::remove_dir_all(dir).ok();
+    }
+
     #[test]
     fn test_normalized_theme_defaults_when_blank() {
         let config = Config {
@@ -292,6 +1092,289 @@ longitude = -74.0060
         assert_eq!(config.normalized_theme(), "retro");
     }
 
+    #[test]
+    fn test_animation_speed_must_be_positive() {
+        let config: Config = toml::from_str("animation_speed = 0.0").unwrap();
+        assert_eq!(
+            config.validate().unwrap_err().kind(),
+            "InvalidAnimationSpeed"
+        );
+
+        let config: Config = toml::from_str("animation_speed = 0.5").unwrap();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_provider_names_round_trip() {
+        for provider in Provider::ALL {
+            assert_eq!(Provider::from_name(provider.name()), Some(provider));
+        }
+        assert_eq!(Provider::from_name("MetOffice"), None);
+        assert!(Provider::MetOffice.requires_api_key());
+        assert!(!Provider::OpenMeteo.requires_api_key());
+    }
+
+    #[test]
+    fn test_provider_selection() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.provider.selected(), Provider::OpenMeteo);
+
+        let config: Config = toml::from_str(
+            r#"
+[provider.met_office]
+api_key = "secret"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.provider.selected(), Provider::MetOffice);
+
+        let config: Config = toml::from_str(
+            r#"
+[provider]
+name = "open_meteo"
+
+[provider.met_office]
+api_key = "secret"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.provider.selected(), Provider::OpenMeteo);
+        assert!(config.provider.options.contains_key(&Provider::MetOffice));
+        assert!(config.provider.fallback);
+
+        let config: Config = toml::from_str(
+            r#"
+[provider]
+fallback = false
+
+[provider.met_norway]
+"#,
+        )
+        .unwrap();
+        assert!(!config.provider.fallback);
+        assert_eq!(config.provider.selected(), Provider::MetNorway);
+    }
+
+    #[test]
+    fn test_bedtime_parsing() {
+        let config: Config = toml::from_str(r#"bedtime = "late""#).unwrap();
+        assert_eq!(config.validate().unwrap_err().kind(), "InvalidBedtime");
+
+        let config: Config = toml::from_str(r#"bedtime = "23:15""#).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.bedtime(),
+            NaiveTime::from_hms_opt(23, 15, 0).unwrap()
+        );
+
+        assert_eq!(
+            Config::default().bedtime(),
+            NaiveTime::from_hms_opt(22, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rendering_is_light_over_ssh_by_default() {
+        let config = Config::default();
+        assert!(config.rendering.is_light(true));
+        assert!(!config.rendering.is_light(false));
+
+        let config: Config = toml::from_str(r#"rendering = "full""#).unwrap();
+        assert!(!config.rendering.is_light(true));
+        let config: Config = toml::from_str(r#"rendering = "light""#).unwrap();
+        assert!(config.rendering.is_light(false));
+    }
+
+    #[test]
+    fn test_commute_times() {
+        assert!(!Config::default().scene.commute.enabled);
+
+        let config: Config = toml::from_str(
+            r#"
+[scene.commute]
+enabled = true
+leave = "07:15"
+"#,
+        )
+        .unwrap();
+        let commute = config.scene.commute;
+        assert!(commute.enabled);
+        assert_eq!(commute.leave, NaiveTime::from_hms_opt(7, 15, 0).unwrap());
+        assert_eq!(commute.home, NaiveTime::from_hms_opt(18, 0, 0).unwrap());
+
+        let invalid = toml::from_str::<Config>(
+            r#"
+[scene.commute]
+home = "teatime"
+"#,
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_events_section() {
+        assert!(Config::default().events.builtin);
+
+        let config: Config = toml::from_str(
+            r#"
+[events]
+builtin = false
+
+[[events.dates]]
+decoration = "fireworks"
+from = "07-04"
+at = "21:30"
+"#,
+        )
+        .unwrap();
+        assert!(!config.events.builtin);
+        assert_eq!(
+            config.events.dates[0].decoration,
+            crate::scene::events::Decoration::Fireworks
+        );
+
+        let invalid = toml::from_str::<Config>(
+            r#"
+[[events.dates]]
+decoration = "pumpkin"
+from = "10-32"
+"#,
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_apply_profile_with_units() {
+        let mut config: Config = toml::from_str(
+            r#"
+[units]
+temperature = "celsius"
+
+[[locations]]
+name = "home"
+latitude = 52.52
+longitude = 13.41
+
+[[locations]]
+name = "US Office"
+latitude = 40.71
+longitude = -74.01
+city = "New York"
+units = { temperature = "fahrenheit", wind_speed = "mph", precipitation = "inch" }
+"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        config.apply_profile("us office").unwrap();
+        assert_eq!(config.location.latitude, 40.71);
+        assert_eq!(config.location.city.as_deref(), Some("New York"));
+        assert!(!config.location.auto);
+        assert_eq!(config.units.temperature, TemperatureUnit::Fahrenheit);
+
+        config.apply_profile("home").unwrap();
+        assert_eq!(config.location.longitude, 13.41);
+        // No units in the profile: the previous ones stay
+        assert_eq!(config.units.temperature, TemperatureUnit::Fahrenheit);
+
+        assert_eq!(
+            config.apply_profile("mars").unwrap_err().kind(),
+            "UnknownProfile"
+        );
+    }
+
+    #[test]
+    fn test_temperature_color_bands() {
+        let config: Config = toml::from_str(
+            r#"
+[temperature_colors]
+cool = 5.0
+hot = 30.0
+"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        let colors = config.temperature_colors;
+        assert_eq!(colors.band(-2.0), TemperatureBand::Cold);
+        assert_eq!(colors.band(5.0), TemperatureBand::Mild);
+        assert_eq!(colors.band(24.0), TemperatureBand::Warm);
+        assert_eq!(colors.band(35.0), TemperatureBand::Hot);
+
+        let config: Config = toml::from_str("[temperature_colors]\nwarm = 40.0").unwrap();
+        assert_eq!(
+            config.validate().unwrap_err().kind(),
+            "InvalidTemperatureColors"
+        );
+    }
+
+    #[test]
+    fn test_scene_decorations() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.scene.decorations.fence);
+        assert_eq!(
+            config.scene.decorations.offsets,
+            DecorationOffsets::default()
+        );
+
+        let config: Config = toml::from_str(
+            r#"
+[scene.decorations]
+fence = false
+mailbox = false
+
+[scene.decorations.offsets]
+tree = -3
+"#,
+        )
+        .unwrap();
+        let decorations = config.scene.decorations;
+        assert!(!decorations.fence && !decorations.mailbox);
+        assert!(decorations.tree && decorations.street_lamps);
+        assert_eq!(decorations.offsets.tree, -3);
+    }
+
+    #[test]
+    fn test_scene_name_shorthand() {
+        let dir = std::env::temp_dir().join("weathr_test_scene_name");
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+
+        fs::write(&config_path, "scene = \"myhouse\"").unwrap();
+        let config = Config::load_from_path(&config_path, WeatherUnits::default()).unwrap();
+        assert_eq!(config.scene.name.as_deref(), Some("myhouse"));
+        assert!(config.scene.decorations.fence);
+
+        fs::write(&config_path, "[scene]\nname = \"cabin\"").unwrap();
+        let config = Config::load_from_path(&config_path, WeatherUnits::default()).unwrap();
+        assert_eq!(config.scene.name.as_deref(), Some("cabin"));
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_scene_ground() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.scene.ground.texture, GroundTexture::Auto);
+
+        assert!(config.scene.ground.shoreline);
+
+        let config: Config = toml::from_str(
+            "[scene.ground]\ntexture = \"pavement\"\ndensity = 0.5\nshoreline = false",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.scene.ground.texture, GroundTexture::Pavement);
+        assert_eq!(config.scene.ground.density, 0.5);
+        assert!(!config.scene.ground.shoreline);
+
+        let config: Config = toml::from_str("[scene.ground]\ndensity = -1.0").unwrap();
+        assert_eq!(
+            config.validate().unwrap_err().kind(),
+            "InvalidGroundDensity"
+        );
+    }
+
     #[test]
     fn test_config_missing_latitude() {
         let toml_content = r#"
@@ -302,7 +1385,7 @@ longitude = 13.41
         let test_config_path = temp_dir.join("weathr_test_missing_lat.toml");
         fs::write(&test_config_path, toml_content).unwrap();
 
-        let config = Config::load_from_path(&test_config_path).unwrap();
+        let config = Config::load_from_path(&test_config_path, WeatherUnits::default()).unwrap();
         assert_eq!(config.location.latitude, default_latitude());
         assert_eq!(config.location.longitude, 13.41);
 
@@ -319,7 +1402,7 @@ latitude = 52.52
         let test_config_path = temp_dir.join("weathr_test_missing_lon.toml");
         fs::write(&test_config_path, toml_content).unwrap();
 
-        let config = Config::load_from_path(&test_config_path).unwrap();
+        let config = Config::load_from_path(&test_config_path, WeatherUnits::default()).unwrap();
         assert_eq!(config.location.latitude, 52.52);
         assert_eq!(config.location.longitude, default_longitude());
 
@@ -365,8 +1448,25 @@ longitude = 0.0
             hide_hud: false,
             units: WeatherUnits::default(),
             silent: false,
-            provider: HashMap::new(),
+            summary_on_exit: false,
+            record_history: false,
+            provider: ProviderConfig::default(),
             theme: "default".to_string(),
+            animation_speed: None,
+            wind_sock: false,
+            beaufort: false,
+            storm_watch: false,
+            icons: IconSet::default(),
+            rendering: Rendering::default(),
+            bedtime: None,
+            audio: AudioConfig::default(),
+            notifications: NotificationsConfig::default(),
+            footer: FooterConfig::default(),
+            network: NetworkConfig::default(),
+            scene: SceneConfig::default(),
+            events: EventsConfig::default(),
+            temperature_colors: TemperatureColors::default(),
+            locations: Vec::new(),
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -388,8 +1488,25 @@ longitude = 0.0
             hide_hud: false,
             units: WeatherUnits::default(),
             silent: false,
-            provider: HashMap::new(),
+            summary_on_exit: false,
+            record_history: false,
+            provider: ProviderConfig::default(),
             theme: "default".to_string(),
+            animation_speed: None,
+            wind_sock: false,
+            beaufort: false,
+            storm_watch: false,
+            icons: IconSet::default(),
+            rendering: Rendering::default(),
+            bedtime: None,
+            audio: AudioConfig::default(),
+            notifications: NotificationsConfig::default(),
+            footer: FooterConfig::default(),
+            network: NetworkConfig::default(),
+            scene: SceneConfig::default(),
+            events: EventsConfig::default(),
+            temperature_colors: TemperatureColors::default(),
+            locations: Vec::new(),
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -411,8 +1528,25 @@ longitude = 0.0
             hide_hud: false,
             units: WeatherUnits::default(),
             silent: false,
-            provider: HashMap::new(),
+            summary_on_exit: false,
+            record_history: false,
+            provider: ProviderConfig::default(),
             theme: "default".to_string(),
+            animation_speed: None,
+            wind_sock: false,
+            beaufort: false,
+            storm_watch: false,
+            icons: IconSet::default(),
+            rendering: Rendering::default(),
+            bedtime: None,
+            audio: AudioConfig::default(),
+            notifications: NotificationsConfig::default(),
+            footer: FooterConfig::default(),
+            network: NetworkConfig::default(),
+            scene: SceneConfig::default(),
+            events: EventsConfig::default(),
+            temperature_colors: TemperatureColors::default(),
+            locations: Vec::new(),
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -434,8 +1568,25 @@ longitude = 0.0
             hide_hud: false,
             units: WeatherUnits::default(),
             silent: false,
-            provider: HashMap::new(),
+            summary_on_exit: false,
+            record_history: false,
+            provider: ProviderConfig::default(),
             theme: "default".to_string(),
+            animation_speed: None,
+            wind_sock: false,
+            beaufort: false,
+            storm_watch: false,
+            icons: IconSet::default(),
+            rendering: Rendering::default(),
+            bedtime: None,
+            audio: AudioConfig::default(),
+            notifications: NotificationsConfig::default(),
+            footer: FooterConfig::default(),
+            network: NetworkConfig::default(),
+            scene: SceneConfig::default(),
+            events: EventsConfig::default(),
+            temperature_colors: TemperatureColors::default(),
+            locations: Vec::new(),
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -457,8 +1608,25 @@ longitude = 0.0
             hide_hud: false,
             units: WeatherUnits::default(),
             silent: false,
-            provider: HashMap::new(),
+            summary_on_exit: false,
+            record_history: false,
+            provider: ProviderConfig::default(),
             theme: "default".to_string(),
+            animation_speed: None,
+            wind_sock: false,
+            beaufort: false,
+            storm_watch: false,
+            icons: IconSet::default(),
+            rendering: Rendering::default(),
+            bedtime: None,
+            audio: AudioConfig::default(),
+            notifications: NotificationsConfig::default(),
+            footer: FooterConfig::default(),
+            network: NetworkConfig::default(),
+            scene: SceneConfig::default(),
+            events: EventsConfig::default(),
+            temperature_colors: TemperatureColors::default(),
+            locations: Vec::new(),
         };
         let result = config.validate();
         assert!(result.is_ok());
@@ -513,6 +1681,68 @@ precipitation = "inch"
         );
     }
 
+    #[test]
+    fn test_units_presets() {
+        use crate::weather::types::{
+            DistanceUnit, PrecipitationUnit, TemperatureUnit, WindSpeedUnit,
+        };
+
+        let config: Config = toml::from_str("units = \"uk\"").unwrap();
+        assert_eq!(config.units.temperature, TemperatureUnit::Celsius);
+        assert_eq!(config.units.wind_speed, WindSpeedUnit::Mph);
+        assert_eq!(config.units.precipitation, PrecipitationUnit::Mm);
+        assert_eq!(config.units.distance, DistanceUnit::Mi);
+
+        let config: Config = toml::from_str("units = \"imperial\"").unwrap();
+        assert_eq!(config.units.distance, DistanceUnit::Mi);
+
+        // In a table, distance follows precipitation unless given
+        let config: Config = toml::from_str("[units]\nprecipitation = \"inch\"").unwrap();
+        assert_eq!(config.units.distance, DistanceUnit::Mi);
+        let config: Config =
+            toml::from_str("[units]\nprecipitation = \"inch\"\ndistance = \"km\"").unwrap();
+        assert_eq!(config.units.distance, DistanceUnit::Km);
+
+        let err = toml::from_str::<Config>("units = \"nautical\"").unwrap_err();
+        assert!(err.to_string().contains("uk"));
+    }
+
+    #[test]
+    fn test_units_for_locale() {
+        use crate::weather::types::{TemperatureUnit, WindSpeedUnit};
+
+        let us = WeatherUnits::for_locale("en_US.UTF-8");
+        assert_eq!(us.temperature, TemperatureUnit::Fahrenheit);
+        let uk = WeatherUnits::for_locale("en_GB.UTF-8");
+        assert_eq!(uk.temperature, TemperatureUnit::Celsius);
+        assert_eq!(uk.wind_speed, WindSpeedUnit::Mph);
+        let german = WeatherUnits::for_locale("de_DE@euro");
+        assert_eq!(german.wind_speed, WindSpeedUnit::Kmh);
+        assert_eq!(
+            WeatherUnits::for_locale("C").temperature,
+            TemperatureUnit::Celsius
+        );
+    }
+
+    #[test]
+    fn test_config_units_win_over_locale() {
+        use crate::weather::types::{PrecipitationUnit, TemperatureUnit, WindSpeedUnit};
+
+        let toml_content = r#"
+[units]
+temperature = "celsius"
+"#;
+        let path = std::env::temp_dir().join("weathr_test_locale_units.toml");
+        fs::write(&path, toml_content).unwrap();
+
+        let config = Config::load_from_path(&path, WeatherUnits::imperial()).unwrap();
+        assert_eq!(config.units.temperature, TemperatureUnit::Celsius);
+        assert_eq!(config.units.wind_speed, WindSpeedUnit::Mph);
+        assert_eq!(config.units.precipitation, PrecipitationUnit::Inch);
+
+        fs::remove_file(path).ok();
+    }
+
     #[test]
     fn test_location_display_default() {
         let toml_content = r#"
@@ -722,7 +1952,7 @@ auto = false
         let temp_dir = std::env::temp_dir();
         let path = temp_dir.join("weathr_test_env_override.toml");
         fs::write(&path, toml_content).unwrap();
-        let mut config = Config::load_from_path(&path).unwrap();
+        let mut config = Config::load_from_path(&path, WeatherUnits::default()).unwrap();
         config.apply_env_overrides().unwrap();
         assert_eq!(config.location.latitude, -33.8688);
         assert_eq!(config.location.longitude, 151.2093);
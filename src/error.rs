@@ -1,4 +1,5 @@
 use std::io;
+use std::time::Duration;
 use thiserror::Error as ThisError;
 
 #[derive(ThisError, Debug)]
@@ -32,6 +33,12 @@ pub enum DataError {
 
     #[error("Provider returned bad data: {0}")]
     BadData(String),
+
+    #[error("Ignored implausible {0}")]
+    Implausible(String),
+
+    #[error("Weather command {command:?} failed: {reason}")]
+    CommandFailed { command: String, reason: String },
 }
 
 #[derive(ThisError, Debug)]
@@ -52,6 +59,13 @@ pub enum NetworkError {
     #[error("connection refused for {url}")]
     ConnectionRefused { url: String },
 
+    #[error("TLS certificate verification failed for {url}")]
+    Tls {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
     #[error("HTTP request failed for {url}: {status}")]
     HttpError {
         url: String,
@@ -60,6 +74,16 @@ pub enum NetworkError {
         source: reqwest::Error,
     },
 
+    #[error("rate limited by {url}")]
+    RateLimited {
+        url: String,
+        /// How long the server asked us to wait, from `Retry-After`.
+        retry_after: Option<Duration>,
+    },
+
+    #[error("no network connection ({url} unreachable)")]
+    Offline { url: String },
+
     #[error("failed to parse JSON response from {url}")]
     JsonParse {
         url: String,
@@ -79,6 +103,12 @@ impl NetworkError {
                 timeout_secs,
             }
         } else if error.is_connect() {
+            if is_certificate_error(&error) {
+                return NetworkError::Tls {
+                    url: url.to_string(),
+                    source: error,
+                };
+            }
             let error_msg = error.to_string();
             if error_msg.contains("dns") || error_msg.contains("DNS") {
                 return NetworkError::DnsFailure {
@@ -120,6 +150,16 @@ impl NetworkError {
         )
     }
 
+    /// The URL that couldn't be reached, when the failure looks like the
+    /// network being down rather than the server misbehaving.
+    pub fn unreachable_url(&self) -> Option<&str> {
+        match self {
+            NetworkError::DnsFailure { url, .. } | NetworkError::Timeout { url, .. } => Some(url),
+            NetworkError::Other(e) if e.is_connect() => e.url().map(|url| url.as_str()),
+            _ => None,
+        }
+    }
+
     pub fn user_friendly_message(&self) -> String {
         match self {
             NetworkError::DnsFailure { url, .. } => {
@@ -133,9 +173,21 @@ impl NetworkError {
             NetworkError::ConnectionRefused { url } => {
                 format!("Cannot connect to {url}. The service may be down.")
             }
+            NetworkError::Tls { url, .. } => {
+                format!(
+                    "Cannot verify the certificate of {url}. If your network intercepts HTTPS, \
+                     set ca_bundle under [network] in your config (or use --insecure)."
+                )
+            }
             NetworkError::HttpError { url, status, .. } => {
                 format!("Server error from {url}: HTTP {status}")
             }
+            NetworkError::RateLimited { url, .. } => {
+                format!("Too many requests to {url}. Waiting before trying again.")
+            }
+            NetworkError::Offline { url } => {
+                format!("No network connection: {url} is unreachable.")
+            }
             NetworkError::JsonParse { url, .. } => {
                 format!("Received invalid data from {url}")
             }
@@ -145,6 +197,20 @@ impl NetworkError {
     }
 }
 
+/// Whether the error chain ends in a rejected server certificate, as behind
+/// TLS-intercepting corporate proxies.
+fn is_certificate_error(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        let message = cause.to_string().to_lowercase();
+        if message.contains("certificate") || message.contains("unknownissuer") {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
+
 #[derive(ThisError, Debug)]
 pub enum ConfigError {
     #[error("failed to read config file at {path}")]
@@ -166,10 +232,55 @@ pub enum ConfigError {
     #[error("invalid longitude {0} (must be between -180 and 180)")]
     InvalidLongitude(f64),
 
+    #[error("invalid animation_speed {0} (must be greater than 0)")]
+    InvalidAnimationSpeed(f64),
+
+    #[error("no [[locations]] profile named {0:?}")]
+    UnknownProfile(String),
+
+    #[error("a [[locations]] profile named {0:?} already exists")]
+    DuplicateProfile(String),
+
+    #[error("failed to write config file at {path}")]
+    WriteError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("invalid bedtime {0:?} (expected HH:MM)")]
+    InvalidBedtime(String),
+
+    #[error("temperature_colors thresholds must increase (cool {cool} < warm {warm} < hot {hot})")]
+    InvalidTemperatureColors { cool: f64, warm: f64, hot: f64 },
+
+    #[error("invalid include in {path}: {reason}")]
+    InvalidInclude { path: String, reason: String },
+
+    #[error("invalid [scene.ground] density {0} (must be between 0 and 4)")]
+    InvalidGroundDensity(f64),
+
     #[error("invalid value for ${name} (expected a float, got {value:?})")]
     InvalidEnvVar { name: &'static str, value: String },
+    #[error("invalid proxy URL {url:?}: {reason}")]
+    InvalidProxy { url: String, reason: String },
+
+    #[error("no usable certificates in CA bundle {path}")]
+    InvalidCaBundle { path: String },
+
+    #[error("keyring error for {provider}: {reason}")]
+    Keyring {
+        provider: &'static str,
+        reason: String,
+    },
+
     #[error("invalid API Key ({0})")]
     InvalidAPIKey(String),
+    #[error("invalid options for provider '{provider}': {reason}")]
+    InvalidProviderOptions {
+        provider: &'static str,
+        reason: String,
+    },
 }
 
 impl ConfigError {
@@ -181,8 +292,20 @@ impl ConfigError {
             ConfigError::NoConfigDir => "NoConfigDir",
             ConfigError::InvalidLatitude(_) => "InvalidLatitude",
             ConfigError::InvalidLongitude(_) => "InvalidLongitude",
+            ConfigError::InvalidAnimationSpeed(_) => "InvalidAnimationSpeed",
+            ConfigError::UnknownProfile(_) => "UnknownProfile",
+            ConfigError::DuplicateProfile(_) => "DuplicateProfile",
+            ConfigError::WriteError { .. } => "WriteError",
+            ConfigError::InvalidBedtime(_) => "InvalidBedtime",
+            ConfigError::InvalidTemperatureColors { .. } => "InvalidTemperatureColors",
+            ConfigError::InvalidInclude { .. } => "InvalidInclude",
+            ConfigError::InvalidGroundDensity(_) => "InvalidGroundDensity",
             ConfigError::InvalidEnvVar { .. } => "InvalidEnvVar",
+            ConfigError::InvalidProxy { .. } => "InvalidProxy",
+            ConfigError::InvalidCaBundle { .. } => "InvalidCaBundle",
+            ConfigError::Keyring { .. } => "Keyring",
             ConfigError::InvalidAPIKey(_) => "InvalidAPIKey",
+            ConfigError::InvalidProviderOptions { .. } => "InvalidProviderOptions",
         }
     }
 }
@@ -251,6 +374,12 @@ pub enum GeolocationError {
 
     #[error("failed after {attempts} retry attempts")]
     RetriesExhausted { attempts: u32 },
+
+    #[error("no place found for {0:?}")]
+    NotFound(String),
+
+    #[error("no location in the clipboard: {0}")]
+    Clipboard(String),
 }
 
 impl GeolocationError {
@@ -273,12 +402,23 @@ impl GeolocationError {
                      Using configured/default location."
                         .to_string()
                 }
+                NetworkError::Tls { .. } => {
+                    "Cannot verify the location service certificate. Set ca_bundle under [network].\n\
+                     Using configured/default location."
+                        .to_string()
+                }
                 NetworkError::HttpError { status, .. } => {
                     format!(
                         "Location service returned error (HTTP {status}).\n\
                          Using configured/default location."
                     )
                 }
+                NetworkError::RateLimited { .. } => "Location service is rate limiting requests.\n\
+                     Using configured/default location."
+                    .to_string(),
+                NetworkError::Offline { .. } => "No network connection.\n\
+                     Using configured/default location."
+                    .to_string(),
                 NetworkError::JsonParse { .. } => "Received invalid data from location service.\n\
                      Using configured/default location."
                     .to_string(),
@@ -300,6 +440,15 @@ impl GeolocationError {
                      Using configured/default location."
                 )
             }
+            GeolocationError::NotFound(query) => {
+                format!("No place found for {query:?}. Try a more specific name.")
+            }
+            GeolocationError::Clipboard(reason) => {
+                format!(
+                    "No location in the clipboard: {reason}.\n\
+                     Using configured/default location."
+                )
+            }
         }
     }
 }
@@ -1,11 +1,16 @@
 use crate::cache;
 use crate::error::{GeolocationError, NetworkError};
+use crate::http::RequestOptions;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 
 const IPINFO_URL: &str = "https://ipinfo.io/json";
 const NOMINATIM_URL: &str = "https://nominatim.openstreetmap.org/reverse";
+const NOMINATIM_SEARCH_URL: &str = "https://nominatim.openstreetmap.org/search";
 const MAX_RETRIES: u32 = 3;
+/// Distance beyond which two unnamed locations count as different places.
+const MOVE_THRESHOLD_KM: f64 = 10.0;
 const INITIAL_RETRY_DELAY_MS: u64 = 500;
 
 #[derive(Deserialize, Debug)]
@@ -29,6 +34,32 @@ pub async fn detect_location() -> Result<GeoLocation, GeolocationError> {
     detect_location_with_retry().await
 }
 
+/// Re-runs IP geolocation, bypassing the cache, and returns the new location
+/// if it is a different place than `current`.
+pub async fn detect_move(current: &GeoLocation) -> Option<GeoLocation> {
+    let detected = detect_location_with_retry().await.ok()?;
+    has_moved(current, &detected).then_some(detected)
+}
+
+fn has_moved(current: &GeoLocation, detected: &GeoLocation) -> bool {
+    if let (Some(a), Some(b)) = (&current.city, &detected.city) {
+        return !a.eq_ignore_ascii_case(b);
+    }
+
+    distance_km(current, detected) > MOVE_THRESHOLD_KM
+}
+
+/// Great-circle distance between two locations.
+pub fn distance_km(a: &GeoLocation, b: &GeoLocation) -> f64 {
+    const EARTH_RADIUS_KM: f64 = 6371.0;
+
+    let (lat1, lat2) = (a.latitude.to_radians(), b.latitude.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (b.longitude - a.longitude).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().asin()
+}
+
 async fn detect_location_with_retry() -> Result<GeoLocation, GeolocationError> {
     let mut last_error = None;
 
@@ -60,11 +91,9 @@ async fn detect_location_with_retry() -> Result<GeoLocation, GeolocationError> {
 }
 
 async fn fetch_location() -> Result<GeoLocation, GeolocationError> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(10))
-        .connect_timeout(Duration::from_secs(5))
-        .build()
-        .map_err(|e| GeolocationError::Unreachable(NetworkError::ClientCreation(e)))?;
+    let client = RequestOptions::with_timeout(10)
+        .client()
+        .map_err(GeolocationError::Unreachable)?;
 
     let response = client
         .get(IPINFO_URL)
@@ -115,11 +144,42 @@ struct NominatimAddress {
 #[derive(Deserialize, Debug)]
 struct NominatimResponse {
     address: Option<NominatimAddress>,
+    #[serde(default)]
+    namedetails: Option<HashMap<String, String>>,
+}
+
+/// Returns the place's native-script name when it differs from the name
+/// localized into `language`, e.g. "東京都" for a lookup in English.
+fn native_name<'a>(
+    localized: &str,
+    language: &str,
+    namedetails: &'a HashMap<String, String>,
+) -> Option<&'a str> {
+    if language == "auto" {
+        return None;
+    }
+
+    let native = namedetails.get("name")?;
+    if native == localized {
+        return None;
+    }
+
+    // Only trust the native name if the looked-up object is the settlement
+    // we're naming, not a surrounding district or county.
+    let primary_tag = language.split(['-', '_']).next().unwrap_or(language);
+    let localized_key = format!("name:{}", primary_tag);
+    match namedetails.get(&localized_key) {
+        Some(name) if name == localized => Some(native),
+        _ => None,
+    }
 }
 
 /// Best-effort reverse geocode: returns a city/town/village name for the given
 /// coordinates, or `None` if the lookup fails or the location doesn't map to a
 /// meaningful settlement (e.g. open sea, administrative-only regions).
+///
+/// When `language` is not "auto" and the place has a different native-script
+/// name, both are returned as "Tokyo (東京都)".
 pub async fn reverse_geocode(latitude: f64, longitude: f64, language: &str) -> Option<String> {
     if let Some(cached) = cache::load_cached_geocode(latitude, longitude, language).await {
         return Some(cached);
@@ -131,21 +191,14 @@ pub async fn reverse_geocode(latitude: f64, longitude: f64, language: &str) -> O
 }
 
 async fn fetch_reverse_geocode(latitude: f64, longitude: f64, language: &str) -> Option<String> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(5))
-        .connect_timeout(Duration::from_secs(3))
-        .build()
-        .ok()?;
+    let client = RequestOptions::with_timeout(5).client().ok()?;
 
     let url = format!(
-        "{}?lat={}&lon={}&format=json&zoom=10",
+        "{}?lat={}&lon={}&format=json&zoom=10&namedetails=1",
         NOMINATIM_URL, latitude, longitude
     );
 
-    let mut req = client.get(&url).header(
-        "User-Agent",
-        format!("weathr/{}", env!("CARGO_PKG_VERSION")),
-    );
+    let mut req = client.get(&url);
 
     if language != "auto" {
         req = req.header("Accept-Language", language);
@@ -156,5 +209,118 @@ async fn fetch_reverse_geocode(latitude: f64, longitude: f64, language: &str) ->
     let data: NominatimResponse = resp.json().await.ok()?;
 
     let addr = data.address?;
-    addr.city.or(addr.town).or(addr.village)
+    let city = addr.city.or(addr.town).or(addr.village)?;
+
+    let native = data
+        .namedetails
+        .as_ref()
+        .and_then(|details| native_name(&city, language, details));
+    match native {
+        Some(native) => Some(format!("{} ({})", city, native)),
+        None => Some(city),
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct NominatimPlace {
+    lat: String,
+    lon: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// Looks up a place name (e.g. "Leeds, UK") and returns its coordinates.
+pub async fn geocode(query: &str) -> Result<GeoLocation, GeolocationError> {
+    let client = RequestOptions::with_timeout(10)
+        .client()
+        .map_err(GeolocationError::Unreachable)?;
+
+    let url = reqwest::Url::parse_with_params(
+        NOMINATIM_SEARCH_URL,
+        [("q", query), ("format", "jsonv2"), ("limit", "1")],
+    )
+    .map_err(|e| GeolocationError::ParseError(e.to_string()))?;
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status())
+        .map_err(|e| {
+            GeolocationError::Unreachable(NetworkError::from_reqwest(e, NOMINATIM_SEARCH_URL, 10))
+        })?;
+
+    let places: Vec<NominatimPlace> = response.json().await.map_err(|e| {
+        GeolocationError::Unreachable(NetworkError::from_reqwest(e, NOMINATIM_SEARCH_URL, 10))
+    })?;
+    let place = places
+        .into_iter()
+        .next()
+        .ok_or_else(|| GeolocationError::NotFound(query.to_string()))?;
+
+    let parse = |value: &str| {
+        value
+            .parse::<f64>()
+            .map_err(|_| GeolocationError::ParseError(format!("Invalid coordinate {value:?}")))
+    };
+    Ok(GeoLocation {
+        latitude: parse(&place.lat)?,
+        longitude: parse(&place.lon)?,
+        city: place.name.filter(|name| !name.is_empty()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn geo(latitude: f64, longitude: f64, city: Option<&str>) -> GeoLocation {
+        GeoLocation {
+            latitude,
+            longitude,
+            city: city.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_has_moved_compares_city_names() {
+        let home = geo(52.52, 13.41, Some("Berlin"));
+        assert!(!has_moved(&home, &geo(52.50, 13.45, Some("berlin"))));
+        assert!(has_moved(&home, &geo(52.39, 13.06, Some("Potsdam"))));
+    }
+
+    #[test]
+    fn test_has_moved_falls_back_to_distance() {
+        let home = geo(52.52, 13.41, None);
+        assert!(!has_moved(&home, &geo(52.53, 13.42, None)));
+        assert!(has_moved(&home, &geo(48.14, 11.58, None)));
+    }
+
+    fn details(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_native_name_added_for_foreign_language() {
+        let names = details(&[("name", "東京都"), ("name:en", "Tokyo")]);
+        assert_eq!(native_name("Tokyo", "en-US", &names), Some("東京都"));
+    }
+
+    #[test]
+    fn test_native_name_skipped_when_same_or_auto() {
+        let names = details(&[("name", "Berlin"), ("name:en", "Berlin")]);
+        assert_eq!(native_name("Berlin", "en", &names), None);
+
+        let names = details(&[("name", "東京都"), ("name:en", "Tokyo")]);
+        assert_eq!(native_name("Tokyo", "auto", &names), None);
+    }
+
+    #[test]
+    fn test_native_name_skipped_for_unrelated_object() {
+        let names = details(&[("name", "新宿区"), ("name:en", "Shinjuku")]);
+        assert_eq!(native_name("Tokyo", "en", &names), None);
+    }
 }
@@ -0,0 +1,631 @@
+//! The weather history: with `record_history` on, every successful fetch is
+//! appended to `history.csv` in the cache dir, and `weathr history` looks
+//! back over what was recorded for the current location.
+
+use crate::cache;
+use crate::config::Config;
+use crate::scheduler::Scheduler;
+use crate::weather::{WeatherCondition, WeatherData, WeatherLocation, WeatherUnits};
+use crate::weather::{format_temperature, format_wind_speed};
+use chrono::{DateTime, Days, FixedOffset, NaiveDate, TimeDelta, Timelike};
+use crossterm::style::Color;
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const FILE_NAME: &str = "history.csv";
+const HEADER: &str = "recorded_at,latitude,longitude,source,condition,temperature,humidity,precipitation,wind_speed,wind_direction,wind_gusts,pressure";
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Bits of the braille dots in each of a cell's two columns, top to bottom.
+const BRAILLE_DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+/// Hours the `g` graph looks back at most.
+const GRAPH_MAX_HOURS: i64 = 72;
+const GRAPH_TEMPERATURE_ROWS: usize = 5;
+const GRAPH_PRESSURE_ROWS: usize = 3;
+/// Days of readings kept in history.csv.
+const KEEP_DAYS: i64 = 365;
+/// Days past `KEEP_DAYS` the oldest reading may get before the file is
+/// trimmed, so it's rewritten about once a month rather than on every fetch.
+const TRIM_SLACK_DAYS: i64 = 31;
+
+/// One fetch as recorded, in weathr's own units: °C, %, mm, m/s and hPa.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub recorded_at: DateTime<FixedOffset>,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Provider abbreviation(s), as in the HUD.
+    pub source: String,
+    pub condition: WeatherCondition,
+    pub temperature: f64,
+    pub humidity: Option<f64>,
+    pub precipitation: f64,
+    pub wind_speed: f64,
+    pub wind_direction: f64,
+    pub wind_gusts: Option<f64>,
+    pub pressure: Option<f64>,
+}
+
+impl Record {
+    pub fn new(
+        weather: &WeatherData,
+        location: &WeatherLocation,
+        recorded_at: DateTime<FixedOffset>,
+    ) -> Self {
+        Self {
+            recorded_at,
+            latitude: location.latitude,
+            longitude: location.longitude,
+            source: weather.source.replace(',', " "),
+            condition: weather.condition,
+            temperature: weather.temperature,
+            humidity: weather.humidity,
+            precipitation: weather.precipitation,
+            wind_speed: weather.wind_speed,
+            wind_direction: weather.wind_direction,
+            wind_gusts: weather.details.wind_gusts,
+            pressure: weather.pressure,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        let optional = |value: Option<f64>| value.map(|v| format!("{:.2}", v)).unwrap_or_default();
+        format!(
+            "{},{:.4},{:.4},{},{},{:.2},{},{:.2},{:.2},{:.0},{},{}",
+            self.recorded_at.to_rfc3339(),
+            self.latitude,
+            self.longitude,
+            self.source,
+            self.condition.as_str(),
+            self.temperature,
+            optional(self.humidity),
+            self.precipitation,
+            self.wind_speed,
+            self.wind_direction,
+            optional(self.wind_gusts),
+            optional(self.pressure),
+        )
+    }
+
+    /// `None` for the header and for lines that don't parse, e.g. one cut
+    /// short by a crash.
+    fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.trim_end().split(',').collect();
+        let [
+            recorded_at,
+            latitude,
+            longitude,
+            source,
+            condition,
+            temperature,
+            humidity,
+            precipitation,
+            wind_speed,
+            wind_direction,
+            wind_gusts,
+            pressure,
+        ] = fields.as_slice()
+        else {
+            return None;
+        };
+        let optional = |field: &str| field.parse().ok();
+        Some(Self {
+            recorded_at: DateTime::parse_from_rfc3339(recorded_at).ok()?,
+            latitude: latitude.parse().ok()?,
+            longitude: longitude.parse().ok()?,
+            source: source.to_string(),
+            condition: condition.parse().ok()?,
+            temperature: temperature.parse().ok()?,
+            humidity: optional(humidity),
+            precipitation: precipitation.parse().ok()?,
+            wind_speed: wind_speed.parse().ok()?,
+            wind_direction: wind_direction.parse().ok()?,
+            wind_gusts: optional(wind_gusts),
+            pressure: optional(pressure),
+        })
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    Some(cache::get_cache_dir()?.join(FILE_NAME))
+}
+
+/// Appends a new reading to the history, dropping those more than a year
+/// old. Simulated weather, which has no source, isn't recorded, and neither
+/// is weather served from the cache, which was recorded when fetched.
+pub async fn record(weather: &WeatherData, location: &WeatherLocation) {
+    if weather.source.is_empty() {
+        return;
+    }
+    let record = Record::new(weather, location, Scheduler::of(weather).now());
+    // Awaited, so a one-shot run doesn't exit before the line is written
+    let _ = tokio::task::spawn_blocking(move || {
+        if let Some(path) = history_path() {
+            let _ = trim(&path, record.recorded_at);
+            let _ = append(&path, &record);
+        }
+    })
+    .await;
+}
+
+/// Drops readings more than `KEEP_DAYS` before `now`, once the oldest is a
+/// month past that.
+fn trim(path: &Path, now: DateTime<FixedOffset>) -> io::Result<()> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let oldest = contents.lines().find_map(Record::parse);
+    if oldest.is_none_or(|oldest| {
+        oldest.recorded_at >= now - TimeDelta::days(KEEP_DAYS + TRIM_SLACK_DAYS)
+    }) {
+        return Ok(());
+    }
+
+    let cutoff = now - TimeDelta::days(KEEP_DAYS);
+    let mut kept = format!("{}\n", HEADER);
+    for line in contents.lines() {
+        if Record::parse(line).is_some_and(|record| record.recorded_at >= cutoff) {
+            kept.push_str(line);
+            kept.push('\n');
+        }
+    }
+    let trimmed = path.with_extension("csv.tmp");
+    std::fs::write(&trimmed, kept)?;
+    std::fs::rename(trimmed, path)
+}
+
+fn append(path: &Path, record: &Record) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{}", HEADER)?;
+    }
+    writeln!(file, "{}", record.to_line())
+}
+
+/// Everything recorded for `location`, oldest first.
+pub fn load(location: &WeatherLocation) -> io::Result<Vec<Record>> {
+    let Some(path) = history_path() else {
+        return Ok(Vec::new());
+    };
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let here = cache::make_location_key(location.latitude, location.longitude);
+    let mut records: Vec<Record> = contents
+        .lines()
+        .filter_map(Record::parse)
+        .filter(|record| cache::make_location_key(record.latitude, record.longitude) == here)
+        .collect();
+    records.sort_by_key(|record| record.recorded_at);
+    Ok(records)
+}
+
+/// Prints the last `days` days recorded for the configured location, a
+/// line a day.
+pub fn print(config: &Config, days: u32) -> io::Result<()> {
+    let location = WeatherLocation {
+        latitude: config.location.latitude,
+        longitude: config.location.longitude,
+        elevation: None,
+    };
+    let mut records = load(&location)?;
+    // On the location's clock, going by the offset last recorded there
+    let now = Scheduler::default().now();
+    let today = records
+        .last()
+        .map_or(now, |last| now.with_timezone(last.recorded_at.offset()))
+        .date_naive();
+    let since = today - Days::new(u64::from(days.max(1) - 1));
+    records.retain(|record| record.recorded_at.date_naive() >= since);
+
+    if records.is_empty() {
+        if config.record_history {
+            println!("Nothing recorded here since {}.", since);
+        } else {
+            println!("No history yet: set record_history = true in config.toml to record it.");
+        }
+        return Ok(());
+    }
+
+    let place = match &config.location.city {
+        Some(city) => city.clone(),
+        None => format!(
+            "{:.2}, {:.2}",
+            config.location.latitude, config.location.longitude
+        ),
+    };
+    println!("{} | since {} | {} readings", place, since, records.len());
+    for line in day_lines(&records, &config.units) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// A line per day: low/high, the temperature through the day hour by hour
+/// (blank where nothing was recorded), the strongest wind and the most
+/// recorded condition.
+fn day_lines(records: &[Record], units: &WeatherUnits) -> Vec<String> {
+    let mut days: BTreeMap<NaiveDate, Vec<&Record>> = BTreeMap::new();
+    for record in records {
+        days.entry(record.recorded_at.date_naive())
+            .or_default()
+            .push(record);
+    }
+    // One scale for the whole span, so the days compare
+    let (coldest, warmest) = records.iter().fold((f64::MAX, f64::MIN), |(lo, hi), r| {
+        (lo.min(r.temperature), hi.max(r.temperature))
+    });
+
+    days.into_iter()
+        .map(|(date, readings)| {
+            let low = readings
+                .iter()
+                .map(|r| r.temperature)
+                .fold(f64::MAX, f64::min);
+            let high = readings
+                .iter()
+                .map(|r| r.temperature)
+                .fold(f64::MIN, f64::max);
+            let (low, unit) = format_temperature(low, units.temperature);
+            let (high, _) = format_temperature(high, units.temperature);
+
+            let mut hours = [(0.0, 0); 24];
+            for reading in &readings {
+                let (sum, count) = &mut hours[reading.recorded_at.hour() as usize];
+                *sum += reading.temperature;
+                *count += 1;
+            }
+            let graph: String = hours
+                .iter()
+                .map(|&(sum, count)| {
+                    if count == 0 {
+                        ' '
+                    } else {
+                        spark(sum / f64::from(count), coldest, warmest)
+                    }
+                })
+                .collect();
+
+            let windiest = readings
+                .iter()
+                .map(|r| r.wind_gusts.unwrap_or(r.wind_speed).max(r.wind_speed))
+                .fold(0.0, f64::max);
+            let (wind, wind_unit) = format_wind_speed(windiest, units.wind_speed);
+
+            let mut conditions: BTreeMap<&str, usize> = BTreeMap::new();
+            for reading in &readings {
+                *conditions.entry(reading.condition.label()).or_default() += 1;
+            }
+            let condition = conditions
+                .into_iter()
+                .max_by_key(|&(_, count)| count)
+                .map(|(label, _)| label)
+                .unwrap_or_default();
+
+            format!(
+                "{}  {:>4.0}/{:.0}{:<3} {}  wind {:>3.0}{:<5} {}",
+                date.format("%a %d %b"),
+                low,
+                high,
+                unit,
+                graph,
+                wind,
+                wind_unit,
+                condition
+            )
+        })
+        .collect()
+}
+
+/// How far back the graph looks: whole days, enough to cover the oldest of
+/// `records` within the last `GRAPH_MAX_HOURS`, and a day at least.
+fn graph_hours(records: &[Record], now: DateTime<FixedOffset>) -> i64 {
+    let oldest = records
+        .iter()
+        .map(|record| (now - record.recorded_at).num_hours())
+        .filter(|&hours| hours < GRAPH_MAX_HOURS)
+        .max()
+        .unwrap_or(0);
+    (oldest / 24 + 1) * 24
+}
+
+/// The `g` panel: temperature and pressure over the last day to three days,
+/// as braille line graphs `width` columns wide. Lines come with their color.
+pub fn graph_lines(
+    records: &[Record],
+    now: DateTime<FixedOffset>,
+    width: usize,
+    units: &WeatherUnits,
+) -> Vec<(String, Color)> {
+    let hours = graph_hours(records, now);
+    let start = now - TimeDelta::hours(hours);
+    let records: Vec<&Record> = records
+        .iter()
+        .filter(|record| record.recorded_at > start && record.recorded_at <= now)
+        .collect();
+    let mut lines = vec![(
+        format!("History, last {} h ({} readings)", hours, records.len()),
+        Color::Cyan,
+    )];
+    if records.is_empty() {
+        lines.push(("Nothing recorded here yet".to_string(), Color::DarkGrey));
+        return lines;
+    }
+
+    // Two dots per column, each the mean of the readings in its slot
+    let dots = width * 2;
+    let slot = |record: &Record| {
+        let elapsed = (record.recorded_at - start).num_seconds() as f64;
+        ((elapsed / (hours * 3600) as f64 * dots as f64) as usize).min(dots - 1)
+    };
+    let series = |value: &dyn Fn(&Record) -> Option<f64>| {
+        let mut sums = vec![(0.0, 0u32); dots];
+        for record in &records {
+            if let Some(value) = value(record) {
+                let (sum, count) = &mut sums[slot(record)];
+                *sum += value;
+                *count += 1;
+            }
+        }
+        sums.into_iter()
+            .map(|(sum, count)| (count > 0).then(|| sum / f64::from(count)))
+            .collect::<Vec<_>>()
+    };
+
+    let temperatures =
+        series(&|record| Some(format_temperature(record.temperature, units.temperature).0));
+    let (_, unit) = format_temperature(0.0, units.temperature);
+    let (low, high) = range(&temperatures);
+    lines.push((
+        format!("Temperature {:.1} to {:.1}{}", low, high, unit),
+        Color::Cyan,
+    ));
+    for line in braille_graph(&temperatures, GRAPH_TEMPERATURE_ROWS, low, high) {
+        lines.push((line, Color::Yellow));
+    }
+
+    let pressures = series(&|record| record.pressure);
+    if pressures.iter().all(Option::is_none) {
+        lines.push(("Pressure not reported".to_string(), Color::DarkGrey));
+    } else {
+        let (low, high) = range(&pressures);
+        lines.push((
+            format!("Pressure {:.0} to {:.0} hPa", low, high),
+            Color::Cyan,
+        ));
+        for line in braille_graph(&pressures, GRAPH_PRESSURE_ROWS, low, high) {
+            lines.push((line, Color::Green));
+        }
+    }
+    lines
+}
+
+fn range(values: &[Option<f64>]) -> (f64, f64) {
+    values
+        .iter()
+        .flatten()
+        .fold((f64::MAX, f64::MIN), |(low, high), &value| {
+            (low.min(value), high.max(value))
+        })
+}
+
+/// Plots `values`, two to a column, as a line `rows` high in braille dots,
+/// scaled from `low` at the bottom to `high` at the top. Neighbouring values
+/// are joined; `None` leaves a gap.
+fn braille_graph(values: &[Option<f64>], rows: usize, low: f64, high: f64) -> Vec<String> {
+    let height = rows * 4;
+    let span = high - low;
+    let dot_row = |value: f64| {
+        if span <= f64::EPSILON {
+            return height / 2;
+        }
+        (((high - value) / span) * (height - 1) as f64).round() as usize
+    };
+
+    let columns = values.len().div_ceil(2);
+    let mut cells = vec![vec![0u8; columns]; rows];
+    let mut previous = None;
+    for (x, value) in values.iter().enumerate() {
+        let Some(value) = value else {
+            previous = None;
+            continue;
+        };
+        let y = dot_row(*value).min(height - 1);
+        let (from, to) = match previous {
+            Some(previous) if previous < y => (previous + 1, y),
+            Some(previous) if previous > y => (y, previous - 1),
+            _ => (y, y),
+        };
+        for y in from..=to {
+            cells[y / 4][x / 2] |= BRAILLE_DOTS[x % 2][y % 4];
+        }
+        previous = Some(y);
+    }
+
+    cells
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|bits| char::from_u32(0x2800 + u32::from(bits)).unwrap_or(' '))
+                .collect()
+        })
+        .collect()
+}
+
+/// The block for `value` on a scale from `low` to `high`.
+fn spark(value: f64, low: f64, high: f64) -> char {
+    let span = high - low;
+    if span <= f64::EPSILON {
+        return SPARKS[SPARKS.len() / 2];
+    }
+    let level = ((value - low) / span * (SPARKS.len() - 1) as f64).round();
+    SPARKS[(level.max(0.0) as usize).min(SPARKS.len() - 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(at: &str, condition: WeatherCondition, temperature: f64) -> Record {
+        Record {
+            recorded_at: DateTime::parse_from_rfc3339(at).unwrap(),
+            latitude: 52.52,
+            longitude: 13.41,
+            source: "OM".to_string(),
+            condition,
+            temperature,
+            humidity: None,
+            precipitation: 0.0,
+            wind_speed: 3.0,
+            wind_direction: 270.0,
+            wind_gusts: Some(8.0),
+            pressure: None,
+        }
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let weather = WeatherData {
+            temperature: 11.25,
+            precipitation: 0.4,
+            wind_speed: 5.5,
+            wind_direction: 225.0,
+            pressure: Some(1012.3),
+            humidity: Some(88.0),
+            source: "OM".to_string(),
+            timestamp: "2026-10-16T14:00".to_string(),
+            ..WeatherData::sample(WeatherCondition::Rain)
+        };
+        let location = WeatherLocation {
+            latitude: 52.52,
+            longitude: 13.41,
+            elevation: None,
+        };
+        let at = DateTime::parse_from_rfc3339("2026-10-16T14:05:00+02:00").unwrap();
+        let record = Record::new(&weather, &location, at);
+
+        let line = record.to_line();
+        assert_eq!(
+            line,
+            "2026-10-16T14:05:00+02:00,52.5200,13.4100,OM,rain,11.25,88.00,0.40,5.50,225,,1012.30"
+        );
+        assert_eq!(Record::parse(&line), Some(record));
+        assert_eq!(Record::parse(HEADER), None);
+        assert_eq!(Record::parse("2026-10-16T14:05:00+02:00,52.52"), None);
+    }
+
+    #[test]
+    fn test_trim_drops_readings_over_a_year_old() {
+        let dir = std::env::temp_dir().join("weathr_test_history_trim");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(FILE_NAME);
+        let _ = std::fs::remove_file(&path);
+        let line = |at: &str| record(at, WeatherCondition::Clear, 10.0).to_line();
+        for at in [
+            "2025-08-01T12:00:00+02:00",
+            "2025-10-20T12:00:00+02:00",
+            "2026-10-16T12:00:00+02:00",
+        ] {
+            append(&path, &record(at, WeatherCondition::Clear, 10.0)).unwrap();
+        }
+
+        // The oldest is within the slack, so the file is left alone
+        let now = DateTime::parse_from_rfc3339("2026-08-15T12:00:00+02:00").unwrap();
+        trim(&path, now).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 4);
+
+        let now = DateTime::parse_from_rfc3339("2026-10-16T12:00:00+02:00").unwrap();
+        trim(&path, now).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines[0], HEADER);
+        assert_eq!(
+            lines[1..],
+            [
+                line("2025-10-20T12:00:00+02:00"),
+                line("2026-10-16T12:00:00+02:00")
+            ]
+        );
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_day_lines() {
+        let records = [
+            record("2026-10-15T06:00:00+02:00", WeatherCondition::Fog, 2.0),
+            record("2026-10-15T14:00:00+02:00", WeatherCondition::Clear, 16.0),
+            record("2026-10-15T15:00:00+02:00", WeatherCondition::Clear, 14.0),
+            record("2026-10-16T09:00:00+02:00", WeatherCondition::Rain, 9.0),
+        ];
+        let lines = day_lines(&records, &WeatherUnits::metric());
+
+        assert_eq!(lines.len(), 2);
+        let graph = format!("{}▁{}█▇{}", " ".repeat(6), " ".repeat(7), " ".repeat(8));
+        assert_eq!(
+            lines[0],
+            format!("Thu 15 Oct     2/16°C  {}  wind  29km/h  Clear", graph)
+        );
+        assert!(lines[1].starts_with("Fri 16 Oct     9/9°C "));
+        assert!(lines[1].ends_with("Rain"));
+    }
+
+    #[test]
+    fn test_braille_graph() {
+        let values = [Some(0.0), Some(10.0), None, Some(5.0)];
+        assert_eq!(braille_graph(&values, 1, 0.0, 10.0), ["⡸⠠"]);
+        // A flat line sits in the middle
+        assert_eq!(braille_graph(&[Some(3.0); 2], 1, 3.0, 3.0), ["⠤"]);
+    }
+
+    #[test]
+    fn test_graph_covers_whole_days() {
+        let now = DateTime::parse_from_rfc3339("2026-10-16T12:00:00+02:00").unwrap();
+        let mut records = vec![record(
+            "2026-10-16T08:00:00+02:00",
+            WeatherCondition::Clear,
+            9.0,
+        )];
+        assert_eq!(graph_hours(&records, now), 24);
+
+        records.push(record(
+            "2026-10-15T08:00:00+02:00",
+            WeatherCondition::Clear,
+            7.0,
+        ));
+        assert_eq!(graph_hours(&records, now), 48);
+        // Older readings are beyond the graph
+        records.push(record(
+            "2026-10-10T08:00:00+02:00",
+            WeatherCondition::Clear,
+            5.0,
+        ));
+        assert_eq!(graph_hours(&records, now), 48);
+
+        let lines = graph_lines(&records, now, 10, &WeatherUnits::metric());
+        assert_eq!(lines[0].0, "History, last 48 h (2 readings)");
+        assert_eq!(lines[1].0, "Temperature 7.0 to 9.0°C");
+        assert_eq!(lines.len(), 2 + GRAPH_TEMPERATURE_ROWS + 1);
+        assert_eq!(lines.last().unwrap().0, "Pressure not reported");
+        assert!(
+            lines[2..7]
+                .iter()
+                .all(|(line, _)| line.chars().count() == 10)
+        );
+    }
+
+    #[test]
+    fn test_spark_scale() {
+        assert_eq!(spark(0.0, 0.0, 10.0), '▁');
+        assert_eq!(spark(10.0, 0.0, 10.0), '█');
+        assert_eq!(spark(5.0, 5.0, 5.0), '▅');
+    }
+}
@@ -0,0 +1,298 @@
+//! Construction of the HTTP clients used for every request, so the
+//! `[network]` settings, timeouts and User-Agent apply to weather,
+//! geolocation and archive fetches alike.
+
+use std::fs;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Certificate, ClientBuilder, Proxy, Response, StatusCode, Url};
+use serde::Deserialize;
+
+use crate::config::NetworkConfig;
+use crate::error::{ConfigError, NetworkError};
+
+/// Seconds before a request is abandoned unless configured otherwise.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+/// Seconds a connectivity probe waits for any answer.
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+struct Settings {
+    proxy: Option<Proxy>,
+    certificates: Vec<Certificate>,
+    insecure: bool,
+}
+
+impl Settings {
+    fn from_config(config: &NetworkConfig) -> Result<Self, ConfigError> {
+        let proxy = config
+            .proxy
+            .as_deref()
+            .map(|url| {
+                Proxy::all(url).map_err(|e| ConfigError::InvalidProxy {
+                    url: url.to_string(),
+                    reason: e.to_string(),
+                })
+            })
+            .transpose()?;
+
+        let certificates = match &config.ca_bundle {
+            Some(path) => {
+                let path_str = path.display().to_string();
+                let pem = fs::read(path).map_err(|e| ConfigError::ReadError {
+                    path: path_str.clone(),
+                    source: e,
+                })?;
+                let certificates = Certificate::from_pem_bundle(&pem).map_err(|_| {
+                    ConfigError::InvalidCaBundle {
+                        path: path_str.clone(),
+                    }
+                })?;
+                if certificates.is_empty() {
+                    return Err(ConfigError::InvalidCaBundle { path: path_str });
+                }
+                certificates
+            }
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            proxy,
+            certificates,
+            insecure: config.insecure,
+        })
+    }
+
+    fn apply(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        if !self.certificates.is_empty() {
+            builder = builder.tls_certs_merge(self.certificates.iter().cloned());
+        }
+        if self.insecure {
+            builder = builder.tls_danger_accept_invalid_certs(true);
+        }
+        builder
+    }
+}
+
+/// Validates and installs the network settings. Call once at startup,
+/// before any client is built; later calls are ignored.
+pub fn configure(config: &NetworkConfig) -> Result<(), ConfigError> {
+    let settings = Settings::from_config(config)?;
+    let _ = SETTINGS.set(settings);
+    Ok(())
+}
+
+/// The User-Agent identifying weathr, as met.no and Nominatim require.
+pub fn user_agent() -> String {
+    format!(
+        "weathr/{} https://github.com/veirt/weathr",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Like `Response::error_for_status`, but reports HTTP 429 as
+/// `NetworkError::RateLimited` with the server's `Retry-After`, so the
+/// caller can back off instead of retrying on schedule. Errors name `url`
+/// rather than the request URL, which may carry an API key.
+pub fn error_for_status(
+    response: Response,
+    url: &str,
+    timeout_secs: u64,
+) -> Result<Response, NetworkError> {
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now()));
+        return Err(NetworkError::RateLimited {
+            url: url.to_string(),
+            retry_after,
+        });
+    }
+    response
+        .error_for_status()
+        .map_err(|e| NetworkError::from_reqwest(e.without_url(), url, timeout_secs))
+}
+
+/// Whether the server behind `url` answers at all, whatever the status: a
+/// cheap check that the network is up. Only the origin is asked, so no path
+/// or query (and no API key) is sent.
+pub async fn is_reachable(url: &str) -> bool {
+    let (Some(origin), Ok(client)) = (
+        origin(url),
+        RequestOptions::with_timeout(PROBE_TIMEOUT_SECS).client(),
+    ) else {
+        return false;
+    };
+    client.head(origin).send().await.is_ok()
+}
+
+fn origin(url: &str) -> Option<Url> {
+    let mut url = Url::parse(url).ok()?;
+    url.set_path("/");
+    url.set_query(None);
+    url.set_fragment(None);
+    Some(url)
+}
+
+/// `Retry-After` is either a number of seconds or an HTTP date.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((at.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+/// Request settings accepted in every provider's options sub-table.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RequestOptions {
+    /// Seconds before a request is abandoned.
+    pub timeout_secs: Option<u64>,
+    /// Replaces the identifying User-Agent, e.g. to add contact details.
+    pub user_agent: Option<String>,
+    /// Requests a day the API key allows, e.g. on a paid plan; refreshes
+    /// are spaced to stay within it.
+    pub requests_per_day: Option<u32>,
+}
+
+impl RequestOptions {
+    pub fn with_timeout(secs: u64) -> Self {
+        Self {
+            timeout_secs: Some(secs),
+            ..Default::default()
+        }
+    }
+
+    pub fn timeout_secs(&self) -> u64 {
+        self.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS).max(1)
+    }
+
+    /// A client builder with these options on top of the `[network]`
+    /// settings, for clients that need more (e.g. default headers).
+    pub fn builder(&self) -> ClientBuilder {
+        let timeout = self.timeout_secs();
+        let builder = match SETTINGS.get() {
+            Some(settings) => settings.apply(reqwest::Client::builder()),
+            None => reqwest::Client::builder(),
+        };
+        builder
+            .user_agent(self.user_agent.clone().unwrap_or_else(user_agent))
+            .timeout(Duration::from_secs(timeout))
+            .connect_timeout(Duration::from_secs(timeout.min(CONNECT_TIMEOUT_SECS)))
+    }
+
+    pub fn client(&self) -> Result<reqwest::Client, NetworkError> {
+        self.builder().build().map_err(NetworkError::ClientCreation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_invalid_settings_are_config_errors() {
+        let config = NetworkConfig {
+            proxy: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            Settings::from_config(&config).err().unwrap().kind(),
+            "InvalidProxy"
+        );
+
+        let config = NetworkConfig {
+            ca_bundle: Some(PathBuf::from("/nonexistent/weathr-ca.pem")),
+            ..Default::default()
+        };
+        assert_eq!(
+            Settings::from_config(&config).err().unwrap().kind(),
+            "ReadError"
+        );
+
+        let path = std::env::temp_dir().join(format!("weathr-ca-{}.pem", std::process::id()));
+        fs::write(&path, "not a certificate").unwrap();
+        let config = NetworkConfig {
+            ca_bundle: Some(path.clone()),
+            ..Default::default()
+        };
+        let result = Settings::from_config(&config);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(result.err().unwrap().kind(), "InvalidCaBundle");
+    }
+
+    #[test]
+    fn test_proxy_settings_build_a_client() {
+        let config = NetworkConfig {
+            proxy: Some("http://proxy.example:3128".to_string()),
+            insecure: true,
+            ..Default::default()
+        };
+        let settings = Settings::from_config(&config).unwrap();
+        assert!(settings.apply(reqwest::Client::builder()).build().is_ok());
+    }
+
+    #[test]
+    fn test_retry_after_forms() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:30:30 GMT", now),
+            Some(Duration::from_secs(150))
+        );
+        // A date already past means "now"
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_probe_asks_only_the_origin() {
+        assert_eq!(
+            origin("https://api.openweathermap.org/data/2.5/weather?appid=***&lat=1")
+                .unwrap()
+                .as_str(),
+            "https://api.openweathermap.org/"
+        );
+        assert_eq!(
+            origin("http://192.168.1.20:8080/api/current#now")
+                .unwrap()
+                .as_str(),
+            "http://192.168.1.20:8080/"
+        );
+        assert!(origin("not a url").is_none());
+    }
+
+    #[test]
+    fn test_request_options_from_provider_table() {
+        let options: RequestOptions =
+            toml::from_str("timeout_secs = 5\napi_key = \"ignored\"").unwrap();
+        assert_eq!(options.timeout_secs(), 5);
+        assert!(options.user_agent.is_none());
+        assert_eq!(
+            RequestOptions::default().timeout_secs(),
+            DEFAULT_TIMEOUT_SECS
+        );
+        assert!(options.client().is_ok());
+    }
+}
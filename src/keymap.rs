@@ -0,0 +1,243 @@
+//! Turns key presses into app actions. Single keys act at once; a chord
+//! such as `g h` waits up to a second for its second key, and a key that is
+//! both a binding and a chord prefix (`g`) acts once that second is up.
+//! Auto-repeat from a held key is dropped, so holding `f` doesn't flicker
+//! the forecast.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::time::{Duration, Instant};
+
+/// A key seen again this soon after its last event is a repeat. Longer than
+/// the usual delay before a held key starts repeating, so the first repeat
+/// is caught too.
+const REPEAT_WINDOW: Duration = Duration::from_millis(600);
+/// How long a chord prefix waits for the rest of the chord.
+const CHORD_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    CycleLocation,
+    HomeLocation,
+    Refresh,
+    ToggleForecast,
+    ToggleComfort,
+    ToggleDetails,
+    ToggleRadar,
+    ToggleHistory,
+    TogglePause,
+}
+
+/// Key sequences, lowercase; letters match either case.
+const BINDINGS: &[(&[char], Action)] = &[
+    (&['q'], Action::Quit),
+    (&['l'], Action::CycleLocation),
+    (&['g', 'h'], Action::HomeLocation),
+    (&['g'], Action::ToggleHistory),
+    (&['r'], Action::Refresh),
+    (&['f'], Action::ToggleForecast),
+    (&['c'], Action::ToggleComfort),
+    (&['d'], Action::ToggleDetails),
+    (&['m'], Action::ToggleRadar),
+    (&['p'], Action::TogglePause),
+];
+
+#[derive(Debug, Default)]
+pub struct Keymap {
+    /// Keys of a chord typed so far.
+    pending: Vec<char>,
+    pending_since: Option<Instant>,
+    last_key: Option<(KeyCode, Instant)>,
+}
+
+impl Keymap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle(&mut self, event: KeyEvent) -> Option<Action> {
+        self.handle_at(event, Instant::now())
+    }
+
+    /// Call once a frame: fires a lone key that could have started a chord
+    /// once the chord has timed out.
+    pub fn tick(&mut self) -> Option<Action> {
+        self.tick_at(Instant::now())
+    }
+
+    fn tick_at(&mut self, now: Instant) -> Option<Action> {
+        let since = self.pending_since?;
+        if now - since <= CHORD_TIMEOUT {
+            return None;
+        }
+        let pending = std::mem::take(&mut self.pending);
+        self.pending_since = None;
+        BINDINGS
+            .iter()
+            .find(|(keys, _)| *keys == pending.as_slice())
+            .map(|&(_, action)| action)
+    }
+
+    fn handle_at(&mut self, event: KeyEvent, now: Instant) -> Option<Action> {
+        // Terminals with the kitty keyboard protocol report these explicitly
+        if event.kind != KeyEventKind::Press {
+            return None;
+        }
+        if event.code == KeyCode::Char('c') && event.modifiers.contains(KeyModifiers::CONTROL) {
+            return Some(Action::Quit);
+        }
+
+        let repeated = self
+            .last_key
+            .is_some_and(|(code, at)| code == event.code && now - at < REPEAT_WINDOW);
+        self.last_key = Some((event.code, now));
+        if repeated {
+            return None;
+        }
+
+        let KeyCode::Char(key) = event.code else {
+            self.pending.clear();
+            return None;
+        };
+        if self
+            .pending_since
+            .is_some_and(|since| now - since > CHORD_TIMEOUT)
+        {
+            self.pending.clear();
+        }
+
+        let key = key.to_ascii_lowercase();
+        self.pending.push(key);
+        let in_chord = self.pending.len() > 1;
+        match self.resolve(now) {
+            // Not part of the chord: start over from this key, so `g q` still quits
+            None if in_chord && self.pending.is_empty() => {
+                self.pending.push(key);
+                self.resolve(now)
+            }
+            action => action,
+        }
+    }
+
+    /// Fires a complete binding, keeps waiting on a chord prefix, or clears
+    /// `pending` when nothing starts with it.
+    fn resolve(&mut self, now: Instant) -> Option<Action> {
+        let pending = self.pending.as_slice();
+        let prefix = BINDINGS
+            .iter()
+            .any(|(keys, _)| keys.len() > pending.len() && keys.starts_with(pending));
+        if !prefix && let Some(&(_, action)) = BINDINGS.iter().find(|(keys, _)| *keys == pending) {
+            self.pending.clear();
+            self.pending_since = None;
+            return Some(action);
+        }
+        if prefix {
+            self.pending_since.get_or_insert(now);
+            return None;
+        }
+        self.pending.clear();
+        self.pending_since = None;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn single_keys_act_immediately_in_either_case() {
+        let mut keymap = Keymap::new();
+        let start = Instant::now();
+        assert_eq!(keymap.handle_at(press('r'), start), Some(Action::Refresh));
+        assert_eq!(
+            keymap.handle_at(press('Q'), start + Duration::from_secs(1)),
+            Some(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn chord_waits_for_second_key() {
+        let mut keymap = Keymap::new();
+        let start = Instant::now();
+        assert_eq!(keymap.handle_at(press('g'), start), None);
+        assert_eq!(
+            keymap.handle_at(press('h'), start + Duration::from_millis(300)),
+            Some(Action::HomeLocation)
+        );
+    }
+
+    #[test]
+    fn stale_or_broken_chord_falls_back_to_single_key() {
+        let mut keymap = Keymap::new();
+        let start = Instant::now();
+        keymap.handle_at(press('g'), start);
+        assert_eq!(
+            keymap.handle_at(press('q'), start + Duration::from_millis(200)),
+            Some(Action::Quit)
+        );
+
+        keymap.handle_at(press('g'), start + Duration::from_secs(5));
+        assert_eq!(
+            keymap.handle_at(press('h'), start + Duration::from_secs(7)),
+            None
+        );
+    }
+
+    #[test]
+    fn chord_prefix_acts_alone_after_the_timeout() {
+        let mut keymap = Keymap::new();
+        let start = Instant::now();
+        assert_eq!(keymap.handle_at(press('g'), start), None);
+        assert_eq!(keymap.tick_at(start + Duration::from_millis(500)), None);
+        assert_eq!(
+            keymap.tick_at(start + Duration::from_millis(1100)),
+            Some(Action::ToggleHistory)
+        );
+        assert_eq!(keymap.tick_at(start + Duration::from_secs(2)), None);
+
+        // Completing the chord in time doesn't fire the lone key as well
+        keymap.handle_at(press('g'), start + Duration::from_secs(3));
+        assert_eq!(
+            keymap.handle_at(press('h'), start + Duration::from_millis(3200)),
+            Some(Action::HomeLocation)
+        );
+        assert_eq!(keymap.tick_at(start + Duration::from_secs(5)), None);
+    }
+
+    #[test]
+    fn held_key_acts_once() {
+        let mut keymap = Keymap::new();
+        let start = Instant::now();
+        assert_eq!(
+            keymap.handle_at(press('f'), start),
+            Some(Action::ToggleForecast)
+        );
+        // Initial repeat delay, then the repeat rate
+        for ms in [500, 530, 560, 590, 620] {
+            assert_eq!(
+                keymap.handle_at(press('f'), start + Duration::from_millis(ms)),
+                None
+            );
+        }
+        assert_eq!(
+            keymap.handle_at(press('f'), start + Duration::from_secs(2)),
+            Some(Action::ToggleForecast)
+        );
+    }
+
+    #[test]
+    fn ctrl_c_quits_and_releases_are_ignored() {
+        let mut keymap = Keymap::new();
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.handle(ctrl_c), Some(Action::Quit));
+
+        let mut release = press('r');
+        release.kind = KeyEventKind::Release;
+        assert_eq!(keymap.handle(release), None);
+    }
+}
@@ -6,7 +6,10 @@ pub mod cli;
 pub mod config;
 pub mod error;
 pub mod geolocation;
+pub mod http;
 pub mod render;
 pub mod scene;
+pub mod scheduler;
+pub mod secrets;
 pub mod theme;
 pub mod weather;
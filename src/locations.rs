@@ -0,0 +1,309 @@
+use crate::cli::{AddLocationArgs, LocationsAction};
+use crate::config::{self, Config, LocationProfile};
+use crate::error::{ConfigError, WeatherError};
+use crate::geolocation;
+use std::fs;
+use std::path::Path;
+
+/// Runs `weathr locations ...` against the config file at `path`.
+pub async fn run(
+    config: &Config,
+    path: &Path,
+    action: LocationsAction,
+) -> Result<(), WeatherError> {
+    match action {
+        LocationsAction::List => list(config),
+        LocationsAction::Add(args) => {
+            let profile = resolve(config, args).await?;
+            if config.profile(&profile.name).is_some() {
+                return Err(ConfigError::DuplicateProfile(profile.name).into());
+            }
+            let content = read(path)?;
+            write(path, &append_profile(&content, &profile))?;
+            println!(
+                "Saved {} ({:.4}, {:.4}). Show it with `weathr {}`.",
+                profile.name, profile.latitude, profile.longitude, profile.name
+            );
+        }
+        LocationsAction::Remove { name } => {
+            // From whichever file the profile is saved in, the main one first
+            let mut files = config::config_files(path);
+            files.reverse();
+            let mut removed = false;
+            for file in &files {
+                if let Some(updated) = remove_profile(&read(file)?, &name) {
+                    write(file, &updated)?;
+                    removed = true;
+                    break;
+                }
+            }
+            if !removed {
+                return Err(ConfigError::UnknownProfile(name).into());
+            }
+            println!("Removed {}.", name);
+        }
+    }
+    Ok(())
+}
+
+fn list(config: &Config) {
+    if config.locations.is_empty() {
+        println!("No saved locations. Add one with `weathr locations add NAME`.");
+        return;
+    }
+
+    let width = config
+        .locations
+        .iter()
+        .map(|profile| profile.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for profile in &config.locations {
+        let line = format!(
+            "{:<width$}  {:>8.4}, {:>9.4}  {}",
+            profile.name,
+            profile.latitude,
+            profile.longitude,
+            profile.city.as_deref().unwrap_or(""),
+            width = width
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+/// Coordinates for a new profile: given explicitly, looked up by place
+/// name, or else the currently configured (or detected) location.
+async fn resolve(config: &Config, args: AddLocationArgs) -> Result<LocationProfile, WeatherError> {
+    let name = args.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ConfigError::UnknownProfile(args.name).into());
+    }
+
+    let (latitude, longitude, city) = match (args.lat, args.lon, &args.place) {
+        (Some(latitude), Some(longitude), _) => (latitude, longitude, None),
+        (_, _, Some(place)) => {
+            let found = geolocation::geocode(place).await?;
+            (found.latitude, found.longitude, found.city)
+        }
+        _ if config.location.auto => {
+            let found = geolocation::detect_location().await?;
+            (found.latitude, found.longitude, found.city)
+        }
+        _ => (
+            config.location.latitude,
+            config.location.longitude,
+            config.location.city.clone(),
+        ),
+    };
+
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(ConfigError::InvalidLatitude(latitude).into());
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Err(ConfigError::InvalidLongitude(longitude).into());
+    }
+
+    Ok(LocationProfile {
+        name,
+        latitude,
+        longitude,
+        city: args.city.or(city),
+        units: None,
+    })
+}
+
+fn read(path: &Path) -> Result<String, ConfigError> {
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(path).map_err(|e| ConfigError::ReadError {
+        path: path.display().to_string(),
+        source: e,
+    })
+}
+
+fn write(path: &Path, content: &str) -> Result<(), ConfigError> {
+    let error = |e| ConfigError::WriteError {
+        path: path.display().to_string(),
+        source: e,
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(error)?;
+    }
+    fs::write(path, content).map_err(error)
+}
+
+/// Appends a `[[locations]]` table, leaving the rest of the file (and its
+/// comments) untouched.
+fn append_profile(content: &str, profile: &LocationProfile) -> String {
+    let mut updated = content.trim_end().to_string();
+    if !updated.is_empty() {
+        updated.push_str("\n\n");
+    }
+    updated.push_str("[[locations]]\n");
+    updated.push_str(&format!(
+        "name = {}\n",
+        toml::Value::from(profile.name.as_str())
+    ));
+    updated.push_str(&format!("latitude = {}\n", profile.latitude));
+    updated.push_str(&format!("longitude = {}\n", profile.longitude));
+    if let Some(city) = &profile.city {
+        updated.push_str(&format!("city = {}\n", toml::Value::from(city.as_str())));
+    }
+    updated
+}
+
+/// Removes the `[[locations]]` table (with any `[locations.units]` below it)
+/// whose name matches, or `None` if there is no such table.
+fn remove_profile(content: &str, name: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let is_header = |line: &str| line.trim_start().starts_with('[');
+    let is_profile_header = |line: &str| line.trim() == "[[locations]]";
+
+    let mut start = 0;
+    while start < lines.len() {
+        if !is_profile_header(lines[start]) {
+            start += 1;
+            continue;
+        }
+
+        let end = (start + 1..lines.len())
+            .find(|&i| is_header(lines[i]) && !lines[i].trim_start().starts_with("[locations."))
+            .unwrap_or(lines.len());
+
+        let table: Option<toml::Table> = toml::from_str(&lines[start..end].join("\n")).ok();
+        let matches = table
+            .as_ref()
+            .and_then(|table| {
+                table
+                    .get("locations")?
+                    .as_array()?
+                    .first()?
+                    .get("name")?
+                    .as_str()
+                    .map(str::to_string)
+            })
+            .is_some_and(|found| found.eq_ignore_ascii_case(name.trim()));
+
+        if matches {
+            let mut kept: Vec<&str> = lines[..start].to_vec();
+            while kept.last().is_some_and(|line| line.trim().is_empty()) {
+                kept.pop();
+            }
+            if end < lines.len() && !kept.is_empty() {
+                kept.push("");
+            }
+            kept.extend_from_slice(&lines[end..]);
+
+            let mut updated = kept.join("\n");
+            if !updated.is_empty() {
+                updated.push('\n');
+            }
+            return Some(updated);
+        }
+        start = end;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherUnits;
+
+    const CONFIG: &str = r#"# My weather
+[location]
+latitude = 52.52
+longitude = 13.41
+
+[[locations]]
+name = "home"
+latitude = 51.5
+longitude = -0.12
+
+[[locations]]
+name = "mum"
+latitude = 53.8
+longitude = -1.55
+[locations.units]
+temperature = "fahrenheit"
+
+[footer]
+position = "hidden"
+"#;
+
+    fn names(content: &str) -> Vec<String> {
+        let config: Config = toml::from_str(content).unwrap();
+        config.locations.into_iter().map(|p| p.name).collect()
+    }
+
+    #[test]
+    fn test_append_profile_keeps_comments() {
+        let profile = LocationProfile {
+            name: "Gran's \"flat\"".to_string(),
+            latitude: 48.85,
+            longitude: 2.35,
+            city: Some("Paris".to_string()),
+            units: None,
+        };
+        let updated = append_profile(CONFIG, &profile);
+
+        assert!(updated.starts_with("# My weather\n"));
+        assert_eq!(names(&updated), vec!["home", "mum", "Gran's \"flat\""]);
+    }
+
+    #[test]
+    fn test_remove_profile_with_sub_table() {
+        let updated = remove_profile(CONFIG, "MUM").unwrap();
+
+        assert_eq!(names(&updated), vec!["home"]);
+        assert!(!updated.contains("fahrenheit"));
+        assert!(updated.contains("[footer]\nposition = \"hidden\""));
+        assert!(remove_profile(CONFIG, "work").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_with_included_profiles() {
+        let dir = std::env::temp_dir().join("weathr_test_locations_include");
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        fs::write(&config_path, "include = [\"locations.toml\"]\n").unwrap();
+        fs::write(
+            dir.join("locations.toml"),
+            "[[locations]]\nname = \"mum\"\nlatitude = 53.8\nlongitude = -1.55\n",
+        )
+        .unwrap();
+        let saved = || -> Vec<String> {
+            Config::load_from_path(&config_path, WeatherUnits::default())
+                .unwrap()
+                .locations
+                .into_iter()
+                .map(|p| p.name)
+                .collect()
+        };
+
+        let add = LocationsAction::Add(AddLocationArgs {
+            name: "work".to_string(),
+            lat: Some(51.5),
+            lon: Some(-0.12),
+            place: None,
+            city: None,
+        });
+        let config = Config::load_from_path(&config_path, WeatherUnits::default()).unwrap();
+        run(&config, &config_path, add).await.unwrap();
+        assert_eq!(saved(), ["mum", "work"]);
+
+        let remove = LocationsAction::Remove {
+            name: "mum".to_string(),
+        };
+        run(&config, &config_path, remove).await.unwrap();
+        assert_eq!(saved(), ["work"]);
+        assert!(
+            !fs::read_to_string(dir.join("locations.toml"))
+                .unwrap()
+                .contains("mum")
+        );
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
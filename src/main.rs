@@ -2,27 +2,45 @@ mod animation;
 mod animation_manager;
 mod app;
 mod app_state;
+#[cfg(feature = "audio")]
+mod audio;
+mod auth;
 mod cache;
+mod cli;
+mod clipboard;
+mod compare;
 mod config;
 mod error;
 mod geolocation;
+mod history;
+mod http;
+mod keymap;
+mod locations;
+mod month;
+mod notify;
+mod preview;
 mod render;
+mod route;
 mod scene;
+mod scheduler;
+mod secrets;
+mod summary;
 mod theme;
+mod watch;
 mod weather;
 
 use clap::{CommandFactory, Parser};
 use clap_complete::generate;
+use cli::Cli;
 use config::Config;
 use crossterm::{
-    cursor, execute,
+    cursor, event, execute,
     style::ResetColor,
     terminal::{LeaveAlternateScreen, disable_raw_mode},
 };
 use render::TerminalRenderer;
 use std::{io, panic};
 use theme::ThemeRegistry;
-use weathr::cli::{self, Cli};
 
 fn info(silent: bool, msg: &str) {
     if !silent {
@@ -30,12 +48,43 @@ fn info(silent: bool, msg: &str) {
     }
 }
 
+/// Takes over the terminal for the scene, or exits explaining why it can't.
+fn open_renderer() -> TerminalRenderer {
+    let mut renderer = match TerminalRenderer::new() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("\n{}\n", e.user_friendly_message());
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = renderer.init() {
+        eprintln!("\n{}\n", e.user_friendly_message());
+        std::process::exit(1);
+    };
+    renderer
+}
+
+/// Ends with `--duration`, or never.
+async fn time_limit(duration: Option<std::time::Duration>) {
+    match duration {
+        Some(duration) => tokio::time::sleep(duration).await,
+        None => std::future::pending().await,
+    }
+}
+
 #[tokio::main]
 async fn main() -> io::Result<()> {
     let default_hook = panic::take_hook();
     panic::set_hook(Box::new(move |info| {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show, ResetColor);
+        let _ = execute!(
+            io::stdout(),
+            event::DisableFocusChange,
+            LeaveAlternateScreen,
+            cursor::Show,
+            ResetColor
+        );
         default_hook(info);
     }));
 
@@ -57,6 +106,31 @@ async fn main() -> io::Result<()> {
         return Ok(());
     }
 
+    let replay = match &cli.command {
+        Some(cli::Command::Replay(args)) => {
+            let yesterday = chrono::Local::now().date_naive() - chrono::Duration::days(1);
+            let to = args
+                .to
+                .unwrap_or(args.from + chrono::Duration::days(6))
+                .min(yesterday);
+            if args.from > to {
+                eprintln!(
+                    "Error: nothing to replay between {} and {} (archived data ends at {}).",
+                    args.from,
+                    args.to.unwrap_or(args.from),
+                    yesterday
+                );
+                std::process::exit(1);
+            }
+            Some(app::Replay {
+                from: args.from,
+                to,
+                speed: args.speed,
+            })
+        }
+        _ => None,
+    };
+
     let mut config = match Config::load() {
         Ok(config) => config,
         Err(e) => {
@@ -77,7 +151,49 @@ async fn main() -> io::Result<()> {
         }
     };
 
+    if cli.insecure {
+        config.network.insecure = true;
+    }
+    if config.network.insecure {
+        eprintln!("Warning: TLS certificate verification is disabled.");
+    }
+    if let Err(e) = http::configure(&config.network) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+
+    if let Some(cli::Command::Auth(args)) = cli.command {
+        if let Err(e) = auth::run(args.action) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(cli::Command::Locations(args)) = cli.command {
+        let action = args.action.unwrap_or(cli::LocationsAction::List);
+        let result = match Config::get_config_path() {
+            Ok(path) => locations::run(&config, &path, action).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(cli::Command::Preview(args)) = cli.command {
+        return preview::run(&config, args);
+    }
+
     // CLI Overrides
+    if let Some(name) = cli.profile.as_ref().or(cli.location.as_ref())
+        && let Err(e) = config.apply_profile(name)
+    {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
     if cli.auto_location {
         config.location.auto = true;
     }
@@ -87,6 +203,15 @@ async fn main() -> io::Result<()> {
     if cli.hide_hud {
         config.hide_hud = true;
     }
+    if cli.wind_sock {
+        config.wind_sock = true;
+    }
+    if let Some(scene) = cli.scene {
+        config.scene.name = Some(scene);
+    }
+    if cli.storm_watch {
+        config.storm_watch = true;
+    }
     if cli.imperial {
         config.units = weather::WeatherUnits::imperial();
     }
@@ -96,6 +221,89 @@ async fn main() -> io::Result<()> {
     if cli.silent {
         config.silent = true;
     }
+    if cli.summary {
+        config.summary_on_exit = true;
+    }
+    config.icons = config.icons.supported(render::supports_unicode());
+    if config.audio.enabled && !cfg!(feature = "audio") && !config.silent {
+        eprintln!(
+            "Warning: audio is enabled in config, but weathr was built without the 'audio' feature."
+        );
+    }
+    // Dumb terminals, pipes and CI logs can't host the scene; print a single
+    // line instead of a stream of escape sequences.
+    let one_shot = match &cli.command {
+        Some(cli::Command::Watch(args)) => args.once,
+        Some(_) => false,
+        None => !render::supports_fullscreen(),
+    };
+    if one_shot
+        || matches!(
+            cli.command,
+            Some(
+                cli::Command::Watch(_)
+                    | cli::Command::Month
+                    | cli::Command::History(_)
+                    | cli::Command::Route(_)
+            )
+        )
+    {
+        // Keep stdout limited to weather lines so it can be piped or logged.
+        config.silent = true;
+    }
+
+    // The compared places stand in for the configured one
+    if let Some(cli::Command::Compare(args)) = &cli.command {
+        info(config.silent, "Looking up places...");
+        let places = match compare::locate(&args.left, &args.right).await {
+            Ok(places) => places,
+            Err(e) => {
+                eprintln!("{}", e.user_friendly_message());
+                std::process::exit(1);
+            }
+        };
+
+        let mut renderer = open_renderer();
+        let (term_width, term_height) = renderer.get_size();
+        let mut compare = compare::Compare::new(&config, places, term_width, term_height);
+        let result = tokio::select! {
+            res = compare.run(&mut renderer) => res,
+            _ = tokio::signal::ctrl_c() => Ok(()),
+            _ = time_limit(cli.duration) => Ok(()),
+        };
+        renderer.cleanup()?;
+        if let Err(e) = result {
+            eprintln!("Application error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // The route's own points stand in for the configured location
+    if let Some(cli::Command::Route(args)) = &cli.command {
+        let track = match route::read(&args.path) {
+            Ok(track) => track,
+            Err(e) => {
+                eprintln!("Error reading route: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let name = args
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let options = route::RouteOptions {
+            depart: args.depart,
+            speed: args.speed,
+            every: args.every,
+        };
+        if let Err(e) = route::print(&config, &name, &track, &options).await {
+            eprintln!("Failed to fetch weather: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
     let lat_from_env = std::env::var(config::ENV_LATITUDE).is_ok();
     let lon_from_env = std::env::var(config::ENV_LONGITUDE).is_ok();
@@ -109,6 +317,29 @@ async fn main() -> io::Result<()> {
         );
     }
 
+    // Falls back to the configured location like auto-detection does
+    if cli.from_clipboard {
+        info(config.silent, "Reading location from clipboard...");
+        match clipboard::location().await {
+            Ok(geo_loc) => {
+                info(
+                    config.silent,
+                    &format!(
+                        "Location from clipboard: {:.4}, {:.4}",
+                        geo_loc.latitude, geo_loc.longitude
+                    ),
+                );
+                config.location.latitude = geo_loc.latitude;
+                config.location.longitude = geo_loc.longitude;
+                config.location.city = geo_loc.city;
+                config.location.auto = false;
+            }
+            Err(e) => {
+                eprintln!("{}", e.user_friendly_message());
+            }
+        }
+    }
+
     if !config.location.auto
         && config.location.latitude == config::default_latitude()
         && config.location.longitude == config::default_longitude()
@@ -171,6 +402,51 @@ async fn main() -> io::Result<()> {
         }
     }
 
+    if let Some(cli::Command::Month) = cli.command {
+        if let Err(e) = month::print(&config).await {
+            eprintln!("Failed to fetch weather: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(cli::Command::History(args)) = &cli.command {
+        if let Err(e) = history::print(&config, args.days) {
+            eprintln!("Error reading weather history: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if one_shot {
+        if let Err(e) = watch::print_once(&config).await {
+            eprintln!("Failed to fetch weather: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(cli::Command::Watch(args)) = &cli.command {
+        if !(args.bucket.is_finite() && args.bucket > 0.0) {
+            eprintln!("Error: --bucket must be greater than zero.");
+            std::process::exit(1);
+        }
+        let options = watch::WatchOptions {
+            bucket: args.bucket,
+            interval: std::time::Duration::from_secs(args.interval.max(1)),
+        };
+        tokio::select! {
+            result = watch::run(&config, options) => {
+                if let Err(e) = result {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {}
+        }
+        return Ok(());
+    }
+
     let mut theme_registry = ThemeRegistry::new();
     let theme_id = config.normalized_theme();
     if theme_registry.set_active(theme_id).is_err() {
@@ -180,26 +456,24 @@ async fn main() -> io::Result<()> {
         );
     }
 
-    let mut renderer = match TerminalRenderer::new() {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("\n{}\n", e.user_friendly_message());
-            std::process::exit(1);
-        }
-    };
-
-    if let Err(e) = renderer.init() {
-        eprintln!("\n{}\n", e.user_friendly_message());
-        std::process::exit(1);
-    };
-
+    let mut renderer = open_renderer();
     let (term_width, term_height) = renderer.get_size();
 
     let mut app = app::App::new(
         &config,
-        cli.simulate,
-        cli.night,
-        cli.leaves,
+        app::SessionOptions {
+            simulate_condition: cli.simulate,
+            simulate_transition: cli.simulate_transition.map(|(from, to)| {
+                app::SimulatedTransition {
+                    from,
+                    to,
+                    over: cli.over,
+                }
+            }),
+            simulate_night: cli.night,
+            show_leaves: cli.leaves,
+            replay,
+        },
         term_width,
         term_height,
         theme_registry,
@@ -210,6 +484,7 @@ async fn main() -> io::Result<()> {
         _ = tokio::signal::ctrl_c() => {
             Ok(())
         }
+        _ = time_limit(cli.duration) => Ok(()),
     };
 
     renderer.cleanup()?;
@@ -219,5 +494,11 @@ async fn main() -> io::Result<()> {
         std::process::exit(1);
     }
 
+    if config.summary_on_exit
+        && let Some((weather, location, units)) = app.live_weather()
+    {
+        summary::print(weather, &location, &units).await;
+    }
+
     Ok(())
 }
@@ -0,0 +1,213 @@
+use crate::config::Config;
+use crate::error::WeatherError;
+use crate::scheduler::Scheduler;
+use crate::weather::archive::{DaySummary, OpenMeteoArchive};
+use crate::weather::types::IconSet;
+use crate::weather::{WeatherCondition, WeatherLocation, WeatherUnits, format_temperature};
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+/// Columns per day, including the gap before the next one.
+const CELL_WIDTH: usize = 9;
+/// Open-Meteo forecasts this many days, today included.
+const FORECAST_DAYS: u64 = 16;
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+/// A condition of each kind the symbols tell apart, for the legend.
+const LEGEND: [(WeatherCondition, &str); 6] = [
+    (WeatherCondition::Clear, "clear"),
+    (WeatherCondition::Overcast, "cloudy"),
+    (WeatherCondition::Fog, "fog"),
+    (WeatherCondition::Rain, "rain"),
+    (WeatherCondition::Snow, "snow"),
+    (WeatherCondition::Thunderstorm, "storm"),
+];
+
+/// Prints this month as a calendar, each day with its weather and low/high:
+/// what happened on the days gone by and the forecast for those to come,
+/// as far as it reaches.
+pub async fn print(config: &Config) -> Result<(), WeatherError> {
+    let location = WeatherLocation {
+        latitude: config.location.latitude,
+        longitude: config.location.longitude,
+        elevation: None,
+    };
+    let archive = OpenMeteoArchive::recent();
+    let mut today = Scheduler::default().now().date_naive();
+    let (mut days, scheduler) = fetch_month(&archive, &location, today).await?;
+    // The location's date can be a day either side of ours
+    let there = scheduler.now().date_naive();
+    if there != today {
+        today = there;
+        days = fetch_month(&archive, &location, today).await?.0;
+    }
+    let (first, _) = month_bounds(today);
+
+    let place = match &config.location.city {
+        Some(city) => city.clone(),
+        None => format!(
+            "{:.2}, {:.2}",
+            config.location.latitude, config.location.longitude
+        ),
+    };
+    println!("{} | {}", first.format("%B %Y"), place);
+    for line in calendar(first, today, &days, &config.units, config.icons) {
+        println!("{}", line.trim_end());
+    }
+    println!("{}", legend(config.icons));
+    Ok(())
+}
+
+/// `today`'s month as far as the forecast reaches.
+async fn fetch_month(
+    archive: &OpenMeteoArchive,
+    location: &WeatherLocation,
+    today: NaiveDate,
+) -> Result<(Vec<DaySummary>, Scheduler), WeatherError> {
+    let (first, last) = month_bounds(today);
+    let forecast_end = today + Days::new(FORECAST_DAYS - 1);
+    archive
+        .fetch_daily(location, first, last.min(forecast_end))
+        .await
+}
+
+fn legend(icons: IconSet) -> String {
+    let mut legend = LEGEND
+        .iter()
+        .map(|(condition, name)| format!("{} {}", condition.icon(icons), name))
+        .collect::<Vec<_>>();
+    legend.push("[day] today".to_string());
+    legend.join("  ")
+}
+
+/// The first and last day of `date`'s month.
+fn month_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let first = date.with_day(1).unwrap_or(date);
+    let last = first + Months::new(1) - Days::new(1);
+    (first, last)
+}
+
+/// Weeks from Monday, two lines a week: the day with its condition glyph,
+/// then its low/high. Days without weather show just their number.
+fn calendar(
+    first: NaiveDate,
+    today: NaiveDate,
+    days: &[DaySummary],
+    units: &WeatherUnits,
+    icons: IconSet,
+) -> Vec<String> {
+    let (_, last) = month_bounds(first);
+    let mut lines = vec![
+        WEEKDAYS
+            .iter()
+            .map(|day| format!("{:<width$}", day, width = CELL_WIDTH))
+            .collect::<String>(),
+    ];
+
+    let mut date = first - Days::new(u64::from(first.weekday().num_days_from_monday()));
+    while date <= last {
+        let (mut top, mut bottom) = (String::new(), String::new());
+        for _ in 0..7 {
+            let (day, range) = if date.month() == first.month() {
+                let summary = days.iter().find(|summary| summary.date == date);
+                let number = if date == today {
+                    format!("[{}]", date.day())
+                } else {
+                    date.day().to_string()
+                };
+                let day = match summary {
+                    Some(summary) => format!("{} {}", number, summary.condition.icon(icons)),
+                    None => number,
+                };
+                let range = summary
+                    .map(|summary| {
+                        let (low, unit) = format_temperature(summary.range.min, units.temperature);
+                        let (high, _) = format_temperature(summary.range.max, units.temperature);
+                        format!("{:.0}/{:.0}{}", low, high, unit)
+                    })
+                    .unwrap_or_default();
+                (day, range)
+            } else {
+                Default::default()
+            };
+            top.push_str(&pad(&day));
+            bottom.push_str(&pad(&range));
+            date = date + Days::new(1);
+        }
+        lines.push(top);
+        lines.push(bottom);
+    }
+    lines
+}
+
+/// Pads to a cell, counting characters rather than bytes for the glyphs.
+fn pad(text: &str) -> String {
+    let width = text.chars().count();
+    format!("{}{}", text, " ".repeat(CELL_WIDTH.saturating_sub(width)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::types::TemperatureRange;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn test_month_bounds() {
+        assert_eq!(month_bounds(date(16)), (date(1), date(31)));
+        let february = NaiveDate::from_ymd_opt(2028, 2, 10).unwrap();
+        assert_eq!(month_bounds(february).1.day(), 29);
+    }
+
+    #[test]
+    fn test_calendar_grid() {
+        let days = [
+            DaySummary {
+                date: date(1),
+                condition: WeatherCondition::Rain,
+                range: TemperatureRange {
+                    min: 6.4,
+                    max: 11.6,
+                },
+            },
+            DaySummary {
+                date: date(16),
+                condition: WeatherCondition::Clear,
+                range: TemperatureRange {
+                    min: 4.0,
+                    max: 14.0,
+                },
+            },
+        ];
+        let lines = calendar(
+            date(1),
+            date(16),
+            &days,
+            &WeatherUnits::metric(),
+            IconSet::Unicode,
+        );
+
+        assert!(lines[0].starts_with("Mon      Tue      Wed      Thu"));
+        // 1 October 2026 is a Thursday
+        assert_eq!(
+            lines[1].trim_end(),
+            format!("{}1 ☂      2        3        4", " ".repeat(27))
+        );
+        assert_eq!(lines[2].trim_end(), format!("{}6/12°C", " ".repeat(27)));
+        // Friday the 16th is today
+        assert!(lines[5].contains("15       [16] ☼   17"));
+        assert!(lines[6].contains("4/14°C"));
+        // Five weeks, two lines each, under the weekdays
+        assert_eq!(lines.len(), 11);
+    }
+
+    #[test]
+    fn test_legend_follows_the_icons() {
+        assert_eq!(
+            legend(IconSet::Unicode),
+            "☼ clear  ☁ cloudy  ≡ fog  ☂ rain  * snow  ϟ storm  [day] today"
+        );
+        assert!(legend(IconSet::Ascii).starts_with("O clear  ~ cloudy  = fog  / rain"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Desktop notifications for rain due soon and for the weather turning
+//! severe, sent through the system's own notifier: `notify-send` on Linux
+//! and the BSDs, `osascript` on macOS and PowerShell on Windows. Nothing is
+//! sent when the notifier is missing.
+
+use crate::config::NotificationsConfig;
+use crate::weather::severity::Severity;
+use crate::weather::{WeatherData, beaufort_description};
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// The nowcast has to expect rain at most this far ahead.
+const RAIN_WARNING_MINUTES: u32 = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+}
+
+/// Decides what's worth a notification as the weather comes in, telling of
+/// each event once until it has passed.
+pub struct Notifier {
+    config: NotificationsConfig,
+    /// Rain already announced, until it starts or the nowcast drops it.
+    rain_announced: bool,
+    severity: Option<Severity>,
+}
+
+impl Notifier {
+    pub fn new(config: NotificationsConfig) -> Self {
+        Self {
+            config,
+            rain_announced: false,
+            severity: None,
+        }
+    }
+
+    /// Sends what's new in `weather`, with `rain_due_in` minutes from the
+    /// nowcast.
+    pub fn check(&mut self, weather: &WeatherData, rain_due_in: Option<u32>, place: &str) {
+        for notification in self.events(weather, rain_due_in, place) {
+            send(notification);
+        }
+    }
+
+    fn events(
+        &mut self,
+        weather: &WeatherData,
+        rain_due_in: Option<u32>,
+        place: &str,
+    ) -> Vec<Notification> {
+        let mut events = Vec::new();
+
+        let rain_soon = rain_due_in.filter(|&minutes| minutes <= RAIN_WARNING_MINUTES);
+        if let Some(minutes) = rain_soon
+            && self.config.rain
+            && !self.rain_announced
+        {
+            let kind = if weather.temperature <= 0.0 {
+                "Snow"
+            } else {
+                "Rain"
+            };
+            events.push(Notification {
+                title: if minutes == 0 {
+                    format!("{} starting", kind)
+                } else {
+                    format!("{} in ~{} min", kind, minutes)
+                },
+                body: format!("Expected in {} by the nowcast", place),
+            });
+        }
+        self.rain_announced = rain_soon.is_some();
+
+        // Only a rise is news; the border shows the rest
+        let severity = Severity::of(weather);
+        if let Some(level) = severity
+            && self.config.alerts
+            && self.severity < severity
+        {
+            let title = match level {
+                Severity::Watch => "Severe weather watch",
+                Severity::Warning => "Severe weather warning",
+            };
+            events.push(Notification {
+                title: title.to_string(),
+                body: format!(
+                    "{}, {} in {}",
+                    weather.condition.label(),
+                    beaufort_description(weather.wind_speed),
+                    place
+                ),
+            });
+        }
+        self.severity = severity;
+
+        events
+    }
+}
+
+/// Shows `notification` in the background, ignoring a missing or failing
+/// notifier.
+fn send(notification: Notification) {
+    let mut command = notifier_command();
+    command
+        .env("WEATHR_TITLE", &notification.title)
+        .env("WEATHR_BODY", &notification.body)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    tokio::spawn(async move {
+        let _ = command.status().await;
+    });
+}
+
+// The text is passed in the environment rather than spliced into a script,
+// so quotes in a place name can't break it.
+
+#[cfg(target_os = "macos")]
+fn notifier_command() -> Command {
+    let mut command = Command::new("osascript");
+    command.args([
+        "-e",
+        r#"display notification (system attribute "WEATHR_BODY") with title (system attribute "WEATHR_TITLE")"#,
+    ]);
+    command
+}
+
+#[cfg(windows)]
+fn notifier_command() -> Command {
+    let mut command = Command::new("powershell");
+    command.args([
+        "-NoProfile",
+        "-Command",
+        "Add-Type -AssemblyName System.Windows.Forms; \
+         $icon = New-Object System.Windows.Forms.NotifyIcon; \
+         $icon.Icon = [System.Drawing.SystemIcons]::Information; \
+         $icon.Visible = $true; \
+         $icon.ShowBalloonTip(10000, $env:WEATHR_TITLE, $env:WEATHR_BODY, 'Info'); \
+         Start-Sleep -Seconds 10; $icon.Dispose()",
+    ]);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn notifier_command() -> Command {
+    let mut command = Command::new("sh");
+    command.args([
+        "-c",
+        r#"exec notify-send --app-name=weathr "$WEATHR_TITLE" "$WEATHR_BODY""#,
+    ]);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    fn weather(condition: WeatherCondition, wind_speed: f64) -> WeatherData {
+        WeatherData {
+            temperature: 12.0,
+            wind_speed,
+            source: "OM".to_string(),
+            timestamp: "2026-10-16T14:00".to_string(),
+            ..WeatherData::sample(condition)
+        }
+    }
+
+    fn all() -> NotificationsConfig {
+        NotificationsConfig {
+            rain: true,
+            alerts: true,
+        }
+    }
+
+    #[test]
+    fn test_rain_is_announced_once() {
+        let mut notifier = Notifier::new(all());
+        let dry = weather(WeatherCondition::Overcast, 3.0);
+        assert!(notifier.events(&dry, Some(45), "Leeds").is_empty());
+        assert_eq!(
+            notifier.events(&dry, Some(20), "Leeds"),
+            [Notification {
+                title: "Rain in ~20 min".to_string(),
+                body: "Expected in Leeds by the nowcast".to_string(),
+            }]
+        );
+        assert!(notifier.events(&dry, Some(15), "Leeds").is_empty());
+
+        // Once it has rained and stopped, the next shower is news again
+        assert!(notifier.events(&dry, None, "Leeds").is_empty());
+        assert_eq!(
+            notifier.events(&dry, Some(0), "Leeds")[0].title,
+            "Rain starting"
+        );
+    }
+
+    #[test]
+    fn test_alerts_on_rising_severity_only() {
+        let mut notifier = Notifier::new(all());
+        let storm = weather(WeatherCondition::Thunderstorm, 5.0);
+        let events = notifier.events(&storm, None, "Leeds");
+        assert_eq!(events[0].title, "Severe weather watch");
+        assert_eq!(events[0].body, "Thunderstorm, gentle breeze in Leeds");
+        assert!(notifier.events(&storm, None, "Leeds").is_empty());
+
+        let hail = weather(WeatherCondition::ThunderstormHail, 5.0);
+        assert_eq!(
+            notifier.events(&hail, None, "Leeds")[0].title,
+            "Severe weather warning"
+        );
+        assert!(notifier.events(&storm, None, "Leeds").is_empty());
+    }
+
+    #[test]
+    fn test_events_can_be_turned_off() {
+        let mut notifier = Notifier::new(NotificationsConfig {
+            rain: false,
+            alerts: true,
+        });
+        let dry = weather(WeatherCondition::Overcast, 30.0);
+        let events = notifier.events(&dry, Some(10), "Leeds");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title, "Severe weather warning");
+    }
+}
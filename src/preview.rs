@@ -0,0 +1,90 @@
+use crate::app::{App, SessionOptions};
+use crate::cli::PreviewArgs;
+use crate::config::Config;
+use crate::render::TerminalRenderer;
+use crate::theme::ThemeRegistry;
+use crate::weather::WeatherCondition;
+use crossterm::terminal;
+use std::io::{self, IsTerminal};
+
+/// Frames run before the one printed, so rain, snow and clouds have filled
+/// the sky (four seconds at the usual 30 fps).
+const WARMUP_FRAMES: u32 = 120;
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// Runs `weathr preview`: prints one labeled frame of a scene under a
+/// simulated condition, or with `--list` what can be previewed.
+pub fn run(config: &Config, args: PreviewArgs) -> io::Result<()> {
+    let mut themes = ThemeRegistry::new();
+    // An unknown theme falls back to the default, as in the live view
+    let _ = themes.set_active(config.normalized_theme());
+
+    let (width, height) = frame_size(&args);
+    let mut renderer = TerminalRenderer::offscreen(width, height);
+    let (width, height) = renderer.get_size();
+
+    let mut app = App::new(
+        config,
+        SessionOptions {
+            simulate_condition: Some(args.condition.clone()),
+            simulate_night: args.night,
+            show_leaves: args.leaves,
+            ..SessionOptions::default()
+        },
+        width,
+        height,
+        themes,
+    );
+
+    if args.list {
+        println!("Scenes:");
+        for id in app.scene_ids() {
+            println!("  {}", id);
+        }
+        println!("\nConditions:");
+        for condition in WeatherCondition::ALL {
+            println!("  {:<16} {}", condition.as_str(), condition.description());
+        }
+        return Ok(());
+    }
+
+    let scene = match &args.scene {
+        Some(scene) => scene.clone(),
+        None => app.active_scene().to_string(),
+    };
+    if !app.select_scene(&scene) {
+        eprintln!(
+            "Error: no scene named {:?} (available: {}).",
+            scene,
+            app.scene_ids().join(", ")
+        );
+        std::process::exit(1);
+    }
+    let label = format!(
+        "{} | {} | {} | {}x{}",
+        scene,
+        args.condition,
+        if args.night { "night" } else { "day" },
+        width,
+        height
+    );
+
+    app.render_preview(&mut renderer, WARMUP_FRAMES)?;
+    println!("{}", label);
+    renderer.print_frame()
+}
+
+/// `--width`/`--height`, else the terminal's size less a line for the label
+/// and the prompt, else 80x24.
+fn frame_size(args: &PreviewArgs) -> (u16, u16) {
+    let detected = io::stdout()
+        .is_terminal()
+        .then(terminal::size)
+        .and_then(Result::ok)
+        .map(|(width, height)| (width, height.saturating_sub(2)))
+        .unwrap_or(DEFAULT_SIZE);
+    (
+        args.width.unwrap_or(detected.0),
+        args.height.unwrap_or(detected.1),
+    )
+}
@@ -0,0 +1,70 @@
+use super::{TerminalRenderer, display_width};
+use crossterm::style::Color;
+use std::io;
+use std::time::{Duration, Instant};
+
+/// How long a banner stays on screen.
+const BANNER_DURATION: Duration = Duration::from_secs(4);
+
+/// A short centered message that disappears on its own, e.g. when a refresh
+/// changes the weather condition.
+#[derive(Default)]
+pub struct Banner {
+    text: String,
+    shown_at: Option<Instant>,
+}
+
+impl Banner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn show(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.shown_at = Some(Instant::now());
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.shown_at
+            .is_some_and(|shown_at| shown_at.elapsed() < BANNER_DURATION)
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        if !self.is_visible() {
+            return Ok(());
+        }
+
+        let (width, height) = renderer.get_size();
+        let inner = format!("  {}  ", self.text);
+        let border = "─".repeat(display_width(&inner));
+        let lines = [
+            format!("┌{}┐", border),
+            format!("│{}│", inner),
+            format!("└{}┘", border),
+        ];
+
+        let x = (width as usize).saturating_sub(display_width(&lines[0])) / 2;
+        let top = height / 3;
+        for (i, line) in lines.iter().enumerate() {
+            renderer.render_line_colored(x as u16, top + i as u16, line, Color::White)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_banner_expires() {
+        let mut banner = Banner::new();
+        assert!(!banner.is_visible());
+
+        banner.show("Rain starting — 2.5 mm/h");
+        assert!(banner.is_visible());
+
+        banner.shown_at = Instant::now().checked_sub(BANNER_DURATION);
+        assert!(!banner.is_visible());
+    }
+}
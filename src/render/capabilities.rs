@@ -75,6 +75,66 @@ impl TerminalCapabilities {
     }
 }
 
+/// Terminals known to lack cursor addressing or an alternate screen.
+const LIMITED_TERMS: [&str; 4] = ["dumb", "unknown", "emacs", "cons25"];
+
+/// Whether the full-screen scene can be drawn. Pipes, CI logs and dumb or
+/// serial terminals would only receive a stream of raw escape sequences.
+pub fn supports_fullscreen() -> bool {
+    fullscreen_supported(
+        std::io::stdout().is_terminal(),
+        env::var("TERM").ok().as_deref(),
+        env::var_os("CI").is_some(),
+    )
+}
+
+fn fullscreen_supported(is_tty: bool, term: Option<&str>, ci: bool) -> bool {
+    if !is_tty || ci {
+        return false;
+    }
+
+    // Windows consoles don't set TERM but handle the alternate screen fine.
+    match term {
+        None => cfg!(windows),
+        Some(term) => !term.is_empty() && !LIMITED_TERMS.contains(&term),
+    }
+}
+
+/// Whether the terminal can show symbols beyond ASCII: it needs a UTF-8
+/// locale, and the Linux console's fonts lack most of them anyway.
+pub fn supports_unicode() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()));
+    unicode_supported(locale.as_deref(), env::var("TERM").ok().as_deref())
+}
+
+fn unicode_supported(locale: Option<&str>, term: Option<&str>) -> bool {
+    if term == Some("linux") {
+        return false;
+    }
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        // Windows has no locale variables; its terminals are Unicode
+        None => cfg!(windows),
+    }
+}
+
+/// Whether we're drawing over SSH, where every frame crosses the network.
+/// The variables carry into tmux and screen sessions started there.
+pub fn is_ssh_session() -> bool {
+    ssh_session(|name| env::var_os(name).is_some_and(|value| !value.is_empty()))
+}
+
+fn ssh_session(is_set: impl Fn(&str) -> bool) -> bool {
+    ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+        .into_iter()
+        .any(is_set)
+}
+
 fn check_term_for_256() -> ColorSupport {
     if env::var("TERM").is_ok_and(|term| term.contains("256color")) {
         return ColorSupport::Ansi256;
@@ -87,6 +147,30 @@ mod tests {
     use super::*;
     use crossterm::style::Color;
 
+    #[test]
+    fn test_fullscreen_requires_capable_tty() {
+        assert!(fullscreen_supported(true, Some("xterm-256color"), false));
+        assert!(!fullscreen_supported(false, Some("xterm-256color"), false));
+        assert!(!fullscreen_supported(true, Some("xterm-256color"), true));
+        assert!(!fullscreen_supported(true, Some("dumb"), false));
+        assert!(!fullscreen_supported(true, Some(""), false));
+    }
+
+    #[test]
+    fn test_unicode_needs_utf8_locale() {
+        assert!(unicode_supported(Some("en_GB.UTF-8"), Some("xterm")));
+        assert!(unicode_supported(Some("C.utf8"), None));
+        assert!(!unicode_supported(Some("C"), Some("xterm")));
+        assert!(!unicode_supported(Some("en_US.UTF-8"), Some("linux")));
+    }
+
+    #[test]
+    fn test_ssh_session_from_environment() {
+        assert!(ssh_session(|name| name == "SSH_CONNECTION"));
+        assert!(ssh_session(|name| name == "SSH_TTY"));
+        assert!(!ssh_session(|name| name == "TERM"));
+    }
+
     #[test]
     fn test_adjust_color_none() {
         let caps = TerminalCapabilities {
@@ -0,0 +1,158 @@
+use super::{TerminalRenderer, display_width};
+use crate::config::FooterPosition;
+use crossterm::style::Color;
+use std::io;
+use unicode_width::UnicodeWidthChar;
+
+/// Columns kept free between the footer and the terminal edge.
+const MARGIN: usize = 2;
+
+/// Bottom-of-screen text: status messages, provider and supplementary
+/// attributions, and the data license note.
+pub struct Footer {
+    position: FooterPosition,
+    /// Shown even when the footer is hidden, e.g. when the provider's terms
+    /// require on-screen attribution.
+    attribution_required: bool,
+    license: Option<&'static str>,
+    status: String,
+    attribution: String,
+}
+
+impl Footer {
+    pub fn new(
+        position: FooterPosition,
+        license: Option<&'static str>,
+        attribution_required: bool,
+    ) -> Self {
+        Self {
+            position,
+            attribution_required,
+            license,
+            status: String::new(),
+            attribution: String::new(),
+        }
+    }
+
+    /// Transient text such as "Awaiting weather data" or a provider error.
+    pub fn set_status(&mut self, status: impl Into<String>) {
+        self.status = status.into();
+    }
+
+    /// Attribution of the latest weather data, one source per line.
+    pub fn set_attribution(&mut self, attribution: impl Into<String>) {
+        self.attribution = attribution.into();
+    }
+
+    pub fn clear(&mut self) {
+        self.status.clear();
+        self.attribution.clear();
+    }
+
+    /// The footer text wrapped to `width` columns, top line first.
+    pub fn lines(&self, width: u16) -> Vec<String> {
+        let hidden = self.position == FooterPosition::Hidden;
+        if hidden && !self.attribution_required {
+            return Vec::new();
+        }
+
+        let max_width = (width as usize).saturating_sub(MARGIN * 2).max(1);
+        let status = (!hidden).then_some(self.status.as_str());
+        let license = (!hidden).then_some(self.license).flatten();
+
+        status
+            .into_iter()
+            .chain(self.attribution.lines())
+            .chain(license)
+            .filter(|line| !line.trim().is_empty())
+            .flat_map(|line| wrap(line, max_width))
+            .collect()
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        let (width, height) = renderer.get_size();
+        let lines = self.lines(width);
+        let top = (height as usize).saturating_sub(lines.len());
+
+        for (i, line) in lines.iter().enumerate() {
+            let line_width = display_width(line);
+            let x = match self.position {
+                FooterPosition::BottomCenter => (width as usize).saturating_sub(line_width) / 2,
+                FooterPosition::BottomRight | FooterPosition::Hidden => {
+                    (width as usize).saturating_sub(line_width + MARGIN)
+                }
+            };
+            renderer.render_line_colored(x as u16, (top + i) as u16, line, Color::DarkGrey)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Greedy word wrap by display width; words longer than a line are split.
+fn wrap(text: &str, max_width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let separator = usize::from(!current.is_empty());
+        if !current.is_empty()
+            && display_width(&current) + separator + display_width(word) > max_width
+        {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        for ch in word.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if !current.is_empty() && display_width(&current) + ch_width > max_width {
+                lines.push(std::mem::take(&mut current));
+            }
+            current.push(ch);
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wraps_long_attribution_on_narrow_terminals() {
+        let mut footer = Footer::new(FooterPosition::BottomRight, Some("CC BY 4.0"), false);
+        footer.set_attribution("Data supplied by the Met Office\nSun data from the USNO");
+
+        let wide = footer.lines(120);
+        assert_eq!(
+            wide,
+            vec![
+                "Data supplied by the Met Office",
+                "Sun data from the USNO",
+                "CC BY 4.0"
+            ]
+        );
+
+        let narrow = footer.lines(24);
+        assert!(narrow.len() > wide.len());
+        assert!(narrow.iter().all(|line| display_width(line) <= 20));
+    }
+
+    #[test]
+    fn test_hidden_keeps_required_attribution_only() {
+        let mut footer = Footer::new(FooterPosition::Hidden, Some("CC BY 4.0"), false);
+        footer.set_status("Awaiting weather data");
+        footer.set_attribution("Weather data by Open-Meteo.com");
+        assert!(footer.lines(80).is_empty());
+
+        let mut footer = Footer::new(FooterPosition::Hidden, None, true);
+        footer.set_status("Awaiting weather data");
+        footer.set_attribution("Data supplied by the Met Office");
+        assert_eq!(footer.lines(80), vec!["Data supplied by the Met Office"]);
+    }
+}
@@ -1,13 +1,19 @@
+mod banner;
 mod capabilities;
+mod footer;
 
 use crate::error::TerminalError;
+pub use banner::Banner;
 use capabilities::TerminalCapabilities;
+pub use capabilities::{is_ssh_session, supports_fullscreen, supports_unicode};
 use crossterm::{
-    cursor, execute, queue,
+    cursor, event, execute, queue,
     style::{Color, Print, ResetColor, SetForegroundColor},
     terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
 };
+pub use footer::Footer;
 use std::io::{self, BufWriter, IsTerminal, Stdout, Write};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 const MIN_TERMINAL_WIDTH: u16 = 70;
 const MIN_TERMINAL_HEIGHT: u16 = 20;
@@ -22,6 +28,17 @@ fn clamp_terminal_size(width: u16, height: u16) -> (u16, u16) {
     )
 }
 
+/// Placeholder for the second column covered by a double-width character.
+const WIDE_CONTINUATION: char = '\0';
+/// Longest run of unchanged cells worth reprinting; a cursor move costs at
+/// least six bytes.
+const MAX_BRIDGED_GAP: u16 = 5;
+
+/// Number of terminal columns `text` occupies.
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 struct Cell {
     character: char,
@@ -44,6 +61,11 @@ pub struct TerminalRenderer {
     buffer: Vec<Cell>,
     last_buffer: Vec<Cell>,
     capabilities: TerminalCapabilities,
+    /// Drawing into memory for `print_frame` rather than owning the screen.
+    offscreen: bool,
+    /// Reprint short runs of unchanged cells rather than moving the cursor
+    /// past them, trading a little redundancy for fewer escape sequences.
+    bridge_gaps: bool,
 }
 
 impl TerminalRenderer {
@@ -76,18 +98,58 @@ impl TerminalRenderer {
             buffer: vec![Cell::default(); buffer_size],
             last_buffer: vec![Cell::default(); buffer_size],
             capabilities,
+            offscreen: false,
+            bridge_gaps: false,
         })
     }
 
+    /// A renderer for a single frame printed with `print_frame`, leaving
+    /// the terminal as it is. Works without a terminal, e.g. when piped.
+    pub fn offscreen(width: u16, height: u16) -> Self {
+        Self::pane(
+            width.max(MIN_TERMINAL_WIDTH),
+            height.max(MIN_TERMINAL_HEIGHT),
+        )
+    }
+
+    /// An offscreen renderer for part of the screen, copied onto the real
+    /// one with `draw_pane`.
+    pub fn pane(width: u16, height: u16) -> Self {
+        let (width, height) = clamp_terminal_size(width, height);
+        let buffer_size = (width as usize) * (height as usize);
+
+        Self {
+            stdout: BufWriter::new(io::stdout()),
+            width,
+            height,
+            buffer: vec![Cell::default(); buffer_size],
+            last_buffer: vec![Cell::default(); buffer_size],
+            capabilities: TerminalCapabilities::detect(),
+            offscreen: true,
+            bridge_gaps: false,
+        }
+    }
+
     pub fn init(&mut self) -> Result<(), TerminalError> {
         terminal::enable_raw_mode().map_err(TerminalError::RawModeError)?;
-        execute!(self.stdout, EnterAlternateScreen, cursor::Hide)
-            .map_err(TerminalError::InitError)?;
+        execute!(
+            self.stdout,
+            EnterAlternateScreen,
+            cursor::Hide,
+            event::EnableFocusChange
+        )
+        .map_err(TerminalError::InitError)?;
         Ok(())
     }
 
     pub fn cleanup(&mut self) -> io::Result<()> {
-        execute!(self.stdout, LeaveAlternateScreen, cursor::Show, ResetColor)?;
+        execute!(
+            self.stdout,
+            event::DisableFocusChange,
+            LeaveAlternateScreen,
+            cursor::Show,
+            ResetColor
+        )?;
         terminal::disable_raw_mode()?;
         Ok(())
     }
@@ -105,6 +167,10 @@ impl TerminalRenderer {
         Ok(())
     }
 
+    pub fn set_bridge_gaps(&mut self, bridge_gaps: bool) {
+        self.bridge_gaps = bridge_gaps;
+    }
+
     pub fn get_size(&self) -> (u16, u16) {
         (self.width, self.height)
     }
@@ -160,18 +226,29 @@ impl TerminalRenderer {
             return Ok(());
         }
         let adjusted_color = self.capabilities.adjust_color(color);
+        let row_start = (y as usize) * (self.width as usize);
 
-        for (idx, ch) in text.chars().enumerate() {
-            let col = x + idx as u16;
-            if col < self.width {
-                let buffer_idx = (y as usize) * (self.width as usize) + (col as usize);
-                if buffer_idx < self.buffer.len() {
-                    self.buffer[buffer_idx] = Cell {
-                        character: ch,
-                        color: adjusted_color,
-                    };
-                }
+        let mut col = x as usize;
+        for ch in text.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if ch_width == 0 {
+                continue;
             }
+            if col + ch_width > self.width as usize {
+                break;
+            }
+
+            self.buffer[row_start + col] = Cell {
+                character: ch,
+                color: adjusted_color,
+            };
+            if ch_width == 2 {
+                self.buffer[row_start + col + 1] = Cell {
+                    character: WIDE_CONTINUATION,
+                    color: adjusted_color,
+                };
+            }
+            col += ch_width;
         }
         Ok(())
     }
@@ -189,6 +266,38 @@ impl TerminalRenderer {
         Ok(())
     }
 
+    /// Draws a heavy frame along the edges of the screen.
+    pub fn render_border(&mut self, color: Color) -> io::Result<()> {
+        let (width, height) = (self.width, self.height);
+        if width < 2 || height < 2 {
+            return Ok(());
+        }
+        let (right, bottom) = (width - 1, height - 1);
+        for x in 1..right {
+            self.render_char(x, 0, '━', color)?;
+            self.render_char(x, bottom, '━', color)?;
+        }
+        for y in 1..bottom {
+            self.render_char(0, y, '┃', color)?;
+            self.render_char(right, y, '┃', color)?;
+        }
+        self.render_char(0, 0, '┏', color)?;
+        self.render_char(right, 0, '┓', color)?;
+        self.render_char(0, bottom, '┗', color)?;
+        self.render_char(right, bottom, '┛', color)
+    }
+
+    /// Copies `pane`'s frame onto this one with its top left corner at
+    /// (`x`, `y`), clipped to the screen.
+    pub fn draw_pane(&mut self, pane: &TerminalRenderer, x: u16, y: u16) {
+        let columns = pane.width.min(self.width.saturating_sub(x)) as usize;
+        for row in 0..pane.height.min(self.height.saturating_sub(y)) {
+            let from = row as usize * pane.width as usize;
+            let to = (y + row) as usize * self.width as usize + x as usize;
+            self.buffer[to..to + columns].copy_from_slice(&pane.buffer[from..from + columns]);
+        }
+    }
+
     pub fn flash_screen(&mut self) -> io::Result<()> {
         let flash_color = self.capabilities.adjust_color(Color::White);
         for cell in &mut self.buffer {
@@ -197,6 +306,34 @@ impl TerminalRenderer {
         Ok(())
     }
 
+    /// Prints the frame as lines of text below the cursor, colored only
+    /// when stdout is a terminal so it can be saved or pasted as is.
+    pub fn print_frame(&mut self) -> io::Result<()> {
+        let colored = io::stdout().is_terminal();
+        for row in self.buffer.chunks(self.width as usize) {
+            let end = row
+                .iter()
+                .rposition(|cell| cell.character != ' ')
+                .map_or(0, |i| i + 1);
+            let mut current_color = Color::Reset;
+            for cell in &row[..end] {
+                if cell.character == WIDE_CONTINUATION {
+                    continue;
+                }
+                if colored && cell.color != current_color {
+                    queue!(self.stdout, SetForegroundColor(cell.color))?;
+                    current_color = cell.color;
+                }
+                queue!(self.stdout, Print(cell.character))?;
+            }
+            if current_color != Color::Reset {
+                queue!(self.stdout, ResetColor)?;
+            }
+            queue!(self.stdout, Print('\n'))?;
+        }
+        self.stdout.flush()
+    }
+
     pub fn flush(&mut self) -> io::Result<()> {
         let mut current_color = Color::Reset;
         let mut last_pos: Option<(u16, u16)> = None;
@@ -213,9 +350,22 @@ impl TerminalRenderer {
                 let last_cell = self.last_buffer[idx];
 
                 if cell != last_cell {
+                    if cell.character == WIDE_CONTINUATION {
+                        // Already covered by the wide character to its left.
+                        continue;
+                    }
+
                     let expected_pos = last_pos.map(|(lx, ly)| (lx + 1, ly));
                     if expected_pos != Some((x, y)) {
-                        queue!(self.stdout, cursor::MoveTo(x, y))?;
+                        let bridged = match last_pos {
+                            Some((lx, ly)) if self.bridge_gaps && ly == y => {
+                                self.bridge_gap(lx + 1, x, y, current_color)?
+                            }
+                            _ => false,
+                        };
+                        if !bridged {
+                            queue!(self.stdout, cursor::MoveTo(x, y))?;
+                        }
                     }
 
                     if cell.color != current_color {
@@ -224,7 +374,8 @@ impl TerminalRenderer {
                     }
 
                     queue!(self.stdout, Print(cell.character))?;
-                    last_pos = Some((x, y));
+                    let advance = cell.character.width().unwrap_or(1).max(1) as u16;
+                    last_pos = Some((x + advance - 1, y));
                 }
             }
         }
@@ -237,10 +388,33 @@ impl TerminalRenderer {
         self.last_buffer.copy_from_slice(&self.buffer);
         Ok(())
     }
+
+    /// Reprints the unchanged cells of row `y` from `from` up to `to`, if
+    /// there are few enough and they need no color change, so the cursor
+    /// ends up at `to` without being moved there.
+    fn bridge_gap(&mut self, from: u16, to: u16, y: u16, color: Color) -> io::Result<bool> {
+        if to - from > MAX_BRIDGED_GAP {
+            return Ok(false);
+        }
+        let row = (y as usize) * (self.width as usize);
+        let gap = &self.buffer[row + from as usize..row + to as usize];
+        let reprintable = gap.iter().all(|cell| {
+            cell.character.width() == Some(1) && (cell.character == ' ' || cell.color == color)
+        });
+        if !reprintable {
+            return Ok(false);
+        }
+        for cell in gap {
+            queue!(self.stdout, Print(cell.character))?;
+        }
+        Ok(true)
+    }
 }
 
 impl Drop for TerminalRenderer {
     fn drop(&mut self) {
-        let _ = self.cleanup();
+        if !self.offscreen {
+            let _ = self.cleanup();
+        }
     }
 }
@@ -0,0 +1,473 @@
+//! `weathr route`: the forecast along a GPX track or KML path, each stop at
+//! the hour you'd get there travelling at a steady speed.
+
+use crate::config::Config;
+use crate::error::{DataError, WeatherError};
+use crate::geolocation::{GeoLocation, distance_km};
+use crate::weather::archive::OpenMeteoArchive;
+use crate::weather::types::{DistanceUnit, IconSet};
+use crate::weather::{
+    WeatherData, WeatherLocation, WeatherUnits, format_precipitation, format_temperature,
+    format_wind_speed,
+};
+use chrono::{DateTime, DurationRound, Local, NaiveDateTime, NaiveTime, TimeDelta, Utc};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// Forecasts fetched for one route at most; longer routes get them further
+/// apart than asked.
+const MAX_STOPS: usize = 48;
+const KM_PER_MILE: f64 = 1.609344;
+
+pub struct RouteOptions {
+    /// When to set off, on the local clock; now when left out.
+    pub depart: Option<NaiveTime>,
+    /// Average speed, per hour in the configured distance unit.
+    pub speed: f64,
+    /// Distance between forecasts, in the configured distance unit.
+    pub every: f64,
+}
+
+/// A point along the route, `km` from the start.
+#[derive(Debug)]
+struct Stop {
+    km: f64,
+    location: GeoLocation,
+    arrival: DateTime<Local>,
+}
+
+/// The track in a GPX file (its track points, else its route points) or the
+/// path in a KML file.
+pub fn read(path: &Path) -> io::Result<Vec<GeoLocation>> {
+    let text = std::fs::read_to_string(path)?;
+    let track = if text.contains("<kml") {
+        kml_points(&text)
+    } else {
+        gpx_points(&text)
+    };
+    if track.len() < 2 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "no route in {} (expected a GPX track or route, or a KML path)",
+                path.display()
+            ),
+        ));
+    }
+    Ok(track)
+}
+
+/// Prints a table of the weather at each stop along `track`, as forecast
+/// for when it's reached. Stops without a forecast, such as those more than
+/// about two weeks ahead, say so.
+pub async fn print(
+    config: &Config,
+    name: &str,
+    track: &[GeoLocation],
+    options: &RouteOptions,
+) -> Result<(), WeatherError> {
+    let units = &config.units;
+    let km_per_unit = match units.distance {
+        DistanceUnit::Km => 1.0,
+        DistanceUnit::Mi => KM_PER_MILE,
+    };
+    let now = Local::now().naive_local();
+    let depart = departure(options.depart, now)
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or_else(Local::now);
+    let stops = stops(
+        track,
+        options.every * km_per_unit,
+        options.speed * km_per_unit,
+        depart,
+    );
+
+    let archive = Arc::new(OpenMeteoArchive::recent());
+    let mut tasks = JoinSet::new();
+    for (i, stop) in stops.iter().enumerate() {
+        let archive = Arc::clone(&archive);
+        let location = WeatherLocation {
+            latitude: stop.location.latitude,
+            longitude: stop.location.longitude,
+            elevation: None,
+        };
+        let hour = forecast_hour(stop.arrival.with_timezone(&Utc));
+        tasks.spawn(async move { (i, archive.fetch_hour(&location, hour).await) });
+    }
+    let mut forecasts = vec![None; stops.len()];
+    let mut error = None;
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((i, result)) = joined else {
+            continue;
+        };
+        match result {
+            Ok(forecast) => forecasts[i] = Some(forecast),
+            Err(e) => {
+                error.get_or_insert(e);
+            }
+        }
+    }
+    if forecasts.iter().all(Option::is_none) {
+        return Err(error.unwrap_or(WeatherError::Data(DataError::NoData)));
+    }
+
+    let total = stops.last().map_or(0.0, |stop| stop.km);
+    let (length, length_unit) = (total / km_per_unit, distance_unit(units));
+    let travel = stops
+        .last()
+        .map_or(TimeDelta::zero(), |stop| stop.arrival - depart);
+    println!(
+        "{} | {:.0} {}, {}h {:02}m at {} {}/h, leaving {}",
+        name,
+        length,
+        length_unit,
+        travel.num_hours(),
+        travel.num_minutes() % 60,
+        options.speed,
+        length_unit,
+        depart.format("%a %H:%M")
+    );
+    for (stop, forecast) in stops.iter().zip(&forecasts) {
+        let line = row(stop, forecast.as_ref(), depart, units, config.icons);
+        println!("{}", line.trim_end());
+    }
+    Ok(())
+}
+
+/// Today at `at`, or tomorrow if that was over an hour ago; `now` when left
+/// out.
+fn departure(at: Option<NaiveTime>, now: NaiveDateTime) -> NaiveDateTime {
+    let Some(at) = at else {
+        return now;
+    };
+    let today = now.date().and_time(at);
+    if today < now - TimeDelta::hours(1) {
+        today + TimeDelta::days(1)
+    } else {
+        today
+    }
+}
+
+/// The hour whose forecast covers `arrival`, the nearest one.
+fn forecast_hour(arrival: DateTime<Utc>) -> NaiveDateTime {
+    arrival
+        .duration_round(TimeDelta::hours(1))
+        .unwrap_or(arrival)
+        .naive_utc()
+}
+
+/// Stops `every_km` apart along `track` from its start to its end, reached
+/// at `speed_kmh` from `depart`.
+fn stops(
+    track: &[GeoLocation],
+    every_km: f64,
+    speed_kmh: f64,
+    depart: DateTime<Local>,
+) -> Vec<Stop> {
+    let Some((first, last)) = track.first().zip(track.last()) else {
+        return Vec::new();
+    };
+    let total: f64 = track
+        .windows(2)
+        .map(|pair| distance_km(&pair[0], &pair[1]))
+        .sum();
+    let every = every_km.max(total / (MAX_STOPS - 1) as f64);
+
+    let mut points = vec![(0.0, first.clone())];
+    let (mut travelled, mut next) = (0.0, every);
+    for pair in track.windows(2) {
+        let length = distance_km(&pair[0], &pair[1]);
+        // Not so close to the end that the last two stops say the same
+        while next < travelled + length && next <= total - every / 2.0 {
+            let share = (next - travelled) / length;
+            points.push((next, between(&pair[0], &pair[1], share)));
+            next += every;
+        }
+        travelled += length;
+    }
+    if total > 0.0 {
+        points.push((total, last.clone()));
+    }
+
+    points
+        .into_iter()
+        .map(|(km, location)| Stop {
+            km,
+            location,
+            arrival: depart + TimeDelta::seconds((km / speed_kmh * 3600.0) as i64),
+        })
+        .collect()
+}
+
+/// The point `share` of the way from `a` to `b`; close enough over the few
+/// hundred metres between track points.
+fn between(a: &GeoLocation, b: &GeoLocation, share: f64) -> GeoLocation {
+    GeoLocation {
+        latitude: a.latitude + (b.latitude - a.latitude) * share,
+        longitude: a.longitude + (b.longitude - a.longitude) * share,
+        city: None,
+    }
+}
+
+fn distance_unit(units: &WeatherUnits) -> &'static str {
+    match units.distance {
+        DistanceUnit::Km => "km",
+        DistanceUnit::Mi => "mi",
+    }
+}
+
+/// Distance, arrival (with the weekday once it's another day), position,
+/// then the weather there.
+fn row(
+    stop: &Stop,
+    forecast: Option<&WeatherData>,
+    depart: DateTime<Local>,
+    units: &WeatherUnits,
+    icons: IconSet,
+) -> String {
+    let distance = match units.distance {
+        DistanceUnit::Km => stop.km,
+        DistanceUnit::Mi => stop.km / KM_PER_MILE,
+    };
+    let arrival = if stop.arrival.date_naive() == depart.date_naive() {
+        stop.arrival.format("%H:%M").to_string()
+    } else {
+        stop.arrival.format("%a %H:%M").to_string()
+    };
+    let position = format!(
+        "{:.2}, {:.2}",
+        stop.location.latitude, stop.location.longitude
+    );
+    let mut row = format!(
+        "{:>5.0} {}  {:<9}  {:<15}  ",
+        distance,
+        distance_unit(units),
+        arrival,
+        position
+    );
+    let Some(forecast) = forecast else {
+        row.push_str("no forecast");
+        return row;
+    };
+    let (temp, temp_unit) = format_temperature(forecast.temperature, units.temperature);
+    let (wind, wind_unit) = format_wind_speed(forecast.wind_speed, units.wind_speed);
+    let (rain, rain_unit) = format_precipitation(forecast.precipitation, units.precipitation);
+    row.push_str(&format!(
+        "{:<20}  {:>4}  {:>7}  {:>8}",
+        format!(
+            "{} {}",
+            forecast.condition.icon(icons),
+            forecast.condition.label()
+        ),
+        format!("{:.0}{}", temp, temp_unit),
+        format!("{:.1}{}", rain, rain_unit),
+        format!("{:.0}{}", wind, wind_unit)
+    ));
+    row
+}
+
+/// `<trkpt lat=".." lon="..">` points, else `<rtept>` ones.
+fn gpx_points(text: &str) -> Vec<GeoLocation> {
+    for tag in ["<trkpt", "<rtept"] {
+        let points: Vec<GeoLocation> = text
+            .split(tag)
+            .skip(1)
+            .filter_map(|rest| {
+                let attributes = rest.split('>').next()?;
+                point(
+                    attribute(attributes, "lat")?.parse().ok()?,
+                    attribute(attributes, "lon")?.parse().ok()?,
+                )
+            })
+            .collect();
+        if !points.is_empty() {
+            return points;
+        }
+    }
+    Vec::new()
+}
+
+/// The value of `name="..."` (or single-quoted) among a tag's attributes.
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let key = format!("{}=", name);
+    let (start, _) = attributes
+        .match_indices(&key)
+        .find(|(i, _)| attributes[..*i].ends_with(char::is_whitespace))?;
+    let value = &attributes[start + key.len()..];
+    let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    value[1..].split(quote).next()
+}
+
+/// The "lon,lat[,alt]" tuples of each `<LineString>`, else of every
+/// `<coordinates>` element, so a path given as a row of placemarks works
+/// too.
+fn kml_points(text: &str) -> Vec<GeoLocation> {
+    let coordinates = |section: &str| -> Vec<GeoLocation> {
+        let Some((_, rest)) = section.split_once("<coordinates>") else {
+            return Vec::new();
+        };
+        let body = rest.split("</coordinates>").next().unwrap_or_default();
+        body.split_whitespace()
+            .filter_map(|tuple| {
+                let mut values = tuple.split(',');
+                let longitude = values.next()?.parse().ok()?;
+                point(values.next()?.parse().ok()?, longitude)
+            })
+            .collect()
+    };
+    let lines: Vec<GeoLocation> = text
+        .split("<LineString")
+        .skip(1)
+        .flat_map(coordinates)
+        .collect();
+    if !lines.is_empty() {
+        return lines;
+    }
+    text.split("<Placemark")
+        .skip(1)
+        .flat_map(coordinates)
+        .collect()
+}
+
+fn point(latitude: f64, longitude: f64) -> Option<GeoLocation> {
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)).then_some(
+        GeoLocation {
+            latitude,
+            longitude,
+            city: None,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+    use crate::weather::types::CelestialEvents;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn local(s: &str) -> DateTime<Local> {
+        at(s).and_local_timezone(Local).earliest().unwrap()
+    }
+
+    #[test]
+    fn test_gpx_track() {
+        let gpx = r#"<?xml version="1.0"?>
+            <gpx version="1.1"><trk><trkseg>
+              <trkpt lat="52.5200" lon="13.4050"><ele>34</ele></trkpt>
+              <trkpt lon='13.7' lat='52.6'/>
+              <trkpt lat="95.0" lon="13.8"></trkpt>
+            </trkseg></trk>
+            <rte><rtept lat="1.0" lon="1.0"/></rte></gpx>"#;
+        let points = gpx_points(gpx);
+        assert_eq!(points.len(), 2);
+        assert_eq!((points[1].latitude, points[1].longitude), (52.6, 13.7));
+
+        let route =
+            r#"<gpx><rte><rtept lat="1.5" lon="-2.5"/><rtept lat="2" lon="-3"/></rte></gpx>"#;
+        assert_eq!(gpx_points(route)[0].longitude, -2.5);
+    }
+
+    #[test]
+    fn test_kml_path() {
+        let kml = r#"<kml><Document>
+            <Placemark><Point><coordinates>0.0,0.0</coordinates></Point></Placemark>
+            <Placemark><LineString><coordinates>
+              -0.1276,51.5072,0 -1.8904,52.4862,0
+              -2.2426,53.4808
+            </coordinates></LineString></Placemark>
+            </Document></kml>"#;
+        let points = kml_points(kml);
+        assert_eq!(points.len(), 3);
+        assert_eq!(
+            (points[0].latitude, points[0].longitude),
+            (51.5072, -0.1276)
+        );
+
+        let pins = r#"<kml><Placemark><Point><coordinates>13.4,52.5</coordinates></Point></Placemark>
+            <Placemark><Point><coordinates>11.6,48.1,500</coordinates></Point></Placemark></kml>"#;
+        assert_eq!(kml_points(pins).len(), 2);
+    }
+
+    #[test]
+    fn test_stops_along_the_track() {
+        // About 111 km due north, in two legs
+        let track = [
+            point(50.0, 10.0).unwrap(),
+            point(50.3, 10.0).unwrap(),
+            point(51.0, 10.0).unwrap(),
+        ];
+        let depart = local("2026-10-16 08:00");
+        let along = stops(&track, 25.0, 50.0, depart);
+
+        let kms: Vec<i64> = along.iter().map(|stop| stop.km.round() as i64).collect();
+        // No stop at 100 km, so close to the end
+        assert_eq!(kms, [0, 25, 50, 75, 111]);
+        assert!((along[1].location.latitude - 50.225).abs() < 0.01);
+        assert_eq!(along[2].arrival, local("2026-10-16 09:00"));
+        assert_eq!(along.last().unwrap().location.latitude, 51.0);
+
+        // A long route at a short spacing gets no more than the cap
+        assert_eq!(stops(&track, 0.1, 50.0, depart).len(), MAX_STOPS);
+    }
+
+    #[test]
+    fn test_departure() {
+        let now = at("2026-10-16 09:30");
+        let eight = NaiveTime::from_hms_opt(8, 0, 0);
+        assert_eq!(departure(None, now), now);
+        assert_eq!(departure(eight, now), at("2026-10-17 08:00"));
+        assert_eq!(
+            departure(eight, at("2026-10-16 08:45")),
+            at("2026-10-16 08:00")
+        );
+        assert_eq!(
+            departure(eight, at("2026-10-16 06:00")),
+            at("2026-10-16 08:00")
+        );
+
+        let arrival = at("2026-10-16 08:40").and_utc();
+        assert_eq!(forecast_hour(arrival), at("2026-10-16 09:00"));
+    }
+
+    #[test]
+    fn test_row() {
+        let depart = local("2026-10-16 20:00");
+        let stop = Stop {
+            km: 160.9344,
+            location: point(52.4862, -1.8904).unwrap(),
+            arrival: local("2026-10-17 00:30"),
+        };
+        let forecast = WeatherData {
+            temperature: 8.4,
+            precipitation: 1.2,
+            wind_speed: 5.0,
+            sun: CelestialEvents::from_bool(false),
+            timestamp: "2026-10-17T00:00".to_string(),
+            ..WeatherData::sample(WeatherCondition::Rain)
+        };
+
+        let units = WeatherUnits::metric();
+        assert_eq!(
+            row(&stop, Some(&forecast), depart, &units, IconSet::Unicode),
+            "  161 km  Sat 00:30  52.49, -1.89     ☂ Rain                 8°C    1.2mm    18km/h"
+        );
+        assert!(
+            row(
+                &stop,
+                None,
+                depart,
+                &WeatherUnits::imperial(),
+                IconSet::Unicode
+            )
+            .starts_with("  100 mi  Sat 00:30")
+        );
+        assert!(row(&stop, None, depart, &units, IconSet::Unicode).ends_with("no forecast"));
+    }
+}
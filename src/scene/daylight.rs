@@ -0,0 +1,54 @@
+//! How light the scene is. Day and night fade into each other over a few
+//! seconds of animation rather than switching between two frames.
+
+/// Seconds of animation a full fade from night to day takes.
+const FADE_SECS: f64 = 8.0;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Daylight {
+    level: f32,
+    updated: Option<f64>,
+}
+
+impl Daylight {
+    /// From 0 at night to 1 by day.
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Fades towards day or night up to `secs` of animation. The first
+    /// update starts out fully there.
+    pub fn update(&mut self, is_day: bool, secs: f64) {
+        let target = if is_day { 1.0 } else { 0.0 };
+        let Some(updated) = self.updated.replace(secs) else {
+            self.level = target;
+            return;
+        };
+        let step = ((secs - updated).max(0.0) / FADE_SECS) as f32;
+        self.level = if target > self.level {
+            (self.level + step).min(target)
+        } else {
+            (self.level - step).max(target)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_night_fades_in() {
+        let mut daylight = Daylight::default();
+        daylight.update(true, 10.0);
+        assert_eq!(daylight.level(), 1.0);
+
+        daylight.update(false, 14.0);
+        assert!((daylight.level() - 0.5).abs() < 1e-6);
+        // Paused, the animation clock stands still and so does the fade
+        daylight.update(false, 14.0);
+        assert!((daylight.level() - 0.5).abs() < 1e-6);
+        daylight.update(false, 30.0);
+        assert_eq!(daylight.level(), 0.0);
+    }
+}
@@ -0,0 +1,218 @@
+//! Dates the yard is decorated for: string lights on the house in late
+//! December, a pumpkin by the door in late October and fireworks at
+//! midnight on New Year's Eve, plus any dates of the user's own.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use serde::{Deserialize, Deserializer};
+
+/// Minutes fireworks go on for.
+const FIREWORKS_MINUTES: i64 = 30;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Decoration {
+    StringLights,
+    Pumpkin,
+    Fireworks,
+}
+
+/// A day of the year, written "MM-DD".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MonthDay {
+    month: u32,
+    day: u32,
+}
+
+impl MonthDay {
+    const fn new(month: u32, day: u32) -> Self {
+        Self { month, day }
+    }
+
+    fn of(date: NaiveDate) -> Self {
+        Self::new(date.month(), date.day())
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let (month, day) = text.trim().split_once('-')?;
+        let (month, day) = (month.parse().ok()?, day.parse().ok()?);
+        // A leap year, so "02-29" is a day too
+        NaiveDate::from_ymd_opt(2024, month, day)?;
+        Some(Self::new(month, day))
+    }
+}
+
+impl<'de> Deserialize<'de> for MonthDay {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Self::parse(&text).ok_or_else(|| {
+            serde::de::Error::custom(format!("invalid date {:?} (expected MM-DD)", text))
+        })
+    }
+}
+
+/// A decoration put up every year from one day to another.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Event {
+    pub decoration: Decoration,
+    pub from: MonthDay,
+    /// The last day, which may be in the next year; just `from` when left
+    /// out.
+    pub to: Option<MonthDay>,
+    /// When fireworks go off on each day, "HH:MM"; midnight when left out.
+    #[serde(default, deserialize_with = "deserialize_clock_time")]
+    pub at: Option<NaiveTime>,
+}
+
+fn deserialize_clock_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<NaiveTime>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    NaiveTime::parse_from_str(text.trim(), "%H:%M")
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("invalid time {:?} (expected HH:MM)", text)))
+}
+
+impl Event {
+    const fn new(decoration: Decoration, from: MonthDay, to: MonthDay) -> Self {
+        Self {
+            decoration,
+            from,
+            to: Some(to),
+            at: None,
+        }
+    }
+
+    fn covers(&self, date: NaiveDate) -> bool {
+        let day = MonthDay::of(date);
+        let to = self.to.unwrap_or(self.from);
+        if self.from <= to {
+            (self.from..=to).contains(&day)
+        } else {
+            day >= self.from || day <= to
+        }
+    }
+
+    fn is_up(&self, now: NaiveDateTime) -> bool {
+        if self.decoration != Decoration::Fireworks {
+            return self.covers(now.date());
+        }
+        // A show starting late in the evening runs past midnight
+        let at = self.at.unwrap_or(NaiveTime::MIN);
+        [now.date(), now.date() - TimeDelta::days(1)]
+            .into_iter()
+            .filter(|&date| self.covers(date))
+            .map(|date| date.and_time(at))
+            .any(|start| start <= now && now < start + TimeDelta::minutes(FIREWORKS_MINUTES))
+    }
+}
+
+const BUILTIN: [Event; 3] = [
+    Event::new(
+        Decoration::StringLights,
+        MonthDay::new(12, 15),
+        MonthDay::new(1, 6),
+    ),
+    Event::new(
+        Decoration::Pumpkin,
+        MonthDay::new(10, 20),
+        MonthDay::new(10, 31),
+    ),
+    Event::new(
+        Decoration::Fireworks,
+        MonthDay::new(1, 1),
+        MonthDay::new(1, 1),
+    ),
+];
+
+/// What's up in the yard at one moment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Holiday {
+    pub string_lights: bool,
+    pub pumpkin: bool,
+    pub fireworks: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Events {
+    events: Vec<Event>,
+}
+
+impl Events {
+    /// The built-in dates unless `builtin` is off, and `dates`.
+    pub fn new(builtin: bool, dates: &[Event]) -> Self {
+        let builtin = if builtin { &BUILTIN[..] } else { &[] };
+        Self {
+            events: builtin.iter().chain(dates).copied().collect(),
+        }
+    }
+
+    /// The decorations up at `now`, on the clock at the location.
+    pub fn at(&self, now: NaiveDateTime) -> Holiday {
+        let mut holiday = Holiday::default();
+        for event in self.events.iter().filter(|event| event.is_up(now)) {
+            match event.decoration {
+                Decoration::StringLights => holiday.string_lights = true,
+                Decoration::Pumpkin => holiday.pumpkin = true,
+                Decoration::Fireworks => holiday.fireworks = true,
+            }
+        }
+        holiday
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_builtin_dates() {
+        let events = Events::new(true, &[]);
+        assert_eq!(events.at(at("2026-07-01 12:00")), Holiday::default());
+        assert!(events.at(at("2026-10-25 09:00")).pumpkin);
+        assert!(!events.at(at("2026-11-01 09:00")).pumpkin);
+        assert!(events.at(at("2026-12-24 20:00")).string_lights);
+        assert!(events.at(at("2027-01-03 20:00")).string_lights);
+
+        // Fireworks only for the first half hour of the new year
+        assert!(!events.at(at("2026-12-31 23:59")).fireworks);
+        assert!(events.at(at("2027-01-01 00:10")).fireworks);
+        assert!(!events.at(at("2027-01-01 00:30")).fireworks);
+
+        assert_eq!(
+            Events::new(false, &[]).at(at("2026-12-24 20:00")),
+            Holiday::default()
+        );
+    }
+
+    #[test]
+    fn test_own_dates() {
+        #[derive(Deserialize)]
+        struct Section {
+            dates: Vec<Event>,
+        }
+        let section: Section = toml::from_str(
+            r#"
+            dates = [
+                { decoration = "fireworks", from = "11-05", at = "23:45" },
+                { decoration = "string_lights", from = "02-29" },
+            ]
+            "#,
+        )
+        .unwrap();
+        let events = Events::new(false, &section.dates);
+
+        assert!(!events.at(at("2026-11-05 23:40")).fireworks);
+        assert!(events.at(at("2026-11-05 23:50")).fireworks);
+        assert!(events.at(at("2026-11-06 00:10")).fireworks);
+        assert!(!events.at(at("2026-11-06 00:15")).fireworks);
+        assert!(events.at(at("2028-02-29 12:00")).string_lights);
+
+        assert!(MonthDay::parse("02-30").is_none());
+        assert!(MonthDay::parse("1231").is_none());
+    }
+}
@@ -1,21 +1,52 @@
+pub mod daylight;
+pub mod events;
 pub mod overlay;
+pub mod season;
+pub mod wetness;
 pub mod world;
 
 use crate::render::TerminalRenderer;
 use crate::theme::Palette;
-use crate::weather::WeatherConditions;
+use crate::weather::marine::MarineConditions;
+use crate::weather::severity::Severity;
+use crate::weather::{WeatherConditions, WeatherData, WeatherUnits};
+use chrono::{DateTime, FixedOffset};
+use events::Holiday;
+use season::Season;
 use std::collections::HashMap;
 use std::io;
 
 pub struct SceneContext<'a> {
     pub conditions: &'a WeatherConditions,
     pub palette: &'a Palette,
+    pub weather: Option<&'a WeatherData>,
+    pub units: WeatherUnits,
+    pub tree_charred: bool,
+    /// Waves and tide, for a place by the sea.
+    pub marine: Option<&'a MarineConditions>,
+    /// The time at the location.
+    pub now: DateTime<FixedOffset>,
+    pub season: Season,
+    /// How wet the ground is, from 0 to 1.
+    pub wetness: f32,
+    /// How light it is, from 0 at night to 1 by day, fading between the two.
+    pub daylight: f32,
+    /// Severe weather under storm watch, which darkens the scene.
+    pub storm_watch: Option<Severity>,
+    /// Decorations up for the date.
+    pub holiday: Holiday,
+    /// Seconds the animations have played, which stand still while paused.
+    pub animation_secs: f64,
 }
 
 #[derive(Clone, Copy)]
 pub struct SceneLayout {
     pub ground_y: u16,
     pub chimney_pos: Option<ChimneyPosition>,
+    /// Where a window air conditioner hangs, if the scene has one.
+    pub ac_unit_pos: Option<ChimneyPosition>,
+    /// Top of the yard tree, for lightning to aim at.
+    pub tree_pos: Option<ChimneyPosition>,
     pub width: u16,
     pub height: u16,
 }
@@ -27,14 +58,14 @@ pub struct ChimneyPosition {
 }
 
 pub trait Scene: Send + Sync {
-    fn id(&self) -> &'static str;
+    fn id(&self) -> &str;
     fn update_size(&mut self, width: u16, height: u16);
     fn render(&self, renderer: &mut TerminalRenderer, ctx: &SceneContext<'_>) -> io::Result<()>;
     fn layout(&self) -> SceneLayout;
 }
 
 pub struct SceneRegistry {
-    scenes: HashMap<&'static str, Box<dyn Scene>>,
+    scenes: HashMap<String, Box<dyn Scene>>,
 }
 
 impl SceneRegistry {
@@ -45,7 +76,14 @@ impl SceneRegistry {
     }
 
     pub fn register(&mut self, scene: Box<dyn Scene>) {
-        self.scenes.insert(scene.id(), scene);
+        self.scenes.insert(scene.id().to_string(), scene);
+    }
+
+    /// Registered scene ids, sorted.
+    pub fn ids(&self) -> Vec<&str> {
+        let mut ids: Vec<_> = self.scenes.keys().map(String::as_str).collect();
+        ids.sort_unstable();
+        ids
     }
 
     pub fn get(&self, id: &str) -> Option<&dyn Scene> {
@@ -0,0 +1,64 @@
+//! Meteorological seasons, which the scene dresses the yard for.
+
+use chrono::{Datelike, NaiveDate};
+
+/// Nearer the equator than this the year has no real seasons, and the tree
+/// stays green.
+const TROPICS_LATITUDE: f64 = 23.44;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// The season on `date` at `latitude`, by whole months: spring is March
+    /// to May in the north and September to November in the south.
+    pub fn at(latitude: f64, date: NaiveDate) -> Self {
+        if latitude.abs() < TROPICS_LATITUDE {
+            return Self::Summer;
+        }
+        let month = if latitude < 0.0 {
+            (date.month() + 5) % 12 + 1
+        } else {
+            date.month()
+        };
+        match month {
+            3..=5 => Self::Spring,
+            6..=8 => Self::Summer,
+            9..=11 => Self::Autumn,
+            _ => Self::Winter,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, 15).unwrap()
+    }
+
+    #[test]
+    fn test_hemispheres_are_opposite() {
+        let berlin = 52.5;
+        let sydney = -33.9;
+        assert_eq!(Season::at(berlin, date(4)), Season::Spring);
+        assert_eq!(Season::at(berlin, date(12)), Season::Winter);
+        assert_eq!(Season::at(berlin, date(2)), Season::Winter);
+        assert_eq!(Season::at(sydney, date(4)), Season::Autumn);
+        assert_eq!(Season::at(sydney, date(7)), Season::Winter);
+        assert_eq!(Season::at(sydney, date(12)), Season::Summer);
+        assert_eq!(Season::at(sydney, date(10)), Season::Spring);
+    }
+
+    #[test]
+    fn test_tropics_stay_green() {
+        assert_eq!(Season::at(1.35, date(1)), Season::Summer);
+        assert_eq!(Season::at(-6.2, date(7)), Season::Summer);
+    }
+}
@@ -0,0 +1,92 @@
+//! How wet the ground is. Rain soaks it over some minutes, harder rain
+//! faster and deeper, and once it stops the puddles take hours to dry.
+
+use crate::weather::RainIntensity;
+use std::time::Instant;
+
+/// Hours a soaked yard takes to dry out.
+const DRYING_HOURS: f32 = 3.0;
+
+/// How wet `rain` can make the ground, and the minutes it takes to get
+/// there from dry.
+fn soaking(rain: RainIntensity) -> (f32, f32) {
+    match rain {
+        RainIntensity::Drizzle => (0.4, 40.0),
+        RainIntensity::Light => (0.7, 25.0),
+        RainIntensity::Heavy => (1.0, 12.0),
+        RainIntensity::Storm => (1.0, 6.0),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Wetness {
+    level: f32,
+    updated: Option<Instant>,
+}
+
+impl Wetness {
+    /// From 0 for dry ground to 1 for soaked.
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Brings the ground up to `now`, with `rain` falling since the last
+    /// update or `None` while it's dry. Rain already falling at the first
+    /// update has soaked the ground as far as it will.
+    pub fn update(&mut self, rain: Option<RainIntensity>, now: Instant) {
+        let Some(updated) = self.updated.replace(now) else {
+            self.level = rain.map_or(0.0, |rain| soaking(rain).0);
+            return;
+        };
+        let minutes = now.saturating_duration_since(updated).as_secs_f32() / 60.0;
+        let drying = minutes / (DRYING_HOURS * 60.0);
+        self.level = match rain.map(soaking) {
+            Some((most, soak_minutes)) if self.level < most => {
+                (self.level + minutes / soak_minutes).min(most)
+            }
+            // Lighter rain than before lets the ground dry back to what it keeps wet
+            Some((most, _)) => (self.level - drying).max(most),
+            None => (self.level - drying).max(0.0),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn minutes(n: u64) -> Duration {
+        Duration::from_secs(n * 60)
+    }
+
+    #[test]
+    fn test_rain_soaks_and_the_sun_dries() {
+        let start = Instant::now();
+        let mut wetness = Wetness::default();
+        wetness.update(None, start);
+        assert_eq!(wetness.level(), 0.0);
+
+        wetness.update(Some(RainIntensity::Heavy), start + minutes(6));
+        assert!((wetness.level() - 0.5).abs() < 1e-3);
+        wetness.update(Some(RainIntensity::Heavy), start + minutes(30));
+        assert_eq!(wetness.level(), 1.0);
+
+        wetness.update(None, start + minutes(120));
+        assert!((wetness.level() - 0.5).abs() < 1e-3);
+        wetness.update(None, start + minutes(300));
+        assert_eq!(wetness.level(), 0.0);
+    }
+
+    #[test]
+    fn test_drizzle_only_dampens() {
+        let start = Instant::now();
+        let mut wetness = Wetness::default();
+        wetness.update(Some(RainIntensity::Storm), start);
+        assert_eq!(wetness.level(), 1.0);
+
+        // Drizzle after a storm keeps the ground as wet as drizzle would
+        wetness.update(Some(RainIntensity::Drizzle), start + minutes(600));
+        assert_eq!(wetness.level(), 0.4);
+    }
+}
@@ -0,0 +1,360 @@
+//! Houses drawn from the user's own ASCII art. Each lives in a directory
+//! under `scenes/` in the config dir, named for the scene, holding the art
+//! and a `scene.toml` manifest:
+//!
+//! ```toml
+//! art = "house.txt"          # the default
+//! chimney = { x = 12, y = 0 } # smoke rises from here
+//! ac_unit = { x = 26, y = 6 } # drips in the heat
+//!
+//! [colors]
+//! "/" = "roof"
+//! "#" = "window"
+//! "*" = "#ff8800"
+//! ```
+//!
+//! Anchors count columns and rows from the top left of the art. A color is
+//! a part of the house, taking that part's day and night colors from the
+//! theme, or a fixed color. Spaces are see-through, and characters without
+//! a color are drawn as wood. A run of window characters along a row is one
+//! window, whose lights go out at bedtime as the built-in house's do.
+
+use crate::render::TerminalRenderer;
+use crate::scene::world::snow_line;
+use crate::scene::world::style::WorldSceneStyle;
+use crossterm::style::Color;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const MANIFEST: &str = "scene.toml";
+const DEFAULT_ART: &str = "house.txt";
+/// Larger art would crowd out the yard, sky and HUD.
+const MAX_WIDTH: usize = 120;
+const MAX_HEIGHT: usize = 24;
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Manifest {
+    art: String,
+    chimney: Option<Anchor>,
+    ac_unit: Option<Anchor>,
+    colors: HashMap<String, String>,
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Self {
+            art: DEFAULT_ART.to_string(),
+            chimney: None,
+            ac_unit: None,
+            colors: HashMap::new(),
+        }
+    }
+}
+
+/// A point on the art, from its top left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Anchor {
+    pub x: u16,
+    pub y: u16,
+}
+
+/// A part of the house, colored as the built-in house's is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Roof,
+    Wood,
+    Door,
+    Window,
+    Trim,
+    Grass,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Paint {
+    Part(Part),
+    Fixed(Color),
+}
+
+impl Paint {
+    fn parse(name: &str) -> Option<Self> {
+        let part = match name {
+            "roof" => Part::Roof,
+            "wood" => Part::Wood,
+            "door" => Part::Door,
+            "window" => Part::Window,
+            "trim" => Part::Trim,
+            "grass" => Part::Grass,
+            _ => return parse_color(name).map(Self::Fixed),
+        };
+        Some(Self::Part(part))
+    }
+
+    fn color(self, style: &WorldSceneStyle) -> Color {
+        match self {
+            Self::Part(Part::Roof) => style.roof,
+            Self::Part(Part::Wood) => style.wood,
+            Self::Part(Part::Door) => style.door,
+            Self::Part(Part::Window) => style.window,
+            Self::Part(Part::Trim) => style.trim,
+            Self::Part(Part::Grass) => style.grass_primary,
+            Self::Fixed(color) => color,
+        }
+    }
+}
+
+/// A color name such as "dark_grey", or "#rrggbb".
+fn parse_color(name: &str) -> Option<Color> {
+    if let Some(hex) = name.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return Some(Color::Rgb {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        });
+    }
+    Color::try_from(name).ok()
+}
+
+/// The user's house, standing in for the built-in one.
+#[derive(Debug)]
+pub struct CustomHouse {
+    lines: Vec<Vec<char>>,
+    width: u16,
+    chimney: Option<Anchor>,
+    ac_unit: Option<Anchor>,
+    colors: HashMap<char, Paint>,
+}
+
+impl CustomHouse {
+    /// Reads the scene in `dir`; the error says what's wrong with it.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let read = |path: PathBuf| {
+            fs::read_to_string(&path).map_err(|e| format!("can't read {}: {}", path.display(), e))
+        };
+        let manifest = read(dir.join(MANIFEST))?;
+        let manifest: Manifest =
+            toml::from_str(&manifest).map_err(|e| format!("invalid {}: {}", MANIFEST, e))?;
+        let art = read(dir.join(&manifest.art))?;
+        Self::new(manifest, &art)
+    }
+
+    fn new(manifest: Manifest, art: &str) -> Result<Self, String> {
+        let mut lines: Vec<Vec<char>> = art
+            .lines()
+            .map(|line| line.trim_end().chars().collect())
+            .collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+        if lines.is_empty() || width == 0 {
+            return Err(format!("{} is empty", manifest.art));
+        }
+        if width > MAX_WIDTH || lines.len() > MAX_HEIGHT {
+            return Err(format!(
+                "{} is {}x{}, larger than {}x{}",
+                manifest.art,
+                width,
+                lines.len(),
+                MAX_WIDTH,
+                MAX_HEIGHT
+            ));
+        }
+
+        for (name, anchor) in [("chimney", manifest.chimney), ("ac_unit", manifest.ac_unit)] {
+            if let Some(anchor) = anchor
+                && (anchor.x as usize >= width || anchor.y as usize >= lines.len())
+            {
+                return Err(format!(
+                    "{} ({}, {}) is outside the art",
+                    name, anchor.x, anchor.y
+                ));
+            }
+        }
+
+        let mut colors = HashMap::new();
+        for (key, name) in &manifest.colors {
+            let mut chars = key.chars();
+            let (Some(ch), None) = (chars.next(), chars.next()) else {
+                return Err(format!("color key {:?} must be a single character", key));
+            };
+            let paint = Paint::parse(name).ok_or_else(|| format!("unknown color {:?}", name))?;
+            colors.insert(ch, paint);
+        }
+
+        Ok(Self {
+            width: width as u16,
+            lines,
+            chimney: manifest.chimney,
+            ac_unit: manifest.ac_unit,
+            colors,
+        })
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.lines.len() as u16
+    }
+
+    pub fn chimney(&self) -> Option<Anchor> {
+        self.chimney
+    }
+
+    pub fn ac_unit(&self) -> Option<Anchor> {
+        self.ac_unit
+    }
+
+    /// Each run of window-colored characters along a row is one window,
+    /// numbered from the top left, and dark unless `lit` says otherwise.
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        y: u16,
+        lit: impl Fn(u32) -> bool,
+        style: &WorldSceneStyle,
+    ) -> io::Result<()> {
+        let mut window_index = 0;
+        for (row, line) in (y..).zip(&self.lines) {
+            let mut window_color = None;
+            for (column, &ch) in (x..).zip(line) {
+                let paint = self.colors.get(&ch).copied();
+                if paint != Some(Paint::Part(Part::Window)) {
+                    window_color = None;
+                } else if window_color.is_none() {
+                    let on = lit(window_index);
+                    window_color = Some(if on { style.window } else { style.window_dark });
+                    window_index += 1;
+                }
+                if ch == ' ' {
+                    continue;
+                }
+                let color = match (window_color, paint) {
+                    (Some(color), _) => color,
+                    (None, Some(paint)) => paint.color(style),
+                    (None, None) => style.wood,
+                };
+                renderer.render_char(column, row, ch, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whatever is topmost, where snow settles and string lights hang.
+    pub fn roof_line(&self) -> Vec<(u16, u16, char)> {
+        snow_line(self.lines.iter().map(|line| line.iter().copied()))
+    }
+
+    /// Snow on whatever is topmost.
+    pub fn render_snow(
+        &self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        y: u16,
+        color: Color,
+    ) -> io::Result<()> {
+        for (column, row, ch) in self.roof_line() {
+            renderer.render_char(x + column, y + row, ch, color)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every scene directory under `dir` with a manifest, by name, with its
+/// house or what's wrong with it.
+pub fn load_all(dir: &Path) -> Vec<(String, Result<CustomHouse, String>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut scenes: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(MANIFEST).is_file())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            Some((name, CustomHouse::load(&path)))
+        })
+        .collect();
+    scenes.sort_by(|a, b| a.0.cmp(&b.0));
+    scenes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ART: &str = "  _||_\n /    \\\n |[]  |\n";
+
+    fn manifest(toml: &str) -> Manifest {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_art_and_manifest() {
+        let house = CustomHouse::new(
+            manifest(
+                r##"
+chimney = { x = 3, y = 0 }
+
+[colors]
+"[" = "window"
+"]" = "window"
+"|" = "#ff8800"
+"##,
+            ),
+            ART,
+        )
+        .unwrap();
+        assert_eq!((house.width(), house.height()), (7, 3));
+        assert_eq!(house.chimney(), Some(Anchor { x: 3, y: 0 }));
+        assert_eq!(house.ac_unit(), None);
+        assert_eq!(house.colors[&'['], Paint::Part(Part::Window));
+        assert_eq!(
+            house.colors[&'|'],
+            Paint::Fixed(Color::Rgb {
+                r: 255,
+                g: 136,
+                b: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_bad_manifests_are_explained() {
+        let error = |toml: &str| CustomHouse::new(manifest(toml), ART).unwrap_err();
+        assert_eq!(
+            error("chimney = { x = 9, y = 0 }"),
+            "chimney (9, 0) is outside the art"
+        );
+        assert_eq!(
+            error("[colors]\n\"ab\" = \"roof\""),
+            "color key \"ab\" must be a single character"
+        );
+        assert_eq!(
+            error("[colors]\n\"/\" = \"plaid\""),
+            "unknown color \"plaid\""
+        );
+        assert_eq!(
+            CustomHouse::new(Manifest::default(), "\n  \n").unwrap_err(),
+            "house.txt is empty"
+        );
+    }
+
+    #[test]
+    fn test_color_names() {
+        assert_eq!(parse_color("dark_grey"), Some(Color::DarkGrey));
+        assert_eq!(parse_color("#00ff0"), None);
+        assert_eq!(Paint::parse("trim"), Some(Paint::Part(Part::Trim)));
+    }
+}
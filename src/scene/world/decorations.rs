@@ -1,33 +1,120 @@
-use crate::render::TerminalRenderer;
+use crate::config::DecorationsConfig;
+use crate::render::{TerminalRenderer, display_width};
+use crate::scene::season::Season;
 use crate::scene::world::style::WorldSceneStyle;
+use crate::weather::{WeatherData, WeatherUnits, format_temperature};
+use crossterm::style::Color;
 use std::io;
 
 const TREE_ASCII: &str = include_str!("assets/tree.txt");
+/// The same tree in winter, no taller.
+const BARE_TREE_ASCII: &str = include_str!("assets/tree_bare.txt");
 const FENCE_ASCII: &str = include_str!("assets/fence.txt");
 const MAILBOX_ASCII: &str = include_str!("assets/mailbox.txt");
 const PINE_TREE_ASCII: &str = include_str!("assets/pine_tree.txt");
+const STREET_LAMP_ASCII: &str = include_str!("assets/street_lamp.txt");
+const ROAD_SIGN_ASCII: &str = include_str!("assets/road_sign.txt");
 
-pub struct Decorations;
+/// Width of the text panel inside `ROAD_SIGN_ASCII`.
+const ROAD_SIGN_PANEL: usize = 5;
+
+/// Roads are treated as icy at or below this temperature when wet.
+const ICE_THRESHOLD_C: f64 = 1.0;
+
+/// Column of the lamp head within `STREET_LAMP_ASCII`, and its row.
+const LAMP_HEAD: (u16, u16) = (1, 1);
+
+pub struct Decorations {
+    config: DecorationsConfig,
+}
+
+/// Roadside temperature display, flashing a snowflake when the road may be icy.
+pub struct RoadSign {
+    pub text: String,
+    pub icy: bool,
+}
+
+impl RoadSign {
+    pub fn from_weather(weather: &WeatherData, units: WeatherUnits) -> Self {
+        let (temp, unit) = format_temperature(weather.temperature, units.temperature);
+        Self {
+            text: format!("{:.0}{}", temp, unit),
+            icy: road_is_icy(weather),
+        }
+    }
+}
+
+fn road_is_icy(weather: &WeatherData) -> bool {
+    let wet = weather.precipitation > 0.0
+        || weather.condition.is_raining()
+        || weather.condition.is_snowing();
+    weather.temperature <= ICE_THRESHOLD_C && wet
+}
 
 pub struct DecorationLayout {
     pub horizon_y: u16,
     pub house_x: u16,
     pub house_width: u16,
     pub width: u16,
+    /// What the tree wears.
+    pub season: Season,
+    /// Seconds the animations have played, for the smoke and the sign.
+    pub animation_secs: f64,
+}
+
+/// `x` shifted by `offset` columns, or `None` past the left edge.
+fn nudge(x: u16, offset: i16) -> Option<u16> {
+    x.checked_add_signed(offset)
 }
 
 impl Decorations {
+    pub fn new(config: DecorationsConfig) -> Self {
+        Self { config }
+    }
+
+    /// Columns the fence is moved by, so what's mounted on it can follow.
+    pub fn fence_offset(&self) -> i16 {
+        self.config.offsets.fence
+    }
+
+    fn tree_x(&self, house_x: u16) -> Option<u16> {
+        let tree_x = house_x.saturating_sub(20);
+        if !self.config.tree || tree_x == 0 {
+            return None;
+        }
+        nudge(tree_x, self.config.offsets.tree)
+    }
+
+    /// Column of the tree's crown and its height above the ground, or `None`
+    /// when the tree is turned off or there's no room for it.
+    pub fn tree_top(&self, house_x: u16) -> Option<(u16, u16)> {
+        let tree_x = self.tree_x(house_x)?;
+        let width = TREE_ASCII.lines().map(|l| l.len()).max().unwrap_or(0) as u16;
+        Some((tree_x + width / 2, TREE_ASCII.lines().count() as u16))
+    }
+
     pub fn render(
         &self,
         renderer: &mut TerminalRenderer,
         layout: &DecorationLayout,
+        sign: Option<&RoadSign>,
         style: &WorldSceneStyle,
     ) -> io::Result<()> {
         self.render_tree(renderer, layout, style)?;
-        self.render_fence(renderer, layout, style)?;
-        self.render_mailbox(renderer, layout, style)?;
+        if self.config.fence {
+            self.render_fence(renderer, layout, style)?;
+        }
+        if self.config.mailbox {
+            self.render_mailbox(renderer, layout, style)?;
+        }
+        if self.config.street_lamps {
+            self.render_street_lamps(renderer, layout, style)?;
+        }
+        if let Some(sign) = sign.filter(|_| self.config.road_sign) {
+            self.render_road_sign(renderer, layout, sign, style)?;
+        }
 
-        if layout.width > 120 {
+        if self.config.pine_tree && layout.width > 120 {
             self.render_pine_tree(renderer, layout, style)?;
         }
 
@@ -40,13 +127,32 @@ impl Decorations {
         layout: &DecorationLayout,
         style: &WorldSceneStyle,
     ) -> io::Result<()> {
-        let tree_x = layout.house_x.saturating_sub(20);
-        if tree_x == 0 {
+        let Some(tree_x) = self.tree_x(layout.house_x) else {
             return Ok(());
-        }
+        };
         let line_count = TREE_ASCII.lines().count() as u16;
         let tree_y = layout.horizon_y.saturating_sub(line_count);
-        render_art(renderer, TREE_ASCII, tree_x, tree_y, style.tree_foliage)
+        if layout.season == Season::Winter {
+            render_art(renderer, BARE_TREE_ASCII, tree_x, tree_y, style.bark)?;
+        } else {
+            render_leafy_tree(renderer, tree_x, tree_y, layout.season, style)?;
+        }
+
+        if let (Some(smoke), Some((crown_x, _))) = (style.tree_smoke, self.tree_top(layout.house_x))
+        {
+            // A thin wisp that drifts from side to side
+            let tick = (layout.animation_secs / 0.4) as u64;
+            for row in 1..=3u16 {
+                let Some(y) = tree_y.checked_sub(row) else {
+                    break;
+                };
+                let sway = ((tick + row as u64) % 3) as u16;
+                let ch = if row == 1 { '~' } else { '.' };
+                renderer.render_char((crown_x + sway).saturating_sub(1), y, ch, smoke)?;
+            }
+        }
+
+        Ok(())
     }
 
     fn render_fence(
@@ -55,10 +161,13 @@ impl Decorations {
         layout: &DecorationLayout,
         style: &WorldSceneStyle,
     ) -> io::Result<()> {
-        let fence_x = layout.house_x + layout.house_width + 2;
-        if fence_x >= layout.width {
+        let fence_x = nudge(
+            layout.house_x + layout.house_width + 2,
+            self.config.offsets.fence,
+        );
+        let Some(fence_x) = fence_x.filter(|&x| x < layout.width) else {
             return Ok(());
-        }
+        };
         let line_count = FENCE_ASCII.lines().count() as u16;
         let fence_y = layout.horizon_y.saturating_sub(line_count);
         render_art(renderer, FENCE_ASCII, fence_x, fence_y, style.fence)
@@ -70,28 +179,109 @@ impl Decorations {
         layout: &DecorationLayout,
         style: &WorldSceneStyle,
     ) -> io::Result<()> {
-        let tree_x = layout.house_x.saturating_sub(20);
-        let Some(mailbox_x) = tree_x.checked_sub(10) else {
+        // Ten columns left of where the tree stands by default
+        let mailbox_x = layout
+            .house_x
+            .saturating_sub(20)
+            .checked_sub(10)
+            .and_then(|x| nudge(x, self.config.offsets.mailbox));
+        let Some(mailbox_x) = mailbox_x.filter(|&x| x < layout.width) else {
             return Ok(());
         };
-        if mailbox_x >= layout.width {
-            return Ok(());
-        }
         let line_count = MAILBOX_ASCII.lines().count() as u16;
         let mailbox_y = layout.horizon_y.saturating_sub(line_count);
         render_art(renderer, MAILBOX_ASCII, mailbox_x, mailbox_y, style.mailbox)
     }
 
-    fn render_pine_tree(
+    fn render_road_sign(
         &self,
         renderer: &mut TerminalRenderer,
         layout: &DecorationLayout,
+        sign: &RoadSign,
         style: &WorldSceneStyle,
     ) -> io::Result<()> {
-        let pine_x = layout.house_x + layout.house_width + 18;
-        if pine_x + 10 >= layout.width {
+        // Stands to the left of the mailbox
+        let sign_x = layout
+            .house_x
+            .checked_sub(40)
+            .and_then(|x| nudge(x, self.config.offsets.road_sign));
+        let Some(sign_x) = sign_x else {
             return Ok(());
+        };
+        let line_count = ROAD_SIGN_ASCII.lines().count() as u16;
+        let sign_y = layout.horizon_y.saturating_sub(line_count);
+        render_art(renderer, ROAD_SIGN_ASCII, sign_x, sign_y, style.sign)?;
+
+        let text: String = sign.text.chars().take(ROAD_SIGN_PANEL).collect();
+        let pad = ROAD_SIGN_PANEL.saturating_sub(display_width(&text)) / 2;
+        let text_color = if sign.icy { Color::Cyan } else { Color::Yellow };
+        renderer.render_line_colored(sign_x + 1 + pad as u16, sign_y + 1, &text, text_color)?;
+
+        if sign.icy && sign_flash_on(layout.animation_secs) {
+            renderer.render_char(sign_x + 3, sign_y, '*', Color::White)?;
+        }
+
+        Ok(())
+    }
+
+    fn render_street_lamps(
+        &self,
+        renderer: &mut TerminalRenderer,
+        layout: &DecorationLayout,
+        style: &WorldSceneStyle,
+    ) -> io::Result<()> {
+        let line_count = STREET_LAMP_ASCII.lines().count() as u16;
+        let lamp_y = layout.horizon_y.saturating_sub(line_count);
+
+        // One between the tree and the house, one between the house and fence.
+        let positions = [
+            layout.house_x.checked_sub(4),
+            Some(layout.house_x + layout.house_width),
+        ];
+        let offset = self.config.offsets.street_lamps;
+        let positions = positions
+            .into_iter()
+            .flatten()
+            .filter_map(|x| nudge(x, offset))
+            .filter(|x| x + 3 < layout.width);
+
+        for lamp_x in positions {
+            render_art(renderer, STREET_LAMP_ASCII, lamp_x, lamp_y, style.lamp_post)?;
+
+            let Some(light) = style.lamp_light else {
+                continue;
+            };
+            let head_x = lamp_x + LAMP_HEAD.0;
+            let head_y = lamp_y + LAMP_HEAD.1;
+            renderer.render_char(head_x, head_y, '@', light)?;
+
+            // Warm halo around the head
+            let halo = Color::DarkYellow;
+            if let Some(left) = head_x.checked_sub(2) {
+                renderer.render_char(left, head_y, '·', halo)?;
+            }
+            renderer.render_char(head_x + 2, head_y, '·', halo)?;
+            if let Some(above) = head_y.checked_sub(2) {
+                renderer.render_line_colored(head_x.saturating_sub(1), above, ". .", halo)?;
+            }
         }
+
+        Ok(())
+    }
+
+    fn render_pine_tree(
+        &self,
+        renderer: &mut TerminalRenderer,
+        layout: &DecorationLayout,
+        style: &WorldSceneStyle,
+    ) -> io::Result<()> {
+        let pine_x = nudge(
+            layout.house_x + layout.house_width + 18,
+            self.config.offsets.pine_tree,
+        );
+        let Some(pine_x) = pine_x.filter(|x| x + 10 < layout.width) else {
+            return Ok(());
+        };
         let line_count = PINE_TREE_ASCII.lines().count() as u16;
         let pine_y = layout.horizon_y.saturating_sub(line_count);
         render_art(
@@ -104,12 +294,49 @@ impl Decorations {
     }
 }
 
+/// Green in summer, in blossom in spring and turning in autumn.
+fn render_leafy_tree(
+    renderer: &mut TerminalRenderer,
+    x: u16,
+    y: u16,
+    season: Season,
+    style: &WorldSceneStyle,
+) -> io::Result<()> {
+    for (row, line) in (0..).zip(TREE_ASCII.lines()) {
+        for (column, ch) in (0..).zip(line.chars()) {
+            let (ch, color) = match (ch, season) {
+                (' ', _) => continue,
+                ('_' | '|', _) => (ch, style.bark),
+                (_, Season::Spring) if (row + column) % 3 == 0 => ('*', style.blossom),
+                (_, Season::Spring) => (ch, style.blossom),
+                (_, Season::Autumn) => {
+                    // Mostly orange, scattered with red and yellow
+                    let leaf = match (row * 31 + column * 17) % 7 {
+                        0..=3 => 0,
+                        4 | 5 => 1,
+                        _ => 2,
+                    };
+                    (ch, style.autumn_leaves[leaf])
+                }
+                _ => (ch, style.tree_foliage),
+            };
+            renderer.render_char(x + column, y + row, ch, color)?;
+        }
+    }
+    Ok(())
+}
+
+/// The icy-road snowflake blinks every half second of the animation.
+fn sign_flash_on(animation_secs: f64) -> bool {
+    (animation_secs / 0.5) as u64 % 2 == 0
+}
+
 fn render_art(
     renderer: &mut TerminalRenderer,
     ascii: &str,
     x: u16,
     y: u16,
-    color: crossterm::style::Color,
+    color: Color,
 ) -> io::Result<()> {
     for (i, line) in ascii.lines().enumerate() {
         for (j, ch) in line.chars().enumerate() {
@@ -120,3 +347,59 @@ fn render_art(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    fn weather(condition: WeatherCondition, temperature: f64, precipitation: f64) -> WeatherData {
+        WeatherData {
+            temperature,
+            precipitation,
+            ..WeatherData::sample(condition)
+        }
+    }
+
+    #[test]
+    fn test_road_icy_only_when_cold_and_wet() {
+        assert!(road_is_icy(&weather(WeatherCondition::Snow, -3.0, 0.0)));
+        assert!(road_is_icy(&weather(WeatherCondition::Overcast, 0.5, 0.2)));
+        assert!(!road_is_icy(&weather(WeatherCondition::Clear, -8.0, 0.0)));
+        assert!(!road_is_icy(&weather(WeatherCondition::Rain, 6.0, 2.0)));
+    }
+
+    #[test]
+    fn test_tree_can_be_moved_or_removed() {
+        let mut config = DecorationsConfig::default();
+        let crown_x = |config| Decorations::new(config).tree_top(40).map(|(x, _)| x);
+        let default_x = crown_x(config).unwrap();
+
+        config.offsets.tree = -5;
+        assert_eq!(crown_x(config), Some(default_x - 5));
+
+        // Pushed off the left edge
+        config.offsets.tree = -30;
+        assert_eq!(crown_x(config), None);
+
+        config.offsets.tree = 0;
+        config.tree = false;
+        assert_eq!(crown_x(config), None);
+    }
+
+    #[test]
+    fn test_road_sign_uses_display_units() {
+        let sign = RoadSign::from_weather(
+            &weather(WeatherCondition::Clear, 0.0, 0.0),
+            WeatherUnits::imperial(),
+        );
+        assert_eq!(sign.text, "32°F");
+    }
+
+    #[test]
+    fn test_sign_flashes_on_the_animation_clock() {
+        assert!(sign_flash_on(0.0));
+        assert!(!sign_flash_on(0.7));
+        assert!(sign_flash_on(1.2));
+    }
+}
@@ -1,47 +1,214 @@
+use crate::config::{GroundConfig, GroundTexture};
 use crate::render::TerminalRenderer;
+use crate::scene::SceneContext;
 use crate::scene::world::style::WorldSceneStyle;
+use crate::weather::marine::MarineConditions;
 use std::io;
 
-pub struct Ground;
+/// Columns between the joints of paving slabs.
+const SLAB_WIDTH: usize = 8;
+/// Columns of sea on the top ground row at low tide.
+const SEA_MIN_WIDTH: f64 = 6.0;
+/// Columns the water line moves in from low to high tide.
+const TIDE_COLUMNS: f64 = 8.0;
+/// Strip of sand between the ground and the water.
+const BEACH_WIDTH: usize = 3;
+/// Narrower terminals have no room for a shore.
+const SHORE_MIN_TERMINAL_WIDTH: u16 = 40;
+/// Wave height (m) from which the swell breaks in whitecaps.
+const ROUGH_SEA: f64 = 1.5;
+/// Columns per second the swell rolls in at.
+const SWELL_SPEED: f64 = 2.0;
+/// Radians per second of the water lapping up and down the beach.
+const LAP_SPEED: f64 = 1.2;
+/// Columns between the spots where puddles form.
+const PUDDLE_SPACING: usize = 11;
+/// Widest a puddle spreads, in columns.
+const PUDDLE_MAX_WIDTH: usize = 6;
+/// Wetness from which the first puddle forms.
+const PUDDLE_MIN_WETNESS: f32 = 0.3;
+
+pub struct Ground {
+    config: GroundConfig,
+}
 
 impl Ground {
+    pub fn new(config: GroundConfig) -> Self {
+        Self { config }
+    }
+
+    /// The configured texture, or for `auto` one that suits the weather.
+    pub fn texture(&self, ctx: &SceneContext<'_>) -> GroundTexture {
+        match self.config.texture {
+            GroundTexture::Auto => {
+                let freezing = ctx.weather.is_some_and(|w| w.temperature <= 0.0);
+                if ctx.conditions.is_snowing || freezing {
+                    GroundTexture::Snowfield
+                } else {
+                    GroundTexture::Grass
+                }
+            }
+            texture => texture,
+        }
+    }
+
     pub fn render(
         &self,
         renderer: &mut TerminalRenderer,
         width: u16,
         height: u16,
         y_start: u16,
+        texture: GroundTexture,
         style: &WorldSceneStyle,
     ) -> io::Result<()> {
-        let width = width as usize;
-        let height = height as usize;
-
-        for y in 0..height {
-            for x in 0..width {
-                let (ch, color) = if y == 0 {
-                    let r = pseudo_rand(x, y);
-                    if r < 5 {
-                        (
-                            '*',
-                            style.flower_colors[(x + y) % style.flower_colors.len()],
-                        )
-                    } else if r < 15 {
-                        (',', style.grass_secondary)
-                    } else {
-                        ('^', style.grass_primary)
+        let density = self.config.density;
+        // Share of cells, out of 100, given a mark at the usual density
+        let below = |share: f64| pseudo_rand_below(share * density);
+
+        for y in 0..height as usize {
+            for x in 0..width as usize {
+                let r = pseudo_rand(x, y);
+                let surface = y == 0;
+                let (ch, color) = match texture {
+                    GroundTexture::Auto | GroundTexture::Grass if surface => {
+                        if r < below(5.0) {
+                            (
+                                '*',
+                                style.flower_colors[(x + y) % style.flower_colors.len()],
+                            )
+                        } else if r < below(15.0) {
+                            (',', style.grass_secondary)
+                        } else {
+                            ('^', style.grass_primary)
+                        }
+                    }
+                    GroundTexture::Auto | GroundTexture::Grass => {
+                        let ch = if r < below(20.0) {
+                            '~'
+                        } else if r < below(25.0) {
+                            '.'
+                        } else {
+                            ' '
+                        };
+                        (ch, style.soil)
+                    }
+                    GroundTexture::Pavement if surface => ('=', style.pavement),
+                    GroundTexture::Pavement => {
+                        // Slabs laid in a running bond
+                        let offset = if y % 2 == 0 { SLAB_WIDTH / 2 } else { 0 };
+                        let ch = if (x + offset) % SLAB_WIDTH == 0 {
+                            '|'
+                        } else if r < below(6.0) {
+                            '.'
+                        } else {
+                            '_'
+                        };
+                        (ch, style.pavement)
+                    }
+                    GroundTexture::Sand if surface => {
+                        if r < below(3.0) {
+                            ('o', style.soil)
+                        } else if r < below(30.0) {
+                            ('~', style.sand)
+                        } else {
+                            ('.', style.sand)
+                        }
                     }
+                    GroundTexture::Sand => {
+                        let ch = if r < below(15.0) {
+                            '.'
+                        } else if r < below(20.0) {
+                            ':'
+                        } else {
+                            ' '
+                        };
+                        (ch, style.sand)
+                    }
+                    GroundTexture::Snowfield if surface => {
+                        // A few blades of grass poke through
+                        if r < below(3.0) {
+                            ('^', style.grass_secondary)
+                        } else if r < below(25.0) {
+                            ('*', style.snow)
+                        } else {
+                            ('-', style.snow)
+                        }
+                    }
+                    GroundTexture::Snowfield => {
+                        let ch = if r < below(10.0) { '.' } else { ' ' };
+                        (ch, style.snow)
+                    }
+                };
+
+                renderer.render_char(x as u16, y_start + y as u16, ch, color)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Puddles along the ground line, more and wider the wetter it is; none
+    /// form on snow.
+    pub fn render_puddles(
+        &self,
+        renderer: &mut TerminalRenderer,
+        width: u16,
+        y: u16,
+        texture: GroundTexture,
+        wetness: f32,
+        style: &WorldSceneStyle,
+    ) -> io::Result<()> {
+        if texture == GroundTexture::Snowfield {
+            return Ok(());
+        }
+        for (start, span) in puddles(width as usize, wetness) {
+            for x in start..start + span {
+                // The sky glints off the water here and there
+                let (ch, color) = if (x + start) % 4 == 1 {
+                    ('-', style.foam)
                 } else {
-                    let r = pseudo_rand(x, y);
-                    let ch = if r < 20 {
-                        '~'
-                    } else if r < 25 {
-                        '.'
-                    } else {
-                        ' '
-                    };
-                    (ch, style.soil)
+                    ('~', style.water)
                 };
+                renderer.render_char(x as u16, y, ch, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sand and sea along the right edge, over the ground. `seconds` is any
+    /// steadily increasing clock, driving the swell and the lapping water.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_shoreline(
+        &self,
+        renderer: &mut TerminalRenderer,
+        width: u16,
+        height: u16,
+        y_start: u16,
+        marine: &MarineConditions,
+        seconds: f64,
+        style: &WorldSceneStyle,
+    ) -> io::Result<()> {
+        if !self.config.shoreline || width < SHORE_MIN_TERMINAL_WIDTH {
+            return Ok(());
+        }
+        let rough = marine.wave_height.is_some_and(|height| height >= ROUGH_SEA);
+        let width = width as usize;
 
+        for y in 0..height as usize {
+            let edge = water_edge(width, y, marine.tide_fraction, seconds);
+            for x in edge.saturating_sub(BEACH_WIDTH)..width {
+                let (ch, color) = if x < edge {
+                    ('.', style.sand)
+                } else if x == edge {
+                    ('~', style.foam)
+                } else {
+                    let swell = (x as f64 + seconds * SWELL_SPEED) as usize % 5;
+                    match swell {
+                        0 if rough => ('^', style.foam),
+                        0 | 2 => ('~', style.water),
+                        _ => ('-', style.water),
+                    }
+                };
                 renderer.render_char(x as u16, y_start + y as u16, ch, color)?;
             }
         }
@@ -50,6 +217,120 @@ impl Ground {
     }
 }
 
+/// First column of water on ground row `y`. The shore runs diagonally,
+/// with more sea nearer the viewer, sits further in the higher the tide,
+/// and the water laps a column either way.
+fn water_edge(width: usize, y: usize, tide_fraction: Option<f64>, seconds: f64) -> usize {
+    let tide = tide_fraction.unwrap_or(0.5).clamp(0.0, 1.0);
+    let lap = (seconds * LAP_SPEED + y as f64).sin().round();
+    let reach = SEA_MIN_WIDTH + tide * TIDE_COLUMNS + y as f64 + lap;
+    width.saturating_sub(reach.max(0.0) as usize)
+}
+
+/// Start column and width of each puddle across `width` columns. Every spot
+/// fills once the ground is wetter than its own threshold, so puddles appear
+/// one by one as it rains and go again as they dry.
+fn puddles(width: usize, wetness: f32) -> Vec<(usize, usize)> {
+    (0..width / PUDDLE_SPACING)
+        .filter_map(|i| {
+            let start = i * PUDDLE_SPACING + pseudo_rand(i, 7) as usize % 4;
+            let threshold =
+                PUDDLE_MIN_WETNESS + pseudo_rand(i, 3) as f32 / 100.0 * (1.0 - PUDDLE_MIN_WETNESS);
+            let depth = wetness - threshold;
+            (depth > 0.0 && start < width).then(|| {
+                let span = 2 + (depth * 2.0 * PUDDLE_MAX_WIDTH as f32) as usize;
+                (start, span.min(PUDDLE_MAX_WIDTH).min(width - start))
+            })
+        })
+        .collect()
+}
+
+/// Threshold for `pseudo_rand`: about `share` cells in 100 fall below it.
+fn pseudo_rand_below(share: f64) -> u32 {
+    share.round().clamp(0.0, 100.0) as u32
+}
+
 fn pseudo_rand(x: usize, y: usize) -> u32 {
     ((x as u32 ^ 0x5DEECE6).wrapping_mul(y as u32 ^ 0xB)) % 100
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::season::Season;
+    use crate::theme::catalogue::DEFAULT_PALETTE;
+    use crate::weather::{WeatherConditions, WeatherUnits};
+
+    fn texture(config: GroundConfig, conditions: &WeatherConditions) -> GroundTexture {
+        let ctx = SceneContext {
+            conditions,
+            palette: &DEFAULT_PALETTE,
+            weather: None,
+            units: WeatherUnits::default(),
+            tree_charred: false,
+            marine: None,
+            now: chrono::Local::now().fixed_offset(),
+            season: Season::Summer,
+            wetness: 0.0,
+            daylight: 1.0,
+            storm_watch: None,
+            holiday: Default::default(),
+            animation_secs: 0.0,
+        };
+        Ground::new(config).texture(&ctx)
+    }
+
+    #[test]
+    fn test_auto_texture_follows_snow() {
+        let mut conditions = WeatherConditions::default();
+        assert_eq!(
+            texture(GroundConfig::default(), &conditions),
+            GroundTexture::Grass
+        );
+
+        conditions.is_snowing = true;
+        assert_eq!(
+            texture(GroundConfig::default(), &conditions),
+            GroundTexture::Snowfield
+        );
+
+        let pinned = GroundConfig {
+            texture: GroundTexture::Sand,
+            ..GroundConfig::default()
+        };
+        assert_eq!(texture(pinned, &conditions), GroundTexture::Sand);
+    }
+
+    #[test]
+    fn test_puddles_spread_as_the_ground_soaks() {
+        assert!(puddles(120, 0.0).is_empty());
+        assert!(puddles(120, PUDDLE_MIN_WETNESS).is_empty());
+
+        let damp = puddles(120, 0.6);
+        let soaked = puddles(120, 1.0);
+        assert!(damp.len() < soaked.len());
+        assert!(soaked.len() <= 120 / PUDDLE_SPACING);
+        // A puddle stays where it formed and only grows
+        for (start, span) in &damp {
+            let (_, wider) = soaked.iter().find(|(s, _)| s == start).unwrap();
+            assert!(wider >= span);
+        }
+        assert!(
+            soaked
+                .iter()
+                .all(|&(start, span)| span <= PUDDLE_MAX_WIDTH && start + span <= 120)
+        );
+    }
+
+    #[test]
+    fn test_water_comes_in_with_the_tide() {
+        let low = water_edge(80, 0, Some(0.0), 0.0);
+        let high = water_edge(80, 0, Some(1.0), 0.0);
+        assert_eq!(low - high, TIDE_COLUMNS as usize);
+        // More sea nearer the viewer, wherever the water has lapped to
+        for seconds in [0.0, 1.0, 2.5] {
+            assert!(water_edge(80, 6, Some(0.0), seconds) < water_edge(80, 0, Some(0.0), seconds));
+        }
+        assert_eq!(water_edge(4, 6, Some(1.0), 0.0), 0);
+    }
+}
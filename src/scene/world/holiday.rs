@@ -0,0 +1,70 @@
+//! Decorations put up for the date: string lights along the roof and a
+//! pumpkin by the door. The fireworks are an animation in the sky.
+
+use crate::render::TerminalRenderer;
+use crate::scene::world::style::WorldSceneStyle;
+use crossterm::style::Color;
+use std::io;
+
+const BULB: char = 'o';
+/// Characters along the roof line from one bulb to the next.
+const BULB_SPACING: usize = 3;
+const BULB_COLORS: [Color; 4] = [Color::Red, Color::Green, Color::Yellow, Color::Blue];
+/// Seconds of animation a bulb stays on or off for.
+const TWINKLE_SECS: f64 = 0.6;
+
+/// A bulb on every third character of `roof_line`, the roof's top as
+/// (column, row, character) from `x`, `y`. Now and then a bulb blinks off,
+/// never many at once, `animation_secs` into the animation.
+pub fn render_string_lights(
+    renderer: &mut TerminalRenderer,
+    roof_line: &[(u16, u16, char)],
+    x: u16,
+    y: u16,
+    animation_secs: f64,
+) -> io::Result<()> {
+    // Strung from left to right, whatever row each column tops out at
+    let mut roof_line = roof_line.to_vec();
+    roof_line.sort_by_key(|&(column, _, _)| column);
+    for (bulb, &(column, row, _)) in roof_line.iter().step_by(BULB_SPACING).enumerate() {
+        if !bulb_lit(bulb, animation_secs) {
+            continue;
+        }
+        let color = BULB_COLORS[bulb % BULB_COLORS.len()];
+        renderer.render_char(x + column, y + row, BULB, color)?;
+    }
+    Ok(())
+}
+
+/// Whether the `bulb`th bulb along is on `animation_secs` into the
+/// animation: off one twinkle in five, out of step with its neighbours.
+fn bulb_lit(bulb: usize, animation_secs: f64) -> bool {
+    let tick = (animation_secs / TWINKLE_SECS) as usize;
+    (tick + bulb * 7) % 5 != 0
+}
+
+/// A carved pumpkin, "(^)", from `x`, whose face glows after dark.
+pub fn render_pumpkin(
+    renderer: &mut TerminalRenderer,
+    x: u16,
+    y: u16,
+    style: &WorldSceneStyle,
+) -> io::Result<()> {
+    renderer.render_char(x, y, '(', style.pumpkin)?;
+    renderer.render_char(x + 1, y, '^', style.pumpkin_glow.unwrap_or(style.pumpkin))?;
+    renderer.render_char(x + 2, y, ')', style.pumpkin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulbs_twinkle_on_the_animation_clock() {
+        let lit_at = |secs| (0..20).filter(|&bulb| bulb_lit(bulb, secs)).count();
+        assert_eq!(lit_at(0.0), 16);
+        assert_eq!(lit_at(3.3), 16);
+        assert!(!bulb_lit(0, 0.0));
+        assert!(bulb_lit(0, TWINKLE_SECS));
+    }
+}
@@ -1,15 +1,64 @@
+use crate::config::CommuteConfig;
 use crate::render::TerminalRenderer;
+use crate::scene::world::snow_line;
 use crate::scene::world::style::WorldSceneStyle;
+use chrono::{NaiveTime, Timelike};
+use crossterm::style::Color;
 use std::io;
 
 const HOUSE_ASCII: &str = include_str!("assets/house.txt");
 
-pub struct House;
+/// Lights go out within this many minutes either side of bedtime.
+const BEDTIME_SPREAD_MINUTES: u32 = 30;
+/// Late-night lights switch on or off in slots of this many minutes.
+const NIGHT_OWL_SLOT_MINUTES: u32 = 20;
+/// Chance (percent) that a given window is lit during a late-night slot.
+const NIGHT_OWL_CHANCE: u32 = 3;
+
+/// Columns from the left of the house to the inside of the front door.
+const DOOR_X_OFFSET: u16 = 17;
+/// Seconds the door stands open when someone leaves,
+const DOOR_OPEN_SECONDS: f64 = 6.0;
+/// of which they spend this long on the doorstep before setting off.
+const DOORSTEP_SECONDS: f64 = 3.0;
+/// Columns per second the figure walks at.
+const WALK_SPEED: f64 = 1.5;
+/// How far along the front of the house the figure walks.
+const WALK_COLUMNS: f64 = 46.0;
+/// Rows below the top of the house of the figure's head: in the doorway,
+/// then out on the ground clear of the fence.
+const DOORWAY_ROW: u16 = 7;
+const WALK_ROW: u16 = 10;
+
+pub struct House {
+    bedtime: NaiveTime,
+    commute: CommuteConfig,
+}
+
+/// The front door and whoever is using it at one moment.
+#[derive(Debug, PartialEq)]
+struct Doorstep {
+    door_open: bool,
+    /// Columns right of the door the figure stands at, if it's out.
+    figure: Option<u16>,
+}
 
 impl House {
     pub const WIDTH: u16 = 64;
     pub const HEIGHT: u16 = 10;
     pub const CHIMNEY_X_OFFSET: u16 = 12;
+    /// Below the right-most upper window.
+    pub const AC_UNIT_X_OFFSET: u16 = 26;
+    pub const AC_UNIT_Y_OFFSET: u16 = 6;
+    /// Beside the path to the front door.
+    pub const PUMPKIN_X_OFFSET: u16 = 21;
+    pub const PUMPKIN_Y_OFFSET: u16 = 9;
+    /// The chimney, roof slopes and ridge.
+    const ROOF_ROWS: usize = 5;
+
+    pub fn new(bedtime: NaiveTime, commute: CommuteConfig) -> Self {
+        Self { bedtime, commute }
+    }
 
     pub fn width(&self) -> u16 {
         Self::WIDTH
@@ -19,13 +68,47 @@ impl House {
         Self::HEIGHT
     }
 
+    /// Whether the house's `index`th window is lit at `now`, for the user's
+    /// own house as well as this one.
+    pub fn lights_on(&self, index: u32, now: NaiveTime, is_day: bool) -> bool {
+        is_day || window_lit(index, now, self.bedtime)
+    }
+
+    /// The top of the chimney and roof, where snow settles and string
+    /// lights hang.
+    pub fn roof_line(&self) -> Vec<(u16, u16, char)> {
+        snow_line(HOUSE_ASCII.lines().take(Self::ROOF_ROWS).map(str::chars))
+    }
+
+    /// Snow on the chimney and along the roof.
+    pub fn render_snow(
+        &self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        y: u16,
+        color: Color,
+    ) -> io::Result<()> {
+        for (column, row, ch) in self.roof_line() {
+            renderer.render_char(x + column, y + row, ch, color)?;
+        }
+        Ok(())
+    }
+
+    /// `raining` sends whoever leaves or comes home out with an umbrella.
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         &self,
         renderer: &mut TerminalRenderer,
         x: u16,
         y: u16,
+        now: NaiveTime,
+        is_day: bool,
+        raining: bool,
         style: &WorldSceneStyle,
     ) -> io::Result<()> {
+        let mut window_index = 0;
+        let mut window_color = style.window;
+
         for (i, line) in HOUSE_ASCII.lines().enumerate() {
             let row = y + i as u16;
 
@@ -49,9 +132,14 @@ impl House {
                 // Upper and mid window rows
                 5..=7 => {
                     for (j, ch) in line.chars().enumerate() {
+                        if ch == '[' {
+                            let lit = self.lights_on(window_index, now, is_day);
+                            window_color = if lit { style.window } else { style.window_dark };
+                            window_index += 1;
+                        }
                         if ch != ' ' {
                             let color = match ch {
-                                '[' | ']' => style.window,
+                                '[' | ']' => window_color,
                                 '|' | '.' | '_' => style.wood,
                                 '(' | ')' => style.door,
                                 '=' => style.trim,
@@ -81,7 +169,7 @@ impl House {
                             let color = match ch {
                                 '^' => style.grass_primary,
                                 '=' => style.trim,
-                                _ => crossterm::style::Color::Reset,
+                                _ => Color::Reset,
                             };
                             renderer.render_char(x + j as u16, row, ch, color)?;
                         }
@@ -91,6 +179,189 @@ impl House {
             }
         }
 
+        if self.commute.enabled {
+            self.render_doorstep(renderer, x, y, doorstep(now, &self.commute), raining, style)?;
+        }
+
+        Ok(())
+    }
+
+    fn render_doorstep(
+        &self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        y: u16,
+        doorstep: Doorstep,
+        raining: bool,
+        style: &WorldSceneStyle,
+    ) -> io::Result<()> {
+        let door_x = x + DOOR_X_OFFSET;
+        if doorstep.door_open {
+            for (dx, dy) in [(0, 7), (1, 7), (0, 8), (1, 8)] {
+                renderer.render_char(door_x + dx, y + dy, ' ', style.door)?;
+            }
+        }
+
+        let Some(offset) = doorstep.figure else {
+            return Ok(());
+        };
+        let figure_x = door_x + offset;
+        let head_y = y + if offset == 0 { DOORWAY_ROW } else { WALK_ROW };
+        renderer.render_char(figure_x, head_y, 'o', style.figure)?;
+        // Legs apart every other column
+        let legs = if offset % 2 == 0 { 'A' } else { 'n' };
+        renderer.render_char(figure_x, head_y + 1, legs, style.figure)?;
+        // Put up once off the doorstep
+        if raining && offset > 0 {
+            for (dx, ch) in [(-1, '/'), (0, '^'), (1, '\\')] {
+                if let Some(ux) = figure_x.checked_add_signed(dx) {
+                    renderer.render_char(ux, head_y - 1, ch, style.umbrella)?;
+                }
+            }
+        }
         Ok(())
     }
 }
+
+/// Where things stand at the front door at `now`. Leaving, the door opens,
+/// the figure waits on the doorstep and walks off along the front of the
+/// house; coming home is the same in reverse. A trip can run on past
+/// midnight.
+fn doorstep(now: NaiveTime, commute: &CommuteConfig) -> Doorstep {
+    const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+    let trip = DOORSTEP_SECONDS + WALK_COLUMNS / WALK_SPEED;
+    let since = |time: NaiveTime| {
+        let millis = now.signed_duration_since(time).num_milliseconds();
+        millis.rem_euclid(DAY_MILLIS) as f64 / 1000.0
+    };
+    let during_trip = |seconds: f64| (0.0..trip).contains(&seconds);
+
+    let t = match (since(commute.leave), since(commute.home)) {
+        (leaving, _) if during_trip(leaving) => leaving,
+        (_, arriving) if during_trip(arriving) => trip - arriving,
+        _ => {
+            return Doorstep {
+                door_open: false,
+                figure: None,
+            };
+        }
+    };
+    Doorstep {
+        door_open: t < DOOR_OPEN_SECONDS,
+        figure: Some(((t - DOORSTEP_SECONDS).max(0.0) * WALK_SPEED) as u16),
+    }
+}
+
+/// Whether a window is lit at night. Windows light up at dusk, go dark one by
+/// one around bedtime and stay dark until dawn, bar the odd night owl.
+fn window_lit(index: u32, now: NaiveTime, bedtime: NaiveTime) -> bool {
+    // Count minutes from noon so the evening runs on past midnight
+    let since_noon =
+        |time: NaiveTime| (time.num_seconds_from_midnight() / 60 + 12 * 60) % (24 * 60);
+
+    let spread = window_hash(index, 0) % (2 * BEDTIME_SPREAD_MINUTES);
+    let lights_out = (since_noon(bedtime) + spread).saturating_sub(BEDTIME_SPREAD_MINUTES);
+    let now = since_noon(now);
+    if now < lights_out {
+        return true;
+    }
+
+    let slot = now / NIGHT_OWL_SLOT_MINUTES + 1;
+    window_hash(index, slot) % 100 < NIGHT_OWL_CHANCE
+}
+
+fn window_hash(index: u32, salt: u32) -> u32 {
+    let mut h = index.wrapping_mul(0x9E37_79B9) ^ salt.wrapping_mul(0x85EB_CA6B);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2C1B_3C6D);
+    h ^ (h >> 13)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_windows_lit_in_the_evening() {
+        for index in 0..8 {
+            assert!(window_lit(index, at(19, 0), at(22, 30)));
+        }
+    }
+
+    #[test]
+    fn test_windows_mostly_dark_before_dawn() {
+        let lit = (0..100)
+            .filter(|&index| window_lit(index, at(4, 0), at(22, 30)))
+            .count();
+        assert!(lit < 15, "{} windows still lit", lit);
+    }
+
+    #[test]
+    fn test_doorstep_leaving_and_coming_home() {
+        let commute = CommuteConfig {
+            enabled: true,
+            ..CommuteConfig::default()
+        };
+        let at = |h, m, s| NaiveTime::from_hms_opt(h, m, s).unwrap();
+        let closed = Doorstep {
+            door_open: false,
+            figure: None,
+        };
+
+        assert_eq!(doorstep(at(7, 59, 59), &commute), closed);
+        assert_eq!(
+            doorstep(at(8, 0, 1), &commute),
+            Doorstep {
+                door_open: true,
+                figure: Some(0),
+            }
+        );
+        assert_eq!(
+            doorstep(at(8, 0, 13), &commute),
+            Doorstep {
+                door_open: false,
+                figure: Some(15),
+            }
+        );
+        assert_eq!(doorstep(at(8, 1, 0), &commute), closed);
+
+        // Coming home walks the same way back to the door
+        let arriving = doorstep(at(18, 0, 0), &commute);
+        assert!(!arriving.door_open);
+        assert!(arriving.figure.unwrap() >= WALK_COLUMNS as u16 - 1);
+        assert_eq!(
+            doorstep(at(18, 0, 32), &commute),
+            Doorstep {
+                door_open: true,
+                figure: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_doorstep_across_midnight() {
+        let at = |h, m, s| NaiveTime::from_hms_opt(h, m, s).unwrap();
+        let commute = CommuteConfig {
+            enabled: true,
+            leave: at(23, 59, 50),
+            home: at(7, 0, 0),
+        };
+        assert_eq!(
+            doorstep(at(0, 0, 13), &commute),
+            Doorstep {
+                door_open: false,
+                figure: Some(30),
+            }
+        );
+        assert_eq!(doorstep(at(0, 1, 0), &commute).figure, None);
+    }
+
+    #[test]
+    fn test_bedtime_after_midnight() {
+        assert!(window_lit(0, at(0, 15), at(2, 0)));
+    }
+}
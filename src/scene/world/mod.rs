@@ -1,20 +1,31 @@
+mod custom;
 mod decorations;
 mod ground;
+mod holiday;
 mod house;
 mod style;
+mod wind_sock;
 
+use crate::config::SceneConfig;
 use crate::render::TerminalRenderer;
+use crate::scene::season::Season;
 use crate::scene::{ChimneyPosition, Scene, SceneContext, SceneLayout};
-use decorations::{DecorationLayout, Decorations};
+use chrono::NaiveTime;
+pub use custom::{CustomHouse, load_all as load_custom_houses};
+use decorations::{DecorationLayout, Decorations, RoadSign};
 use ground::Ground;
 use house::House;
 use std::io;
 use style::WorldSceneStyle;
+use wind_sock::WindSock;
 
 pub struct WorldScene {
+    id: String,
     house: House,
+    custom: Option<CustomHouse>,
     ground: Ground,
     decorations: Decorations,
+    wind_sock: Option<WindSock>,
     width: u16,
     height: u16,
 }
@@ -22,20 +33,43 @@ pub struct WorldScene {
 impl WorldScene {
     const GROUND_HEIGHT: u16 = 7;
 
-    pub fn new(width: u16, height: u16) -> Self {
+    pub fn new(
+        width: u16,
+        height: u16,
+        show_wind_sock: bool,
+        bedtime: NaiveTime,
+        config: SceneConfig,
+    ) -> Self {
         Self {
-            house: House,
-            ground: Ground,
-            decorations: Decorations,
+            id: "world".to_string(),
+            house: House::new(bedtime, config.commute),
+            custom: None,
+            ground: Ground::new(config.ground),
+            decorations: Decorations::new(config.decorations),
+            wind_sock: show_wind_sock.then_some(WindSock),
             width,
             height,
         }
     }
+
+    /// The same world around the user's own house, as scene `id`.
+    pub fn with_house(mut self, id: String, house: CustomHouse) -> Self {
+        self.id = id;
+        self.custom = Some(house);
+        self
+    }
+
+    fn house_size(&self) -> (u16, u16) {
+        match &self.custom {
+            Some(custom) => (custom.width(), custom.height()),
+            None => (self.house.width(), self.house.height()),
+        }
+    }
 }
 
 impl Scene for WorldScene {
-    fn id(&self) -> &'static str {
-        "world"
+    fn id(&self) -> &str {
+        &self.id
     }
 
     fn update_size(&mut self, width: u16, height: u16) {
@@ -45,16 +79,35 @@ impl Scene for WorldScene {
 
     fn layout(&self) -> SceneLayout {
         let ground_y = self.height.saturating_sub(Self::GROUND_HEIGHT);
-        let house_x = (self.width / 2).saturating_sub(House::WIDTH / 2);
-        let house_y = ground_y.saturating_sub(House::HEIGHT);
-        let chimney_x = house_x + House::CHIMNEY_X_OFFSET;
+        let (house_width, house_height) = self.house_size();
+        let house_x = (self.width / 2).saturating_sub(house_width / 2);
+        let house_y = ground_y.saturating_sub(house_height);
+        let (chimney, ac_unit) = match &self.custom {
+            Some(custom) => (
+                custom.chimney().map(|anchor| (anchor.x, anchor.y)),
+                custom.ac_unit().map(|anchor| (anchor.x, anchor.y)),
+            ),
+            None => (
+                Some((House::CHIMNEY_X_OFFSET, 0)),
+                Some((House::AC_UNIT_X_OFFSET, House::AC_UNIT_Y_OFFSET)),
+            ),
+        };
+        let on_house = |(x, y): (u16, u16)| ChimneyPosition {
+            x: house_x + x,
+            y: house_y + y,
+        };
 
         SceneLayout {
             ground_y,
-            chimney_pos: Some(ChimneyPosition {
-                x: chimney_x,
-                y: house_y,
-            }),
+            chimney_pos: chimney.map(on_house),
+            tree_pos: self
+                .decorations
+                .tree_top(house_x)
+                .map(|(x, y_offset)| ChimneyPosition {
+                    x,
+                    y: ground_y.saturating_sub(y_offset),
+                }),
+            ac_unit_pos: ac_unit.map(on_house),
             width: self.width,
             height: self.height,
         }
@@ -62,29 +115,172 @@ impl Scene for WorldScene {
 
     fn render(&self, renderer: &mut TerminalRenderer, ctx: &SceneContext<'_>) -> io::Result<()> {
         let layout = self.layout();
-        let house_x = (self.width / 2).saturating_sub(self.house.width() / 2);
-        let house_y = layout.ground_y.saturating_sub(self.house.height());
+        let (house_width, house_height) = self.house_size();
+        let house_x = (self.width / 2).saturating_sub(house_width / 2);
+        let house_y = layout.ground_y.saturating_sub(house_height);
         let style = WorldSceneStyle::resolve(ctx);
+        let texture = self.ground.texture(ctx);
 
         self.ground.render(
             renderer,
             self.width,
             Self::GROUND_HEIGHT,
             layout.ground_y,
+            texture,
+            &style,
+        )?;
+        self.ground.render_puddles(
+            renderer,
+            self.width,
+            layout.ground_y,
+            texture,
+            ctx.wetness,
             &style,
         )?;
-        self.house.render(renderer, house_x, house_y, &style)?;
+        if let Some(marine) = ctx.marine {
+            self.ground.render_shoreline(
+                renderer,
+                self.width,
+                Self::GROUND_HEIGHT,
+                layout.ground_y,
+                marine,
+                ctx.animation_secs,
+                &style,
+            )?;
+        }
+        match &self.custom {
+            Some(custom) => custom.render(
+                renderer,
+                house_x,
+                house_y,
+                |window| {
+                    self.house
+                        .lights_on(window, ctx.now.time(), ctx.conditions.sun.is_day)
+                },
+                &style,
+            )?,
+            None => self.house.render(
+                renderer,
+                house_x,
+                house_y,
+                ctx.now.time(),
+                ctx.conditions.sun.is_day,
+                ctx.conditions.is_raining || ctx.conditions.is_thunderstorm,
+                &style,
+            )?,
+        }
+        if ctx.season == Season::Winter {
+            match &self.custom {
+                Some(custom) => custom.render_snow(renderer, house_x, house_y, style.snow)?,
+                None => self
+                    .house
+                    .render_snow(renderer, house_x, house_y, style.snow)?,
+            }
+        }
+        if ctx.holiday.string_lights {
+            let roof_line = match &self.custom {
+                Some(custom) => custom.roof_line(),
+                None => self.house.roof_line(),
+            };
+            holiday::render_string_lights(
+                renderer,
+                &roof_line,
+                house_x,
+                house_y,
+                ctx.animation_secs,
+            )?;
+        }
+        if ctx.holiday.pumpkin {
+            // A custom house's door could be anywhere; it goes out front
+            let (x, y) = match &self.custom {
+                Some(_) => (house_x + house_width / 2 + 2, layout.ground_y),
+                None => (
+                    house_x + House::PUMPKIN_X_OFFSET,
+                    house_y + House::PUMPKIN_Y_OFFSET,
+                ),
+            };
+            holiday::render_pumpkin(renderer, x, y, &style)?;
+        }
         self.decorations.render(
             renderer,
             &DecorationLayout {
                 horizon_y: layout.ground_y,
                 house_x,
-                house_width: self.house.width(),
+                house_width,
                 width: self.width,
+                // A charred tree keeps its burnt crown whatever the season
+                season: if ctx.tree_charred {
+                    Season::Summer
+                } else {
+                    ctx.season
+                },
+                animation_secs: ctx.animation_secs,
             },
+            ctx.weather
+                .map(|weather| RoadSign::from_weather(weather, ctx.units))
+                .as_ref(),
             &style,
         )?;
 
+        if let (Some(wind_sock), Some(weather)) = (&self.wind_sock, ctx.weather) {
+            // Mounted on the last fence post
+            let pole_x =
+                (house_x + house_width + 14).checked_add_signed(self.decorations.fence_offset());
+            if let Some(pole_x) = pole_x.filter(|x| x + WindSock::REACH < self.width) {
+                wind_sock.render(
+                    renderer,
+                    pole_x,
+                    layout.ground_y.saturating_sub(2),
+                    weather,
+                    &style,
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
+
+/// Where snow settles on a drawing: the topmost character of each column,
+/// as (column, row, character).
+fn snow_line<R>(rows: impl IntoIterator<Item = R>) -> Vec<(u16, u16, char)>
+where
+    R: IntoIterator<Item = char>,
+{
+    let mut covered = Vec::new();
+    let mut snow = Vec::new();
+    for (y, row) in (0..).zip(rows) {
+        for (x, ch) in (0..).zip(row) {
+            let column = x as usize;
+            if covered.len() <= column {
+                covered.resize(column + 1, false);
+            }
+            if ch != ' ' && !covered[column] {
+                covered[column] = true;
+                snow.push((x, y, ch));
+            }
+        }
+    }
+    snow
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snow_settles_on_top() {
+        let art = ["  _  ", " / \\", "/___\\"];
+        let snow = snow_line(art.iter().map(|row| row.chars()));
+        assert_eq!(
+            snow,
+            [
+                (2, 0, '_'),
+                (1, 1, '/'),
+                (3, 1, '\\'),
+                (0, 2, '/'),
+                (4, 2, '\\'),
+            ]
+        );
+    }
+}
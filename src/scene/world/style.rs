@@ -1,81 +1,385 @@
 use crate::scene::SceneContext;
+use crate::weather::severity::Severity;
 use crossterm::style::Color;
 
+/// How much darker soaked ground is.
+const WET_DARKENING: f32 = 0.4;
+/// How much darker everything is under a storm watch, and a warning.
+const WATCH_DARKENING: f32 = 0.2;
+const WARNING_DARKENING: f32 = 0.35;
+
 #[derive(Clone, Copy)]
 pub struct WorldSceneStyle {
     pub roof: Color,
     pub wood: Color,
     pub door: Color,
     pub window: Color,
+    /// Windows whose lights are off.
+    pub window_dark: Color,
     pub trim: Color,
     pub grass_primary: Color,
     pub grass_secondary: Color,
     pub flower_colors: [Color; 4],
     pub soil: Color,
+    pub pavement: Color,
+    pub sand: Color,
+    pub snow: Color,
+    pub water: Color,
+    pub foam: Color,
     pub tree_foliage: Color,
+    /// The tree's trunk, and its branches once the leaves have fallen.
+    pub bark: Color,
+    /// Cherry blossom on the tree in spring.
+    pub blossom: Color,
+    pub autumn_leaves: [Color; 3],
     pub fence: Color,
     pub mailbox: Color,
+    pub sign: Color,
+    /// Smoke curling off the tree after a lightning strike.
+    pub tree_smoke: Option<Color>,
+    pub lamp_post: Color,
+    /// Whoever leaves and comes home through the front door.
+    pub figure: Color,
+    pub umbrella: Color,
+    /// Glow color of the street lamps, or `None` while they're switched off.
+    pub lamp_light: Option<Color>,
+    pub pumpkin: Color,
+    /// The candle inside the pumpkin, lit after dark.
+    pub pumpkin_glow: Option<Color>,
 }
 
 impl WorldSceneStyle {
     pub fn resolve(ctx: &SceneContext<'_>) -> Self {
-        let palette = ctx.palette;
-
-        if ctx.conditions.sun.is_day {
-            Self {
-                roof: palette.accent_primary,
-                wood: palette.accent_secondary,
-                door: Color::Rgb {
-                    r: 139,
-                    g: 69,
-                    b: 19,
-                },
-                window: Color::Cyan,
-                trim: Color::DarkGrey,
-                grass_primary: palette.ground_day,
-                grass_secondary: Color::DarkGreen,
-                flower_colors: [Color::Magenta, Color::Red, Color::Cyan, Color::Yellow],
-                soil: Color::Rgb {
-                    r: 101,
-                    g: 67,
-                    b: 33,
-                },
-                tree_foliage: Color::DarkGreen,
-                fence: Color::White,
-                mailbox: Color::Blue,
+        let mut style = Self::for_time_of_day(ctx);
+        if ctx.tree_charred {
+            style.tree_foliage = Color::Rgb {
+                r: 70,
+                g: 60,
+                b: 50,
+            };
+            style.tree_smoke = Some(Color::DarkGrey);
+        }
+        if ctx.wetness > 0.0 {
+            for color in [
+                &mut style.grass_primary,
+                &mut style.grass_secondary,
+                &mut style.soil,
+                &mut style.pavement,
+                &mut style.sand,
+            ] {
+                *color = darken(*color, ctx.wetness * WET_DARKENING);
+            }
+        }
+        if let Some(severity) = ctx.storm_watch {
+            let by = match severity {
+                Severity::Watch => WATCH_DARKENING,
+                Severity::Warning => WARNING_DARKENING,
+            };
+            for color in [
+                &mut style.roof,
+                &mut style.wood,
+                &mut style.door,
+                &mut style.trim,
+                &mut style.grass_primary,
+                &mut style.grass_secondary,
+                &mut style.soil,
+                &mut style.pavement,
+                &mut style.sand,
+                &mut style.water,
+                &mut style.tree_foliage,
+                &mut style.bark,
+                &mut style.fence,
+            ] {
+                *color = darken(*color, by);
             }
+        }
+        style
+    }
+
+    /// Day's colors, night's, or between the two while one fades into the
+    /// other.
+    fn for_time_of_day(ctx: &SceneContext<'_>) -> Self {
+        if ctx.daylight >= 1.0 {
+            Self::day(ctx)
+        } else if ctx.daylight <= 0.0 {
+            Self::night(ctx)
         } else {
-            Self {
-                roof: Color::DarkMagenta,
-                wood: Color::Rgb {
-                    r: 100,
-                    g: 70,
-                    b: 50,
+            Self::night(ctx).fade_to(&Self::day(ctx), ctx.daylight)
+        }
+    }
+
+    fn day(ctx: &SceneContext<'_>) -> Self {
+        let palette = ctx.palette;
+        Self {
+            roof: palette.accent_primary,
+            wood: palette.accent_secondary,
+            door: Color::Rgb {
+                r: 139,
+                g: 69,
+                b: 19,
+            },
+            window: Color::Cyan,
+            window_dark: Color::Cyan,
+            trim: Color::DarkGrey,
+            grass_primary: palette.ground_day,
+            grass_secondary: Color::DarkGreen,
+            flower_colors: [Color::Magenta, Color::Red, Color::Cyan, Color::Yellow],
+            soil: Color::Rgb {
+                r: 101,
+                g: 67,
+                b: 33,
+            },
+            pavement: Color::Grey,
+            sand: Color::Rgb {
+                r: 218,
+                g: 196,
+                b: 140,
+            },
+            snow: Color::White,
+            water: Color::Blue,
+            foam: Color::White,
+            tree_foliage: Color::DarkGreen,
+            bark: Color::Rgb {
+                r: 110,
+                g: 80,
+                b: 50,
+            },
+            blossom: Color::Rgb {
+                r: 255,
+                g: 183,
+                b: 197,
+            },
+            autumn_leaves: [
+                Color::Rgb {
+                    r: 230,
+                    g: 120,
+                    b: 30,
                 },
-                door: Color::Rgb {
-                    r: 80,
-                    g: 40,
-                    b: 10,
+                Color::Rgb {
+                    r: 190,
+                    g: 60,
+                    b: 20,
+                },
+                Color::Rgb {
+                    r: 220,
+                    g: 170,
+                    b: 40,
                 },
-                window: Color::Yellow,
-                trim: Color::DarkGrey,
-                grass_primary: palette.ground_night,
-                grass_secondary: Color::Rgb { r: 0, g: 50, b: 0 },
-                flower_colors: [
-                    Color::DarkMagenta,
-                    Color::DarkRed,
-                    Color::Blue,
-                    Color::DarkYellow,
-                ],
-                soil: Color::Rgb {
-                    r: 60,
-                    g: 40,
+            ],
+            fence: Color::White,
+            mailbox: Color::Blue,
+            sign: Color::White,
+            lamp_post: Color::DarkGrey,
+            figure: Color::White,
+            umbrella: Color::Red,
+            lamp_light: None,
+            tree_smoke: None,
+            pumpkin: Color::Rgb {
+                r: 230,
+                g: 120,
+                b: 20,
+            },
+            pumpkin_glow: None,
+        }
+    }
+
+    fn night(ctx: &SceneContext<'_>) -> Self {
+        let palette = ctx.palette;
+        Self {
+            roof: Color::DarkMagenta,
+            wood: Color::Rgb {
+                r: 100,
+                g: 70,
+                b: 50,
+            },
+            door: Color::Rgb {
+                r: 80,
+                g: 40,
+                b: 10,
+            },
+            window: Color::Yellow,
+            window_dark: Color::DarkGrey,
+            trim: Color::DarkGrey,
+            grass_primary: palette.ground_night,
+            grass_secondary: Color::Rgb { r: 0, g: 50, b: 0 },
+            flower_colors: [
+                Color::DarkMagenta,
+                Color::DarkRed,
+                Color::Blue,
+                Color::DarkYellow,
+            ],
+            soil: Color::Rgb {
+                r: 60,
+                g: 40,
+                b: 20,
+            },
+            pavement: Color::DarkGrey,
+            sand: Color::Rgb {
+                r: 120,
+                g: 105,
+                b: 70,
+            },
+            snow: Color::Grey,
+            water: Color::DarkBlue,
+            foam: Color::Grey,
+            tree_foliage: Color::Rgb { r: 0, g: 50, b: 0 },
+            bark: Color::Rgb {
+                r: 70,
+                g: 50,
+                b: 35,
+            },
+            blossom: Color::Rgb {
+                r: 140,
+                g: 90,
+                b: 105,
+            },
+            autumn_leaves: [
+                Color::Rgb {
+                    r: 120,
+                    g: 60,
                     b: 20,
                 },
-                tree_foliage: Color::Rgb { r: 0, g: 50, b: 0 },
-                fence: Color::Grey,
-                mailbox: Color::DarkBlue,
+                Color::Rgb {
+                    r: 95,
+                    g: 35,
+                    b: 15,
+                },
+                Color::Rgb {
+                    r: 110,
+                    g: 85,
+                    b: 25,
+                },
+            ],
+            fence: Color::Grey,
+            mailbox: Color::DarkBlue,
+            sign: Color::Grey,
+            lamp_post: Color::Grey,
+            figure: Color::Grey,
+            umbrella: Color::DarkRed,
+            tree_smoke: None,
+            lamp_light: if ctx.conditions.is_thunderstorm && storm_flicker(ctx.animation_secs) {
+                None
+            } else {
+                Some(Color::Rgb {
+                    r: 255,
+                    g: 190,
+                    b: 90,
+                })
+            },
+            pumpkin: Color::Rgb {
+                r: 150,
+                g: 75,
+                b: 15,
+            },
+            pumpkin_glow: Some(Color::Rgb {
+                r: 255,
+                g: 200,
+                b: 60,
+            }),
+        }
+    }
+
+    /// These colors `by` (0 to 1) of the way to `to`'s.
+    fn fade_to(&self, to: &Self, by: f32) -> Self {
+        let mix = |from: Color, to: Color| mix(from, to, by);
+        let either = |from: Option<Color>, to: Option<Color>| if by < 0.5 { from } else { to };
+        Self {
+            roof: mix(self.roof, to.roof),
+            wood: mix(self.wood, to.wood),
+            door: mix(self.door, to.door),
+            window: mix(self.window, to.window),
+            window_dark: mix(self.window_dark, to.window_dark),
+            trim: mix(self.trim, to.trim),
+            grass_primary: mix(self.grass_primary, to.grass_primary),
+            grass_secondary: mix(self.grass_secondary, to.grass_secondary),
+            flower_colors: std::array::from_fn(|i| mix(self.flower_colors[i], to.flower_colors[i])),
+            soil: mix(self.soil, to.soil),
+            pavement: mix(self.pavement, to.pavement),
+            sand: mix(self.sand, to.sand),
+            snow: mix(self.snow, to.snow),
+            water: mix(self.water, to.water),
+            foam: mix(self.foam, to.foam),
+            tree_foliage: mix(self.tree_foliage, to.tree_foliage),
+            bark: mix(self.bark, to.bark),
+            blossom: mix(self.blossom, to.blossom),
+            autumn_leaves: std::array::from_fn(|i| mix(self.autumn_leaves[i], to.autumn_leaves[i])),
+            fence: mix(self.fence, to.fence),
+            mailbox: mix(self.mailbox, to.mailbox),
+            sign: mix(self.sign, to.sign),
+            tree_smoke: either(self.tree_smoke, to.tree_smoke),
+            lamp_post: mix(self.lamp_post, to.lamp_post),
+            figure: mix(self.figure, to.figure),
+            umbrella: mix(self.umbrella, to.umbrella),
+            lamp_light: either(self.lamp_light, to.lamp_light),
+            pumpkin: mix(self.pumpkin, to.pumpkin),
+            pumpkin_glow: either(self.pumpkin_glow, to.pumpkin_glow),
+        }
+    }
+}
+
+/// `by` (0 to 1) of the way from `from` to `to`. The terminal's own colors
+/// have no shades in between, so they switch over halfway.
+fn mix(from: Color, to: Color, by: f32) -> Color {
+    match (from, to) {
+        (
+            Color::Rgb { r, g, b },
+            Color::Rgb {
+                r: r2,
+                g: g2,
+                b: b2,
+            },
+        ) => {
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * by).round() as u8;
+            Color::Rgb {
+                r: mix(r, r2),
+                g: mix(g, g2),
+                b: mix(b, b2),
             }
         }
+        _ if by < 0.5 => from,
+        _ => to,
+    }
+}
+
+/// `color` with its brightness cut by `by`, from 0 to 1. The terminal's
+/// own colors have no shades in between, so they turn to their dark variant
+/// from a fifth darker.
+fn darken(color: Color, by: f32) -> Color {
+    let scale = |channel: u8| (channel as f32 * (1.0 - by)) as u8;
+    match color {
+        Color::Rgb { r, g, b } => Color::Rgb {
+            r: scale(r),
+            g: scale(g),
+            b: scale(b),
+        },
+        _ if by < 0.2 => color,
+        Color::Green => Color::DarkGreen,
+        Color::Grey => Color::DarkGrey,
+        Color::Yellow => Color::DarkYellow,
+        Color::White => Color::Grey,
+        other => other,
+    }
+}
+
+/// Cheap time-based flicker: the lamps drop out for roughly one in five
+/// tenth-of-a-second slots of the animation while a storm is overhead.
+fn storm_flicker(animation_secs: f64) -> bool {
+    let slot = (animation_secs * 10.0) as u64;
+    (slot.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 60) < 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storm_flicker_follows_the_animation() {
+        let slots: Vec<bool> = (0..100)
+            .map(|slot| storm_flicker(slot as f64 / 10.0))
+            .collect();
+        let dark = slots.iter().filter(|&&off| off).count();
+        assert!((5..=40).contains(&dark), "{} of 100 slots dark", dark);
+        // The same moment of the animation always looks the same
+        assert_eq!(storm_flicker(4.21), storm_flicker(4.29));
     }
 }
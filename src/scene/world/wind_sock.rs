@@ -0,0 +1,117 @@
+use crate::render::TerminalRenderer;
+use crate::scene::world::style::WorldSceneStyle;
+use crate::weather::WeatherData;
+use crossterm::style::Color;
+use std::io;
+
+/// Below this speed (m/s) the sock hangs limp.
+const LIMP_MAX_MS: f64 = 2.5;
+/// Full extension is reached at roughly 15 knots.
+const FULL_MIN_MS: f64 = 7.7;
+
+const POLE_HEIGHT: u16 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Extension {
+    Limp,
+    Half,
+    Full,
+}
+
+impl Extension {
+    fn from_speed(speed_ms: f64) -> Self {
+        if speed_ms < LIMP_MAX_MS {
+            Self::Limp
+        } else if speed_ms < FULL_MIN_MS {
+            Self::Half
+        } else {
+            Self::Full
+        }
+    }
+
+    /// Sock cells as (column offset, row offset, char) relative to the pole
+    /// top, for a sock blowing to the right.
+    fn cells(self) -> &'static [(i16, u16, char)] {
+        match self {
+            Self::Limp => &[(1, 0, '.'), (1, 1, ')')],
+            Self::Half => &[(1, 0, '='), (2, 1, '\\')],
+            Self::Full => &[(1, 0, '='), (2, 0, '='), (3, 0, '-'), (4, 0, '-')],
+        }
+    }
+}
+
+pub struct WindSock;
+
+impl WindSock {
+    /// Furthest the sock reaches from its pole.
+    pub const REACH: u16 = 4;
+
+    /// Renders the pole standing on `base_y` with the sock at its top.
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        pole_x: u16,
+        base_y: u16,
+        weather: &WeatherData,
+        style: &WorldSceneStyle,
+    ) -> io::Result<()> {
+        let Some(top_y) = base_y.checked_sub(POLE_HEIGHT) else {
+            return Ok(());
+        };
+        for y in top_y..base_y {
+            renderer.render_char(pole_x, y, '|', style.fence)?;
+        }
+
+        let extension = Extension::from_speed(weather.wind_speed);
+        let leftward = blows_left(weather.wind_direction);
+        for &(dx, dy, ch) in extension.cells() {
+            let (dx, ch) = if leftward {
+                (-dx, mirror(ch))
+            } else {
+                (dx, ch)
+            };
+            let Some(x) = pole_x.checked_add_signed(dx) else {
+                continue;
+            };
+            renderer.render_char(x, top_y + dy, ch, Color::Red)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Wind direction is where the wind comes from, so the sock points the other
+/// way; only the east-west component is visible from the front.
+fn blows_left(direction_deg: f64) -> bool {
+    -direction_deg.to_radians().sin() < -f64::EPSILON
+}
+
+fn mirror(ch: char) -> char {
+    match ch {
+        '\\' => '/',
+        '/' => '\\',
+        ')' => '(',
+        '(' => ')',
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_bands() {
+        assert_eq!(Extension::from_speed(0.5), Extension::Limp);
+        assert_eq!(Extension::from_speed(4.0), Extension::Half);
+        assert_eq!(Extension::from_speed(12.0), Extension::Full);
+    }
+
+    #[test]
+    fn test_sock_points_downwind() {
+        // Westerly wind blows toward the east (right)
+        assert!(!blows_left(270.0));
+        // Easterly wind blows toward the west (left)
+        assert!(blows_left(90.0));
+    }
+}
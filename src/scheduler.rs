@@ -0,0 +1,150 @@
+//! The clock at the place being shown. Sunrise countdowns, window lights,
+//! the commute and bedtime read the time through a [`Scheduler`], so a
+//! remote city runs on its own time and a night when the clocks change
+//! doesn't throw them an hour out.
+//!
+//! The zone comes with the weather: Open-Meteo reports it, and for other
+//! providers the weather client looks it up. Until then the system's is
+//! used.
+
+use crate::weather::WeatherData;
+use chrono::{
+    DateTime, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeDelta, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Scheduler {
+    zone: Option<Tz>,
+}
+
+impl Scheduler {
+    /// For an IANA zone such as "Asia/Tokyo"; the system's when `name`
+    /// isn't one.
+    pub fn for_zone(name: &str) -> Self {
+        Self {
+            zone: name.parse().ok(),
+        }
+    }
+
+    /// On the clock of the zone `weather` came with, else the system's.
+    pub fn of(weather: &WeatherData) -> Self {
+        weather
+            .details
+            .timezone
+            .as_deref()
+            .map_or_else(Self::default, Self::for_zone)
+    }
+
+    pub fn now(&self) -> DateTime<FixedOffset> {
+        self.local(Utc::now())
+    }
+
+    /// `at` on the clock at the location.
+    pub fn local(&self, at: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self.zone {
+            Some(zone) => at.with_timezone(&zone).fixed_offset(),
+            None => at.with_timezone(&Local).fixed_offset(),
+        }
+    }
+
+    /// When the clock at the location shows `time` on `date`. A time skipped
+    /// as the clocks go forward comes that much after the change, as 02:30
+    /// does at 03:30, and a time that comes round twice as they go back is
+    /// the first of the two.
+    pub fn at(&self, date: NaiveDate, time: NaiveTime) -> DateTime<FixedOffset> {
+        let naive = date.and_time(time);
+        match self.zone {
+            Some(zone) => resolve(&zone, naive),
+            None => resolve(&Local, naive),
+        }
+    }
+
+    /// How long after `now` the clock next shows `time`; a real duration, so
+    /// an hour more or less across a clock change.
+    pub fn until(&self, now: DateTime<FixedOffset>, time: NaiveTime) -> TimeDelta {
+        let today = now.date_naive();
+        let mut next = self.at(today, time);
+        if next <= now
+            && let Some(tomorrow) = today.succ_opt()
+        {
+            next = self.at(tomorrow, time);
+        }
+        next - now
+    }
+}
+
+fn resolve<Z: TimeZone>(zone: &Z, naive: NaiveDateTime) -> DateTime<FixedOffset> {
+    match zone.from_local_datetime(&naive) {
+        LocalResult::Single(at) | LocalResult::Ambiguous(at, _) => at.fixed_offset(),
+        LocalResult::None => {
+            // In the gap: read it with the offset from before the change
+            let before = zone
+                .offset_from_utc_datetime(&(naive - TimeDelta::days(1)))
+                .fix();
+            zone.from_utc_datetime(&(naive - before)).fixed_offset()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn berlin() -> Scheduler {
+        Scheduler::for_zone("Europe/Berlin")
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_zone_names() {
+        assert_eq!(berlin().zone, Some(Tz::Europe__Berlin));
+        assert_eq!(Scheduler::for_zone("Mars/Olympus_Mons").zone, None);
+    }
+
+    #[test]
+    fn test_local_time_at_the_location() {
+        let noon_utc = Utc.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+        let tokyo = Scheduler::for_zone("Asia/Tokyo").local(noon_utc);
+        assert_eq!(tokyo.time(), time(21, 0));
+        // Summer time
+        assert_eq!(berlin().local(noon_utc).time(), time(14, 0));
+    }
+
+    #[test]
+    fn test_times_skipped_or_repeated_by_the_clock_change() {
+        // Clocks went forward from 02:00 to 03:00 on 31 March
+        let skipped = berlin().at(date(3, 31), time(2, 30));
+        assert_eq!(skipped.time(), time(3, 30));
+        assert_eq!(skipped.offset().local_minus_utc(), 2 * 3600);
+
+        // And back from 03:00 to 02:00 on 27 October
+        let repeated = berlin().at(date(10, 27), time(2, 30));
+        assert_eq!(repeated.time(), time(2, 30));
+        assert_eq!(repeated.offset().local_minus_utc(), 2 * 3600);
+    }
+
+    #[test]
+    fn test_until_across_the_clock_change() {
+        let berlin = berlin();
+        let evening = berlin.at(date(3, 30), time(22, 0));
+        // Eight hours until 06:00 on the clock, but only seven pass
+        assert_eq!(berlin.until(evening, time(6, 0)), TimeDelta::hours(7));
+        let night = berlin.at(date(3, 31), time(1, 0));
+        assert_eq!(berlin.until(night, time(6, 0)), TimeDelta::hours(4));
+
+        let autumn_night = berlin.at(date(10, 27), time(1, 0));
+        assert_eq!(berlin.until(autumn_night, time(6, 0)), TimeDelta::hours(6));
+
+        let morning = berlin.at(date(7, 1), time(7, 0));
+        assert_eq!(berlin.until(morning, time(6, 0)), TimeDelta::hours(23));
+    }
+}
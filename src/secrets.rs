@@ -0,0 +1,207 @@
+//! Provider API keys kept in the system keyring (Secret Service, macOS
+//! Keychain or Windows Credential Manager) rather than in plaintext
+//! config.toml. Needs the `keyring` feature; without it nothing is found.
+//! Keys can also come from a secret manager through `api_key_cmd`, and
+//! config files that hold one are checked for loose permissions.
+
+use crate::config::Provider;
+use crate::error::ConfigError;
+use std::fmt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Service name the keys are stored under, one entry per provider.
+#[cfg(feature = "keyring")]
+const SERVICE: &str = "weathr";
+
+#[cfg(feature = "keyring")]
+fn entry(provider: Provider) -> Result<keyring::Entry, ConfigError> {
+    keyring::Entry::new(SERVICE, provider.name()).map_err(|e| keyring_error(provider, e))
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_error(provider: Provider, error: keyring::Error) -> ConfigError {
+    ConfigError::Keyring {
+        provider: provider.name(),
+        reason: error.to_string(),
+    }
+}
+
+/// The stored key for `provider`, if any. A keyring that is locked or
+/// unreachable counts as having none.
+#[cfg(feature = "keyring")]
+pub fn api_key(provider: Provider) -> Option<String> {
+    entry(provider)
+        .ok()?
+        .get_password()
+        .ok()
+        .filter(|key| !key.trim().is_empty())
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn api_key(_provider: Provider) -> Option<String> {
+    None
+}
+
+#[cfg(feature = "keyring")]
+pub fn set_api_key(provider: Provider, key: &str) -> Result<(), ConfigError> {
+    entry(provider)?
+        .set_password(key)
+        .map_err(|e| keyring_error(provider, e))
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn set_api_key(provider: Provider, _key: &str) -> Result<(), ConfigError> {
+    ensure_available(provider)
+}
+
+/// Removes the stored key; `false` when there was none.
+#[cfg(feature = "keyring")]
+pub fn delete_api_key(provider: Provider) -> Result<bool, ConfigError> {
+    match entry(provider)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(keyring_error(provider, e)),
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn delete_api_key(provider: Provider) -> Result<bool, ConfigError> {
+    ensure_available(provider).map(|_| false)
+}
+
+/// Fails when weathr was built without keyring support, so callers can
+/// say so before prompting for a key.
+pub fn ensure_available(provider: Provider) -> Result<(), ConfigError> {
+    if cfg!(feature = "keyring") {
+        return Ok(());
+    }
+    Err(ConfigError::Keyring {
+        provider: provider.name(),
+        reason: "weathr was built without the 'keyring' feature".to_string(),
+    })
+}
+
+/// Runs `command` (an `api_key_cmd` such as `pass show metoffice`) through
+/// the shell and takes the first line it prints as the key, as password
+/// managers put anything else on the lines after.
+pub fn api_key_from_command(provider: Provider, command: &str) -> Result<String, ConfigError> {
+    let failed = |reason: String| {
+        ConfigError::InvalidAPIKey(format!(
+            "api_key_cmd for {} failed: {}",
+            provider.name(),
+            reason
+        ))
+    };
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let output = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| failed(e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or("").trim();
+        return Err(failed(
+            format!("{} {}", output.status, reason).trim().to_string(),
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let key = stdout.lines().next().unwrap_or("").trim();
+    if key.is_empty() {
+        return Err(failed("it printed nothing".to_string()));
+    }
+    Ok(key.to_string())
+}
+
+/// Whether a config file sets an `api_key` for any provider.
+pub fn holds_api_key(config: &toml::Value) -> bool {
+    config
+        .get("provider")
+        .and_then(toml::Value::as_table)
+        .is_some_and(|providers| {
+            providers.values().any(|options| {
+                options
+                    .get("api_key")
+                    .and_then(toml::Value::as_str)
+                    .is_some_and(|key| !key.trim().is_empty())
+            })
+        })
+}
+
+/// Warns when `path`, a config file holding an API key, can be read by
+/// every user on the machine.
+#[cfg(unix)]
+pub fn warn_if_world_readable(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+    if metadata.permissions().mode() & 0o004 != 0 {
+        eprintln!(
+            "Warning: {} holds an API key but every user can read it. Run `chmod 600 {}`, or move the key to `weathr auth set` or `api_key_cmd`.",
+            path.display(),
+            path.display()
+        );
+    }
+}
+
+#[cfg(not(unix))]
+pub fn warn_if_world_readable(_path: &Path) {}
+
+/// Shows whether a key is set without showing the key, for `Debug`.
+pub struct Redacted<'a>(pub &'a str);
+
+impl fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            f.write_str("\"\"")
+        } else {
+            f.write_str("\"***\"")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_holds_api_key() {
+        let config = |toml: &str| toml::from_str::<toml::Value>(toml).unwrap();
+        assert!(holds_api_key(&config(
+            "[provider.met_office]\napi_key = \"secret\""
+        )));
+        assert!(!holds_api_key(&config(
+            "[provider.met_office]\napi_key = \"\"\napi_key_cmd = \"pass show metoffice\""
+        )));
+        assert!(!holds_api_key(&config("theme = \"nord\"")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_api_key_from_command() {
+        let key = api_key_from_command(Provider::MetOffice, "printf 'secret\\nurl: example'");
+        assert_eq!(key.unwrap(), "secret");
+
+        let error = api_key_from_command(Provider::MetOffice, "echo locked >&2; exit 1")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("api_key_cmd for met_office failed"));
+        assert!(error.contains("locked"));
+
+        assert!(api_key_from_command(Provider::MetOffice, "true").is_err());
+    }
+
+    #[test]
+    fn test_redacted_debug() {
+        assert_eq!(format!("{:?}", Redacted("secret")), "\"***\"");
+        assert_eq!(format!("{:?}", Redacted("")), "\"\"");
+    }
+}
@@ -0,0 +1,150 @@
+use crate::weather::archive::OpenMeteoArchive;
+use crate::weather::{
+    WeatherCondition, WeatherData, WeatherLocation, WeatherUnits, format_precipitation,
+    format_temperature, format_wind_speed,
+};
+use std::time::Duration;
+
+/// Hours of forecast covered by the summary.
+const SUMMARY_HOURS: u32 = 12;
+/// Quitting shouldn't hang on a slow network.
+const FORECAST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Hourly precipitation below this (mm) counts as dry.
+const WET_HOUR_MM: f64 = 0.1;
+
+/// Prints a plain-text paragraph about the current weather and the next
+/// twelve hours, for the scrollback after quitting. The forecast part is
+/// left out if it can't be fetched quickly.
+pub async fn print(current: &WeatherData, location: &WeatherLocation, units: &WeatherUnits) {
+    let upcoming = tokio::time::timeout(
+        FORECAST_TIMEOUT,
+        OpenMeteoArchive::recent().fetch_upcoming(location, SUMMARY_HOURS),
+    )
+    .await
+    .ok()
+    .and_then(Result::ok)
+    .unwrap_or_default();
+
+    println!("{}", compose(current, &upcoming, units));
+}
+
+fn compose(current: &WeatherData, upcoming: &[WeatherData], units: &WeatherUnits) -> String {
+    let (temp, temp_unit) = format_temperature(current.temperature, units.temperature);
+    let (wind, wind_unit) = format_wind_speed(current.wind_speed, units.wind_speed);
+    let mut summary = format!(
+        "Now: {}, {:.1}{}, wind {:.1}{}.",
+        current.condition.label(),
+        temp,
+        temp_unit,
+        wind,
+        wind_unit
+    );
+
+    let Some(condition) = prevailing_condition(upcoming) else {
+        return summary;
+    };
+
+    let (low, high) = upcoming
+        .iter()
+        .map(|frame| format_temperature(frame.temperature, units.temperature).0)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), t| {
+            (low.min(t), high.max(t))
+        });
+    summary.push_str(&format!(
+        " Next {} hours: mostly {}, {:.0} to {:.0}{}",
+        upcoming.len(),
+        condition.label(),
+        low,
+        high,
+        temp_unit
+    ));
+
+    let total: f64 = upcoming.iter().map(|frame| frame.precipitation).sum();
+    match upcoming
+        .iter()
+        .find(|frame| frame.precipitation >= WET_HOUR_MM)
+    {
+        Some(first_wet) => {
+            let (amount, unit) = format_precipitation(total, units.precipitation);
+            summary.push_str(&format!(
+                ", {:.1}{} of precipitation from {}.",
+                amount,
+                unit,
+                hour_of(&first_wet.timestamp)
+            ));
+        }
+        None => summary.push_str(", staying dry."),
+    }
+
+    summary
+}
+
+/// The condition covering the most hours; ties go to the earliest.
+fn prevailing_condition(frames: &[WeatherData]) -> Option<WeatherCondition> {
+    let hours = |condition: WeatherCondition| {
+        frames
+            .iter()
+            .filter(|frame| frame.condition == condition)
+            .count()
+    };
+    frames
+        .iter()
+        .rev()
+        .map(|frame| frame.condition)
+        .max_by_key(|condition| hours(*condition))
+}
+
+/// "15:00" from "2024-01-15T15:00".
+fn hour_of(timestamp: &str) -> &str {
+    timestamp.get(11..16).unwrap_or(timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(
+        hour: u32,
+        condition: WeatherCondition,
+        temperature: f64,
+        precipitation: f64,
+    ) -> WeatherData {
+        WeatherData {
+            temperature,
+            precipitation,
+            wind_speed: 5.0,
+            timestamp: format!("2024-01-15T{:02}:00", hour),
+            ..WeatherData::sample(condition)
+        }
+    }
+
+    #[test]
+    fn test_summary_with_rain_ahead() {
+        let current = frame(12, WeatherCondition::Overcast, 14.2, 0.0);
+        let upcoming = [
+            frame(12, WeatherCondition::Overcast, 14.0, 0.0),
+            frame(13, WeatherCondition::Overcast, 15.1, 0.0),
+            frame(14, WeatherCondition::LightRain, 13.0, 0.6),
+            frame(15, WeatherCondition::Rain, 11.6, 2.4),
+            frame(16, WeatherCondition::Overcast, 10.2, 0.0),
+        ];
+
+        assert_eq!(
+            compose(&current, &upcoming, &WeatherUnits::metric()),
+            "Now: Overcast, 14.2°C, wind 18.0km/h. Next 5 hours: mostly Overcast, \
+             10 to 15°C, 3.0mm of precipitation from 14:00."
+        );
+    }
+
+    #[test]
+    fn test_summary_without_forecast() {
+        let current = frame(12, WeatherCondition::Clear, 20.0, 0.0);
+        assert_eq!(
+            compose(&current, &[], &WeatherUnits::metric()),
+            "Now: Clear, 20.0°C, wind 18.0km/h."
+        );
+
+        let upcoming = [frame(12, WeatherCondition::Clear, 20.0, 0.0)];
+        assert!(compose(&current, &upcoming, &WeatherUnits::metric()).ends_with("staying dry."));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::config::{Config, Provider};
+use crate::error::WeatherError;
+use crate::history;
+use crate::weather::provider;
+use crate::weather::severity::Severity;
+use crate::weather::types::IconSet;
+use crate::weather::{
+    WeatherClient, WeatherCondition, WeatherData, WeatherLocation, WeatherUnits,
+    format_precipitation, format_temperature, format_wind_speed,
+};
+use chrono::Local;
+use std::time::Duration;
+
+pub struct WatchOptions {
+    /// Width of a temperature bucket in the configured display unit.
+    pub bucket: f64,
+    pub interval: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WatchState {
+    condition: WeatherCondition,
+    temperature_bucket: i64,
+    severity: Option<Severity>,
+}
+
+impl WatchState {
+    fn from_weather(weather: &WeatherData, units: &WeatherUnits, bucket: f64) -> Self {
+        let (temp, _) = format_temperature(weather.temperature, units.temperature);
+        Self {
+            condition: weather.condition,
+            temperature_bucket: (temp / bucket).floor() as i64,
+            severity: Severity::of(weather),
+        }
+    }
+}
+
+/// Nerd Font icons go before the condition; the others add nothing to it.
+fn format_line(weather: &WeatherData, units: &WeatherUnits, icons: IconSet) -> String {
+    let (temp, temp_unit) = format_temperature(weather.temperature, units.temperature);
+    let (wind, wind_unit) = format_wind_speed(weather.wind_speed, units.wind_speed);
+    let (precip, precip_unit) = format_precipitation(weather.precipitation, units.precipitation);
+
+    let icon = match icons {
+        IconSet::Nerd => format!("{} ", weather.condition.icon(icons)),
+        IconSet::Unicode | IconSet::Ascii => String::new(),
+    };
+    let alert = Severity::of(weather)
+        .map(|severity| format!("  storm {}", severity.label()))
+        .unwrap_or_default();
+    format!(
+        "{}  {}{}  temp {:.1}{}  wind {:.1}{}  precip {:.1}{}{}",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        icon,
+        weather.condition.label(),
+        temp,
+        temp_unit,
+        wind,
+        wind_unit,
+        precip,
+        precip_unit,
+        alert
+    )
+}
+
+fn live_client(
+    config: &Config,
+    interval: Duration,
+) -> Result<(Provider, WeatherClient, WeatherLocation), WeatherError> {
+    let (provider_kind, provider) = provider::from_config(&config.provider)?;
+    let location = WeatherLocation {
+        latitude: config.location.latitude,
+        longitude: config.location.longitude,
+        elevation: None,
+    };
+    Ok((
+        provider_kind,
+        WeatherClient::new(provider, interval),
+        location,
+    ))
+}
+
+/// Fetches the current weather once and prints a single line.
+pub async fn print_once(config: &Config) -> Result<(), WeatherError> {
+    let (provider_kind, client, location) = live_client(config, Duration::ZERO)?;
+    let weather = client
+        .get_current_weather(&location, &config.units, provider_kind)
+        .await?;
+    if config.record_history && !client.last_was_cached() {
+        history::record(&weather, &location).await;
+    }
+    println!("{}", format_line(&weather, &config.units, config.icons));
+    Ok(())
+}
+
+/// Polls the configured provider and prints a line only when the condition,
+/// temperature bucket or alert level changes. Runs until interrupted.
+pub async fn run(config: &Config, options: WatchOptions) -> Result<(), WeatherError> {
+    // Polling faster than the provider's daily budget allows only gets us blocked
+    let interval = options
+        .interval
+        .max(provider::min_refresh_interval(&config.provider));
+    let (provider_kind, client, location) = live_client(config, interval)?;
+    let units = config.units;
+    let mut last_state: Option<WatchState> = None;
+
+    loop {
+        match client
+            .get_current_weather(&location, &units, provider_kind)
+            .await
+        {
+            Ok(weather) => {
+                if config.record_history && !client.last_was_cached() {
+                    history::record(&weather, &location).await;
+                }
+                let state = WatchState::from_weather(&weather, &units, options.bucket);
+                if last_state != Some(state) {
+                    println!("{}", format_line(&weather, &units, config.icons));
+                    last_state = Some(state);
+                }
+            }
+            Err(e) => {
+                eprintln!(
+                    "{}  Failed to fetch weather: {}",
+                    Local::now().format("%Y-%m-%d %H:%M:%S"),
+                    e
+                );
+            }
+        }
+
+        tokio::time::sleep(client.refresh_delay().await).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weather(condition: WeatherCondition, temperature: f64) -> WeatherData {
+        WeatherData {
+            temperature,
+            wind_speed: 3.0,
+            wind_direction: 90.0,
+            timestamp: "2024-01-01T12:00".to_string(),
+            ..WeatherData::sample(condition)
+        }
+    }
+
+    #[test]
+    fn test_state_ignores_changes_within_bucket() {
+        let units = WeatherUnits::metric();
+        let a = WatchState::from_weather(&weather(WeatherCondition::Clear, 12.1), &units, 2.0);
+        let b = WatchState::from_weather(&weather(WeatherCondition::Clear, 13.9), &units, 2.0);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_state_changes_on_bucket_or_condition() {
+        let units = WeatherUnits::metric();
+        let a = WatchState::from_weather(&weather(WeatherCondition::Clear, 13.9), &units, 2.0);
+        let b = WatchState::from_weather(&weather(WeatherCondition::Clear, 14.1), &units, 2.0);
+        let c = WatchState::from_weather(&weather(WeatherCondition::Rain, 13.9), &units, 2.0);
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_state_changes_on_alert_level() {
+        let units = WeatherUnits::metric();
+        let windy = |wind_speed| WeatherData {
+            wind_speed,
+            ..weather(WeatherCondition::Overcast, 13.0)
+        };
+        let calm = WatchState::from_weather(&windy(3.0), &units, 2.0);
+        let gale = WatchState::from_weather(&windy(18.0), &units, 2.0);
+        let storm = WatchState::from_weather(&windy(25.0), &units, 2.0);
+        assert_ne!(calm, gale);
+        assert_ne!(gale, storm);
+        assert_ne!(storm, calm);
+        assert!(format_line(&windy(25.0), &units, IconSet::Ascii).ends_with("  storm warning"));
+        assert!(!format_line(&windy(3.0), &units, IconSet::Ascii).contains("storm"));
+    }
+}
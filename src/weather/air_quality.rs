@@ -0,0 +1,251 @@
+//! Pollen and pollution from the Open-Meteo Air Quality API, which the
+//! scene shows as motes drifting through the air.
+
+use crate::error::{NetworkError, WeatherError};
+use crate::http::{DEFAULT_TIMEOUT_SECS, RequestOptions};
+use crate::weather::types::WeatherLocation;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Deserialize;
+
+const OPEN_METEO_AIR_QUALITY_URL: &str = "https://air-quality-api.open-meteo.com/v1/air-quality";
+const HOURLY: &str =
+    "us_aqi,alder_pollen,birch_pollen,grass_pollen,mugwort_pollen,olive_pollen,ragweed_pollen";
+/// Grains per m³ of the most abundant pollen from which it shows, and at
+/// which it's thickest.
+const POLLEN_HIGH: f64 = 50.0;
+const POLLEN_VERY_HIGH: f64 = 200.0;
+/// US AQI from which ash and smoke hang in the air, "unhealthy for
+/// sensitive groups", and at which they're thickest, "hazardous".
+const AQI_UNHEALTHY: f64 = 101.0;
+const AQI_HAZARDOUS: f64 = 301.0;
+
+/// The air at the current hour.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AirQuality {
+    pub us_aqi: Option<f64>,
+    /// Grains per m³ of the most abundant pollen. Only forecast for Europe.
+    pub pollen: Option<f64>,
+}
+
+impl AirQuality {
+    /// How thick the pollen hangs, from 0 below a high count to 1.
+    pub fn pollen_level(&self) -> f32 {
+        level(self.pollen, POLLEN_HIGH, POLLEN_VERY_HIGH)
+    }
+
+    /// How thick the ash and smoke hang, from 0 while the air is healthy
+    /// to 1.
+    pub fn smoke_level(&self) -> f32 {
+        level(self.us_aqi, AQI_UNHEALTHY, AQI_HAZARDOUS)
+    }
+}
+
+/// A faint 0.2 at `from`, rising to 1 at `full`.
+fn level(value: Option<f64>, from: f64, full: f64) -> f32 {
+    match value {
+        Some(value) if value >= from => {
+            (0.2 + 0.8 * (value - from) / (full - from)).min(1.0) as f32
+        }
+        _ => 0.0,
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AirQualityResponse {
+    hourly: AirQualityHourly,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AirQualityHourly {
+    time: Vec<String>,
+    us_aqi: Vec<Option<f64>>,
+    alder_pollen: Vec<Option<f64>>,
+    birch_pollen: Vec<Option<f64>>,
+    grass_pollen: Vec<Option<f64>>,
+    mugwort_pollen: Vec<Option<f64>>,
+    olive_pollen: Vec<Option<f64>>,
+    ragweed_pollen: Vec<Option<f64>>,
+}
+
+pub struct OpenMeteoAirQuality {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl OpenMeteoAirQuality {
+    pub fn new() -> Self {
+        let client = RequestOptions::default()
+            .client()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            base_url: OPEN_METEO_AIR_QUALITY_URL.to_string(),
+        }
+    }
+
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        format!(
+            "{}?latitude={}&longitude={}&hourly={}&forecast_days=1&timezone=auto",
+            self.base_url, location.latitude, location.longitude, HOURLY
+        )
+    }
+
+    async fn fetch_today(
+        &self,
+        location: &WeatherLocation,
+    ) -> Result<AirQualityHourly, WeatherError> {
+        let url = self.build_url(location);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+            })?;
+
+        let data: AirQualityResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+        })?;
+        Ok(data.hourly)
+    }
+}
+
+impl Default for OpenMeteoAirQuality {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Today's air for one place, fetched once per day and place.
+pub struct AirQualityForecast {
+    air_quality: OpenMeteoAirQuality,
+    fetched_for: Option<(NaiveDate, f64, f64)>,
+    hours: AirQualityHourly,
+}
+
+impl Default for AirQualityForecast {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AirQualityForecast {
+    pub fn new() -> Self {
+        Self {
+            air_quality: OpenMeteoAirQuality::new(),
+            fetched_for: None,
+            hours: AirQualityHourly::default(),
+        }
+    }
+
+    /// The air at the hour of `now`, a timestamp in the location's local
+    /// time ("YYYY-MM-DDTHH:MM"), or `None` when nothing is forecast there.
+    pub async fn at(&mut self, location: &WeatherLocation, now: &str) -> Option<AirQuality> {
+        let now = NaiveDateTime::parse_from_str(now.get(..16)?, "%Y-%m-%dT%H:%M").ok()?;
+
+        let key = (now.date(), location.latitude, location.longitude);
+        if self.fetched_for != Some(key) {
+            self.hours = self.air_quality.fetch_today(location).await.ok()?;
+            self.fetched_for = Some(key);
+        }
+
+        conditions_at(&self.hours, now)
+    }
+}
+
+fn conditions_at(hours: &AirQualityHourly, now: NaiveDateTime) -> Option<AirQuality> {
+    let wanted = now.format("%Y-%m-%dT%H").to_string();
+    let i = hours
+        .time
+        .iter()
+        .position(|time| time.starts_with(&wanted))?;
+    let value = |series: &[Option<f64>]| series.get(i).copied().flatten();
+
+    let us_aqi = value(&hours.us_aqi);
+    let pollen = [
+        &hours.alder_pollen,
+        &hours.birch_pollen,
+        &hours.grass_pollen,
+        &hours.mugwort_pollen,
+        &hours.olive_pollen,
+        &hours.ragweed_pollen,
+    ]
+    .into_iter()
+    .filter_map(|series| value(series))
+    .reduce(f64::max);
+    if us_aqi.is_none() && pollen.is_none() {
+        return None;
+    }
+
+    Some(AirQuality { us_aqi, pollen })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hours(json: &str) -> AirQualityHourly {
+        serde_json::from_str::<AirQualityResponse>(json)
+            .unwrap()
+            .hourly
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_most_abundant_pollen_counts() {
+        let hours = hours(
+            r#"{"hourly": {
+                "time": ["2026-05-02T00:00", "2026-05-02T01:00"],
+                "us_aqi": [35, 180],
+                "birch_pollen": [12.0, 90.0],
+                "grass_pollen": [60.5, null],
+                "ragweed_pollen": [null, null]
+            }}"#,
+        );
+
+        let night = conditions_at(&hours, at("2026-05-02T00:40")).unwrap();
+        assert_eq!(night.pollen, Some(60.5));
+        assert_eq!(night.us_aqi, Some(35.0));
+        assert_eq!(night.smoke_level(), 0.0);
+
+        let later = conditions_at(&hours, at("2026-05-02T01:00")).unwrap();
+        assert_eq!(later.pollen, Some(90.0));
+        assert!(later.smoke_level() > 0.2 && later.smoke_level() < 1.0);
+
+        assert!(conditions_at(&hours, at("2026-05-03T01:00")).is_none());
+    }
+
+    #[test]
+    fn test_outside_europe_only_the_aqi() {
+        let hours = hours(
+            r#"{"hourly": {
+                "time": ["2026-05-02T00:00"],
+                "us_aqi": [42],
+                "grass_pollen": [null]
+            }}"#,
+        );
+        let air = conditions_at(&hours, at("2026-05-02T00:00")).unwrap();
+        assert_eq!(air.pollen, None);
+        assert_eq!(air.pollen_level(), 0.0);
+    }
+
+    #[test]
+    fn test_levels() {
+        let air = |us_aqi, pollen| AirQuality {
+            us_aqi: Some(us_aqi),
+            pollen: Some(pollen),
+        };
+        assert_eq!(air(50.0, 49.0).pollen_level(), 0.0);
+        assert_eq!(air(50.0, 50.0).pollen_level(), 0.2);
+        assert_eq!(air(50.0, 500.0).pollen_level(), 1.0);
+        assert_eq!(air(101.0, 0.0).smoke_level(), 0.2);
+        assert_eq!(air(450.0, 0.0).smoke_level(), 1.0);
+    }
+}
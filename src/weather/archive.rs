@@ -0,0 +1,468 @@
+use crate::config::Provider;
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::http::{DEFAULT_TIMEOUT_SECS, RequestOptions};
+use crate::scheduler::Scheduler;
+use crate::weather::normalizer::WeatherNormalizer;
+use crate::weather::provider::WeatherProviderResponse;
+use crate::weather::provider::open_meteo::moon_phase_at;
+use crate::weather::types::{
+    CelestialEvents, TemperatureRange, WeatherCondition, WeatherData, WeatherLocation,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Deserialize;
+
+const OPEN_METEO_ARCHIVE_URL: &str = "https://archive-api.open-meteo.com/v1/archive";
+/// The forecast API also serves the last few months of hourly data, which the
+/// reanalysis archive only gets about five days later.
+const OPEN_METEO_RECENT_URL: &str = "https://api.open-meteo.com/v1/forecast";
+const ARCHIVE_ATTRIBUTION: &str = "Weather data by Open-Meteo.com";
+
+pub struct OpenMeteoArchive {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveResponse {
+    hourly: ArchiveHourly,
+    daily: ArchiveDaily,
+    /// How far the timestamps' clock is ahead of UTC.
+    #[serde(default)]
+    utc_offset_seconds: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveHourly {
+    time: Vec<String>,
+    temperature_2m: Vec<Option<f64>>,
+    precipitation: Vec<Option<f64>>,
+    weather_code: Vec<Option<f64>>,
+    wind_speed_10m: Vec<Option<f64>>,
+    wind_direction_10m: Vec<Option<f64>>,
+    is_day: Vec<Option<f64>>,
+    #[serde(default)]
+    pressure_msl: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveDaily {
+    time: Vec<String>,
+    sunrise: Vec<Option<String>>,
+    sunset: Vec<Option<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyResponse {
+    daily: DailySeries,
+    #[serde(default)]
+    timezone: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailySeries {
+    time: Vec<String>,
+    weather_code: Vec<Option<f64>>,
+    temperature_2m_min: Vec<Option<f64>>,
+    temperature_2m_max: Vec<Option<f64>>,
+}
+
+/// A day's weather at a glance: its most significant condition and its
+/// low and high.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaySummary {
+    pub date: NaiveDate,
+    pub condition: WeatherCondition,
+    pub range: TemperatureRange,
+}
+
+impl OpenMeteoArchive {
+    pub fn new() -> Self {
+        let client = RequestOptions::default()
+            .client()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            base_url: OPEN_METEO_ARCHIVE_URL.to_string(),
+        }
+    }
+
+    /// An archive for the last few days, including yesterday, and the
+    /// forecast for the coming hours.
+    pub fn recent() -> Self {
+        Self {
+            base_url: OPEN_METEO_RECENT_URL.to_string(),
+            ..Self::new()
+        }
+    }
+
+    /// `range` selects the hours, e.g. "start_date=...&end_date=...", on the
+    /// clock of `timezone` ("auto" for the location's own).
+    fn build_url(&self, location: &WeatherLocation, range: &str, timezone: &str) -> String {
+        format!(
+            "{}?latitude={}&longitude={}&{}&hourly=temperature_2m,precipitation,weather_code,wind_speed_10m,wind_direction_10m,is_day,pressure_msl&daily=sunrise,sunset&wind_speed_unit=ms&timezone={}",
+            self.base_url, location.latitude, location.longitude, range, timezone
+        )
+    }
+
+    /// Fetches hourly observations between `from` and `to` (inclusive) as a
+    /// chronological list of normalized `WeatherData` frames.
+    pub async fn fetch_hourly(
+        &self,
+        location: &WeatherLocation,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<WeatherData>, WeatherError> {
+        let range = format!(
+            "start_date={}&end_date={}",
+            from.format("%Y-%m-%d"),
+            to.format("%Y-%m-%d")
+        );
+        self.fetch(&self.build_url(location, &range, "auto")).await
+    }
+
+    /// Fetches forecast frames for the next `hours` hours, starting with the
+    /// current one. Only works with [`OpenMeteoArchive::recent`].
+    pub async fn fetch_upcoming(
+        &self,
+        location: &WeatherLocation,
+        hours: u32,
+    ) -> Result<Vec<WeatherData>, WeatherError> {
+        let range = format!("forecast_hours={}", hours);
+        self.fetch(&self.build_url(location, &range, "auto")).await
+    }
+
+    /// Fetches the forecast frame for the hour starting at `hour`, in UTC,
+    /// e.g. for when a route gets to `location`. Only works with
+    /// [`OpenMeteoArchive::recent`], up to about two weeks ahead.
+    pub async fn fetch_hour(
+        &self,
+        location: &WeatherLocation,
+        hour: NaiveDateTime,
+    ) -> Result<WeatherData, WeatherError> {
+        let hour = hour.format("%Y-%m-%dT%H:00");
+        let range = format!("start_hour={}&end_hour={}", hour, hour);
+        let mut frames = self.fetch(&self.build_url(location, &range, "GMT")).await?;
+        Ok(frames.remove(0))
+    }
+
+    /// Fetches a summary of each day from `from` to `to` (inclusive), with
+    /// the clock the days are counted on. With [`OpenMeteoArchive::recent`]
+    /// that covers the last few months and the next two weeks.
+    pub async fn fetch_daily(
+        &self,
+        location: &WeatherLocation,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<(Vec<DaySummary>, Scheduler), WeatherError> {
+        let url = format!(
+            "{}?latitude={}&longitude={}&start_date={}&end_date={}&daily=weather_code,temperature_2m_min,temperature_2m_max&timezone=auto",
+            self.base_url,
+            location.latitude,
+            location.longitude,
+            from.format("%Y-%m-%d"),
+            to.format("%Y-%m-%d")
+        );
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+            })?;
+
+        let data: DailyResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+        })?;
+
+        let days = Self::to_days(data.daily);
+        if days.is_empty() {
+            return Err(WeatherError::Data(DataError::NoData));
+        }
+        let scheduler = data
+            .timezone
+            .as_deref()
+            .map_or_else(Scheduler::default, Scheduler::for_zone);
+        Ok((days, scheduler))
+    }
+
+    /// Days missing any value are left out.
+    fn to_days(daily: DailySeries) -> Vec<DaySummary> {
+        daily
+            .time
+            .iter()
+            .enumerate()
+            .filter_map(|(i, time)| {
+                let value = |series: &[Option<f64>]| series.get(i).copied().flatten();
+                Some(DaySummary {
+                    date: NaiveDate::parse_from_str(time, "%Y-%m-%d").ok()?,
+                    condition: WeatherNormalizer::wmo_code_to_condition(
+                        value(&daily.weather_code)?.round() as i32,
+                    ),
+                    range: TemperatureRange {
+                        min: value(&daily.temperature_2m_min)?,
+                        max: value(&daily.temperature_2m_max)?,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Vec<WeatherData>, WeatherError> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                WeatherError::Network(NetworkError::from_reqwest(e, url, DEFAULT_TIMEOUT_SECS))
+            })?;
+
+        let data: ArchiveResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, url, DEFAULT_TIMEOUT_SECS))
+        })?;
+
+        let frames = Self::to_frames(data);
+        if frames.is_empty() {
+            return Err(WeatherError::Data(DataError::NoData));
+        }
+
+        Ok(frames)
+    }
+
+    fn to_frames(data: ArchiveResponse) -> Vec<WeatherData> {
+        let hourly = data.hourly;
+        let mut frames = Vec::with_capacity(hourly.time.len());
+
+        for (i, time) in hourly.time.into_iter().enumerate() {
+            let value = |series: &[Option<f64>]| series.get(i).copied().flatten();
+
+            let (Some(temperature), Some(weather_code)) =
+                (value(&hourly.temperature_2m), value(&hourly.weather_code))
+            else {
+                continue;
+            };
+
+            let Ok(timestamp) = NaiveDateTime::parse_from_str(&time, "%Y-%m-%dT%H:%M") else {
+                continue;
+            };
+
+            let is_day = value(&hourly.is_day).unwrap_or(1.0) >= 1.0;
+            let sun = Self::sun_for_day(&data.daily, timestamp.date(), is_day);
+
+            let response = WeatherProviderResponse {
+                weather_code: weather_code.round() as i32,
+                temperature,
+                precipitation: value(&hourly.precipitation).unwrap_or(0.0),
+                wind_speed: value(&hourly.wind_speed_10m).unwrap_or(0.0),
+                wind_direction: value(&hourly.wind_direction_10m).unwrap_or(0.0),
+                sun,
+                moon_phase: Some(moon_phase_at(timestamp, data.utc_offset_seconds)),
+                pressure: value(&hourly.pressure_msl),
+                disagreement: None,
+                humidity: None,
+                apparent_temperature: None,
+                uv_index: None,
+                source: Provider::OpenMeteo.abbreviation().to_string(),
+                nowcast: Vec::new(),
+                hourly: Vec::new(),
+                today: None,
+                details: Default::default(),
+                timestamp: time,
+                attribution: ARCHIVE_ATTRIBUTION.to_string(),
+            };
+
+            frames.push(WeatherNormalizer::normalize(response));
+        }
+
+        frames
+    }
+
+    fn sun_for_day(daily: &ArchiveDaily, date: NaiveDate, is_day: bool) -> CelestialEvents {
+        let day = date.format("%Y-%m-%d").to_string();
+        let Some(idx) = daily.time.iter().position(|d| *d == day) else {
+            return CelestialEvents::from_bool(is_day);
+        };
+
+        let parse = |series: &[Option<String>]| {
+            series
+                .get(idx)
+                .cloned()
+                .flatten()
+                .and_then(|t| NaiveDateTime::parse_from_str(&t, "%Y-%m-%dT%H:%M").ok())
+                .map(|dt| dt.time())
+        };
+
+        let rise = parse(&daily.sunrise);
+        let set = parse(&daily.sunset);
+        let upper_transit = match (rise, set) {
+            (Some(rise), Some(set)) => {
+                Some(rise + chrono::Duration::seconds((set - rise).num_seconds() / 2))
+            }
+            _ => None,
+        };
+
+        CelestialEvents {
+            is_day,
+            begin_twilight: rise,
+            rise,
+            upper_transit,
+            set,
+            end_twilight: set,
+        }
+    }
+}
+
+impl Default for OpenMeteoArchive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Yesterday's hourly temperatures for one place, fetched once per day and
+/// place.
+pub struct YesterdayTemperatures {
+    archive: OpenMeteoArchive,
+    fetched_for: Option<(NaiveDate, f64, f64)>,
+    frames: Vec<WeatherData>,
+}
+
+impl Default for YesterdayTemperatures {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl YesterdayTemperatures {
+    pub fn new() -> Self {
+        Self {
+            archive: OpenMeteoArchive::recent(),
+            fetched_for: None,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Temperature at the same hour yesterday as `now`, a timestamp in the
+    /// location's local time ("YYYY-MM-DDTHH:MM").
+    pub async fn same_hour(&mut self, location: &WeatherLocation, now: &str) -> Option<f64> {
+        let now = NaiveDateTime::parse_from_str(now.get(..16)?, "%Y-%m-%dT%H:%M").ok()?;
+        let yesterday = now.date().pred_opt()?;
+
+        let key = (yesterday, location.latitude, location.longitude);
+        if self.fetched_for != Some(key) {
+            self.frames = self
+                .archive
+                .fetch_hourly(location, yesterday, yesterday)
+                .await
+                .ok()?;
+            self.fetched_for = Some(key);
+        }
+
+        same_hour(&self.frames, now)
+    }
+}
+
+fn same_hour(frames: &[WeatherData], now: NaiveDateTime) -> Option<f64> {
+    let wanted = (now - chrono::Duration::days(1))
+        .format("%Y-%m-%dT%H")
+        .to_string();
+    frames
+        .iter()
+        .find(|frame| frame.timestamp.starts_with(&wanted))
+        .map(|frame| frame.temperature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn sample_response() -> ArchiveResponse {
+        serde_json::from_str(
+            r#"{
+                "hourly": {
+                    "time": ["2024-12-01T00:00", "2024-12-01T12:00", "2024-12-01T13:00"],
+                    "temperature_2m": [1.5, 4.0, null],
+                    "precipitation": [0.0, 1.2, 0.0],
+                    "weather_code": [0, 61, 3],
+                    "wind_speed_10m": [2.0, 5.5, 3.0],
+                    "wind_direction_10m": [180, 200, 210],
+                    "is_day": [0, 1, 1]
+                },
+                "daily": {
+                    "time": ["2024-12-01"],
+                    "sunrise": ["2024-12-01T08:00"],
+                    "sunset": ["2024-12-01T16:00"]
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_frames_skip_missing_values() {
+        let frames = OpenMeteoArchive::to_frames(sample_response());
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].condition, WeatherCondition::Clear);
+        assert!(!frames[0].sun.is_day);
+        assert_eq!(frames[1].condition, WeatherCondition::LightRain);
+        assert_eq!(frames[1].precipitation, 1.2);
+    }
+
+    #[test]
+    fn test_same_hour_yesterday() {
+        let frames = OpenMeteoArchive::to_frames(sample_response());
+        let at = |s| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M").unwrap();
+
+        assert_eq!(same_hour(&frames, at("2024-12-02T12:40")), Some(4.0));
+        // 13:00 had no temperature
+        assert_eq!(same_hour(&frames, at("2024-12-02T13:00")), None);
+    }
+
+    #[test]
+    fn test_frames_carry_sun_times() {
+        let frames = OpenMeteoArchive::to_frames(sample_response());
+        let sun = frames[1].sun;
+
+        assert!(sun.is_day);
+        assert_eq!(sun.rise, NaiveTime::from_hms_opt(8, 0, 0));
+        assert_eq!(sun.upper_transit, NaiveTime::from_hms_opt(12, 0, 0));
+        assert_eq!(sun.set, NaiveTime::from_hms_opt(16, 0, 0));
+    }
+
+    #[test]
+    fn test_days_skip_missing_values() {
+        let daily: DailyResponse = serde_json::from_str(
+            r#"{
+                "daily": {
+                    "time": ["2026-10-15", "2026-10-16", "2026-10-17"],
+                    "weather_code": [61, 0, null],
+                    "temperature_2m_min": [6.2, 4.0, 5.0],
+                    "temperature_2m_max": [11.5, 14.1, 12.0]
+                }
+            }"#,
+        )
+        .unwrap();
+        let days = OpenMeteoArchive::to_days(daily.daily);
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].condition, WeatherCondition::LightRain);
+        assert_eq!(days[1].date, NaiveDate::from_ymd_opt(2026, 10, 16).unwrap());
+        assert_eq!(days[1].range.max, 14.1);
+    }
+
+    #[test]
+    fn test_moon_phase_full_moon() {
+        // Full moon on 2024-12-15 09:01 UTC, 10:01 in Berlin
+        let full = NaiveDate::from_ymd_opt(2024, 12, 15)
+            .unwrap()
+            .and_hms_opt(10, 1, 0)
+            .unwrap();
+        let phase = moon_phase_at(full, 3600);
+        assert!((phase - 0.5).abs() < 0.05, "phase was {}", phase);
+    }
+}
@@ -0,0 +1,241 @@
+//! Offline sunrise, sunset, civil twilight and moon phase, computed from
+//! latitude, longitude and the clock at the place.
+//!
+//! Uses the NOAA/Meeus low-precision solar equations (good to about a minute
+//! outside the polar regions) and a mean synodic month for the moon.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+use crate::{
+    error::WeatherError,
+    weather::{
+        WeatherLocation, WeatherUnits,
+        provider::supplementary::{
+            SupplementaryProviderRequest, SupplementaryProviderResponse,
+            SupplementaryWeatherProvider,
+        },
+        timezone::TimeZoneLookup,
+        types::CelestialEvents,
+    },
+};
+
+/// Julian date of the J2000.0 epoch.
+const J2000: f64 = 2451545.0;
+/// Julian date of the Unix epoch.
+const UNIX_EPOCH_JD: f64 = 2440587.5;
+/// Mean length of a lunar cycle in days.
+const SYNODIC_MONTH: f64 = 29.530588853;
+/// A new moon (2000-01-06 18:14 UTC) that phases are counted from.
+const REFERENCE_NEW_MOON_JD: f64 = 2451550.1;
+const OBLIQUITY_DEG: f64 = 23.4397;
+
+/// Solar altitude at sunrise/sunset, allowing for refraction and the
+/// sun's radius.
+const SUNRISE_ALTITUDE_DEG: f64 = -0.833;
+/// Solar altitude at the start of morning and end of evening civil twilight.
+const CIVIL_TWILIGHT_ALTITUDE_DEG: f64 = -6.0;
+
+/// Sun events for one day, in UTC. Rise/set and twilight are `None` when the
+/// sun never crosses that altitude (polar day or night).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunTimes {
+    pub dawn: Option<DateTime<Utc>>,
+    pub rise: Option<DateTime<Utc>>,
+    pub transit: DateTime<Utc>,
+    pub set: Option<DateTime<Utc>>,
+    pub dusk: Option<DateTime<Utc>>,
+    /// The sun stays above the horizon all day.
+    pub midnight_sun: bool,
+}
+
+fn julian_date(time: DateTime<Utc>) -> f64 {
+    time.timestamp_millis() as f64 / 86_400_000.0 + UNIX_EPOCH_JD
+}
+
+fn from_julian_date(jd: f64) -> DateTime<Utc> {
+    let millis = ((jd - UNIX_EPOCH_JD) * 86_400_000.0).round() as i64;
+    DateTime::from_timestamp_millis(millis).unwrap_or_default()
+}
+
+/// Sunrise, sunset, solar noon and civil twilight on `date` at the given
+/// coordinates (degrees, east positive).
+pub fn sun_times(date: NaiveDate, latitude: f64, longitude: f64) -> SunTimes {
+    let noon = date.and_hms_opt(12, 0, 0).unwrap_or_default().and_utc();
+    let day = (julian_date(noon) - J2000 + 0.0008).round();
+    let mean_solar_time = day - longitude / 360.0;
+
+    let anomaly = (357.5291 + 0.98560028 * mean_solar_time).rem_euclid(360.0);
+    let m = anomaly.to_radians();
+    let center = 1.9148 * m.sin() + 0.02 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+    let ecliptic_longitude = (anomaly + center + 180.0 + 102.9372).rem_euclid(360.0);
+    let lambda = ecliptic_longitude.to_radians();
+
+    let transit = J2000 + mean_solar_time + 0.0053 * m.sin() - 0.0069 * (2.0 * lambda).sin();
+    let declination = (lambda.sin() * OBLIQUITY_DEG.to_radians().sin()).asin();
+    let phi = latitude.to_radians();
+
+    // Hour angle at which the sun reaches `altitude`, or why it doesn't
+    let hour_angle = |altitude: f64| -> Result<f64, bool> {
+        let cos_omega = (altitude.to_radians().sin() - phi.sin() * declination.sin())
+            / (phi.cos() * declination.cos());
+        if cos_omega < -1.0 {
+            Err(true)
+        } else if cos_omega > 1.0 {
+            Err(false)
+        } else {
+            Ok(cos_omega.acos().to_degrees())
+        }
+    };
+    let crossing = |altitude: f64, sign: f64| {
+        hour_angle(altitude)
+            .ok()
+            .map(|omega| from_julian_date(transit + sign * omega / 360.0))
+    };
+
+    SunTimes {
+        dawn: crossing(CIVIL_TWILIGHT_ALTITUDE_DEG, -1.0),
+        rise: crossing(SUNRISE_ALTITUDE_DEG, -1.0),
+        transit: from_julian_date(transit),
+        set: crossing(SUNRISE_ALTITUDE_DEG, 1.0),
+        dusk: crossing(CIVIL_TWILIGHT_ALTITUDE_DEG, 1.0),
+        midnight_sun: hour_angle(SUNRISE_ALTITUDE_DEG) == Err(true),
+    }
+}
+
+/// Moon phase at `time` from 0.0 (new) through 0.5 (full) back towards 1.0.
+pub fn moon_phase(time: DateTime<Utc>) -> f64 {
+    ((julian_date(time) - REFERENCE_NEW_MOON_JD) / SYNODIC_MONTH).rem_euclid(1.0)
+}
+
+/// Today's sun events on the clock of `now`'s zone, with `is_day` set for
+/// `now`.
+pub fn celestial_events<Z: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    now: DateTime<Z>,
+) -> CelestialEvents {
+    let times = sun_times(now.date_naive(), latitude, longitude);
+    let zone = now.timezone();
+    let local = |time: Option<DateTime<Utc>>| time.map(|t| t.with_timezone(&zone).time());
+
+    let now_utc = now.with_timezone(&Utc);
+    let is_day = match (times.rise, times.set) {
+        (Some(rise), Some(set)) => rise <= now_utc && now_utc < set,
+        _ => times.midnight_sun,
+    };
+
+    CelestialEvents {
+        is_day,
+        begin_twilight: local(times.dawn),
+        rise: local(times.rise),
+        upper_transit: local(Some(times.transit)),
+        set: local(times.set),
+        end_twilight: local(times.dusk),
+    }
+}
+
+/// Supplementary provider backed by the calculations above; only the
+/// place's time zone is looked up, once.
+pub struct AstroProvider;
+
+#[async_trait]
+impl SupplementaryWeatherProvider for AstroProvider {
+    async fn get_supplementary_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+        wanted: SupplementaryProviderRequest,
+    ) -> Result<SupplementaryProviderResponse, WeatherError> {
+        let now = TimeZoneLookup::shared().clock_at(location).await.now();
+        let phase = Some(moon_phase(now.with_timezone(&Utc)));
+
+        Ok(match wanted {
+            SupplementaryProviderRequest::PhasesOfMoon => {
+                SupplementaryProviderResponse::PhasesOfMoon(phase)
+            }
+            SupplementaryProviderRequest::SunAndMoonForOneDay => {
+                SupplementaryProviderResponse::SunAndMoonForOneDay {
+                    sun: celestial_events(location.latitude, location.longitude, now),
+                    moon_phase: phase,
+                }
+            }
+        })
+    }
+
+    fn get_attribution(&self) -> &'static str {
+        ""
+    }
+
+    fn capabilities(&self) -> Vec<SupplementaryProviderRequest> {
+        vec![
+            SupplementaryProviderRequest::PhasesOfMoon,
+            SupplementaryProviderRequest::SunAndMoonForOneDay,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::Scheduler;
+    use chrono::{NaiveTime, TimeZone};
+
+    fn minutes_between(a: DateTime<Utc>, b: DateTime<Utc>) -> i64 {
+        (a - b).num_minutes().abs()
+    }
+
+    #[test]
+    fn test_london_midsummer() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let times = sun_times(date, 51.5074, -0.1278);
+
+        // NOAA: sunrise 03:43 UTC, sunset 20:21 UTC, civil dusk 21:08 UTC
+        let at = |h, m| Utc.with_ymd_and_hms(2024, 6, 21, h, m, 0).unwrap();
+        assert!(minutes_between(times.rise.unwrap(), at(3, 43)) <= 3);
+        assert!(minutes_between(times.set.unwrap(), at(20, 21)) <= 3);
+        assert!(minutes_between(times.dusk.unwrap(), at(21, 8)) <= 4);
+        assert!(times.dawn.unwrap() < times.rise.unwrap());
+    }
+
+    #[test]
+    fn test_events_on_the_clock_at_the_place() {
+        // Tokyo at 07:00 on midsummer morning, whatever this machine's zone
+        let now = Scheduler::for_zone("Asia/Tokyo").at(
+            NaiveDate::from_ymd_opt(2024, 6, 21).unwrap(),
+            NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+        );
+        let events = celestial_events(35.68, 139.69, now);
+
+        // NOAA: sunrise 04:25 JST, sunset 19:00 JST
+        let minutes = |time: NaiveTime, h, m| {
+            (time - NaiveTime::from_hms_opt(h, m, 0).unwrap())
+                .num_minutes()
+                .abs()
+        };
+        assert!(events.is_day);
+        assert!(minutes(events.rise.unwrap(), 4, 25) <= 3);
+        assert!(minutes(events.set.unwrap(), 19, 0) <= 3);
+    }
+
+    #[test]
+    fn test_polar_day_and_night() {
+        let summer = sun_times(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap(), 78.2, 15.6);
+        assert!(summer.rise.is_none() && summer.set.is_none());
+        assert!(summer.midnight_sun);
+
+        let winter = sun_times(NaiveDate::from_ymd_opt(2024, 12, 21).unwrap(), 78.2, 15.6);
+        assert!(winter.rise.is_none());
+        assert!(!winter.midnight_sun);
+    }
+
+    #[test]
+    fn test_moon_phase() {
+        let full = Utc.with_ymd_and_hms(2024, 1, 25, 17, 54, 0).unwrap();
+        assert!((moon_phase(full) - 0.5).abs() < 0.02);
+
+        let new = Utc.with_ymd_and_hms(2024, 1, 11, 11, 57, 0).unwrap();
+        let phase = moon_phase(new);
+        assert!(!(0.02..=0.98).contains(&phase));
+    }
+}
@@ -0,0 +1,170 @@
+//! Geomagnetic activity from NOAA SWPC's planetary K-index forecast, to tell
+//! whether the aurora might be seen tonight.
+
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::http::{DEFAULT_TIMEOUT_SECS, RequestOptions};
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+const SWPC_KP_FORECAST_URL: &str =
+    "https://services.swpc.noaa.gov/products/noaa-planetary-k-index-forecast.json";
+/// SWPC updates the forecast every three hours.
+const REFETCH_INTERVAL: Duration = Duration::from_secs(3 * 60 * 60);
+/// How far ahead to look for the strongest activity.
+const FORECAST_HOURS: i64 = 24;
+/// Nearer the equator than this the aurora is out of reach whatever the Kp,
+/// so the forecast isn't fetched.
+pub const AURORA_MIN_LATITUDE: f64 = 45.0;
+/// Latitude the auroral oval reaches down to at Kp 0, and how much further
+/// it spreads with each step of Kp.
+const OVAL_EDGE_AT_KP0: f64 = 66.5;
+const OVAL_DEGREES_PER_KP: f64 = 2.0;
+
+/// The lowest Kp at which the aurora can be seen from `latitude`, going by
+/// geographic latitude as a stand-in for geomagnetic latitude.
+pub fn kp_needed(latitude: f64) -> f64 {
+    ((OVAL_EDGE_AT_KP0 - latitude.abs()) / OVAL_DEGREES_PER_KP).max(0.0)
+}
+
+pub struct SwpcKpForecast {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl SwpcKpForecast {
+    pub fn new() -> Self {
+        let client = RequestOptions::default()
+            .client()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            base_url: SWPC_KP_FORECAST_URL.to_string(),
+        }
+    }
+
+    /// The highest Kp forecast over the next day.
+    pub async fn fetch(&self, now: DateTime<Utc>) -> Result<f64, WeatherError> {
+        let url = &self.base_url;
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                WeatherError::Network(NetworkError::from_reqwest(e, url, DEFAULT_TIMEOUT_SECS))
+            })?;
+
+        let rows: Vec<Vec<Value>> = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, url, DEFAULT_TIMEOUT_SECS))
+        })?;
+        max_kp(&rows, now)
+    }
+}
+
+impl Default for SwpcKpForecast {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The Kp forecast, fetched again once SWPC has had time to update it.
+pub struct KpForecast {
+    swpc: SwpcKpForecast,
+    fetched: Option<(Instant, Option<f64>)>,
+}
+
+impl Default for KpForecast {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KpForecast {
+    pub fn new() -> Self {
+        Self {
+            swpc: SwpcKpForecast::new(),
+            fetched: None,
+        }
+    }
+
+    /// The highest Kp over the next day, or `None` when it couldn't be had.
+    pub async fn tonight(&mut self) -> Option<f64> {
+        if let Some((at, kp)) = self.fetched
+            && at.elapsed() < REFETCH_INTERVAL
+        {
+            return kp;
+        }
+        let kp = self.swpc.fetch(Utc::now()).await.ok();
+        self.fetched = Some((Instant::now(), kp));
+        kp
+    }
+}
+
+/// The highest Kp in the three-hour periods that haven't ended before
+/// `now`, up to a day ahead. The first row names the columns.
+fn max_kp(rows: &[Vec<Value>], now: DateTime<Utc>) -> Result<f64, WeatherError> {
+    let bad_data = |message: &str| WeatherError::Data(DataError::BadData(message.to_string()));
+    let header = rows.first().ok_or(WeatherError::Data(DataError::NoData))?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|value| value.as_str() == Some(name))
+            .ok_or_else(|| bad_data(&format!("Kp forecast has no {} column", name)))
+    };
+    let (time_column, kp_column) = (column("time_tag")?, column("kp")?);
+
+    let from = now.naive_utc() - TimeDelta::hours(3);
+    let until = now.naive_utc() + TimeDelta::hours(FORECAST_HOURS);
+    rows[1..]
+        .iter()
+        .filter_map(|row| {
+            let time = row.get(time_column)?.as_str()?;
+            let time = NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S").ok()?;
+            // Older versions of the product send numbers as strings
+            let kp = match row.get(kp_column)? {
+                Value::String(text) => text.parse().ok()?,
+                value => value.as_f64()?,
+            };
+            (time > from && time <= until).then_some(kp)
+        })
+        .reduce(f64::max)
+        .ok_or(WeatherError::Data(DataError::NoData))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_kp_needed_by_latitude() {
+        assert_eq!(kp_needed(70.0), 0.0);
+        assert_eq!(kp_needed(-60.5), 3.0);
+        assert_eq!(kp_needed(54.5), 6.0);
+        assert!(kp_needed(40.0) > 9.0);
+    }
+
+    #[test]
+    fn test_max_kp_over_the_next_day() {
+        let rows: Vec<Vec<Value>> = serde_json::from_str(
+            r#"[
+                ["time_tag", "kp", "observed", "noaa_scale"],
+                ["2026-10-15 21:00:00", "7.33", "observed", "G3"],
+                ["2026-10-16 09:00:00", "2.67", "observed", null],
+                ["2026-10-16 12:00:00", 4.0, "estimated", null],
+                ["2026-10-16 21:00:00", "5.67", "predicted", "G1"],
+                ["2026-10-18 00:00:00", "8.00", "predicted", "G4"]
+            ]"#,
+        )
+        .unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 10, 30, 0).unwrap();
+        assert_eq!(max_kp(&rows, now).unwrap(), 5.67);
+
+        let later = Utc.with_ymd_and_hms(2026, 10, 19, 0, 0, 0).unwrap();
+        assert!(max_kp(&rows, later).is_err());
+        assert!(max_kp(&[], now).is_err());
+    }
+}
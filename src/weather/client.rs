@@ -1,10 +1,14 @@
 use crate::cache;
 use crate::config::Provider;
-use crate::error::WeatherError;
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::http;
 use crate::weather::normalizer::WeatherNormalizer;
 use crate::weather::provider::WeatherProvider;
+use crate::weather::timezone::TimeZoneLookup;
 use crate::weather::types::{WeatherData, WeatherLocation, WeatherUnits};
+use crate::weather::validation::implausible_reading;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
@@ -13,6 +17,15 @@ pub struct WeatherClient {
     provider: Arc<dyn WeatherProvider>,
     cache: Arc<RwLock<Option<CachedWeather>>>,
     cache_duration: Duration,
+    backoff: Arc<RwLock<Option<Backoff>>>,
+    /// Skip the weather cached on disk for the next fetch, as it may be as
+    /// old as what was invalidated.
+    bypass_disk_cache: Arc<AtomicBool>,
+    /// Set when the last weather returned came from a cache rather than the
+    /// provider.
+    served_cached: Arc<AtomicBool>,
+    /// For providers, and fallbacks, that don't say the location's zone.
+    zones: &'static TimeZoneLookup,
 }
 
 struct CachedWeather {
@@ -20,15 +33,78 @@ struct CachedWeather {
     fetched_at: Instant,
 }
 
+/// Longest wait after repeated rate limiting without a `Retry-After`.
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// Set while the provider is rate limiting us.
+struct Backoff {
+    url: String,
+    until: Instant,
+    /// Consecutive rate-limited requests, doubling the wait each time.
+    strikes: u32,
+}
+
 impl WeatherClient {
     pub fn new(provider: Arc<dyn WeatherProvider>, cache_duration: Duration) -> Self {
         Self {
             provider,
             cache: Arc::new(RwLock::new(None)),
             cache_duration,
+            backoff: Arc::new(RwLock::new(None)),
+            bypass_disk_cache: Arc::new(AtomicBool::new(false)),
+            served_cached: Arc::new(AtomicBool::new(false)),
+            zones: TimeZoneLookup::shared(),
         }
     }
 
+    /// How long to wait before the next fetch: the refresh interval, or
+    /// longer while backing off from rate limiting or eking out the
+    /// provider's daily request budget.
+    pub async fn refresh_delay(&self) -> Duration {
+        self.refresh_delay_from(self.cache_duration).await
+    }
+
+    /// As `refresh_delay`, but from `interval` rather than the refresh
+    /// interval, e.g. to fetch more often in severe weather.
+    pub async fn refresh_delay_from(&self, interval: Duration) -> Duration {
+        let backoff = self.backoff.read().await;
+        let remaining = backoff
+            .as_ref()
+            .map(|b| b.until.saturating_duration_since(Instant::now()))
+            .unwrap_or_default();
+        interval.max(remaining).max(self.provider.refresh_spacing())
+    }
+
+    /// Whether the weather last returned was cached, in memory or on disk,
+    /// rather than a new reading from the provider.
+    pub fn last_was_cached(&self) -> bool {
+        self.served_cached.load(Ordering::Relaxed)
+    }
+
+    /// Set once most of the provider's daily request budget is spent.
+    pub fn budget_warning(&self) -> Option<String> {
+        self.provider.budget_warning()
+    }
+
+    /// Records a rate-limited response and returns how long to wait:
+    /// `Retry-After` when given, else the refresh interval doubled for each
+    /// rate-limited attempt in a row.
+    async fn back_off(&self, url: String, retry_after: Option<Duration>) -> Duration {
+        let mut backoff = self.backoff.write().await;
+        let strikes = backoff.as_ref().map_or(0, |b| b.strikes) + 1;
+        let delay = retry_after.unwrap_or_else(|| {
+            self.cache_duration
+                .saturating_mul(1 << (strikes - 1).min(16))
+                .min(MAX_BACKOFF)
+        });
+        *backoff = Some(Backoff {
+            url,
+            until: Instant::now() + delay,
+            strikes,
+        });
+        delay
+    }
+
     pub async fn get_current_weather(
         &self,
         location: &WeatherLocation,
@@ -40,12 +116,15 @@ impl WeatherClient {
             if let Some(cached) = cache.as_ref()
                 && cached.fetched_at.elapsed() < self.cache_duration
             {
+                self.served_cached.store(true, Ordering::Relaxed);
                 return Ok(cached.data.clone());
             }
         }
 
-        if let Some(cached_data) =
-            cache::load_cached_weather(location.latitude, location.longitude, provider).await
+        let bypass_disk_cache = self.bypass_disk_cache.swap(false, Ordering::Relaxed);
+        if !bypass_disk_cache
+            && let Some(cached_data) =
+                cache::load_cached_weather(location.latitude, location.longitude, provider).await
             && std::env::var("CACHE_DISABLED").is_err()
         // Should've done this sooner
         {
@@ -54,12 +133,52 @@ impl WeatherClient {
                 data: cached_data.clone(),
                 fetched_at: Instant::now(),
             });
+            self.served_cached.store(true, Ordering::Relaxed);
             return Ok(cached_data);
         }
 
-        let response = self.provider.get_current_weather(location, units).await?;
+        // Don't spend requests (e.g. manual refreshes) while rate limited
+        if let Some(backoff) = self.backoff.read().await.as_ref()
+            && backoff.until > Instant::now()
+        {
+            return Err(WeatherError::Network(NetworkError::RateLimited {
+                url: backoff.url.clone(),
+                retry_after: Some(backoff.until - Instant::now()),
+            }));
+        }
 
-        let data = WeatherNormalizer::normalize(response);
+        let response = match self.provider.get_current_weather(location, units).await {
+            Ok(response) => response,
+            Err(WeatherError::Network(NetworkError::RateLimited { url, retry_after })) => {
+                let delay = self.back_off(url.clone(), retry_after).await;
+                return Err(WeatherError::Network(NetworkError::RateLimited {
+                    url,
+                    retry_after: Some(delay),
+                }));
+            }
+            Err(WeatherError::Network(error)) => {
+                // A host that doesn't answer even a probe means we're offline
+                if let Some(url) = error.unreachable_url()
+                    && !http::is_reachable(url).await
+                {
+                    return Err(WeatherError::Network(NetworkError::Offline {
+                        url: url.to_string(),
+                    }));
+                }
+                return Err(WeatherError::Network(error));
+            }
+            Err(e) => return Err(e),
+        };
+        *self.backoff.write().await = None;
+
+        let mut data = WeatherNormalizer::normalize(response);
+        // Neither cached nor shown, so the last good observation stays up
+        if let Some(reading) = implausible_reading(&data) {
+            return Err(WeatherError::Data(DataError::Implausible(reading)));
+        }
+        if data.details.timezone.is_none() {
+            data.details.timezone = self.zones.at(location).await;
+        }
 
         {
             let mut cache = self.cache.write().await;
@@ -70,14 +189,16 @@ impl WeatherClient {
         }
 
         cache::save_weather_cache(&data, location.latitude, location.longitude, provider);
+        self.served_cached.store(false, Ordering::Relaxed);
 
         Ok(data)
     }
 
-    #[allow(dead_code)]
+    /// Drops the cached weather, so the next fetch asks the provider.
     pub async fn invalidate_cache(&self) {
         let mut cache = self.cache.write().await;
         *cache = None;
+        self.bypass_disk_cache.store(true, Ordering::Relaxed);
     }
 }
 
@@ -97,4 +218,44 @@ mod tests {
         let cache = client.cache.read().await;
         assert!(cache.is_none());
     }
+
+    #[tokio::test]
+    async fn test_rate_limit_backoff() {
+        let provider = Arc::new(OpenMeteoProvider::new());
+        let client = WeatherClient::new(provider, Duration::from_secs(300));
+        assert_eq!(client.refresh_delay().await, Duration::from_secs(300));
+
+        let url = "https://api.open-meteo.com".to_string();
+        assert_eq!(
+            client.back_off(url.clone(), None).await,
+            Duration::from_secs(300)
+        );
+        assert_eq!(
+            client.back_off(url.clone(), None).await,
+            Duration::from_secs(600)
+        );
+        assert!(client.refresh_delay().await > Duration::from_secs(590));
+
+        // The server's Retry-After wins, however long
+        let delay = client
+            .back_off(url.clone(), Some(Duration::from_secs(7200)))
+            .await;
+        assert_eq!(delay, Duration::from_secs(7200));
+
+        // Requests are held back until the wait is over
+        let location = WeatherLocation {
+            latitude: -12.3456,
+            longitude: 65.4321,
+            elevation: None,
+        };
+        let err = client
+            .get_current_weather(&location, &WeatherUnits::metric(), Provider::OpenMeteo)
+            .await
+            .err();
+        assert!(matches!(
+            err,
+            Some(WeatherError::Network(NetworkError::RateLimited { retry_after: Some(d), .. }))
+                if d > Duration::from_secs(7000)
+        ));
+    }
 }
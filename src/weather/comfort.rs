@@ -0,0 +1,80 @@
+use crate::weather::units::dew_point;
+
+/// Below this many °C it's cold whatever the humidity.
+const COLD: f64 = 5.0;
+/// Below this many °C it's cool.
+const COOL: f64 = 15.0;
+/// From this many °C it's hot, even in dry air.
+const HOT: f64 = 30.0;
+/// A dew point from this many °C feels sticky.
+const STICKY_DEW_POINT: f64 = 16.0;
+/// A dew point from this many °C feels oppressive.
+const OPPRESSIVE_DEW_POINT: f64 = 21.0;
+
+/// How an hour feels from its temperature and humidity, for the `c`
+/// comfort strip. Humidity is judged by the dew point, which tracks how
+/// sticky the air feels better than the relative humidity does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Comfort {
+    Cold,
+    Cool,
+    Pleasant,
+    Sticky,
+    Hot,
+    Oppressive,
+}
+
+impl Comfort {
+    /// From °C and relative humidity in percent; with the humidity unknown
+    /// it goes by the temperature alone.
+    pub fn of(celsius: f64, humidity: Option<f64>) -> Self {
+        let dew_point = humidity.map(|rh| dew_point(celsius, rh));
+        let over = |threshold| dew_point.is_some_and(|dp| dp >= threshold);
+        if over(OPPRESSIVE_DEW_POINT) {
+            Self::Oppressive
+        } else if celsius >= HOT {
+            Self::Hot
+        } else if over(STICKY_DEW_POINT) {
+            Self::Sticky
+        } else if celsius < COLD {
+            Self::Cold
+        } else if celsius < COOL {
+            Self::Cool
+        } else {
+            Self::Pleasant
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Cold => "cold",
+            Self::Cool => "cool",
+            Self::Pleasant => "pleasant",
+            Self::Sticky => "sticky",
+            Self::Hot => "hot",
+            Self::Oppressive => "oppressive",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comfort_levels() {
+        assert_eq!(Comfort::of(-2.0, Some(90.0)), Comfort::Cold);
+        assert_eq!(Comfort::of(11.0, Some(95.0)), Comfort::Cool);
+        assert_eq!(Comfort::of(22.0, Some(50.0)), Comfort::Pleasant);
+        // Same temperature, dew point around 19°C
+        assert_eq!(Comfort::of(22.0, Some(85.0)), Comfort::Sticky);
+        assert_eq!(Comfort::of(33.0, Some(20.0)), Comfort::Hot);
+        assert_eq!(Comfort::of(27.0, Some(80.0)), Comfort::Oppressive);
+    }
+
+    #[test]
+    fn test_unknown_humidity_goes_by_temperature() {
+        assert_eq!(Comfort::of(24.0, None), Comfort::Pleasant);
+        assert_eq!(Comfort::of(31.0, None), Comfort::Hot);
+    }
+}
@@ -0,0 +1,256 @@
+//! Waves and tide from the Open-Meteo Marine API, for places by the sea.
+
+use crate::error::{NetworkError, WeatherError};
+use crate::http::{DEFAULT_TIMEOUT_SECS, RequestOptions};
+use crate::weather::types::WeatherLocation;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Deserialize;
+
+const OPEN_METEO_MARINE_URL: &str = "https://marine-api.open-meteo.com/v1/marine";
+/// Change in sea level over the coming hour, in metres, below which the
+/// tide counts as turning.
+const SLACK_THRESHOLD: f64 = 0.02;
+/// Tidal range, in metres, below which there's no tide worth drawing.
+const MIN_TIDAL_RANGE: f64 = 0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tide {
+    Rising,
+    Falling,
+    Slack,
+}
+
+impl Tide {
+    pub fn label(self) -> &'static str {
+        match self {
+            Tide::Rising => "rising",
+            Tide::Falling => "falling",
+            Tide::Slack => "turning",
+        }
+    }
+}
+
+/// The sea at the current hour.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarineConditions {
+    /// Significant wave height in metres.
+    pub wave_height: Option<f64>,
+    /// Sea level against mean sea level in metres, tides included.
+    pub sea_level: Option<f64>,
+    pub tide: Option<Tide>,
+    /// Where the sea level sits between the day's low (0) and high (1)
+    /// water, when there's a tide.
+    pub tide_fraction: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MarineResponse {
+    hourly: MarineHourly,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MarineHourly {
+    time: Vec<String>,
+    #[serde(default)]
+    wave_height: Vec<Option<f64>>,
+    #[serde(default)]
+    sea_level_height_msl: Vec<Option<f64>>,
+}
+
+pub struct OpenMeteoMarine {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl OpenMeteoMarine {
+    pub fn new() -> Self {
+        let client = RequestOptions::default()
+            .client()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            base_url: OPEN_METEO_MARINE_URL.to_string(),
+        }
+    }
+
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        format!(
+            "{}?latitude={}&longitude={}&hourly=wave_height,sea_level_height_msl&forecast_days=1&timezone=auto",
+            self.base_url, location.latitude, location.longitude
+        )
+    }
+
+    /// Today's hourly sea state. Inland the values are all null.
+    async fn fetch_today(&self, location: &WeatherLocation) -> Result<MarineHourly, WeatherError> {
+        let url = self.build_url(location);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+            })?;
+
+        let data: MarineResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+        })?;
+        Ok(data.hourly)
+    }
+}
+
+impl Default for OpenMeteoMarine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Today's sea state for one place, fetched once per day and place.
+pub struct MarineForecast {
+    marine: OpenMeteoMarine,
+    fetched_for: Option<(NaiveDate, f64, f64)>,
+    hours: MarineHourly,
+}
+
+impl Default for MarineForecast {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarineForecast {
+    pub fn new() -> Self {
+        Self {
+            marine: OpenMeteoMarine::new(),
+            fetched_for: None,
+            hours: MarineHourly::default(),
+        }
+    }
+
+    /// The sea at the hour of `now` on the location's clock, or `None` away
+    /// from the coast.
+    pub async fn at(
+        &mut self,
+        location: &WeatherLocation,
+        now: NaiveDateTime,
+    ) -> Option<MarineConditions> {
+        let key = (now.date(), location.latitude, location.longitude);
+        if self.fetched_for != Some(key) {
+            self.hours = self.marine.fetch_today(location).await.ok()?;
+            self.fetched_for = Some(key);
+        }
+
+        conditions_at(&self.hours, now)
+    }
+}
+
+fn conditions_at(hours: &MarineHourly, now: NaiveDateTime) -> Option<MarineConditions> {
+    let wanted = now.format("%Y-%m-%dT%H").to_string();
+    let i = hours
+        .time
+        .iter()
+        .position(|time| time.starts_with(&wanted))?;
+    let value = |series: &[Option<f64>], i: usize| series.get(i).copied().flatten();
+
+    let wave_height = value(&hours.wave_height, i);
+    let sea_level = value(&hours.sea_level_height_msl, i);
+    if wave_height.is_none() && sea_level.is_none() {
+        return None;
+    }
+
+    let levels: Vec<f64> = hours
+        .sea_level_height_msl
+        .iter()
+        .flatten()
+        .copied()
+        .collect();
+    let low = levels.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = levels.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let tidal = high - low >= MIN_TIDAL_RANGE;
+
+    // The last hour of the day compares with the one before it
+    let trend = match (value(&hours.sea_level_height_msl, i + 1), i.checked_sub(1)) {
+        (Some(next), _) => sea_level.map(|level| next - level),
+        (None, Some(previous)) => sea_level
+            .zip(value(&hours.sea_level_height_msl, previous))
+            .map(|(level, previous)| level - previous),
+        (None, None) => None,
+    };
+    let tide = trend.filter(|_| tidal).map(|change| match change {
+        c if c > SLACK_THRESHOLD => Tide::Rising,
+        c if c < -SLACK_THRESHOLD => Tide::Falling,
+        _ => Tide::Slack,
+    });
+
+    Some(MarineConditions {
+        wave_height,
+        sea_level,
+        tide,
+        tide_fraction: sea_level
+            .filter(|_| tidal)
+            .map(|level| (level - low) / (high - low)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hours(json: &str) -> MarineHourly {
+        serde_json::from_str::<MarineResponse>(json).unwrap().hourly
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_tide_follows_the_next_hour() {
+        let hours = hours(
+            r#"{"hourly": {
+                "time": ["2026-10-16T00:00", "2026-10-16T01:00", "2026-10-16T02:00", "2026-10-16T03:00"],
+                "wave_height": [0.8, 1.0, 1.2, null],
+                "sea_level_height_msl": [-1.0, 0.0, 1.0, 0.9]
+            }}"#,
+        );
+
+        let rising = conditions_at(&hours, at("2026-10-16T01:35")).unwrap();
+        assert_eq!(rising.wave_height, Some(1.0));
+        assert_eq!(rising.tide, Some(Tide::Rising));
+        assert_eq!(rising.tide_fraction, Some(0.5));
+
+        let falling = conditions_at(&hours, at("2026-10-16T03:00")).unwrap();
+        assert_eq!(falling.wave_height, None);
+        assert_eq!(falling.tide, Some(Tide::Falling));
+
+        assert!(conditions_at(&hours, at("2026-10-17T01:00")).is_none());
+    }
+
+    #[test]
+    fn test_inland_has_no_sea() {
+        let hours = hours(
+            r#"{"hourly": {
+                "time": ["2026-10-16T00:00", "2026-10-16T01:00"],
+                "wave_height": [null, null],
+                "sea_level_height_msl": [null, null]
+            }}"#,
+        );
+        assert!(conditions_at(&hours, at("2026-10-16T00:10")).is_none());
+    }
+
+    #[test]
+    fn test_no_tide_in_a_tideless_sea() {
+        let hours = hours(
+            r#"{"hourly": {
+                "time": ["2026-10-16T00:00", "2026-10-16T01:00"],
+                "wave_height": [0.3, 0.3],
+                "sea_level_height_msl": [0.10, 0.12]
+            }}"#,
+        );
+        let sea = conditions_at(&hours, at("2026-10-16T00:00")).unwrap();
+        assert_eq!(sea.tide, None);
+        assert_eq!(sea.tide_fraction, None);
+    }
+}
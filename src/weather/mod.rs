@@ -1,8 +1,21 @@
+pub mod air_quality;
+pub mod archive;
+pub mod astro;
+pub mod aurora;
 pub mod client;
+pub mod comfort;
+pub mod marine;
 pub mod normalizer;
+pub mod normals;
+pub mod pressure;
 pub mod provider;
+pub mod radar;
+pub mod severity;
+pub mod timezone;
+pub mod transition;
 pub mod types;
 pub mod units;
+pub mod validation;
 
 pub use client::WeatherClient;
 pub use provider::open_meteo::OpenMeteoProvider;
@@ -10,4 +23,7 @@ pub use types::{
     FogIntensity, RainIntensity, SnowIntensity, WeatherCondition, WeatherConditions, WeatherData,
     WeatherLocation, WeatherUnits,
 };
-pub use units::{format_precipitation, format_temperature, format_wind_speed};
+pub use units::{
+    beaufort_description, format_distance, format_precipitation, format_temperature,
+    format_wind_speed,
+};
@@ -1,11 +1,20 @@
 use crate::weather::provider::WeatherProviderResponse;
-use crate::weather::types::{WeatherCondition, WeatherData};
+use crate::weather::types::{HourlyForecast, WeatherCondition, WeatherData, WeatherDetails};
+use crate::weather::units::dew_point;
 
 pub struct WeatherNormalizer;
 
 impl WeatherNormalizer {
     pub fn normalize(response: WeatherProviderResponse) -> WeatherData {
         let condition = Self::wmo_code_to_condition(response.weather_code);
+        let details = WeatherDetails {
+            dew_point: response.details.dew_point.or_else(|| {
+                response
+                    .humidity
+                    .map(|humidity| dew_point(response.temperature, humidity))
+            }),
+            ..response.details
+        };
 
         WeatherData {
             condition,
@@ -15,28 +24,60 @@ impl WeatherNormalizer {
             wind_direction: response.wind_direction,
             sun: response.sun,
             moon_phase: response.moon_phase,
+            pressure: response.pressure,
+            disagreement: response.disagreement,
+            humidity: response.humidity,
+            apparent_temperature: response.apparent_temperature,
+            uv_index: response.uv_index,
+            source: response.source,
+            nowcast: response.nowcast,
+            hourly: response
+                .hourly
+                .into_iter()
+                .map(|hour| HourlyForecast {
+                    time: hour.time,
+                    condition: Self::wmo_code_to_condition(hour.weather_code),
+                    temperature: hour.temperature,
+                    precipitation_probability: hour.precipitation_probability,
+                    humidity: hour.humidity,
+                })
+                .collect(),
+            today: response.today,
+            details,
             timestamp: response.timestamp,
             attribution: response.attribution,
         }
     }
 
-    fn wmo_code_to_condition(code: i32) -> WeatherCondition {
+    pub(crate) fn wmo_code_to_condition(code: i32) -> WeatherCondition {
         match code {
             0 => WeatherCondition::Clear,
-            1 => WeatherCondition::PartlyCloudy,
+            1 => WeatherCondition::MainlyClear,
             2 => WeatherCondition::PartlyCloudy,
             3 => WeatherCondition::Overcast,
-            45 | 48 => WeatherCondition::Fog,
-            51 | 53 | 55 => WeatherCondition::Drizzle,
-            56 | 57 => WeatherCondition::FreezingRain,
-            61 | 63 | 65 => WeatherCondition::Rain,
-            66 | 67 => WeatherCondition::FreezingRain,
-            71 | 73 | 75 => WeatherCondition::Snow,
+            45 => WeatherCondition::Fog,
+            48 => WeatherCondition::RimeFog,
+            51 => WeatherCondition::LightDrizzle,
+            53 => WeatherCondition::Drizzle,
+            55 => WeatherCondition::DenseDrizzle,
+            56 => WeatherCondition::FreezingDrizzle,
+            57 => WeatherCondition::DenseFreezingDrizzle,
+            61 => WeatherCondition::LightRain,
+            63 => WeatherCondition::Rain,
+            65 => WeatherCondition::HeavyRain,
+            66 => WeatherCondition::FreezingRain,
+            67 => WeatherCondition::HeavyFreezingRain,
+            71 => WeatherCondition::LightSnow,
+            73 => WeatherCondition::Snow,
+            75 => WeatherCondition::HeavySnow,
             77 => WeatherCondition::SnowGrains,
-            80..=82 => WeatherCondition::RainShowers,
-            85 | 86 => WeatherCondition::SnowShowers,
+            80 | 81 => WeatherCondition::RainShowers,
+            82 => WeatherCondition::ViolentRainShowers,
+            85 => WeatherCondition::SnowShowers,
+            86 => WeatherCondition::HeavySnowShowers,
             95 => WeatherCondition::Thunderstorm,
-            96 | 99 => WeatherCondition::ThunderstormHail,
+            96 => WeatherCondition::ThunderstormHail,
+            99 => WeatherCondition::ThunderstormHeavyHail,
             _ => WeatherCondition::Clear,
         }
     }
@@ -44,7 +85,6 @@ impl WeatherNormalizer {
 
 #[cfg(test)]
 mod tests {
-    use crate::weather::types::CelestialEvents;
 
     use super::*;
 
@@ -56,7 +96,7 @@ mod tests {
         );
         assert_eq!(
             WeatherNormalizer::wmo_code_to_condition(1),
-            WeatherCondition::PartlyCloudy
+            WeatherCondition::MainlyClear
         );
         assert_eq!(
             WeatherNormalizer::wmo_code_to_condition(3),
@@ -68,15 +108,15 @@ mod tests {
         );
         assert_eq!(
             WeatherNormalizer::wmo_code_to_condition(51),
-            WeatherCondition::Drizzle
+            WeatherCondition::LightDrizzle
         );
         assert_eq!(
             WeatherNormalizer::wmo_code_to_condition(61),
-            WeatherCondition::Rain
+            WeatherCondition::LightRain
         );
         assert_eq!(
             WeatherNormalizer::wmo_code_to_condition(71),
-            WeatherCondition::Snow
+            WeatherCondition::LightSnow
         );
         assert_eq!(
             WeatherNormalizer::wmo_code_to_condition(80),
@@ -88,22 +128,20 @@ mod tests {
         );
         assert_eq!(
             WeatherNormalizer::wmo_code_to_condition(99),
-            WeatherCondition::ThunderstormHail
+            WeatherCondition::ThunderstormHeavyHail
         );
     }
 
     #[test]
     fn test_normalize_response() {
         let response = WeatherProviderResponse {
-            weather_code: 61,
             temperature: 20.5,
             precipitation: 2.5,
             wind_speed: 15.0,
             wind_direction: 180.0,
-            sun: CelestialEvents::from_bool(true),
             moon_phase: Some(0.5),
             timestamp: "2024-01-01T12:00".to_string(),
-            attribution: "".to_string(),
+            ..WeatherProviderResponse::sample(63)
         };
 
         let data = WeatherNormalizer::normalize(response);
@@ -0,0 +1,270 @@
+//! Climate normals: each month's average temperature over 1991–2020, from
+//! the Open-Meteo archive, to say how today compares.
+
+use crate::cache;
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::http::{DEFAULT_TIMEOUT_SECS, RequestOptions};
+use crate::weather::types::WeatherLocation;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const OPEN_METEO_ARCHIVE_URL: &str = "https://archive-api.open-meteo.com/v1/archive";
+/// The current WMO reference period.
+const PERIOD_START: &str = "1991-01-01";
+const PERIOD_END: &str = "2020-12-31";
+/// A month needs this many years of days to have a normal.
+const MIN_DAYS_PER_MONTH: usize = 25 * 28;
+/// Wait after a failed fetch before trying the same place again, doubling
+/// with each failure up to the longest.
+const RETRY_AFTER: Duration = Duration::from_secs(15 * 60);
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(6 * 60 * 60);
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The average daily mean temperature of each month, in °C, January first.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyNormals {
+    pub means: [f64; 12],
+}
+
+impl MonthlyNormals {
+    /// How far `celsius`, a day's mean temperature in `month` (1–12), is
+    /// above the normal; negative when below.
+    pub fn anomaly(&self, month: u32, celsius: f64) -> Option<f64> {
+        let normal = self.means.get(month.checked_sub(1)? as usize)?;
+        Some(celsius - normal)
+    }
+}
+
+/// The name of `month` (1–12).
+pub fn month_name(month: u32) -> &'static str {
+    MONTH_NAMES
+        .get(month.wrapping_sub(1) as usize)
+        .copied()
+        .unwrap_or("")
+}
+
+#[derive(Debug, Deserialize)]
+struct NormalsResponse {
+    daily: NormalsDaily,
+}
+
+#[derive(Debug, Deserialize)]
+struct NormalsDaily {
+    time: Vec<String>,
+    temperature_2m_mean: Vec<Option<f64>>,
+}
+
+impl NormalsDaily {
+    /// `None` when a month has too few days to go by.
+    fn into_normals(self) -> Option<MonthlyNormals> {
+        let mut sums = [(0.0, 0); 12];
+        for (time, mean) in self.time.iter().zip(self.temperature_2m_mean) {
+            let (Some(month), Some(mean)) =
+                (time.get(5..7).and_then(|m| m.parse::<usize>().ok()), mean)
+            else {
+                continue;
+            };
+            if let Some((sum, count)) = sums.get_mut(month.wrapping_sub(1)) {
+                *sum += mean;
+                *count += 1;
+            }
+        }
+
+        let mut means = [0.0; 12];
+        for (mean, (sum, count)) in means.iter_mut().zip(sums) {
+            if count < MIN_DAYS_PER_MONTH {
+                return None;
+            }
+            *mean = sum / count as f64;
+        }
+        Some(MonthlyNormals { means })
+    }
+}
+
+pub struct OpenMeteoNormals {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl OpenMeteoNormals {
+    pub fn new() -> Self {
+        let client = RequestOptions::default()
+            .client()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            base_url: OPEN_METEO_ARCHIVE_URL.to_string(),
+        }
+    }
+
+    /// Thirty years of daily means, averaged by month. A large request, so
+    /// [`ClimateNormals`] keeps the result.
+    pub async fn fetch(&self, location: &WeatherLocation) -> Result<MonthlyNormals, WeatherError> {
+        let url = format!(
+            "{}?latitude={}&longitude={}&start_date={}&end_date={}&daily=temperature_2m_mean&timezone=auto",
+            self.base_url, location.latitude, location.longitude, PERIOD_START, PERIOD_END
+        );
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+            })?;
+
+        let data: NormalsResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+        })?;
+        data.daily
+            .into_normals()
+            .ok_or(WeatherError::Data(DataError::NoData))
+    }
+}
+
+impl Default for OpenMeteoNormals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The normals for the place shown, fetched once and then kept in the
+/// cache dir, as they don't change.
+pub struct ClimateNormals {
+    archive: OpenMeteoNormals,
+    fetched_for: Option<(f64, f64)>,
+    normals: Option<MonthlyNormals>,
+    /// Failed fetches for this place in a row, and when to try again.
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl Default for ClimateNormals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClimateNormals {
+    pub fn new() -> Self {
+        Self {
+            archive: OpenMeteoNormals::new(),
+            fetched_for: None,
+            normals: None,
+            failures: 0,
+            retry_at: None,
+        }
+    }
+
+    /// `None` when the archive can't be reached; tried again after a while.
+    pub async fn at(&mut self, location: &WeatherLocation) -> Option<MonthlyNormals> {
+        let place = (location.latitude, location.longitude);
+        if self.fetched_for != Some(place) {
+            self.fetched_for = Some(place);
+            self.normals = cache::load_cached_normals(place.0, place.1).await;
+            self.failures = 0;
+            self.retry_at = None;
+        }
+        if self.normals.is_none() && self.retry_at.is_none_or(|at| Instant::now() >= at) {
+            match self.archive.fetch(location).await {
+                Ok(normals) => {
+                    cache::save_normals_cache(normals, place.0, place.1);
+                    self.normals = Some(normals);
+                    self.retry_at = None;
+                }
+                Err(_) => {
+                    self.failures += 1;
+                    let wait = RETRY_AFTER
+                        .saturating_mul(1 << (self.failures - 1).min(16))
+                        .min(MAX_RETRY_AFTER);
+                    self.retry_at = Some(Instant::now() + wait);
+                }
+            }
+        }
+        self.normals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Days, NaiveDate};
+
+    #[test]
+    fn test_daily_means_average_by_month() {
+        let start = NaiveDate::from_ymd_opt(1991, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2020, 12, 31).unwrap();
+        let mut time = Vec::new();
+        let mut temperature_2m_mean = Vec::new();
+        let mut date = start;
+        while date <= end {
+            time.push(date.format("%Y-%m-%d").to_string());
+            // Warmer by a degree each month, with a gap in the record
+            let gap = date.year() == 2000 && date.month() == 3;
+            temperature_2m_mean.push((!gap).then_some(date.month() as f64));
+            date = date + Days::new(1);
+        }
+
+        let normals = NormalsDaily {
+            time,
+            temperature_2m_mean,
+        }
+        .into_normals()
+        .unwrap();
+        assert_eq!(normals.means[0], 1.0);
+        assert_eq!(normals.means[11], 12.0);
+        assert!((normals.anomaly(2, 5.2).unwrap() - 3.2).abs() < 1e-9);
+        assert_eq!(normals.anomaly(13, 5.0), None);
+        assert_eq!(month_name(2), "February");
+    }
+
+    #[tokio::test]
+    async fn test_failed_fetch_is_retried_after_a_wait() {
+        let mut normals = ClimateNormals::new();
+        // Nothing listens there, so every fetch fails at once
+        normals.archive.base_url = "http://127.0.0.1:9".to_string();
+        let location = WeatherLocation {
+            latitude: -89.99,
+            longitude: 0.01,
+            elevation: None,
+        };
+
+        assert_eq!(normals.at(&location).await, None);
+        assert_eq!(normals.failures, 1);
+        let retry_at = normals.retry_at.unwrap();
+        assert!(retry_at >= Instant::now() + RETRY_AFTER - Duration::from_secs(5));
+
+        // Not again until the wait is over, then for twice as long
+        normals.at(&location).await;
+        assert_eq!(normals.failures, 1);
+        normals.retry_at = Some(Instant::now());
+        normals.at(&location).await;
+        assert_eq!(normals.failures, 2);
+        assert!(normals.retry_at.unwrap() > retry_at + RETRY_AFTER / 2);
+    }
+
+    #[test]
+    fn test_short_record_has_no_normals() {
+        let daily = NormalsDaily {
+            time: vec!["2020-01-01".to_string()],
+            temperature_2m_mean: vec![Some(3.0)],
+        };
+        assert_eq!(daily.into_normals(), None);
+    }
+}
@@ -0,0 +1,196 @@
+//! Barometer-style pressure tendency from recent readings, kept across
+//! restarts through the cache.
+
+use chrono::{NaiveDateTime, TimeDelta};
+use std::collections::VecDeque;
+
+/// Tendencies are quoted per three hours, the synoptic convention.
+const WINDOW: TimeDelta = TimeDelta::hours(3);
+/// Readings must span at least this long before a tendency is given;
+/// shorter spans scaled up to three hours are mostly rounding noise.
+const MIN_SPAN: TimeDelta = TimeDelta::hours(1);
+/// A drop of this much (hPa per three hours) often comes before a storm.
+const STORM_DROP_HPA: f64 = 6.0;
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureTendency {
+    RisingFast,
+    Rising,
+    Steady,
+    Falling,
+    FallingFast,
+}
+
+impl PressureTendency {
+    /// Classifies a change in hPa over three hours.
+    pub fn from_change(change: f64) -> Self {
+        match change {
+            c if c >= 3.6 => Self::RisingFast,
+            c if c >= 1.6 => Self::Rising,
+            c if c > -1.6 => Self::Steady,
+            c if c > -3.6 => Self::Falling,
+            _ => Self::FallingFast,
+        }
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            Self::RisingFast => "⬆",
+            Self::Rising => "↗",
+            Self::Steady => "→",
+            Self::Falling => "↘",
+            Self::FallingFast => "⬇",
+        }
+    }
+}
+
+/// Timestamped sea level pressure readings over the last three hours.
+#[derive(Debug, Default)]
+pub struct PressureHistory {
+    samples: VecDeque<(NaiveDateTime, f64)>,
+}
+
+impl PressureHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a reading taken at `time` (the provider's "YYYY-MM-DDTHH:MM"
+    /// timestamp). Repeated timestamps replace the previous reading.
+    pub fn record(&mut self, time: &str, hpa: f64) {
+        if let Some(time) = parse_time(time) {
+            self.push(time, hpa);
+        }
+    }
+
+    /// Puts readings saved by an earlier session, as from [`Self::samples`],
+    /// in front of this session's.
+    pub fn restore(&mut self, saved: &[(String, f64)]) {
+        let current = std::mem::take(&mut self.samples);
+        let first = current.front().map(|(time, _)| *time);
+        for (time, hpa) in saved {
+            if let Some(time) = parse_time(time)
+                && first.is_none_or(|first| time < first)
+            {
+                self.push(time, *hpa);
+            }
+        }
+        for (time, hpa) in current {
+            self.push(time, hpa);
+        }
+    }
+
+    /// The readings held, oldest first, for saving.
+    pub fn samples(&self) -> Vec<(String, f64)> {
+        self.samples
+            .iter()
+            .map(|(time, hpa)| (time.format(TIME_FORMAT).to_string(), *hpa))
+            .collect()
+    }
+
+    fn push(&mut self, time: NaiveDateTime, hpa: f64) {
+        match self.samples.back() {
+            Some((last, _)) if *last == time => {
+                self.samples.pop_back();
+            }
+            // Time went backwards, e.g. a replay starting over
+            Some((last, _)) if *last > time => self.samples.clear(),
+            _ => {}
+        }
+        self.samples.push_back((time, hpa));
+
+        while self
+            .samples
+            .front()
+            .is_some_and(|(oldest, _)| time - *oldest > WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Pressure change scaled to hPa per three hours.
+    fn change(&self) -> Option<f64> {
+        let (first_time, first) = self.samples.front()?;
+        let (last_time, last) = self.samples.back()?;
+        let span = *last_time - *first_time;
+        if span < MIN_SPAN {
+            return None;
+        }
+        Some((last - first) * WINDOW.num_seconds() as f64 / span.num_seconds() as f64)
+    }
+
+    pub fn tendency(&self) -> Option<PressureTendency> {
+        self.change().map(PressureTendency::from_change)
+    }
+
+    /// Pressure is dropping fast enough to hint at a storm.
+    pub fn storm_possible(&self) -> bool {
+        self.change()
+            .is_some_and(|change| change <= -STORM_DROP_HPA)
+    }
+}
+
+fn parse_time(time: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(time.get(..16)?, TIME_FORMAT).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tendency_needs_an_hour_of_readings() {
+        let mut history = PressureHistory::new();
+        history.record("2024-01-15T12:00", 1012.0);
+        history.record("2024-01-15T12:30", 1011.0);
+        assert_eq!(history.tendency(), None);
+
+        history.record("2024-01-15T13:00", 1011.0);
+        assert_eq!(history.tendency(), Some(PressureTendency::Falling));
+        assert!(!history.storm_possible());
+    }
+
+    #[test]
+    fn test_rapid_drop_hints_at_storm() {
+        let mut history = PressureHistory::new();
+        for (i, hpa) in [1005.0, 1003.0, 1001.0, 998.0, 996.0]
+            .into_iter()
+            .enumerate()
+        {
+            history.record(&format!("2024-01-15T{:02}:00", 10 + i), hpa);
+        }
+        // 10:00 has aged out: 1003 -> 996 over three hours
+        assert_eq!(history.tendency(), Some(PressureTendency::FallingFast));
+        assert!(history.storm_possible());
+
+        history.record("2024-01-15T09:00", 1010.0);
+        assert_eq!(history.tendency(), None);
+    }
+
+    #[test]
+    fn test_restore_puts_saved_readings_first() {
+        let mut earlier = PressureHistory::new();
+        earlier.record("2024-01-15T10:00", 1013.5);
+        earlier.record("2024-01-15T11:00", 1013.0);
+        let saved = earlier.samples();
+        assert_eq!(saved[0], ("2024-01-15T10:00".to_string(), 1013.5));
+
+        let mut history = PressureHistory::new();
+        history.record("2024-01-15T11:00", 1012.5);
+        history.record("2024-01-15T12:00", 1012.0);
+        history.restore(&saved);
+        // 11:00 comes from this session; 10:00 from the saved readings
+        assert_eq!(history.samples().len(), 3);
+        assert_eq!(history.samples()[1].1, 1012.5);
+        assert_eq!(history.tendency(), Some(PressureTendency::Falling));
+
+        // Readings more than three hours older than the newest are dropped
+        history.record("2024-01-15T14:00", 1011.0);
+        assert_eq!(history.samples()[0].0, "2024-01-15T11:00");
+    }
+}
@@ -0,0 +1,227 @@
+//! Daily request budgets, so a free-tier API key isn't suspended for going
+//! over its quota: refreshes are spaced further apart as the day's budget
+//! runs down, with a warning once most of it is spent.
+
+use crate::cache;
+use crate::config::Provider;
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::provider::{SECS_PER_DAY, WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{WeatherLocation, WeatherUnits};
+use async_trait::async_trait;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Share of the budget spent before the status line warns about it.
+const WARN_AT: f64 = 0.8;
+
+/// Requests made to one provider on one UTC day, which is when free tiers
+/// reset.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RequestCount {
+    pub day: NaiveDate,
+    pub used: u32,
+}
+
+#[derive(Debug)]
+struct RequestBudget {
+    per_day: u32,
+    count: RequestCount,
+}
+
+impl RequestBudget {
+    fn new(per_day: u32, now: DateTime<Utc>) -> Self {
+        Self {
+            per_day: per_day.max(1),
+            count: RequestCount {
+                day: now.date_naive(),
+                used: 0,
+            },
+        }
+    }
+
+    /// Starts the count afresh once the day is over.
+    fn roll_over(&mut self, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        if self.count.day != today {
+            self.count = RequestCount {
+                day: today,
+                used: 0,
+            };
+        }
+    }
+
+    fn remaining(&self) -> u32 {
+        self.per_day.saturating_sub(self.count.used)
+    }
+
+    /// The wait that spreads what's left of the budget evenly over the rest
+    /// of the day.
+    fn spacing(&self, now: DateTime<Utc>) -> Duration {
+        if self.count.day != now.date_naive() {
+            return Duration::from_secs(SECS_PER_DAY) / self.per_day;
+        }
+        until_reset(now) / self.remaining().max(1)
+    }
+
+    fn warning(&self, kind: Provider, now: DateTime<Utc>) -> Option<String> {
+        let spent = self.count.day == now.date_naive()
+            && f64::from(self.count.used) >= f64::from(self.per_day) * WARN_AT;
+        spent.then(|| {
+            format!(
+                "{} used {} of {} requests today, refreshing less often",
+                kind.abbreviation(),
+                self.count.used,
+                self.per_day
+            )
+        })
+    }
+}
+
+/// Time left until midnight UTC.
+fn until_reset(now: DateTime<Utc>) -> Duration {
+    let midnight = (now.date_naive() + Days::new(1))
+        .and_hms_opt(0, 0, 0)
+        .map(|midnight| midnight.and_utc())
+        .unwrap_or(now);
+    (midnight - now).to_std().unwrap_or_default()
+}
+
+/// Counts the requests made to a provider against its daily budget, and
+/// refuses them as rate limited once it's spent. The count is kept in the
+/// cache dir so restarts don't reset it.
+pub struct BudgetedProvider {
+    kind: Provider,
+    inner: Arc<dyn WeatherProvider>,
+    budget: Mutex<RequestBudget>,
+    restored: AtomicBool,
+}
+
+impl BudgetedProvider {
+    pub fn new(kind: Provider, per_day: u32, inner: Arc<dyn WeatherProvider>) -> Self {
+        Self {
+            kind,
+            inner,
+            budget: Mutex::new(RequestBudget::new(per_day, Utc::now())),
+            restored: AtomicBool::new(false),
+        }
+    }
+
+    fn budget(&self) -> std::sync::MutexGuard<'_, RequestBudget> {
+        self.budget.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Counts a request, or says how long until the budget resets when
+    /// there's none left.
+    fn spend(&self, now: DateTime<Utc>) -> Result<RequestCount, Duration> {
+        let mut budget = self.budget();
+        budget.roll_over(now);
+        if budget.remaining() == 0 {
+            return Err(until_reset(now));
+        }
+        budget.count.used += 1;
+        Ok(budget.count)
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for BudgetedProvider {
+    fn get_attribution(&self) -> &'static str {
+        self.inner.get_attribution()
+    }
+
+    fn refresh_spacing(&self) -> Duration {
+        self.budget()
+            .spacing(Utc::now())
+            .max(self.inner.refresh_spacing())
+    }
+
+    fn budget_warning(&self) -> Option<String> {
+        self.budget()
+            .warning(self.kind, Utc::now())
+            .or_else(|| self.inner.budget_warning())
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        if !self.restored.swap(true, Ordering::Relaxed)
+            && let Some(saved) = cache::load_request_count(self.kind).await
+        {
+            self.budget().count = saved;
+        }
+
+        let count = self.spend(Utc::now()).map_err(|until_reset| {
+            WeatherError::Network(NetworkError::RateLimited {
+                url: format!("{} (daily budget spent)", self.kind.name()),
+                retry_after: Some(until_reset),
+            })
+        })?;
+        cache::save_request_count(self.kind, count);
+
+        self.inner.get_current_weather(location, units).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_budget_stretches_refreshes_and_warns() {
+        let noon = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let mut budget = RequestBudget::new(360, noon);
+        assert_eq!(budget.spacing(noon), Duration::from_secs(120));
+        assert_eq!(budget.warning(Provider::MetOffice, noon), None);
+
+        // Manual refreshes ate into the budget: the rest of it is spread thin
+        budget.count.used = 300;
+        assert_eq!(budget.spacing(noon), Duration::from_secs(720));
+        assert_eq!(
+            budget.warning(Provider::MetOffice, noon).as_deref(),
+            Some("MetO used 300 of 360 requests today, refreshing less often")
+        );
+
+        budget.count.used = 360;
+        assert_eq!(budget.spacing(noon), until_reset(noon));
+
+        let tomorrow = noon + Days::new(1);
+        budget.roll_over(tomorrow);
+        assert_eq!(budget.remaining(), 360);
+        assert_eq!(budget.warning(Provider::MetOffice, tomorrow), None);
+    }
+
+    #[test]
+    fn test_spent_budget_is_refused_until_midnight() {
+        struct Unreachable;
+
+        #[async_trait]
+        impl WeatherProvider for Unreachable {
+            fn get_attribution(&self) -> &'static str {
+                "unreachable"
+            }
+
+            async fn get_current_weather(
+                &self,
+                _location: &WeatherLocation,
+                _units: &WeatherUnits,
+            ) -> Result<WeatherProviderResponse, WeatherError> {
+                unreachable!()
+            }
+        }
+
+        let provider = BudgetedProvider::new(Provider::MetOffice, 2, Arc::new(Unreachable));
+        let evening = Utc.with_ymd_and_hms(2026, 10, 16, 22, 0, 0).unwrap();
+        assert_eq!(provider.spend(evening).map(|count| count.used), Ok(1));
+        assert_eq!(provider.spend(evening).map(|count| count.used), Ok(2));
+        assert_eq!(
+            provider.spend(evening),
+            Err(Duration::from_secs(2 * 60 * 60))
+        );
+    }
+}
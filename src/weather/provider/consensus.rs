@@ -0,0 +1,247 @@
+use crate::error::{DataError, WeatherError};
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{Disagreement, WeatherDetails, WeatherLocation, WeatherUnits};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// Temperature spread (°C) from which providers count as disagreeing.
+const TEMPERATURE_SPREAD_THRESHOLD: f64 = 3.0;
+/// WMO codes from this one up are drizzle, rain, snow or storms.
+const FIRST_PRECIPITATION_CODE: i32 = 51;
+
+/// Queries several providers at once and blends their answers: median
+/// temperature, wind, precipitation, pressure, humidity and UV, and the weather
+/// code most of them agree on. Sun, moon and timestamp come from the first
+/// provider that answered, the nowcast, hourly forecast, today's low and high
+/// and extra details from the first that has them.
+pub struct ConsensusProvider {
+    providers: Vec<Arc<dyn WeatherProvider>>,
+}
+
+impl ConsensusProvider {
+    pub fn new(providers: Vec<Arc<dyn WeatherProvider>>) -> Self {
+        Self { providers }
+    }
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+/// The most common weather code; ties go to the earliest provider.
+fn majority_code(codes: &[i32]) -> i32 {
+    let count = |code: i32| codes.iter().filter(|&&c| c == code).count();
+    codes
+        .iter()
+        .copied()
+        .fold(None, |best: Option<i32>, code| match best {
+            Some(best) if count(best) >= count(code) => Some(best),
+            _ => Some(code),
+        })
+        .unwrap_or(0)
+}
+
+/// Blends responses given in provider priority order.
+fn blend(responses: Vec<WeatherProviderResponse>) -> Option<WeatherProviderResponse> {
+    if responses.len() < 2 {
+        return responses.into_iter().next();
+    }
+
+    let temperatures: Vec<f64> = responses.iter().map(|r| r.temperature).collect();
+    let codes: Vec<i32> = responses.iter().map(|r| r.weather_code).collect();
+    let spread = temperatures.iter().copied().fold(f64::MIN, f64::max)
+        - temperatures.iter().copied().fold(f64::MAX, f64::min);
+    let wet = codes
+        .iter()
+        .filter(|&&c| c >= FIRST_PRECIPITATION_CODE)
+        .count();
+    let precipitation_differs = wet > 0 && wet < codes.len();
+
+    let disagreement =
+        (spread >= TEMPERATURE_SPREAD_THRESHOLD || precipitation_differs).then_some(Disagreement {
+            temperature_spread: spread,
+            precipitation: precipitation_differs,
+        });
+
+    let temperature = median(temperatures)?;
+    let precipitation = median(responses.iter().map(|r| r.precipitation).collect())?;
+    let wind_speed = median(responses.iter().map(|r| r.wind_speed).collect())?;
+    let pressure = median(responses.iter().filter_map(|r| r.pressure).collect());
+    let uv_index = median(responses.iter().filter_map(|r| r.uv_index).collect());
+    let humidity = median(responses.iter().filter_map(|r| r.humidity).collect());
+    let apparent_temperature = median(
+        responses
+            .iter()
+            .filter_map(|r| r.apparent_temperature)
+            .collect(),
+    );
+    let attribution = responses
+        .iter()
+        .map(|r| r.attribution.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut sources: Vec<&str> = Vec::new();
+    for source in responses.iter().map(|r| r.source.as_str()) {
+        if !source.is_empty() && !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+    let source = sources.join("+");
+    let hourly = responses
+        .iter()
+        .find(|r| !r.hourly.is_empty())
+        .map(|r| r.hourly.clone())
+        .unwrap_or_default();
+    let nowcast = responses
+        .iter()
+        .find(|r| !r.nowcast.is_empty())
+        .map(|r| r.nowcast.clone())
+        .unwrap_or_default();
+    let today = responses.iter().find_map(|r| r.today);
+    let details = responses
+        .iter()
+        .find(|r| r.details != WeatherDetails::default())
+        .map(|r| r.details.clone())
+        .unwrap_or_default();
+
+    let first = responses.into_iter().next()?;
+    Some(WeatherProviderResponse {
+        weather_code: majority_code(&codes),
+        temperature,
+        precipitation,
+        wind_speed,
+        pressure,
+        humidity,
+        apparent_temperature,
+        uv_index,
+        disagreement,
+        source,
+        nowcast,
+        hourly,
+        today,
+        details,
+        attribution,
+        ..first
+    })
+}
+
+#[async_trait]
+impl WeatherProvider for ConsensusProvider {
+    fn get_attribution(&self) -> &'static str {
+        self.providers
+            .first()
+            .map(|p| p.get_attribution())
+            .unwrap_or_default()
+    }
+
+    fn refresh_spacing(&self) -> Duration {
+        self.providers
+            .iter()
+            .map(|p| p.refresh_spacing())
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn budget_warning(&self) -> Option<String> {
+        self.providers.iter().find_map(|p| p.budget_warning())
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let mut tasks = JoinSet::new();
+        for (index, provider) in self.providers.iter().enumerate() {
+            let provider = Arc::clone(provider);
+            let (location, units) = (*location, *units);
+            tasks.spawn(
+                async move { (index, provider.get_current_weather(&location, &units).await) },
+            );
+        }
+
+        let mut results: Vec<_> = tasks.join_all().await;
+        results.sort_by_key(|(index, _)| *index);
+
+        let mut first_error = None;
+        let mut responses = Vec::new();
+        for (_, result) in results {
+            match result {
+                Ok(response) => responses.push(response),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match blend(responses) {
+            Some(response) => Ok(response),
+            None => Err(first_error.unwrap_or(WeatherError::Data(DataError::NoData))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(temperature: f64, weather_code: i32, attribution: &str) -> WeatherProviderResponse {
+        WeatherProviderResponse {
+            temperature,
+            precipitation: if weather_code >= 51 { 1.0 } else { 0.0 },
+            wind_speed: 4.0,
+            wind_direction: 180.0,
+            pressure: Some(1010.0),
+            source: attribution.to_string(),
+            timestamp: "2024-06-21T09:00".to_string(),
+            attribution: attribution.to_string(),
+            ..WeatherProviderResponse::sample(weather_code)
+        }
+    }
+
+    #[test]
+    fn test_blend_takes_median_and_majority() {
+        let blended = blend(vec![
+            response(14.0, 3, "A"),
+            response(15.0, 61, "B"),
+            response(16.0, 3, "C"),
+        ])
+        .unwrap();
+
+        assert_eq!(blended.temperature, 15.0);
+        assert_eq!(blended.weather_code, 3);
+        assert_eq!(blended.attribution, "A\nB\nC");
+        assert_eq!(blended.source, "A+B+C");
+        assert_eq!(
+            blended.disagreement,
+            Some(Disagreement {
+                temperature_spread: 2.0,
+                precipitation: true
+            })
+        );
+    }
+
+    #[test]
+    fn test_close_providers_agree() {
+        let blended = blend(vec![response(14.0, 2, "A"), response(15.5, 3, "B")]).unwrap();
+        assert_eq!(blended.temperature, 14.75);
+        // A two-way tie goes to the first provider
+        assert_eq!(blended.weather_code, 2);
+        assert_eq!(blended.disagreement, None);
+
+        let blended = blend(vec![response(10.0, 2, "A"), response(14.0, 2, "B")]).unwrap();
+        assert_eq!(blended.disagreement.unwrap().temperature_spread, 4.0);
+        assert!(blend(Vec::new()).is_none());
+    }
+}
@@ -0,0 +1,305 @@
+use crate::config::Provider;
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::http::{self, RequestOptions};
+use crate::scheduler::Scheduler;
+use crate::weather::astro;
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::timezone::TimeZoneLookup;
+use crate::weather::types::{WeatherLocation, WeatherUnits};
+use crate::weather::units::kmh_to_ms;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Bright Sky serves DWD open data (station observations and SYNOP reports)
+/// as JSON. See: https://brightsky.dev/docs/
+const BRIGHT_SKY_BASE_URL: &str = "https://api.brightsky.dev/current_weather";
+
+pub struct DwdProvider {
+    client: reqwest::Client,
+    timeout_secs: u64,
+    base_url: String,
+    config: DwdProviderConfig,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DwdProviderConfig {
+    #[serde(flatten)]
+    pub request: RequestOptions,
+    /// DWD station ID (e.g. "01766") to use instead of the nearest station.
+    #[serde(default)]
+    pub station_id: Option<String>,
+    /// How far (in km) to look for a station around the location. Bright
+    /// Sky's default is 50 km.
+    #[serde(default)]
+    pub max_distance_km: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BrightSkyResponse {
+    weather: BrightSkyWeather,
+    #[serde(default)]
+    sources: Vec<BrightSkySource>,
+}
+
+/// Current conditions in Bright Sky's default "dwd" units: °C, km/h, mm and
+/// hPa.
+#[derive(Debug, Deserialize)]
+struct BrightSkyWeather {
+    timestamp: DateTime<Utc>,
+    temperature: Option<f64>,
+    /// "dry", "fog", "rain", "sleet", "snow", "hail" or "thunderstorm".
+    condition: Option<String>,
+    /// Percent.
+    cloud_cover: Option<f64>,
+    precipitation_60: Option<f64>,
+    wind_speed_10: Option<f64>,
+    wind_direction_10: Option<f64>,
+    pressure_msl: Option<f64>,
+    /// Percent.
+    relative_humidity: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BrightSkySource {
+    station_name: Option<String>,
+    lat: f64,
+    lon: f64,
+}
+
+impl DwdProvider {
+    pub fn new(config: DwdProviderConfig) -> Result<Self, WeatherError> {
+        let client = config.request.client().map_err(WeatherError::Network)?;
+
+        Ok(Self {
+            client,
+            timeout_secs: config.request.timeout_secs(),
+            base_url: BRIGHT_SKY_BASE_URL.to_string(),
+            config,
+        })
+    }
+
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        if let Some(station_id) = &self.config.station_id {
+            return format!("{}?dwd_station_id={}", self.base_url, station_id.trim());
+        }
+
+        let mut url = format!(
+            "{}?lat={}&lon={}",
+            self.base_url, location.latitude, location.longitude
+        );
+        if let Some(max_distance) = self.config.max_distance_km {
+            url.push_str(&format!("&max_dist={}", (max_distance * 1000.0).round()));
+        }
+        url
+    }
+
+    /// Maps a Bright Sky condition, with the last hour's precipitation for
+    /// intensity and the cloud cover for dry weather, onto a WMO code.
+    fn condition_to_wmo(
+        condition: Option<&str>,
+        precipitation: f64,
+        cloud_cover: Option<f64>,
+    ) -> i32 {
+        match condition {
+            Some("thunderstorm") => 95,
+            Some("hail") => 96,
+            Some("fog") => 45,
+            Some("sleet") => 66,
+            Some("rain") if precipitation >= 7.6 => 65,
+            Some("rain") if precipitation >= 2.5 => 63,
+            Some("rain") => 61,
+            Some("snow") if precipitation >= 3.0 => 75,
+            Some("snow") if precipitation >= 1.0 => 73,
+            Some("snow") => 71,
+            _ => match cloud_cover.unwrap_or(0.0) {
+                c if c <= 12.5 => 0,
+                c if c <= 37.5 => 1,
+                c if c <= 75.0 => 2,
+                _ => 3,
+            },
+        }
+    }
+
+    /// The observation, with day and night on `clock`, the one at
+    /// `location`.
+    fn to_response(
+        &self,
+        data: BrightSkyResponse,
+        location: &WeatherLocation,
+        clock: Scheduler,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let weather = data.weather;
+        let temperature = weather.temperature.ok_or_else(|| {
+            WeatherError::Data(DataError::BadData(
+                "DWD observation has no temperature".to_string(),
+            ))
+        })?;
+        let precipitation = weather.precipitation_60.unwrap_or(0.0);
+        let source = data.sources.first();
+
+        // Day and night at the station, which may be some way off, else
+        // at the place asked about
+        let (latitude, longitude) =
+            source.map_or((location.latitude, location.longitude), |s| (s.lat, s.lon));
+        let attribution = match source.and_then(|s| s.station_name.as_deref()) {
+            Some(station) => format!("{} ({})", self.get_attribution(), station),
+            None => self.get_attribution().to_string(),
+        };
+
+        Ok(WeatherProviderResponse {
+            weather_code: Self::condition_to_wmo(
+                weather.condition.as_deref(),
+                precipitation,
+                weather.cloud_cover,
+            ),
+            temperature,
+            precipitation,
+            wind_speed: kmh_to_ms(weather.wind_speed_10.unwrap_or(0.0)),
+            wind_direction: weather.wind_direction_10.unwrap_or(0.0),
+            sun: astro::celestial_events(latitude, longitude, clock.local(weather.timestamp)),
+            moon_phase: Some(astro::moon_phase(weather.timestamp)),
+            pressure: weather.pressure_msl,
+            disagreement: None,
+            humidity: weather.relative_humidity,
+            apparent_temperature: None,
+            uv_index: None,
+            source: Provider::Dwd.abbreviation().to_string(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: None,
+            details: Default::default(),
+            timestamp: weather.timestamp.format("%Y-%m-%dT%H:%M").to_string(),
+            attribution,
+        })
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for DwdProvider {
+    fn get_attribution(&self) -> &'static str {
+        // Required by the DWD terms of use
+        "Source: Deutscher Wetterdienst"
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = self.build_url(location);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| NetworkError::from_reqwest(e, &url, self.timeout_secs))
+            .and_then(|resp| http::error_for_status(resp, &url, self.timeout_secs))
+            .map_err(WeatherError::Network)?;
+
+        let data: BrightSkyResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, self.timeout_secs))
+        })?;
+
+        let clock = TimeZoneLookup::shared().clock_at(location).await;
+        self.to_response(data, location, clock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_condition_mapping() {
+        let cases = [
+            (Some("dry"), 0.0, Some(0.0), 0),
+            (Some("dry"), 0.0, Some(50.0), 2),
+            (None, 0.0, Some(100.0), 3),
+            (Some("rain"), 0.4, None, 61),
+            (Some("rain"), 9.0, None, 65),
+            (Some("snow"), 1.5, None, 73),
+            (Some("thunderstorm"), 4.0, None, 95),
+        ];
+        for (condition, precipitation, cloud_cover, expected) in cases {
+            assert_eq!(
+                DwdProvider::condition_to_wmo(condition, precipitation, cloud_cover),
+                expected,
+                "{:?}",
+                condition
+            );
+        }
+    }
+
+    #[test]
+    fn test_response_conversion() {
+        let json = r#"{
+            "weather": {
+                "source_id": 1234,
+                "timestamp": "2024-01-15T18:30:00+00:00",
+                "cloud_cover": 100,
+                "condition": "snow",
+                "precipitation_60": 0.6,
+                "pressure_msl": 1003.2,
+                "temperature": -1.4,
+                "wind_direction_10": 250,
+                "wind_speed_10": 18.0,
+                "icon": "snow"
+            },
+            "sources": [{
+                "id": 1234,
+                "dwd_station_id": "01766",
+                "station_name": "Münster/Osnabrück",
+                "lat": 52.1344,
+                "lon": 7.6969,
+                "distance": 8765.0
+            }]
+        }"#;
+        let data: BrightSkyResponse = serde_json::from_str(json).unwrap();
+        let provider = DwdProvider::new(DwdProviderConfig::default()).unwrap();
+        let location = WeatherLocation {
+            latitude: 52.0,
+            longitude: 7.6,
+            elevation: None,
+        };
+        let response = provider
+            .to_response(data, &location, Scheduler::for_zone("Europe/Berlin"))
+            .unwrap();
+
+        assert_eq!(response.weather_code, 71);
+        assert_eq!(response.temperature, -1.4);
+        assert_eq!(response.wind_speed, 5.0);
+        assert_eq!(response.pressure, Some(1003.2));
+        assert!(!response.sun.is_day);
+        assert_eq!(response.timestamp, "2024-01-15T18:30");
+        assert_eq!(
+            response.attribution,
+            "Source: Deutscher Wetterdienst (Münster/Osnabrück)"
+        );
+    }
+
+    #[test]
+    fn test_sun_at_requested_place_without_station() {
+        // Dawn in Münster, still dark at 0°N 0°E
+        let json = r#"{
+            "weather": {
+                "timestamp": "2024-06-21T03:30:00+00:00",
+                "temperature": 14.0
+            }
+        }"#;
+        let data: BrightSkyResponse = serde_json::from_str(json).unwrap();
+        let provider = DwdProvider::new(DwdProviderConfig::default()).unwrap();
+        let location = WeatherLocation {
+            latitude: 51.96,
+            longitude: 7.63,
+            elevation: None,
+        };
+        let response = provider
+            .to_response(data, &location, Scheduler::for_zone("Europe/Berlin"))
+            .unwrap();
+
+        assert!(response.sun.is_day);
+        assert_eq!(response.attribution, "Source: Deutscher Wetterdienst");
+    }
+}
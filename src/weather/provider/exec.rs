@@ -0,0 +1,291 @@
+//! Runs a user-supplied command and reads the current weather from the JSON
+//! it prints, so local sensors and unsupported services need no new
+//! provider. The command gets the location in `WEATHR_LATITUDE` and
+//! `WEATHR_LONGITUDE` and must print an object such as:
+//!
+//! ```json
+//! {"temperature": 12.5, "weather_code": 61, "precipitation": 0.4,
+//!  "wind_speed": 3.2, "wind_direction": 220, "pressure": 1012.3}
+//! ```
+//!
+//! Only `temperature` (°C) is required. `weather_code` is a WMO code
+//! (default 0, clear), `precipitation` is mm/h, `wind_speed` m/s,
+//! `wind_direction` degrees and `pressure` sea level hPa. Optional
+//! `is_day`, `timestamp` ("YYYY-MM-DDTHH:MM") and `attribution` override
+//! the computed defaults.
+
+use crate::config::{ENV_LATITUDE, ENV_LONGITUDE, Provider};
+use crate::error::{ConfigError, DataError, WeatherError};
+use crate::weather::astro;
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::timezone::TimeZoneLookup;
+use crate::weather::types::{WeatherLocation, WeatherUnits};
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::Deserialize;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+pub struct ExecProvider {
+    program: String,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ExecProviderConfig {
+    /// Program and arguments, e.g. `["python3", "/home/me/station.py"]`.
+    /// Not run through a shell.
+    pub command: Vec<String>,
+    pub timeout_secs: u64,
+}
+
+impl Default for ExecProviderConfig {
+    fn default() -> Self {
+        Self {
+            command: Vec::new(),
+            timeout_secs: 10,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExecOutput {
+    temperature: f64,
+    #[serde(default)]
+    weather_code: i32,
+    #[serde(default)]
+    precipitation: f64,
+    #[serde(default)]
+    wind_speed: f64,
+    #[serde(default)]
+    wind_direction: f64,
+    pressure: Option<f64>,
+    humidity: Option<f64>,
+    feels_like: Option<f64>,
+    uv_index: Option<f64>,
+    is_day: Option<bool>,
+    timestamp: Option<String>,
+    attribution: Option<String>,
+}
+
+impl ExecProvider {
+    pub fn new(config: ExecProviderConfig) -> Result<Self, WeatherError> {
+        let mut command = config.command.into_iter();
+        let program = command
+            .next()
+            .filter(|program| !program.trim().is_empty())
+            .ok_or_else(|| {
+                WeatherError::Config(ConfigError::InvalidProviderOptions {
+                    provider: "exec",
+                    reason: "`command` must name a program to run".to_string(),
+                })
+            })?;
+
+        Ok(Self {
+            program,
+            args: command.collect(),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+        })
+    }
+
+    fn failed(&self, reason: impl Into<String>) -> WeatherError {
+        WeatherError::Data(DataError::CommandFailed {
+            command: self.program.clone(),
+            reason: reason.into(),
+        })
+    }
+
+    async fn run(&self, location: &WeatherLocation) -> Result<Vec<u8>, WeatherError> {
+        let child = Command::new(&self.program)
+            .args(&self.args)
+            .env(ENV_LATITUDE, location.latitude.to_string())
+            .env(ENV_LONGITUDE, location.longitude.to_string())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| self.failed(e.to_string()))?;
+
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| self.failed(format!("timed out after {}s", self.timeout.as_secs())))?
+            .map_err(|e| self.failed(e.to_string()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = match stderr.lines().last() {
+                Some(line) => format!("{}: {}", output.status, line.trim()),
+                None => output.status.to_string(),
+            };
+            return Err(self.failed(reason));
+        }
+        Ok(output.stdout)
+    }
+
+    /// The command's output `now`, on the clock at `location`.
+    fn to_response(
+        &self,
+        output: ExecOutput,
+        location: &WeatherLocation,
+        now: DateTime<FixedOffset>,
+    ) -> WeatherProviderResponse {
+        let mut sun = astro::celestial_events(location.latitude, location.longitude, now);
+        if let Some(is_day) = output.is_day {
+            sun.is_day = is_day;
+        }
+
+        WeatherProviderResponse {
+            weather_code: output.weather_code,
+            temperature: output.temperature,
+            precipitation: output.precipitation,
+            wind_speed: output.wind_speed,
+            wind_direction: output.wind_direction,
+            sun,
+            moon_phase: Some(astro::moon_phase(now.with_timezone(&Utc))),
+            pressure: output.pressure,
+            disagreement: None,
+            humidity: output.humidity,
+            apparent_temperature: output.feels_like,
+            uv_index: output.uv_index,
+            source: Provider::Exec.abbreviation().to_string(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: None,
+            details: Default::default(),
+            timestamp: output
+                .timestamp
+                .unwrap_or_else(|| now.format("%Y-%m-%dT%H:%M").to_string()),
+            attribution: output
+                .attribution
+                .unwrap_or_else(|| self.get_attribution().to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for ExecProvider {
+    fn get_attribution(&self) -> &'static str {
+        "Weather data from a local command"
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let stdout = self.run(location).await?;
+        let output: ExecOutput = serde_json::from_slice(&stdout)
+            .map_err(|e| WeatherError::Data(DataError::SerdeParseError(e)))?;
+        let now = TimeZoneLookup::shared().clock_at(location).await.now();
+        Ok(self.to_response(output, location, now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::Scheduler;
+    use chrono::{NaiveDate, NaiveTime};
+
+    fn location() -> WeatherLocation {
+        WeatherLocation {
+            latitude: 51.5,
+            longitude: -0.1,
+            elevation: None,
+        }
+    }
+
+    #[test]
+    fn test_empty_command_is_rejected() {
+        let err = ExecProvider::new(ExecProviderConfig::default()).err();
+        assert!(matches!(
+            err,
+            Some(WeatherError::Config(
+                ConfigError::InvalidProviderOptions { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_minimal_output_uses_defaults() {
+        let provider = ExecProvider::new(ExecProviderConfig {
+            command: vec!["station".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        let output: ExecOutput =
+            serde_json::from_str(r#"{"temperature": 7.5, "is_day": false}"#).unwrap();
+        let response = provider.to_response(output, &location(), Utc::now().fixed_offset());
+
+        assert_eq!(response.temperature, 7.5);
+        assert_eq!(response.weather_code, 0);
+        assert_eq!(response.pressure, None);
+        assert!(!response.sun.is_day);
+        assert_eq!(response.attribution, "Weather data from a local command");
+    }
+
+    #[test]
+    fn test_day_on_the_clock_at_the_place() {
+        let provider = ExecProvider::new(ExecProviderConfig {
+            command: vec!["station".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        let output: ExecOutput = serde_json::from_str(r#"{"temperature": 21.0}"#).unwrap();
+        let tokyo = WeatherLocation {
+            latitude: 35.68,
+            longitude: 139.69,
+            elevation: None,
+        };
+        // 07:00 in Tokyo is 22:00 the evening before in London or UTC
+        let now = Scheduler::for_zone("Asia/Tokyo").at(
+            NaiveDate::from_ymd_opt(2024, 6, 21).unwrap(),
+            NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+        );
+        let response = provider.to_response(output, &tokyo, now);
+
+        assert!(response.sun.is_day);
+        let rise = response.sun.rise.unwrap();
+        assert!(rise > NaiveTime::from_hms_opt(4, 0, 0).unwrap());
+        assert!(rise < NaiveTime::from_hms_opt(5, 0, 0).unwrap());
+        assert_eq!(response.timestamp, "2024-06-21T07:00");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_runs_command_with_location() {
+        let provider = ExecProvider::new(ExecProviderConfig {
+            command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                r#"echo "{\"temperature\": $WEATHR_LATITUDE, \"weather_code\": 63}""#.to_string(),
+            ],
+            ..Default::default()
+        })
+        .unwrap();
+        let response = provider
+            .get_current_weather(&location(), &WeatherUnits::metric())
+            .await
+            .unwrap();
+        assert_eq!(response.temperature, 51.5);
+        assert_eq!(response.weather_code, 63);
+
+        let provider = ExecProvider::new(ExecProviderConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        let err = provider
+            .get_current_weather(&location(), &WeatherUnits::metric())
+            .await
+            .err();
+        assert!(matches!(
+            err,
+            Some(WeatherError::Data(DataError::CommandFailed { .. }))
+        ));
+    }
+}
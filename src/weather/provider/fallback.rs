@@ -0,0 +1,207 @@
+use crate::error::{NetworkError, WeatherError};
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{WeatherLocation, WeatherUnits};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long a rate-limited provider is left alone without a `Retry-After`.
+const DEFAULT_REST: Duration = Duration::from_secs(15 * 60);
+
+/// Tries each provider in turn until one answers. When all of them fail
+/// the first provider's error is returned, as that is the one the user
+/// configured. A provider that rate limits us is skipped until its
+/// `Retry-After` has passed, rather than asked again on every refresh.
+pub struct FallbackProvider {
+    providers: Vec<Arc<dyn WeatherProvider>>,
+    /// Per provider, the URL that was rate limited and when to try it again.
+    resting: Mutex<Vec<Option<(String, Instant)>>>,
+}
+
+impl FallbackProvider {
+    pub fn new(primary: Arc<dyn WeatherProvider>) -> Self {
+        Self {
+            providers: vec![primary],
+            resting: Mutex::new(vec![None]),
+        }
+    }
+
+    pub fn then(mut self, provider: Arc<dyn WeatherProvider>) -> Self {
+        self.providers.push(provider);
+        self.resting
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(None);
+        self
+    }
+
+    /// The rate limiting provider `i` is still waiting out, if any.
+    fn rate_limited(&self, i: usize) -> Option<WeatherError> {
+        let mut resting = self.resting.lock().unwrap_or_else(|e| e.into_inner());
+        let (url, until) = resting[i].clone()?;
+        let now = Instant::now();
+        if until <= now {
+            resting[i] = None;
+            return None;
+        }
+        Some(WeatherError::Network(NetworkError::RateLimited {
+            url,
+            retry_after: Some(until - now),
+        }))
+    }
+
+    fn rest(&self, i: usize, url: &str, retry_after: Option<Duration>) {
+        let until = Instant::now() + retry_after.unwrap_or(DEFAULT_REST);
+        self.resting.lock().unwrap_or_else(|e| e.into_inner())[i] = Some((url.to_string(), until));
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for FallbackProvider {
+    fn get_attribution(&self) -> &'static str {
+        self.providers[0].get_attribution()
+    }
+
+    fn refresh_spacing(&self) -> Duration {
+        self.providers
+            .iter()
+            .map(|p| p.refresh_spacing())
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn budget_warning(&self) -> Option<String> {
+        self.providers.iter().find_map(|p| p.budget_warning())
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let mut first_error = None;
+        for (i, provider) in self.providers.iter().enumerate() {
+            if let Some(e) = self.rate_limited(i) {
+                first_error.get_or_insert(e);
+                continue;
+            }
+            match provider.get_current_weather(location, units).await {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    if let WeatherError::Network(NetworkError::RateLimited { url, retry_after }) =
+                        &e
+                    {
+                        self.rest(i, url, *retry_after);
+                    }
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        Err(first_error.expect("a fallback chain has at least one provider"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DataError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Fixed(Option<&'static str>);
+
+    #[async_trait]
+    impl WeatherProvider for Fixed {
+        fn get_attribution(&self) -> &'static str {
+            self.0.unwrap_or("broken")
+        }
+
+        async fn get_current_weather(
+            &self,
+            _location: &WeatherLocation,
+            _units: &WeatherUnits,
+        ) -> Result<WeatherProviderResponse, WeatherError> {
+            let attribution = self
+                .0
+                .ok_or(WeatherError::Data(DataError::BadData("down".to_string())))?;
+            Ok(WeatherProviderResponse {
+                attribution: attribution.to_string(),
+                ..WeatherProviderResponse::sample(0)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_falls_through_to_first_working_provider() {
+        let location = WeatherLocation {
+            latitude: 51.5,
+            longitude: -0.1,
+            elevation: None,
+        };
+        let units = WeatherUnits::metric();
+
+        let chain = FallbackProvider::new(Arc::new(Fixed(None)))
+            .then(Arc::new(Fixed(Some("second"))))
+            .then(Arc::new(Fixed(Some("third"))));
+        let response = chain.get_current_weather(&location, &units).await.unwrap();
+        assert_eq!(response.attribution, "second");
+
+        let chain = FallbackProvider::new(Arc::new(Fixed(None))).then(Arc::new(Fixed(None)));
+        let err = chain.get_current_weather(&location, &units).await;
+        assert!(matches!(
+            err,
+            Err(WeatherError::Data(DataError::BadData(reason))) if reason == "down"
+        ));
+    }
+
+    /// Rate limited on every request, counting them.
+    struct Limited(AtomicUsize);
+
+    #[async_trait]
+    impl WeatherProvider for Limited {
+        fn get_attribution(&self) -> &'static str {
+            "limited"
+        }
+
+        async fn get_current_weather(
+            &self,
+            _location: &WeatherLocation,
+            _units: &WeatherUnits,
+        ) -> Result<WeatherProviderResponse, WeatherError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Err(WeatherError::Network(NetworkError::RateLimited {
+                url: "https://limited.example".to_string(),
+                retry_after: Some(Duration::from_secs(60)),
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_primary_is_skipped_until_retry_after() {
+        let location = WeatherLocation {
+            latitude: 51.5,
+            longitude: -0.1,
+            elevation: None,
+        };
+        let units = WeatherUnits::metric();
+        let primary = Arc::new(Limited(AtomicUsize::new(0)));
+
+        let chain = FallbackProvider::new(primary.clone()).then(Arc::new(Fixed(Some("second"))));
+        for _ in 0..3 {
+            let response = chain.get_current_weather(&location, &units).await.unwrap();
+            assert_eq!(response.attribution, "second");
+        }
+        assert_eq!(primary.0.load(Ordering::SeqCst), 1);
+
+        // With nothing to fall back on, the wait is reported for the client
+        // to back off
+        let chain = FallbackProvider::new(primary.clone()).then(Arc::new(Fixed(None)));
+        let _ = chain.get_current_weather(&location, &units).await;
+        let err = chain.get_current_weather(&location, &units).await;
+        assert!(matches!(
+            err,
+            Err(WeatherError::Network(NetworkError::RateLimited { retry_after: Some(d), .. }))
+                if d <= Duration::from_secs(60)
+        ));
+        assert_eq!(primary.0.load(Ordering::SeqCst), 2);
+    }
+}
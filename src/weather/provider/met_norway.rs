@@ -0,0 +1,292 @@
+use crate::config::Provider;
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::http::{self, RequestOptions};
+use crate::weather::astro;
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{CelestialEvents, WeatherLocation, WeatherUnits};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+const MET_NORWAY_BASE_URL: &str = "https://api.met.no/weatherapi/locationforecast/2.0/compact";
+
+pub struct MetNorwayProvider {
+    client: reqwest::Client,
+    timeout_secs: u64,
+    base_url: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MetNorwayProviderConfig {
+    /// Timeout and User-Agent; met.no asks for contact details in the latter.
+    #[serde(flatten)]
+    pub request: RequestOptions,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetNorwayResponse {
+    properties: MetNorwayProperties,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetNorwayProperties {
+    timeseries: Vec<MetNorwayTimestep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetNorwayTimestep {
+    time: DateTime<Utc>,
+    data: MetNorwayData,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetNorwayData {
+    instant: MetNorwayInstant,
+    next_1_hours: Option<MetNorwayPeriod>,
+    next_6_hours: Option<MetNorwayPeriod>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetNorwayInstant {
+    details: MetNorwayInstantDetails,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetNorwayInstantDetails {
+    air_temperature: f64,
+    #[serde(default)]
+    wind_speed: f64,
+    #[serde(default)]
+    wind_from_direction: f64,
+    air_pressure_at_sea_level: Option<f64>,
+    relative_humidity: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetNorwayPeriod {
+    summary: MetNorwaySummary,
+    #[serde(default)]
+    details: MetNorwayPeriodDetails,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetNorwaySummary {
+    symbol_code: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MetNorwayPeriodDetails {
+    #[serde(default)]
+    precipitation_amount: f64,
+}
+
+impl MetNorwayProvider {
+    pub fn new(config: MetNorwayProviderConfig) -> Result<Self, WeatherError> {
+        // api.met.no rejects requests without an identifying User-Agent,
+        // which the shared client always sends
+        // See: https://api.met.no/doc/TermsOfService
+        let client = config.request.client().map_err(WeatherError::Network)?;
+
+        Ok(Self {
+            client,
+            timeout_secs: config.request.timeout_secs(),
+            base_url: MET_NORWAY_BASE_URL.to_string(),
+        })
+    }
+
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        // More than four decimals is refused as it defeats their caching
+        let mut url = format!(
+            "{}?lat={:.4}&lon={:.4}",
+            self.base_url, location.latitude, location.longitude
+        );
+        if let Some(elevation) = location.elevation {
+            url.push_str(&format!("&altitude={}", elevation.round() as i64));
+        }
+        url
+    }
+
+    /// Maps a MET Norway symbol code (e.g. "lightrainshowers_day") onto the
+    /// WMO weather code the normalizer understands.
+    /// See: https://api.met.no/weatherapi/weathericon/2.0/documentation
+    fn symbol_to_wmo(symbol: &str) -> i32 {
+        let name = symbol.split('_').next().unwrap_or(symbol);
+        if name.contains("thunder") {
+            return 95;
+        }
+
+        match name {
+            "clearsky" => 0,
+            "fair" => 1,
+            "partlycloudy" => 2,
+            "cloudy" => 3,
+            "fog" => 45,
+            "lightrain" => 61,
+            "rain" => 63,
+            "heavyrain" => 65,
+            "lightsleet" | "lightsleetshowers" | "sleet" | "sleetshowers" => 66,
+            "heavysleet" | "heavysleetshowers" => 67,
+            "lightsnow" => 71,
+            "snow" => 73,
+            "heavysnow" => 75,
+            "lightrainshowers" => 80,
+            "rainshowers" => 81,
+            "heavyrainshowers" => 82,
+            "lightsnowshowers" | "snowshowers" => 85,
+            "heavysnowshowers" => 86,
+            _ => 0,
+        }
+    }
+
+    fn to_response(
+        &self,
+        data: MetNorwayResponse,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let current = data
+            .properties
+            .timeseries
+            .into_iter()
+            .next()
+            .ok_or(WeatherError::Data(DataError::NoData))?;
+
+        let period = current
+            .data
+            .next_1_hours
+            .as_ref()
+            .or(current.data.next_6_hours.as_ref());
+        let symbol = period.map(|p| p.summary.symbol_code.as_str()).unwrap_or("");
+        let details = &current.data.instant.details;
+
+        Ok(WeatherProviderResponse {
+            weather_code: Self::symbol_to_wmo(symbol),
+            temperature: details.air_temperature,
+            // Only the hourly amount is a rate; the six-hour total is not
+            precipitation: current
+                .data
+                .next_1_hours
+                .as_ref()
+                .map(|p| p.details.precipitation_amount)
+                .unwrap_or(0.0),
+            wind_speed: details.wind_speed,
+            wind_direction: details.wind_from_direction,
+            sun: CelestialEvents::from_bool(!symbol.ends_with("_night")),
+            moon_phase: Some(astro::moon_phase(current.time)),
+            pressure: details.air_pressure_at_sea_level,
+            disagreement: None,
+            humidity: details.relative_humidity,
+            apparent_temperature: None,
+            uv_index: None,
+            source: Provider::MetNorway.abbreviation().to_string(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: None,
+            details: Default::default(),
+            timestamp: current.time.format("%Y-%m-%dT%H:%M").to_string(),
+            attribution: self.get_attribution().to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for MetNorwayProvider {
+    fn get_attribution(&self) -> &'static str {
+        // Required by the MET Norway terms (CC BY 4.0)
+        "Weather data from MET Norway"
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = self.build_url(location);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| NetworkError::from_reqwest(e, &url, self.timeout_secs))
+            .and_then(|resp| http::error_for_status(resp, &url, self.timeout_secs))
+            .map_err(WeatherError::Network)?;
+
+        let data: MetNorwayResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, self.timeout_secs))
+        })?;
+
+        self.to_response(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+    use crate::weather::normalizer::WeatherNormalizer;
+
+    #[test]
+    fn test_symbol_mapping() {
+        let cases = [
+            ("clearsky_night", WeatherCondition::Clear),
+            ("fair_day", WeatherCondition::MainlyClear),
+            ("cloudy", WeatherCondition::Overcast),
+            ("fog", WeatherCondition::Fog),
+            ("heavyrain", WeatherCondition::HeavyRain),
+            ("lightrainshowers_day", WeatherCondition::RainShowers),
+            ("sleet", WeatherCondition::FreezingRain),
+            ("snow", WeatherCondition::Snow),
+            (
+                "heavysnowshowers_polartwilight",
+                WeatherCondition::HeavySnowShowers,
+            ),
+            ("rainandthunder", WeatherCondition::Thunderstorm),
+        ];
+        for (symbol, expected) in cases {
+            let code = MetNorwayProvider::symbol_to_wmo(symbol);
+            assert_eq!(
+                WeatherNormalizer::wmo_code_to_condition(code),
+                expected,
+                "symbol {}",
+                symbol
+            );
+        }
+    }
+
+    #[test]
+    fn test_response_conversion() {
+        let json = r#"{
+            "type": "Feature",
+            "properties": {
+                "timeseries": [{
+                    "time": "2024-01-15T18:00:00Z",
+                    "data": {
+                        "instant": {"details": {
+                            "air_temperature": -3.2,
+                            "wind_speed": 5.4,
+                            "wind_from_direction": 200.1
+                        }},
+                        "next_1_hours": {
+                            "summary": {"symbol_code": "lightsnow_night"},
+                            "details": {"precipitation_amount": 0.4}
+                        },
+                        "next_6_hours": {
+                            "summary": {"symbol_code": "snow_night"},
+                            "details": {"precipitation_amount": 3.1}
+                        }
+                    }
+                }]
+            }
+        }"#;
+        let data: MetNorwayResponse = serde_json::from_str(json).unwrap();
+        let provider = MetNorwayProvider::new(MetNorwayProviderConfig::default()).unwrap();
+        let response = provider.to_response(data).unwrap();
+
+        assert_eq!(response.weather_code, 71);
+        assert_eq!(response.temperature, -3.2);
+        assert_eq!(response.precipitation, 0.4);
+        assert_eq!(response.wind_direction, 200.1);
+        assert!(!response.sun.is_day);
+        assert_eq!(response.timestamp, "2024-01-15T18:00");
+        assert_eq!(response.attribution, "Weather data from MET Norway");
+    }
+}
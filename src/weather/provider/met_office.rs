@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt;
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -7,17 +8,17 @@ use serde::Deserialize;
 use tokio::sync::Mutex;
 
 use crate::{
+    config::Provider,
     error::{ConfigError, NetworkError, WeatherError},
+    http::{self, RequestOptions},
+    secrets,
     weather::{
         WeatherLocation, WeatherUnits,
         provider::{
             WeatherProvider, WeatherProviderResponse,
-            supplementary::{
-                SupplementaryProviderRequest, SupplementaryProviderResponse,
-                SupplementaryWeatherProvider, aad::AADProvider,
-            },
+            supplementary::{SupplementaryField, SupplementaryRegistry},
         },
-        types::CelestialEvents,
+        types::{CelestialEvents, WeatherDetails},
         units::{normalize_precipitation, normalize_temperature, normalize_wind_speed},
     },
 };
@@ -28,17 +29,27 @@ pub struct MetOfficeProvider {
     client: reqwest::Client,
     config: MetOfficeProviderConfig,
     last_weather_results: Mutex<Option<MetOfficeResponse>>,
+    supplementary: SupplementaryRegistry,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct MetOfficeProviderConfig {
     #[serde(default)]
     pub include_location_name: bool,
 
+    #[serde(default)]
     pub api_key: String,
 
+    /// Prints the key, e.g. `pass show metoffice`; used when `api_key`
+    /// isn't set.
+    #[serde(default)]
+    pub api_key_cmd: Option<String>,
+
     #[serde(default)]
     pub data_source: String,
+
+    #[serde(flatten)]
+    pub request: RequestOptions,
 }
 
 impl Default for MetOfficeProviderConfig {
@@ -47,10 +58,24 @@ impl Default for MetOfficeProviderConfig {
             include_location_name: true,
             data_source: "BD1".to_owned(),
             api_key: String::new(),
+            api_key_cmd: None,
+            request: RequestOptions::default(),
         }
     }
 }
 
+impl fmt::Debug for MetOfficeProviderConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetOfficeProviderConfig")
+            .field("include_location_name", &self.include_location_name)
+            .field("api_key", &secrets::Redacted(&self.api_key))
+            .field("api_key_cmd", &self.api_key_cmd)
+            .field("data_source", &self.data_source)
+            .field("request", &self.request)
+            .finish()
+    }
+}
+
 impl MetOfficeProvider {
     pub fn new(mut config: MetOfficeProviderConfig) -> Result<Self, WeatherError> {
         if config.data_source.is_empty() {
@@ -61,13 +86,26 @@ impl MetOfficeProvider {
             config.api_key = api_key;
         }
 
+        if config.api_key.is_empty()
+            && let Some(command) = &config.api_key_cmd
+        {
+            config.api_key = secrets::api_key_from_command(Provider::MetOffice, command)
+                .map_err(WeatherError::Config)?;
+        }
+
+        if config.api_key.is_empty()
+            && let Some(api_key) = secrets::api_key(Provider::MetOffice)
+        {
+            config.api_key = api_key;
+        }
+
         if config.api_key.is_empty() {
             return Err(WeatherError::Config(ConfigError::InvalidAPIKey(
                 "API key is empty for Met Office Provider".to_string(),
             )));
         }
 
-        let client = reqwest::ClientBuilder::new();
+        let client = config.request.builder();
 
         let mut headers = header::HeaderMap::new();
 
@@ -83,12 +121,13 @@ impl MetOfficeProvider {
         let client = client.default_headers(headers);
         let client = client
             .build()
-            .map_err(|e| WeatherError::Network(NetworkError::Other(e)))?;
+            .map_err(|e| WeatherError::Network(NetworkError::ClientCreation(e)))?;
 
         Ok(Self {
             client,
             config,
             last_weather_results: Mutex::new(None),
+            supplementary: SupplementaryRegistry::new(),
         })
     }
 
@@ -107,19 +146,20 @@ impl MetOfficeProvider {
         location: &WeatherLocation,
     ) -> Result<MetOfficeResponse, WeatherError> {
         let url = self.build_url(location);
+        let timeout_secs = self.config.request.timeout_secs();
         let response = self
             .client
             .get(&url)
             .send()
             .await
-            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
+            .map_err(|e| NetworkError::from_reqwest(e, &url, timeout_secs))
+            .and_then(|resp| http::error_for_status(resp, &url, timeout_secs))
+            .map_err(WeatherError::Network)?;
 
         response
-            .error_for_status()
-            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?
             .json()
             .await
-            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))
+            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, timeout_secs)))
     }
 
     fn get_current_time_series(data: &MetOfficeResponse) -> Option<MetOfficeTimeSeries> {
@@ -201,27 +241,63 @@ impl WeatherProvider for MetOfficeProvider {
                 "windSpeed10m",
             )?,
             wind_direction: current_weather.wind_direction_from_10m as f64,
-            sun: CelestialEvents::from_bool(true), // Defaults - Theses will be gathered by the supplementary provider
-            moon_phase: Some(0.5),
+            // Filled in by the supplementary providers below
+            sun: CelestialEvents::from_bool(true),
+            moon_phase: None,
+            pressure: Some(current_weather.mslp as f64 / 100.0),
+            disagreement: None,
+            humidity: Some(current_weather.screen_relative_humidity),
+            apparent_temperature: current_weather
+                .normalize_temperature(
+                    units,
+                    &data.parameters,
+                    current_weather.feels_like_temperature,
+                    "feelsLikeTemperature",
+                )
+                .ok(),
+            uv_index: Some(current_weather.uv_index as f64),
+            source: Provider::MetOffice.abbreviation().to_string(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: None,
+            details: WeatherDetails {
+                dew_point: current_weather
+                    .normalize_temperature(
+                        units,
+                        &data.parameters,
+                        current_weather.screen_dew_point_temp,
+                        "screenDewPointTemperature",
+                    )
+                    .ok(),
+                wind_gusts: current_weather
+                    .normalize_wind_speeds(
+                        units,
+                        &data.parameters,
+                        current_weather.wind_gust_speed_10m,
+                        "windGustSpeed10m",
+                    )
+                    .ok(),
+                visibility: Some(current_weather.visibility as f64),
+                cloud_layers: Vec::new(),
+                ..WeatherDetails::default()
+            },
             timestamp: current_weather.time,
             attribution: self.get_attribution().to_string(),
         };
 
-        // A provider should ask something else if it doesn't have the data, the provider shouldn't have to care about
-        // what supplementary data provider to use, rather if it can get the data or not, for now I will care about it
-        let sup_provider = AADProvider::new();
-        let celestial_data = sup_provider
-            .get_supplementary_weather(
+        // The site-specific forecast has no sun or moon data
+        let attributions = self
+            .supplementary
+            .fill(
                 location,
                 units,
-                SupplementaryProviderRequest::SunAndMoonForOneDay,
+                &mut current_weather,
+                &[SupplementaryField::Sun, SupplementaryField::MoonPhase],
             )
-            .await?;
-        if let SupplementaryProviderResponse::SunAndMoonForOneDay { sun, moon_phase } =
-            celestial_data
-        {
-            current_weather.sun = sun;
-            current_weather.moon_phase = moon_phase;
+            .await;
+        for attribution in attributions {
+            current_weather.attribution.push('\n');
+            current_weather.attribution.push_str(attribution);
         }
 
         Ok(current_weather)
@@ -277,11 +353,9 @@ pub struct MetOfficeProperties {
 pub struct MetOfficeTimeSeries {
     // Weather event Per Hour
     #[serde(rename = "feelsLikeTemperature")]
-    #[allow(dead_code)]
     pub feels_like_temperature: f64,
 
-    /// Mean Sea Level Pressure
-    #[allow(dead_code)]
+    /// Mean Sea Level Pressure in Pa
     pub mslp: usize,
     #[serde(rename = "precipitationRate")]
     pub precipitation_rate: f64,
@@ -290,10 +364,9 @@ pub struct MetOfficeTimeSeries {
     pub _probability_of_precipitation: f64,
 
     #[serde(rename = "screenDewPointTemperature")]
-    pub _screen_dew_point_temp: f64,
+    pub screen_dew_point_temp: f64,
 
     #[serde(rename = "screenRelativeHumidity")]
-    #[allow(dead_code)]
     pub screen_relative_humidity: f64,
 
     #[serde(rename = "screenTemperature")]
@@ -305,17 +378,16 @@ pub struct MetOfficeTimeSeries {
     pub time: String,
 
     #[serde(rename = "uvIndex")]
-    #[allow(dead_code)]
     pub uv_index: usize,
 
+    /// In metres.
     #[serde(rename = "visibility")]
-    #[allow(dead_code)]
     pub visibility: usize,
 
     #[serde(rename = "windDirectionFrom10m")]
     pub wind_direction_from_10m: usize,
     #[serde(rename = "windGustSpeed10m")]
-    pub _wind_gust_speed_10m: f64,
+    pub wind_gust_speed_10m: f64,
 
     #[serde(rename = "windSpeed10m")]
     pub wind_speed_10m: f64,
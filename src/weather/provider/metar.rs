@@ -0,0 +1,482 @@
+use crate::config::Provider;
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::http::{self, RequestOptions};
+use crate::scheduler::Scheduler;
+use crate::weather::astro;
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::timezone::TimeZoneLookup;
+use crate::weather::types::{
+    CloudLayer, CloudLevel, WeatherDetails, WeatherLocation, WeatherUnits,
+};
+use crate::weather::units::{inch_to_mm, kn_to_ms};
+use async_trait::async_trait;
+use chrono::DateTime;
+use serde::Deserialize;
+
+const AVIATION_WEATHER_BASE_URL: &str = "https://aviationweather.gov/api/data/metar";
+
+/// Half-widths (degrees) of the boxes searched for reporting stations, tried
+/// in order until one contains a station.
+const SEARCH_RADII_DEG: [f64; 3] = [0.5, 1.5, 4.0];
+
+const METRES_PER_STATUTE_MILE: f64 = 1609.344;
+const METRES_PER_FOOT: f64 = 0.3048;
+
+pub struct MetarProvider {
+    client: reqwest::Client,
+    timeout_secs: u64,
+    base_url: String,
+    station: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MetarProviderConfig {
+    #[serde(flatten)]
+    pub request: RequestOptions,
+    /// ICAO identifier (e.g. "EGLL") to use instead of the nearest station.
+    #[serde(default)]
+    pub station: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetarReport {
+    icao_id: String,
+    obs_time: i64,
+    lat: f64,
+    lon: f64,
+    temp: Option<f64>,
+    /// Degrees, or "VRB" for variable wind.
+    #[serde(default)]
+    wdir: serde_json::Value,
+    /// Knots.
+    wspd: Option<f64>,
+    /// Gusts in knots.
+    wgst: Option<f64>,
+    dewp: Option<f64>,
+    /// Statute miles, or a string such as "10+".
+    #[serde(default)]
+    visib: serde_json::Value,
+    /// Present weather groups, e.g. "-SHRA BR".
+    wx_string: Option<String>,
+    /// Hourly precipitation in inches, reported by some (mostly US) stations.
+    precip: Option<f64>,
+    /// Altimeter setting in hPa, close to sea level pressure.
+    altim: Option<f64>,
+    #[serde(default)]
+    clouds: Vec<MetarCloud>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetarCloud {
+    cover: String,
+    /// Feet above ground.
+    base: Option<f64>,
+}
+
+impl MetarProvider {
+    pub fn new(config: MetarProviderConfig) -> Result<Self, WeatherError> {
+        let client = config.request.client().map_err(WeatherError::Network)?;
+
+        Ok(Self {
+            client,
+            timeout_secs: config.request.timeout_secs(),
+            base_url: AVIATION_WEATHER_BASE_URL.to_string(),
+            station: config
+                .station
+                .map(|s| s.trim().to_uppercase())
+                .filter(|s| !s.is_empty()),
+        })
+    }
+
+    fn station_url(&self, station: &str) -> String {
+        format!("{}?ids={}&format=json", self.base_url, station)
+    }
+
+    /// One box around `location`, or two when it crosses the antimeridian,
+    /// as the API doesn't wrap longitudes past ±180°.
+    fn search_urls(&self, location: &WeatherLocation, radius: f64) -> Vec<String> {
+        let south = (location.latitude - radius).max(-90.0);
+        let north = (location.latitude + radius).min(90.0);
+        let west = location.longitude - radius;
+        let east = location.longitude + radius;
+        let spans = if west < -180.0 {
+            vec![(west + 360.0, 180.0), (-180.0, east)]
+        } else if east > 180.0 {
+            vec![(west, 180.0), (-180.0, east - 360.0)]
+        } else {
+            vec![(west, east)]
+        };
+
+        spans
+            .into_iter()
+            .map(|(west, east)| {
+                format!(
+                    "{}?bbox={:.2},{:.2},{:.2},{:.2}&format=json",
+                    self.base_url, south, west, north, east
+                )
+            })
+            .collect()
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Vec<MetarReport>, WeatherError> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| NetworkError::from_reqwest(e, url, self.timeout_secs))
+            .and_then(|resp| http::error_for_status(resp, url, self.timeout_secs))
+            .map_err(WeatherError::Network)?;
+
+        // No matching stations comes back as an empty body rather than `[]`
+        let body = response.text().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, url, self.timeout_secs))
+        })?;
+        if body.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        serde_json::from_str(&body).map_err(|e| WeatherError::Data(DataError::SerdeParseError(e)))
+    }
+
+    /// The report from the station closest to `location`.
+    fn nearest(reports: Vec<MetarReport>, location: &WeatherLocation) -> Option<MetarReport> {
+        let cos_lat = location.latitude.to_radians().cos();
+        let distance = |report: &MetarReport| {
+            let dlat = report.lat - location.latitude;
+            let dlon =
+                ((report.lon - location.longitude + 540.0).rem_euclid(360.0) - 180.0) * cos_lat;
+            dlat * dlat + dlon * dlon
+        };
+        reports
+            .into_iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+    }
+
+    /// Maps METAR present weather groups (e.g. "+TSRA", "-FZDZ", "SHSN BR")
+    /// onto a WMO weather code. Groups in the vicinity (VC) are ignored.
+    fn present_weather_to_wmo(wx: &str) -> Option<i32> {
+        let groups: Vec<&str> = wx
+            .split_whitespace()
+            .filter(|group| !group.starts_with("VC") && !group.starts_with("RE"))
+            .collect();
+
+        let pick = |group: &str, light: i32, moderate: i32, heavy: i32| {
+            if group.starts_with('-') {
+                light
+            } else if group.starts_with('+') {
+                heavy
+            } else {
+                moderate
+            }
+        };
+
+        for group in &groups {
+            if group.contains("TS") {
+                let hail = group.contains("GR") || group.contains("GS");
+                return Some(match (hail, group.starts_with('+')) {
+                    (true, true) => 99,
+                    (true, false) => 96,
+                    (false, _) => 95,
+                });
+            }
+        }
+
+        for group in &groups {
+            let code = if group.contains("FZ") && group.contains("DZ") {
+                pick(group, 56, 56, 57)
+            } else if group.contains("FZ") && group.contains("RA") {
+                pick(group, 66, 66, 67)
+            } else if group.contains("SH") && group.contains("SN") {
+                pick(group, 85, 85, 86)
+            } else if group.contains("SH") && (group.contains("RA") || group.contains("GS")) {
+                pick(group, 80, 81, 82)
+            } else if group.contains("SN") {
+                pick(group, 71, 73, 75)
+            } else if group.contains("RA") {
+                pick(group, 61, 63, 65)
+            } else if group.contains("DZ") {
+                pick(group, 51, 53, 55)
+            } else if group.contains("SG") || group.contains("PL") || group.contains("IC") {
+                77
+            } else {
+                continue;
+            };
+            return Some(code);
+        }
+
+        groups
+            .iter()
+            .find(|group| group.ends_with("FG"))
+            .map(|group| if group.contains("FZ") { 48 } else { 45 })
+    }
+
+    /// WMO code for the sky cover when there is no present weather.
+    fn cloud_cover_to_wmo(clouds: &[MetarCloud]) -> i32 {
+        clouds
+            .iter()
+            .map(|cloud| match cloud.cover.as_str() {
+                "FEW" => 1,
+                "SCT" => 2,
+                "BKN" | "OVC" | "OVX" => 3,
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Share of the sky each cover code stands for, from its okta range.
+    fn cover_percent(cover: &str) -> Option<f64> {
+        match cover {
+            "FEW" => Some(19.0),
+            "SCT" => Some(44.0),
+            "BKN" => Some(75.0),
+            "OVC" | "OVX" => Some(100.0),
+            _ => None,
+        }
+    }
+
+    fn details(report: &MetarReport) -> WeatherDetails {
+        let mut cloud_layers: Vec<CloudLayer> = Vec::new();
+        for cloud in &report.clouds {
+            let (Some(cover), Some(base)) = (Self::cover_percent(&cloud.cover), cloud.base) else {
+                continue;
+            };
+            let level = CloudLevel::from_base(base * METRES_PER_FOOT);
+            match cloud_layers.iter_mut().find(|layer| layer.level == level) {
+                Some(layer) => layer.cover = layer.cover.max(cover),
+                None => cloud_layers.push(CloudLayer { level, cover }),
+            }
+        }
+        cloud_layers.sort_by_key(|layer| layer.level);
+
+        let visibility = match &report.visib {
+            serde_json::Value::String(text) => text.trim_end_matches('+').parse().ok(),
+            value => value.as_f64(),
+        };
+        WeatherDetails {
+            dew_point: report.dewp,
+            wind_gusts: report.wgst.map(kn_to_ms),
+            visibility: visibility.map(|miles| miles * METRES_PER_STATUTE_MILE),
+            cloud_layers,
+            ..WeatherDetails::default()
+        }
+    }
+
+    /// The report, with day and night on `clock`, the one at the location.
+    fn to_response(
+        &self,
+        report: MetarReport,
+        clock: Scheduler,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let temperature = report.temp.ok_or_else(|| {
+            WeatherError::Data(DataError::BadData(format!(
+                "METAR from {} has no temperature",
+                report.icao_id
+            )))
+        })?;
+        let observed = DateTime::from_timestamp(report.obs_time, 0).ok_or_else(|| {
+            WeatherError::Data(DataError::BadData(format!(
+                "invalid observation time {}",
+                report.obs_time
+            )))
+        })?;
+
+        let weather_code = report
+            .wx_string
+            .as_deref()
+            .and_then(Self::present_weather_to_wmo)
+            .unwrap_or_else(|| Self::cloud_cover_to_wmo(&report.clouds));
+
+        Ok(WeatherProviderResponse {
+            weather_code,
+            temperature,
+            precipitation: report.precip.map(inch_to_mm).unwrap_or(0.0),
+            wind_speed: kn_to_ms(report.wspd.unwrap_or(0.0)),
+            // Variable wind has no direction
+            wind_direction: report.wdir.as_f64().unwrap_or(0.0),
+            sun: astro::celestial_events(report.lat, report.lon, clock.local(observed)),
+            moon_phase: Some(astro::moon_phase(observed)),
+            pressure: report.altim,
+            disagreement: None,
+            humidity: None,
+            apparent_temperature: None,
+            uv_index: None,
+            source: Provider::Metar.abbreviation().to_string(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: None,
+            details: Self::details(&report),
+            timestamp: observed.format("%Y-%m-%dT%H:%M").to_string(),
+            attribution: format!("{} ({})", self.get_attribution(), report.icao_id),
+        })
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for MetarProvider {
+    fn get_attribution(&self) -> &'static str {
+        "METAR from aviationweather.gov"
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let clock = TimeZoneLookup::shared().clock_at(location).await;
+        if let Some(station) = &self.station {
+            let report = self
+                .fetch(&self.station_url(station))
+                .await?
+                .into_iter()
+                .next()
+                .ok_or(WeatherError::Data(DataError::NoData))?;
+            return self.to_response(report, clock);
+        }
+
+        for radius in SEARCH_RADII_DEG {
+            let mut reports = Vec::new();
+            for url in self.search_urls(location, radius) {
+                reports.extend(self.fetch(&url).await?);
+            }
+            if let Some(report) = Self::nearest(reports, location) {
+                return self.to_response(report, clock);
+            }
+        }
+
+        Err(WeatherError::Data(DataError::NoData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_weather_mapping() {
+        let cases = [
+            ("-RA", Some(61)),
+            ("+RA BR", Some(65)),
+            ("-SHRA", Some(80)),
+            ("+SHSN", Some(86)),
+            ("FZDZ", Some(56)),
+            ("TSRA", Some(95)),
+            ("+TSRAGR", Some(99)),
+            ("BR", None),
+            ("FZFG", Some(48)),
+            ("VCSH", None),
+        ];
+        for (wx, expected) in cases {
+            assert_eq!(
+                MetarProvider::present_weather_to_wmo(wx),
+                expected,
+                "{}",
+                wx
+            );
+        }
+    }
+
+    #[test]
+    fn test_nearest_station_report() {
+        let json = r#"[
+            {"icaoId": "EGLL", "obsTime": 1718960400, "lat": 51.477, "lon": -0.461,
+             "temp": 18, "wdir": 250, "wspd": 10, "wxString": null,
+             "clouds": [{"cover": "FEW", "base": 2500}, {"cover": "BKN", "base": 4000}]},
+            {"icaoId": "EGLC", "obsTime": 1718960400, "lat": 51.505, "lon": 0.055,
+             "temp": 19, "wdir": "VRB", "wspd": 3, "wgst": 20, "wxString": "-SHRA",
+             "precip": 0.1, "dewp": 12, "visib": "6+",
+             "clouds": [{"cover": "SCT", "base": 2000}, {"cover": "BKN", "base": 4500},
+                        {"cover": "OVC", "base": 9000}]}
+        ]"#;
+        let reports: Vec<MetarReport> = serde_json::from_str(json).unwrap();
+        let location = WeatherLocation {
+            latitude: 51.5074,
+            longitude: -0.1278,
+            elevation: None,
+        };
+
+        let provider = MetarProvider::new(MetarProviderConfig::default()).unwrap();
+        let report = MetarProvider::nearest(reports, &location).unwrap();
+        let response = provider
+            .to_response(report, Scheduler::for_zone("Europe/London"))
+            .unwrap();
+
+        assert_eq!(response.weather_code, 80);
+        assert_eq!(response.temperature, 19.0);
+        assert_eq!(response.wind_direction, 0.0);
+        assert!((response.wind_speed - 1.54).abs() < 0.01);
+        assert!((response.precipitation - 2.54).abs() < 0.01);
+        assert_eq!(response.timestamp, "2024-06-21T09:00");
+        assert!(response.sun.is_day);
+        assert!(response.attribution.ends_with("(EGLC)"));
+
+        let details = response.details;
+        assert_eq!(details.dew_point, Some(12.0));
+        assert!((details.wind_gusts.unwrap() - 10.29).abs() < 0.01);
+        assert!((details.visibility.unwrap() - 9656.1).abs() < 0.1);
+        assert_eq!(
+            details.cloud_layers,
+            vec![
+                CloudLayer {
+                    level: CloudLevel::Low,
+                    cover: 75.0
+                },
+                CloudLayer {
+                    level: CloudLevel::Mid,
+                    cover: 100.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_across_antimeridian() {
+        let provider = MetarProvider::new(MetarProviderConfig::default()).unwrap();
+        let bbox = |bbox| format!("{}?bbox={}&format=json", AVIATION_WEATHER_BASE_URL, bbox);
+        let fiji = WeatherLocation {
+            latitude: -16.8,
+            longitude: 179.5,
+            elevation: None,
+        };
+
+        assert_eq!(
+            provider.search_urls(&fiji, 1.5),
+            vec![
+                bbox("-18.30,178.00,-15.30,180.00"),
+                bbox("-18.30,-180.00,-15.30,-179.00"),
+            ]
+        );
+        let samoa = WeatherLocation {
+            latitude: -13.8,
+            longitude: -171.8,
+            elevation: None,
+        };
+        assert_eq!(
+            provider.search_urls(&samoa, 4.0),
+            vec![bbox("-17.80,-175.80,-9.80,-167.80")]
+        );
+        let wallis = WeatherLocation {
+            latitude: -13.3,
+            longitude: -179.5,
+            elevation: None,
+        };
+        assert_eq!(
+            provider.search_urls(&wallis, 1.5),
+            vec![
+                bbox("-14.80,179.00,-11.80,180.00"),
+                bbox("-14.80,-180.00,-11.80,-178.00"),
+            ]
+        );
+
+        let reports: Vec<MetarReport> = serde_json::from_str(
+            r#"[
+            {"icaoId": "NFFN", "obsTime": 1718960400, "lat": -17.755, "lon": 177.443},
+            {"icaoId": "NFTV", "obsTime": 1718960400, "lat": -16.8, "lon": -179.9}
+        ]"#,
+        )
+        .unwrap();
+        let nearest = MetarProvider::nearest(reports, &fiji).unwrap();
+        assert_eq!(nearest.lon, -179.9);
+    }
+}
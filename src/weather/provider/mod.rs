@@ -1,11 +1,43 @@
-use crate::error::WeatherError;
-use crate::weather::types::{CelestialEvents, WeatherLocation, WeatherUnits};
+use crate::config::{Provider, ProviderConfig};
+use crate::error::{ConfigError, WeatherError};
+use crate::http::RequestOptions;
+use crate::weather::OpenMeteoProvider;
+use crate::weather::types::{
+    CelestialEvents, Disagreement, ForecastHour, NowcastStep, TemperatureRange, WeatherDetails,
+    WeatherLocation, WeatherUnits,
+};
 use async_trait::async_trait;
+use budget::BudgetedProvider;
+use consensus::ConsensusProvider;
+use dwd::{DwdProvider, DwdProviderConfig};
+use exec::{ExecProvider, ExecProviderConfig};
+use fallback::FallbackProvider;
+use met_norway::{MetNorwayProvider, MetNorwayProviderConfig};
+use met_office::{MetOfficeProvider, MetOfficeProviderConfig};
+use metar::{MetarProvider, MetarProviderConfig};
+use open_weather_map::{OpenWeatherMapProvider, OpenWeatherMapProviderConfig};
 use serde::{Deserialize, Serialize};
+use station::{StationProvider, StationProviderConfig};
+use std::sync::Arc;
+use std::time::Duration;
+use toml::Table;
+use wttr_in::WttrInProvider;
 
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub mod budget;
+pub mod consensus;
+pub mod dwd;
+pub mod exec;
+pub mod fallback;
+pub mod met_norway;
 pub mod met_office;
+pub mod metar;
 pub mod open_meteo;
+pub mod open_weather_map;
+pub mod station;
 pub mod supplementary;
+pub mod wttr_in;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeatherProviderResponse {
@@ -16,10 +48,67 @@ pub struct WeatherProviderResponse {
     pub wind_direction: f64,
     pub sun: CelestialEvents,
     pub moon_phase: Option<f64>,
+    /// Mean sea level pressure in hPa.
+    #[serde(default)]
+    pub pressure: Option<f64>,
+    /// Set by the consensus provider when its sources differ noticeably.
+    #[serde(default)]
+    pub disagreement: Option<Disagreement>,
+    /// Relative humidity in percent.
+    #[serde(default)]
+    pub humidity: Option<f64>,
+    /// Feels-like temperature in °C, when the provider reports one.
+    #[serde(default)]
+    pub apparent_temperature: Option<f64>,
+    #[serde(default)]
+    pub uv_index: Option<f64>,
+    /// Short name of the provider(s) the data came from, e.g. "OM" or
+    /// "OM+MetNo"; empty for simulated weather.
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub nowcast: Vec<NowcastStep>,
+    /// Up to the next 24 hours; providers without a forecast leave it empty.
+    #[serde(default)]
+    pub hourly: Vec<ForecastHour>,
+    /// Today's forecast low and high in °C.
+    #[serde(default)]
+    pub today: Option<TemperatureRange>,
+    #[serde(default)]
+    pub details: WeatherDetails,
     pub timestamp: String,
     pub attribution: String,
 }
 
+#[cfg(test)]
+impl WeatherProviderResponse {
+    /// A calm day at 10°C with WMO code `weather_code`, for tests to change
+    /// what they need.
+    pub fn sample(weather_code: i32) -> Self {
+        Self {
+            weather_code,
+            temperature: 10.0,
+            precipitation: 0.0,
+            wind_speed: 0.0,
+            wind_direction: 0.0,
+            sun: CelestialEvents::from_bool(true),
+            moon_phase: None,
+            pressure: None,
+            disagreement: None,
+            humidity: None,
+            apparent_temperature: None,
+            uv_index: None,
+            source: String::new(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: None,
+            details: Default::default(),
+            timestamp: String::new(),
+            attribution: String::new(),
+        }
+    }
+}
+
 #[async_trait]
 pub trait WeatherProvider: Send + Sync {
     async fn get_current_weather(
@@ -29,4 +118,225 @@ pub trait WeatherProvider: Send + Sync {
     ) -> Result<WeatherProviderResponse, WeatherError>;
 
     fn get_attribution(&self) -> &'static str;
+
+    /// How long to wait before asking again to stay within the provider's
+    /// daily request budget.
+    fn refresh_spacing(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    /// A warning for the status line once most of the day's request budget
+    /// is spent.
+    fn budget_warning(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Builds the provider selected in the `[provider]` config section, passing it
+/// its own sub-table of options. Unless `fallback` is turned off, Open-Meteo
+/// and then wttr.in stand in when it fails.
+pub fn from_config(
+    config: &ProviderConfig,
+) -> Result<(Provider, Arc<dyn WeatherProvider>), WeatherError> {
+    if config.consensus.len() >= 2 {
+        return consensus_from_config(config);
+    }
+
+    let kind = config.selected();
+    let options = config.options.get(&kind);
+
+    let provider: Arc<dyn WeatherProvider> = match kind {
+        Provider::OpenMeteo => {
+            let options: RequestOptions = parse_options(kind, options)?;
+            Arc::new(OpenMeteoProvider::with_options(options))
+        }
+        Provider::MetOffice => {
+            let options: MetOfficeProviderConfig = parse_options(kind, options)?;
+            Arc::new(MetOfficeProvider::new(options)?)
+        }
+        Provider::MetNorway => {
+            let options: MetNorwayProviderConfig = parse_options(kind, options)?;
+            Arc::new(MetNorwayProvider::new(options)?)
+        }
+        Provider::Dwd => {
+            let options: DwdProviderConfig = parse_options(kind, options)?;
+            Arc::new(DwdProvider::new(options)?)
+        }
+        Provider::Metar => {
+            let options: MetarProviderConfig = parse_options(kind, options)?;
+            Arc::new(MetarProvider::new(options)?)
+        }
+        Provider::Exec => {
+            let options: ExecProviderConfig = parse_options(kind, options)?;
+            Arc::new(ExecProvider::new(options)?)
+        }
+        Provider::Station => {
+            let options: StationProviderConfig = parse_options(kind, options)?;
+            let remote = match options.remote.unwrap_or_default() {
+                Provider::Station => {
+                    return Err(WeatherError::Config(ConfigError::InvalidProviderOptions {
+                        provider: kind.name(),
+                        reason: "`remote` cannot be the station itself".to_string(),
+                    }));
+                }
+                remote => {
+                    let remote_config = ProviderConfig {
+                        name: Some(remote),
+                        fallback: false,
+                        consensus: Vec::new(),
+                        options: config.options.clone(),
+                    };
+                    from_config(&remote_config)?.1
+                }
+            };
+            Arc::new(StationProvider::new(options, Some(remote))?)
+        }
+        Provider::OpenWeatherMap => {
+            let options: OpenWeatherMapProviderConfig = parse_options(kind, options)?;
+            Arc::new(OpenWeatherMapProvider::new(options)?)
+        }
+    };
+
+    let provider: Arc<dyn WeatherProvider> = match daily_budget(kind, config) {
+        Some(per_day) => Arc::new(BudgetedProvider::new(kind, per_day, provider)),
+        None => provider,
+    };
+
+    if !config.fallback {
+        return Ok((kind, provider));
+    }
+    let mut chain = FallbackProvider::new(provider);
+    if kind != Provider::OpenMeteo {
+        chain = chain.then(Arc::new(OpenMeteoProvider::new()));
+    }
+    let chain = chain.then(Arc::new(WttrInProvider::new()));
+
+    Ok((kind, Arc::new(chain)))
+}
+
+/// Every provider listed in `consensus`, each built from its own options,
+/// blended by one `ConsensusProvider`.
+fn consensus_from_config(
+    config: &ProviderConfig,
+) -> Result<(Provider, Arc<dyn WeatherProvider>), WeatherError> {
+    let providers = config
+        .consensus
+        .iter()
+        .map(|&kind| {
+            let single = ProviderConfig {
+                name: Some(kind),
+                fallback: false,
+                consensus: Vec::new(),
+                options: config.options.clone(),
+            };
+            from_config(&single).map(|(_, provider)| provider)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let chain = FallbackProvider::new(Arc::new(ConsensusProvider::new(providers)));
+    let provider: Arc<dyn WeatherProvider> = if config.fallback {
+        Arc::new(chain.then(Arc::new(WttrInProvider::new())))
+    } else {
+        Arc::new(chain)
+    };
+    Ok((config.consensus[0], provider))
+}
+
+/// The shortest refresh interval that keeps every provider queried on each
+/// refresh within its daily budget: `requests_per_day` from its options,
+/// else the provider's free tier allowance.
+pub fn min_refresh_interval(config: &ProviderConfig) -> Duration {
+    let queried = if config.consensus.len() >= 2 {
+        config.consensus.clone()
+    } else {
+        vec![config.selected()]
+    };
+    queried
+        .into_iter()
+        .filter_map(|kind| daily_budget(kind, config))
+        .map(|per_day| Duration::from_secs(SECS_PER_DAY / u64::from(per_day.max(1))))
+        .max()
+        .unwrap_or(Duration::ZERO)
+}
+
+/// Requests a day `kind` may make: `requests_per_day` from its options,
+/// else its free tier allowance.
+fn daily_budget(kind: Provider, config: &ProviderConfig) -> Option<u32> {
+    let options: RequestOptions =
+        parse_options(kind, config.options.get(&kind)).unwrap_or_default();
+    options.requests_per_day.or(kind.daily_request_limit())
+}
+
+fn parse_options<T>(kind: Provider, options: Option<&Table>) -> Result<T, WeatherError>
+where
+    T: Default + for<'de> Deserialize<'de>,
+{
+    let Some(options) = options else {
+        return Ok(T::default());
+    };
+    T::deserialize(options.clone()).map_err(|e| {
+        WeatherError::Config(ConfigError::InvalidProviderOptions {
+            provider: kind.name(),
+            reason: e.to_string(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_met_office_without_key_is_rejected() {
+        if std::env::var("MET_OFFICE_API_KEY").is_ok() {
+            return;
+        }
+        let config = ProviderConfig {
+            name: Some(Provider::MetOffice),
+            ..Default::default()
+        };
+        let err = from_config(&config).err().expect("missing key should fail");
+        assert!(matches!(
+            err,
+            WeatherError::Config(ConfigError::InvalidAPIKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_options_are_reported() {
+        let options: Table = toml::from_str("include_location_name = \"yes\"").unwrap();
+        let config = ProviderConfig {
+            name: None,
+            options: [(Provider::MetOffice, options)].into_iter().collect(),
+            ..Default::default()
+        };
+        let err = from_config(&config).err().expect("bad options should fail");
+        assert!(matches!(
+            err,
+            WeatherError::Config(ConfigError::InvalidProviderOptions { .. })
+        ));
+    }
+
+    #[test]
+    fn test_min_refresh_interval_follows_budget() {
+        let config = ProviderConfig {
+            name: Some(Provider::MetOffice),
+            ..Default::default()
+        };
+        assert_eq!(min_refresh_interval(&config), Duration::from_secs(240));
+
+        let options: Table = toml::from_str("requests_per_day = 96").unwrap();
+        let config = ProviderConfig {
+            consensus: vec![Provider::OpenWeatherMap, Provider::MetNorway],
+            options: [(Provider::OpenWeatherMap, options)].into_iter().collect(),
+            ..Default::default()
+        };
+        assert_eq!(min_refresh_interval(&config), Duration::from_secs(900));
+
+        let config = ProviderConfig {
+            name: Some(Provider::Dwd),
+            ..Default::default()
+        };
+        assert_eq!(min_refresh_interval(&config), Duration::ZERO);
+    }
 }
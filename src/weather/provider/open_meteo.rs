@@ -1,25 +1,68 @@
+use crate::config::Provider;
 use crate::error::{NetworkError, WeatherError};
+use crate::http::{self, RequestOptions};
+use crate::weather::astro;
 use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
 use crate::weather::types::{
-    CelestialEvents, PrecipitationUnit, TemperatureUnit, WeatherLocation, WeatherUnits,
+    CelestialEvents, CloudLayer, CloudLevel, ForecastHour, NowcastStep, PrecipitationUnit,
+    TemperatureRange, TemperatureUnit, WeatherDetails, WeatherLocation, WeatherUnits,
     WindSpeedUnit,
 };
-use crate::weather::units::{normalize_precipitation, normalize_temperature, normalize_wind_speed};
+use crate::weather::units::{
+    normalize_precipitation, normalize_snow_depth, normalize_snowfall, normalize_temperature,
+    normalize_wind_speed,
+};
 use async_trait::async_trait;
+use chrono::{NaiveDateTime, TimeDelta};
 use serde::Deserialize;
 use serde::de::{self, Deserializer};
-use std::time::Duration;
 
 const OPEN_METEO_BASE_URL: &str = "https://api.open-meteo.com/v1/forecast";
 
 pub struct OpenMeteoProvider {
     client: reqwest::Client,
+    timeout_secs: u64,
     base_url: String,
 }
 
+/// The moon's phase at a time on a clock `utc_offset_seconds` ahead of UTC.
+pub(crate) fn moon_phase_at(time: NaiveDateTime, utc_offset_seconds: i64) -> f64 {
+    astro::moon_phase((time - TimeDelta::seconds(utc_offset_seconds)).and_utc())
+}
+
 #[derive(Debug, Deserialize)]
 struct OpenMeteoResponse {
     current: CurrentWeather,
+    #[serde(default)]
+    hourly: Option<HourlyWeather>,
+    #[serde(default)]
+    minutely_15: Option<Minutely15Weather>,
+    #[serde(default)]
+    daily: Option<DailyWeather>,
+    /// With `timezone=auto`, the zone at the coordinates.
+    #[serde(default)]
+    timezone: Option<String>,
+    /// How far the timestamps' clock is ahead of UTC.
+    #[serde(default)]
+    utc_offset_seconds: i64,
+}
+
+/// One entry per day, today first.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DailyWeather {
+    temperature_2m_min: Vec<Option<f64>>,
+    temperature_2m_max: Vec<Option<f64>>,
+}
+
+impl DailyWeather {
+    fn today(&self, unit: TemperatureUnit) -> Option<TemperatureRange> {
+        let first = |values: &[Option<f64>]| values.first().copied().flatten();
+        Some(TemperatureRange {
+            min: normalize_temperature(first(&self.temperature_2m_min)?, unit),
+            max: normalize_temperature(first(&self.temperature_2m_max)?, unit),
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,6 +76,147 @@ struct CurrentWeather {
     weather_code: i32,
     wind_speed_10m: f64,
     wind_direction_10m: f64,
+    #[serde(default)]
+    pressure_msl: Option<f64>,
+    #[serde(default)]
+    relative_humidity_2m: Option<f64>,
+    #[serde(default)]
+    apparent_temperature: Option<f64>,
+    #[serde(default)]
+    uv_index: Option<f64>,
+    #[serde(default)]
+    wind_gusts_10m: Option<f64>,
+    /// In metres.
+    #[serde(default)]
+    visibility: Option<f64>,
+    #[serde(default)]
+    dew_point_2m: Option<f64>,
+    #[serde(default)]
+    cloud_cover_low: Option<f64>,
+    #[serde(default)]
+    cloud_cover_mid: Option<f64>,
+    #[serde(default)]
+    cloud_cover_high: Option<f64>,
+    /// In the preceding hour; cm, or inches with imperial units.
+    #[serde(default)]
+    snowfall: Option<f64>,
+    /// Metres, or feet with imperial units.
+    #[serde(default)]
+    snow_depth: Option<f64>,
+}
+
+impl CurrentWeather {
+    fn details(&self, units: &WeatherUnits) -> WeatherDetails {
+        let layers = [
+            (CloudLevel::Low, self.cloud_cover_low),
+            (CloudLevel::Mid, self.cloud_cover_mid),
+            (CloudLevel::High, self.cloud_cover_high),
+        ];
+        WeatherDetails {
+            dew_point: self
+                .dew_point_2m
+                .map(|t| normalize_temperature(t, units.temperature)),
+            wind_gusts: self
+                .wind_gusts_10m
+                .map(|speed| normalize_wind_speed(speed, units.wind_speed)),
+            visibility: self.visibility,
+            cloud_layers: layers
+                .into_iter()
+                .filter_map(|(level, cover)| {
+                    Some(CloudLayer {
+                        level,
+                        cover: cover?,
+                    })
+                })
+                .collect(),
+            snowfall: self
+                .snowfall
+                .map(|amount| normalize_snowfall(amount, units.precipitation)),
+            snow_depth: self
+                .snow_depth
+                .map(|depth| normalize_snow_depth(depth, units.precipitation)),
+            ..WeatherDetails::default()
+        }
+    }
+}
+
+/// Parallel arrays, one entry per hour; any value may be null.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct HourlyWeather {
+    time: Vec<String>,
+    temperature_2m: Vec<Option<f64>>,
+    precipitation_probability: Vec<Option<f64>>,
+    weather_code: Vec<Option<f64>>,
+    relative_humidity_2m: Vec<Option<f64>>,
+}
+
+impl HourlyWeather {
+    /// Hours missing a temperature or weather code are dropped.
+    fn into_forecast(self, unit: TemperatureUnit) -> Vec<ForecastHour> {
+        let value = |values: &[Option<f64>], i: usize| values.get(i).copied().flatten();
+        self.time
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, time)| {
+                Some(ForecastHour {
+                    time,
+                    weather_code: value(&self.weather_code, i)?.round() as i32,
+                    temperature: normalize_temperature(value(&self.temperature_2m, i)?, unit),
+                    precipitation_probability: value(&self.precipitation_probability, i),
+                    humidity: value(&self.relative_humidity_2m, i),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Precipitation over the 15 minutes before each `time`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Minutely15Weather {
+    time: Vec<String>,
+    precipitation: Vec<Option<f64>>,
+}
+
+impl Minutely15Weather {
+    /// Steps still ahead of `now`, as rates in mm/h. The chance of
+    /// precipitation comes from the hourly forecast for the same hour.
+    fn into_nowcast(
+        self,
+        now: &str,
+        hourly: Option<&HourlyWeather>,
+        unit: PrecipitationUnit,
+    ) -> Vec<NowcastStep> {
+        let parse = |time: &str| NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").ok();
+        let Some(now) = parse(now) else {
+            return Vec::new();
+        };
+        let probability = |time: &str| {
+            let hourly = hourly?;
+            let hour = time.get(..13)?;
+            let index = hourly.time.iter().position(|t| t.get(..13) == Some(hour))?;
+            hourly
+                .precipitation_probability
+                .get(index)
+                .copied()
+                .flatten()
+        };
+
+        self.time
+            .iter()
+            .zip(self.precipitation)
+            .filter_map(|(time, amount)| {
+                let start = parse(time)? - TimeDelta::minutes(15);
+                let minutes_ahead = u32::try_from((start - now).num_minutes()).ok()?;
+                Some(NowcastStep {
+                    minutes_ahead,
+                    precipitation: normalize_precipitation(amount? * 4.0, unit),
+                    probability: probability(&start.format("%Y-%m-%dT%H:%M").to_string()),
+                })
+            })
+            .collect()
+    }
 }
 
 fn deserialize_i32_from_number<'de, D>(deserializer: D) -> Result<i32, D::Error>
@@ -59,18 +243,19 @@ where
 
 impl OpenMeteoProvider {
     pub fn new() -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .connect_timeout(Duration::from_secs(10))
-            .build()
-            .unwrap_or_else(|e| {
-                eprintln!("Warning: Failed to create custom HTTP client: {}", e);
-                eprintln!("Using default client with standard timeout settings.");
-                reqwest::Client::new()
-            });
+        Self::with_options(RequestOptions::default())
+    }
+
+    pub fn with_options(options: RequestOptions) -> Self {
+        let client = options.client().unwrap_or_else(|e| {
+            eprintln!("Warning: Failed to create custom HTTP client: {}", e);
+            eprintln!("Using default client with standard timeout settings.");
+            reqwest::Client::new()
+        });
 
         Self {
             client,
+            timeout_secs: options.timeout_secs(),
             base_url: OPEN_METEO_BASE_URL.to_string(),
         }
     }
@@ -100,7 +285,7 @@ impl OpenMeteoProvider {
 
     fn build_url(&self, location: &WeatherLocation, units: &WeatherUnits) -> String {
         format!(
-            "{}?latitude={}&longitude={}&current=temperature_2m,is_day,precipitation,weather_code,wind_speed_10m,wind_direction_10m&temperature_unit={}&wind_speed_unit={}&precipitation_unit={}&timezone=auto",
+            "{}?latitude={}&longitude={}&current=temperature_2m,is_day,precipitation,weather_code,wind_speed_10m,wind_direction_10m,pressure_msl,relative_humidity_2m,apparent_temperature,uv_index,wind_gusts_10m,visibility,dew_point_2m,cloud_cover_low,cloud_cover_mid,cloud_cover_high,snowfall,snow_depth&hourly=temperature_2m,precipitation_probability,weather_code,relative_humidity_2m&forecast_hours=24&minutely_15=precipitation&forecast_minutely_15=8&daily=temperature_2m_min,temperature_2m_max&temperature_unit={}&wind_speed_unit={}&precipitation_unit={}&timezone=auto",
             self.base_url,
             location.latitude,
             location.longitude,
@@ -134,15 +319,17 @@ impl WeatherProvider for OpenMeteoProvider {
             .get(&url)
             .send()
             .await
-            .and_then(|resp| resp.error_for_status())
-            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
+            .map_err(|e| NetworkError::from_reqwest(e, &url, self.timeout_secs))
+            .and_then(|resp| http::error_for_status(resp, &url, self.timeout_secs))
+            .map_err(WeatherError::Network)?;
 
-        let data: OpenMeteoResponse = response
-            .json()
-            .await
-            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
+        let data: OpenMeteoResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, self.timeout_secs))
+        })?;
 
-        let moon_phase = Some(0.5);
+        let moon_phase = NaiveDateTime::parse_from_str(&data.current.time, "%Y-%m-%dT%H:%M")
+            .ok()
+            .map(|time| moon_phase_at(time, data.utc_offset_seconds));
 
         Ok(WeatherProviderResponse {
             weather_code: data.current.weather_code,
@@ -152,6 +339,34 @@ impl WeatherProvider for OpenMeteoProvider {
             wind_direction: data.current.wind_direction_10m,
             sun: CelestialEvents::only_day(data.current.is_day),
             moon_phase,
+            pressure: data.current.pressure_msl,
+            disagreement: None,
+            humidity: data.current.relative_humidity_2m,
+            apparent_temperature: data
+                .current
+                .apparent_temperature
+                .map(|t| normalize_temperature(t, units.temperature)),
+            uv_index: data.current.uv_index,
+            source: Provider::OpenMeteo.abbreviation().to_string(),
+            nowcast: data
+                .minutely_15
+                .map(|steps| {
+                    steps.into_nowcast(
+                        &data.current.time,
+                        data.hourly.as_ref(),
+                        units.precipitation,
+                    )
+                })
+                .unwrap_or_default(),
+            hourly: data
+                .hourly
+                .map(|hourly| hourly.into_forecast(units.temperature))
+                .unwrap_or_default(),
+            today: data.daily.and_then(|daily| daily.today(units.temperature)),
+            details: WeatherDetails {
+                timezone: data.timezone.clone(),
+                ..data.current.details(units)
+            },
             timestamp: data.current.time,
             attribution: self.get_attribution().to_string(),
         })
@@ -185,4 +400,132 @@ mod tests {
             "mm"
         );
     }
+
+    #[test]
+    fn test_hourly_forecast_parsing() {
+        let json = r#"{
+            "current": {
+                "time": "2024-01-01T12:00",
+                "temperature_2m": 41.0,
+                "is_day": 1,
+                "precipitation": 0.0,
+                "weather_code": 3,
+                "wind_speed_10m": 5.0,
+                "wind_direction_10m": 180.0
+            },
+            "hourly": {
+                "time": ["2024-01-01T12:00", "2024-01-01T13:00", "2024-01-01T14:00"],
+                "temperature_2m": [41.0, null, 50.0],
+                "precipitation_probability": [10, 20, null],
+                "weather_code": [3, 61, 61.0],
+                "relative_humidity_2m": [80, 85, null]
+            }
+        }"#;
+        let data: OpenMeteoResponse = serde_json::from_str(json).unwrap();
+        let hours = data
+            .hourly
+            .unwrap()
+            .into_forecast(TemperatureUnit::Fahrenheit);
+
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].time, "2024-01-01T12:00");
+        assert!((hours[0].temperature - 5.0).abs() < 1e-9);
+        assert_eq!(hours[0].precipitation_probability, Some(10.0));
+        assert_eq!(hours[0].humidity, Some(80.0));
+        assert_eq!(hours[1].time, "2024-01-01T14:00");
+        assert_eq!(hours[1].weather_code, 61);
+        assert_eq!(hours[1].precipitation_probability, None);
+        assert_eq!(hours[1].humidity, None);
+    }
+
+    #[test]
+    fn test_details_parsing() {
+        let json = r#"{"time": "t", "temperature_2m": 1.0, "is_day": 0,
+            "precipitation": 0.0, "weather_code": 0, "wind_speed_10m": 0.0,
+            "wind_direction_10m": 0.0, "wind_gusts_10m": 36.0, "visibility": 2400.0,
+            "dew_point_2m": -1.5, "cloud_cover_low": 80, "cloud_cover_mid": null,
+            "cloud_cover_high": 20, "snowfall": 1.5, "snow_depth": 0.32}"#;
+        let current: CurrentWeather = serde_json::from_str(json).unwrap();
+        let details = current.details(&WeatherUnits::default());
+
+        assert!((details.wind_gusts.unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(details.visibility, Some(2400.0));
+        assert_eq!(details.dew_point, Some(-1.5));
+        assert_eq!(details.snowfall, Some(1.5));
+        assert_eq!(details.snow_depth, Some(0.32));
+        assert_eq!(
+            details.cloud_layers,
+            vec![
+                CloudLayer {
+                    level: CloudLevel::Low,
+                    cover: 80.0
+                },
+                CloudLayer {
+                    level: CloudLevel::High,
+                    cover: 20.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_daily_range_is_today() {
+        let json = r#"{"temperature_2m_min": [23.0, 20.0], "temperature_2m_max": [59.0, 61.0]}"#;
+        let daily: DailyWeather = serde_json::from_str(json).unwrap();
+        let today = daily.today(TemperatureUnit::Fahrenheit).unwrap();
+        assert!((today.min - -5.0).abs() < 1e-9);
+        assert!((today.max - 15.0).abs() < 1e-9);
+
+        let json = r#"{"temperature_2m_min": [null], "temperature_2m_max": [15.0]}"#;
+        let daily: DailyWeather = serde_json::from_str(json).unwrap();
+        assert_eq!(daily.today(TemperatureUnit::Celsius), None);
+    }
+
+    #[test]
+    fn test_missing_hourly_is_empty() {
+        let json = r#"{"current": {"time": "t", "temperature_2m": 1.0, "is_day": 0,
+            "precipitation": 0.0, "weather_code": 0, "wind_speed_10m": 0.0,
+            "wind_direction_10m": 0.0}}"#;
+        let data: OpenMeteoResponse = serde_json::from_str(json).unwrap();
+        assert!(data.hourly.is_none());
+    }
+
+    #[test]
+    fn test_nowcast_parsing() {
+        let json = r#"{
+            "current": {
+                "time": "2024-01-01T12:00",
+                "temperature_2m": 8.0,
+                "is_day": 1,
+                "precipitation": 0.0,
+                "weather_code": 3,
+                "wind_speed_10m": 5.0,
+                "wind_direction_10m": 180.0
+            },
+            "hourly": {
+                "time": ["2024-01-01T12:00", "2024-01-01T13:00"],
+                "temperature_2m": [8.0, 7.0],
+                "precipitation_probability": [40, 80],
+                "weather_code": [3, 61]
+            },
+            "minutely_15": {
+                "time": ["2024-01-01T12:00", "2024-01-01T12:15", "2024-01-01T13:15"],
+                "precipitation": [0.0, 0.0, 0.5]
+            }
+        }"#;
+        let data: OpenMeteoResponse = serde_json::from_str(json).unwrap();
+        let steps = data.minutely_15.unwrap().into_nowcast(
+            &data.current.time,
+            data.hourly.as_ref(),
+            PrecipitationUnit::Mm,
+        );
+
+        // The first step ended at the observation time
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].minutes_ahead, 0);
+        assert_eq!(steps[0].probability, Some(40.0));
+        assert_eq!(steps[1].minutes_ahead, 60);
+        assert_eq!(steps[1].precipitation, 2.0);
+        assert_eq!(steps[1].probability, Some(80.0));
+    }
 }
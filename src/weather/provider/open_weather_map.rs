@@ -0,0 +1,334 @@
+use crate::config::Provider;
+use crate::error::{ConfigError, NetworkError, WeatherError};
+use crate::http::{self, RequestOptions};
+use crate::secrets;
+use crate::weather::astro;
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::types::{CelestialEvents, WeatherDetails, WeatherLocation, WeatherUnits};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime};
+use serde::Deserialize;
+use std::fmt;
+
+const OPEN_WEATHER_MAP_BASE_URL: &str = "https://api.openweathermap.org/data/2.5/weather";
+const API_KEY_ENV: &str = "OPENWEATHERMAP_API_KEY";
+
+pub struct OpenWeatherMapProvider {
+    client: reqwest::Client,
+    timeout_secs: u64,
+    base_url: String,
+    api_key: String,
+}
+
+#[derive(Clone, Default, Deserialize)]
+pub struct OpenWeatherMapProviderConfig {
+    #[serde(flatten)]
+    pub request: RequestOptions,
+    #[serde(default)]
+    pub api_key: String,
+    /// Prints the key, e.g. `pass show openweathermap`; used when
+    /// `api_key` isn't set.
+    #[serde(default)]
+    pub api_key_cmd: Option<String>,
+}
+
+impl fmt::Debug for OpenWeatherMapProviderConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenWeatherMapProviderConfig")
+            .field("request", &self.request)
+            .field("api_key", &secrets::Redacted(&self.api_key))
+            .field("api_key_cmd", &self.api_key_cmd)
+            .finish()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMapResponse {
+    weather: Vec<OpenWeatherMapCondition>,
+    main: OpenWeatherMapMain,
+    #[serde(default)]
+    wind: OpenWeatherMapWind,
+    #[serde(default)]
+    rain: OpenWeatherMapPrecipitation,
+    #[serde(default)]
+    snow: OpenWeatherMapPrecipitation,
+    /// In metres, capped at 10 km.
+    #[serde(default)]
+    visibility: Option<f64>,
+    dt: i64,
+    sys: OpenWeatherMapSys,
+    /// Offset from UTC in seconds.
+    #[serde(default)]
+    timezone: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMapCondition {
+    id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMapMain {
+    temp: f64,
+    feels_like: Option<f64>,
+    /// Sea level pressure in hPa.
+    pressure: Option<f64>,
+    /// Relative humidity in percent.
+    humidity: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenWeatherMapWind {
+    #[serde(default)]
+    speed: f64,
+    #[serde(default)]
+    deg: f64,
+    #[serde(default)]
+    gust: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenWeatherMapPrecipitation {
+    #[serde(rename = "1h", default)]
+    one_hour: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMapSys {
+    sunrise: Option<i64>,
+    sunset: Option<i64>,
+}
+
+impl OpenWeatherMapProvider {
+    pub fn new(mut config: OpenWeatherMapProviderConfig) -> Result<Self, WeatherError> {
+        if let Ok(api_key) = std::env::var(API_KEY_ENV) {
+            config.api_key = api_key;
+        }
+
+        if config.api_key.trim().is_empty()
+            && let Some(command) = &config.api_key_cmd
+        {
+            config.api_key = secrets::api_key_from_command(Provider::OpenWeatherMap, command)
+                .map_err(WeatherError::Config)?;
+        }
+
+        if config.api_key.trim().is_empty()
+            && let Some(api_key) = secrets::api_key(Provider::OpenWeatherMap)
+        {
+            config.api_key = api_key;
+        }
+
+        if config.api_key.trim().is_empty() {
+            return Err(WeatherError::Config(ConfigError::InvalidAPIKey(
+                "API key is empty for OpenWeatherMap Provider".to_string(),
+            )));
+        }
+
+        let client = config.request.client().map_err(WeatherError::Network)?;
+
+        Ok(Self {
+            client,
+            timeout_secs: config.request.timeout_secs(),
+            base_url: OPEN_WEATHER_MAP_BASE_URL.to_string(),
+            api_key: config.api_key.trim().to_string(),
+        })
+    }
+
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        // Metric units already match the internal °C, m/s and mm
+        format!(
+            "{}?lat={}&lon={}&units=metric&appid={}",
+            self.base_url, location.latitude, location.longitude, self.api_key
+        )
+    }
+
+    /// Maps an OpenWeatherMap condition id onto the WMO weather code the
+    /// normalizer understands.
+    /// See: https://openweathermap.org/weather-conditions
+    fn condition_to_wmo(id: i32) -> i32 {
+        match id {
+            200..=232 => 95,
+            300 | 310 => 51,
+            302 | 312 | 314 => 55,
+            301..=321 => 53,
+            500 => 61,
+            501 => 63,
+            502..=504 => 65,
+            511 => 66,
+            520 => 80,
+            521 | 531 => 81,
+            522 => 82,
+            600 | 615 | 616 => 71,
+            601 => 73,
+            602 => 75,
+            611..=613 => 77,
+            620 | 621 => 85,
+            622 => 86,
+            701..=762 => 45,
+            771 => 81,
+            781 => 95,
+            800 => 0,
+            801 => 1,
+            802 => 2,
+            803 | 804 => 3,
+            _ => 0,
+        }
+    }
+
+    fn local_time(timestamp: i64, offset: i64) -> Option<NaiveDateTime> {
+        DateTime::from_timestamp(timestamp + offset, 0).map(|t| t.naive_utc())
+    }
+
+    fn to_response(&self, data: OpenWeatherMapResponse) -> WeatherProviderResponse {
+        let weather_code = data
+            .weather
+            .first()
+            .map(|condition| Self::condition_to_wmo(condition.id))
+            .unwrap_or(0);
+
+        let is_day = match (data.sys.sunrise, data.sys.sunset) {
+            (Some(rise), Some(set)) => (rise..set).contains(&data.dt),
+            _ => true,
+        };
+        let mut sun = CelestialEvents::from_bool(is_day);
+        sun.rise = data
+            .sys
+            .sunrise
+            .and_then(|t| Self::local_time(t, data.timezone))
+            .map(|t| t.time());
+        sun.set = data
+            .sys
+            .sunset
+            .and_then(|t| Self::local_time(t, data.timezone))
+            .map(|t| t.time());
+
+        let timestamp = Self::local_time(data.dt, data.timezone)
+            .map(|t| t.format("%Y-%m-%dT%H:%M").to_string())
+            .unwrap_or_default();
+
+        WeatherProviderResponse {
+            weather_code,
+            temperature: data.main.temp,
+            precipitation: data.rain.one_hour + data.snow.one_hour,
+            wind_speed: data.wind.speed,
+            wind_direction: data.wind.deg,
+            sun,
+            moon_phase: DateTime::from_timestamp(data.dt, 0).map(astro::moon_phase),
+            pressure: data.main.pressure,
+            disagreement: None,
+            humidity: data.main.humidity,
+            apparent_temperature: data.main.feels_like,
+            uv_index: None,
+            source: Provider::OpenWeatherMap.abbreviation().to_string(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: None,
+            details: WeatherDetails {
+                wind_gusts: data.wind.gust,
+                visibility: data.visibility,
+                ..WeatherDetails::default()
+            },
+            timestamp,
+            attribution: self.get_attribution().to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenWeatherMapProvider {
+    fn get_attribution(&self) -> &'static str {
+        // Required by the OpenWeather terms (CC BY-SA 4.0)
+        "Weather data provided by OpenWeather"
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = self.build_url(location);
+        // Keep the key out of error messages
+        let redacted = url.replace(&self.api_key, "***");
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| NetworkError::from_reqwest(e.without_url(), &redacted, self.timeout_secs))
+            .and_then(|resp| http::error_for_status(resp, &redacted, self.timeout_secs))
+            .map_err(WeatherError::Network)?;
+
+        let data: OpenWeatherMapResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(
+                e.without_url(),
+                &redacted,
+                self.timeout_secs,
+            ))
+        })?;
+
+        Ok(self.to_response(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+    use crate::weather::normalizer::WeatherNormalizer;
+
+    fn provider() -> OpenWeatherMapProvider {
+        OpenWeatherMapProvider::new(OpenWeatherMapProviderConfig {
+            api_key: "test-key".to_string(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_condition_mapping() {
+        let cases = [
+            (211, WeatherCondition::Thunderstorm),
+            (301, WeatherCondition::Drizzle),
+            (502, WeatherCondition::HeavyRain),
+            (511, WeatherCondition::FreezingRain),
+            (521, WeatherCondition::RainShowers),
+            (601, WeatherCondition::Snow),
+            (741, WeatherCondition::Fog),
+            (800, WeatherCondition::Clear),
+            (804, WeatherCondition::Overcast),
+        ];
+        for (id, expected) in cases {
+            let code = OpenWeatherMapProvider::condition_to_wmo(id);
+            assert_eq!(
+                WeatherNormalizer::wmo_code_to_condition(code),
+                expected,
+                "OWM id {}",
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn test_response_conversion() {
+        let json = r#"{
+            "weather": [{"id": 500, "main": "Rain"}],
+            "main": {"temp": 7.5},
+            "wind": {"speed": 4.1, "deg": 250},
+            "rain": {"1h": 0.6},
+            "dt": 1700000000,
+            "sys": {"sunrise": 1699950000, "sunset": 1699985000},
+            "timezone": 3600
+        }"#;
+        let data: OpenWeatherMapResponse = serde_json::from_str(json).unwrap();
+        let response = provider().to_response(data);
+
+        assert_eq!(response.weather_code, 61);
+        assert_eq!(response.temperature, 7.5);
+        assert_eq!(response.precipitation, 0.6);
+        assert_eq!(response.wind_speed, 4.1);
+        assert!(!response.sun.is_day);
+        assert_eq!(response.timestamp, "2023-11-14T23:13");
+        assert_eq!(response.attribution, "Weather data provided by OpenWeather");
+    }
+}
@@ -0,0 +1,394 @@
+//! A weather station on the local network, read through the live data API
+//! of Ecowitt-compatible gateways (GW1000/GW1100/GW2000, WH2650 and
+//! rebrands). Temperature, wind, rain and pressure come from the station;
+//! another provider fills in the weather code and moon phase.
+
+use crate::config::Provider;
+use crate::error::{ConfigError, DataError, NetworkError, WeatherError};
+use crate::http::RequestOptions;
+use crate::weather::astro;
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::timezone::TimeZoneLookup;
+use crate::weather::types::{WeatherLocation, WeatherUnits};
+use crate::weather::units::{fahrenheit_to_celsius, inch_to_mm, kmh_to_ms, kn_to_ms, mph_to_ms};
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+const LIVE_DATA_PATH: &str = "/get_livedata_info";
+
+/// Sensor ids in the gateway's `common_list`.
+const OUTDOOR_TEMPERATURE: &str = "0x02";
+const WIND_DIRECTION: &str = "0x0A";
+const WIND_SPEED: &str = "0x0B";
+const RAIN_RATE: &str = "0x0E";
+
+pub struct StationProvider {
+    client: reqwest::Client,
+    timeout_secs: u64,
+    url: String,
+    remote: Option<Arc<dyn WeatherProvider>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StationProviderConfig {
+    #[serde(flatten)]
+    pub request: RequestOptions,
+    /// Address of the gateway, e.g. "http://192.168.1.50".
+    #[serde(default)]
+    pub url: String,
+    /// Provider for the weather code and moon phase; Open-Meteo by default.
+    #[serde(default)]
+    pub remote: Option<Provider>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LiveData {
+    #[serde(default)]
+    common_list: Vec<Reading>,
+    #[serde(default)]
+    rain: Vec<Reading>,
+    #[serde(default)]
+    piezo_rain: Vec<Reading>,
+    #[serde(default)]
+    wh25: Vec<Barometer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Reading {
+    id: String,
+    /// A number, optionally followed by its unit, e.g. "3.2 m/s".
+    val: String,
+    /// Set for temperatures: "C" or "F".
+    unit: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Barometer {
+    /// Relative (sea level) pressure, e.g. "1013.5 hPa".
+    rel: Option<String>,
+}
+
+/// Readings converted to the internal units (°C, m/s, mm/h, hPa).
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StationReadings {
+    temperature: f64,
+    wind_speed: f64,
+    wind_direction: f64,
+    rain_rate: f64,
+    pressure: Option<f64>,
+}
+
+/// Splits "3.2 m/s" into 3.2 and "m/s".
+fn split_value(value: &str) -> Option<(f64, &str)> {
+    let value = value.trim();
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(value.len());
+    let number = value[..end].parse().ok()?;
+    Some((number, value[end..].trim()))
+}
+
+fn pressure_hpa(value: &str) -> Option<f64> {
+    let (number, unit) = split_value(value)?;
+    Some(match unit.to_lowercase().as_str() {
+        "inhg" => number * 33.8639,
+        "mmhg" => number * 1.33322,
+        _ => number,
+    })
+}
+
+impl LiveData {
+    fn find<'a>(readings: &'a [Reading], id: &str) -> Option<&'a Reading> {
+        readings.iter().find(|r| r.id.eq_ignore_ascii_case(id))
+    }
+
+    fn readings(&self) -> Result<StationReadings, WeatherError> {
+        let temperature = Self::find(&self.common_list, OUTDOOR_TEMPERATURE)
+            .and_then(|r| {
+                let (value, _) = split_value(&r.val)?;
+                Some(match r.unit.as_deref() {
+                    Some("F") | Some("℉") => fahrenheit_to_celsius(value),
+                    _ => value,
+                })
+            })
+            .ok_or_else(|| {
+                WeatherError::Data(DataError::BadData(
+                    "weather station reports no outdoor temperature".to_string(),
+                ))
+            })?;
+
+        let wind_speed = Self::find(&self.common_list, WIND_SPEED)
+            .and_then(|r| split_value(&r.val))
+            .map(|(value, unit)| match unit.to_lowercase().as_str() {
+                "km/h" => kmh_to_ms(value),
+                "mph" => mph_to_ms(value),
+                "knots" | "kn" => kn_to_ms(value),
+                _ => value,
+            })
+            .unwrap_or(0.0);
+
+        let wind_direction = Self::find(&self.common_list, WIND_DIRECTION)
+            .and_then(|r| split_value(&r.val))
+            .map(|(value, _)| value)
+            .unwrap_or(0.0);
+
+        // Stations with a piezo gauge (WS90) report rain there instead
+        let rain_rate = Self::find(&self.rain, RAIN_RATE)
+            .or_else(|| Self::find(&self.piezo_rain, RAIN_RATE))
+            .and_then(|r| split_value(&r.val))
+            .map(|(value, unit)| {
+                if unit.to_lowercase().starts_with("in") {
+                    inch_to_mm(value)
+                } else {
+                    value
+                }
+            })
+            .unwrap_or(0.0);
+
+        let pressure = self
+            .wh25
+            .first()
+            .and_then(|b| b.rel.as_deref())
+            .and_then(pressure_hpa);
+
+        Ok(StationReadings {
+            temperature,
+            wind_speed,
+            wind_direction,
+            rain_rate,
+            pressure,
+        })
+    }
+}
+
+impl StationProvider {
+    pub fn new(
+        config: StationProviderConfig,
+        remote: Option<Arc<dyn WeatherProvider>>,
+    ) -> Result<Self, WeatherError> {
+        let base = config.url.trim().trim_end_matches('/');
+        if base.is_empty() {
+            return Err(WeatherError::Config(ConfigError::InvalidProviderOptions {
+                provider: "station",
+                reason: "`url` must point at the station gateway".to_string(),
+            }));
+        }
+        let base = if base.contains("://") {
+            base.to_string()
+        } else {
+            format!("http://{base}")
+        };
+
+        Ok(Self {
+            client: config.request.client().map_err(WeatherError::Network)?,
+            timeout_secs: config.request.timeout_secs(),
+            url: format!("{base}{LIVE_DATA_PATH}"),
+            remote,
+        })
+    }
+
+    async fn fetch_readings(&self) -> Result<StationReadings, WeatherError> {
+        let response = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                WeatherError::Network(NetworkError::from_reqwest(e, &self.url, self.timeout_secs))
+            })?;
+
+        let data: LiveData = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &self.url, self.timeout_secs))
+        })?;
+        data.readings()
+    }
+
+    /// WMO code for the rain gauge alone, used when the remote provider
+    /// is unavailable.
+    fn rain_to_wmo(rain_rate: f64) -> i32 {
+        match rain_rate {
+            r if r >= 7.6 => 65,
+            r if r >= 2.5 => 63,
+            r if r > 0.0 => 61,
+            _ => 0,
+        }
+    }
+
+    /// Station readings on top of the remote response, or on their own as
+    /// of `now` on the clock at `location`.
+    fn combine(
+        &self,
+        readings: StationReadings,
+        remote: Option<WeatherProviderResponse>,
+        location: &WeatherLocation,
+        now: DateTime<FixedOffset>,
+    ) -> WeatherProviderResponse {
+        let attribution = self.get_attribution();
+        match remote {
+            Some(remote) => WeatherProviderResponse {
+                // Trust the gauge over the model when they disagree on rain
+                weather_code: if readings.rain_rate > 0.0 && remote.weather_code < 51 {
+                    Self::rain_to_wmo(readings.rain_rate)
+                } else {
+                    remote.weather_code
+                },
+                temperature: readings.temperature,
+                precipitation: readings.rain_rate,
+                wind_speed: readings.wind_speed,
+                wind_direction: readings.wind_direction,
+                pressure: readings.pressure.or(remote.pressure),
+                // The remote figure was for the remote temperature
+                apparent_temperature: None,
+                attribution: format!("{attribution}\n{}", remote.attribution),
+                source: format!("{}+{}", Provider::Station.abbreviation(), remote.source),
+                ..remote
+            },
+            None => WeatherProviderResponse {
+                weather_code: Self::rain_to_wmo(readings.rain_rate),
+                temperature: readings.temperature,
+                precipitation: readings.rain_rate,
+                wind_speed: readings.wind_speed,
+                wind_direction: readings.wind_direction,
+                sun: astro::celestial_events(location.latitude, location.longitude, now),
+                moon_phase: Some(astro::moon_phase(now.with_timezone(&Utc))),
+                pressure: readings.pressure,
+                disagreement: None,
+                humidity: None,
+                apparent_temperature: None,
+                uv_index: None,
+                source: Provider::Station.abbreviation().to_string(),
+                nowcast: Vec::new(),
+                hourly: Vec::new(),
+                today: None,
+                details: Default::default(),
+                timestamp: now.format("%Y-%m-%dT%H:%M").to_string(),
+                attribution: attribution.to_string(),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for StationProvider {
+    fn get_attribution(&self) -> &'static str {
+        "Local weather station"
+    }
+
+    fn refresh_spacing(&self) -> Duration {
+        self.remote
+            .as_ref()
+            .map(|remote| remote.refresh_spacing())
+            .unwrap_or_default()
+    }
+
+    fn budget_warning(&self) -> Option<String> {
+        self.remote.as_ref()?.budget_warning()
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let remote = async {
+            match &self.remote {
+                Some(remote) => remote.get_current_weather(location, units).await.ok(),
+                None => None,
+            }
+        };
+        let (readings, remote, clock) = tokio::join!(
+            self.fetch_readings(),
+            remote,
+            TimeZoneLookup::shared().clock_at(location)
+        );
+        Ok(self.combine(readings?, remote, location, clock.now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::types::CelestialEvents;
+
+    const LIVE_DATA: &str = r#"{
+        "common_list": [
+            {"id": "0x02", "val": "71.6", "unit": "F"},
+            {"id": "0x07", "val": "55%"},
+            {"id": "0x0A", "val": "225"},
+            {"id": "0x0B", "val": "7.2 km/h"},
+            {"id": "0x0C", "val": "10.8 km/h"}
+        ],
+        "rain": [
+            {"id": "0x0D", "val": "0.12 in"},
+            {"id": "0x0E", "val": "0.10 in/Hr"}
+        ],
+        "wh25": [{"intemp": "23.1", "unit": "C", "inhumi": "45%",
+                  "abs": "1001.2 hPa", "rel": "29.92 inHg"}]
+    }"#;
+
+    fn provider() -> StationProvider {
+        StationProvider::new(
+            StationProviderConfig {
+                url: "192.168.1.50/".to_string(),
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_live_data_conversion() {
+        let data: LiveData = serde_json::from_str(LIVE_DATA).unwrap();
+        let readings = data.readings().unwrap();
+
+        assert!((readings.temperature - 22.0).abs() < 0.01);
+        assert!((readings.wind_speed - 2.0).abs() < 0.01);
+        assert_eq!(readings.wind_direction, 225.0);
+        assert!((readings.rain_rate - 2.54).abs() < 0.01);
+        assert!((readings.pressure.unwrap() - 1013.2).abs() < 0.1);
+        assert_eq!(provider().url, "http://192.168.1.50/get_livedata_info");
+    }
+
+    #[test]
+    fn test_station_overrides_remote_measurements() {
+        let data: LiveData = serde_json::from_str(LIVE_DATA).unwrap();
+        let readings = data.readings().unwrap();
+        let location = WeatherLocation {
+            latitude: 51.5,
+            longitude: -0.1,
+            elevation: None,
+        };
+        let now = Utc::now().fixed_offset();
+        let remote = WeatherProviderResponse {
+            temperature: 15.0,
+            wind_speed: 8.0,
+            wind_direction: 90.0,
+            sun: CelestialEvents::from_bool(false),
+            moon_phase: Some(0.4),
+            pressure: Some(1000.0),
+            timestamp: "2024-06-21T09:00".to_string(),
+            attribution: "Weather data by Open-Meteo.com".to_string(),
+            ..WeatherProviderResponse::sample(3)
+        };
+
+        let combined = provider().combine(readings, Some(remote), &location, now);
+        assert_eq!(combined.weather_code, 63);
+        assert!((combined.temperature - 22.0).abs() < 0.01);
+        assert_eq!(combined.moon_phase, Some(0.4));
+        assert!(!combined.sun.is_day);
+        assert_eq!(combined.timestamp, "2024-06-21T09:00");
+        assert_eq!(
+            combined.attribution,
+            "Local weather station\nWeather data by Open-Meteo.com"
+        );
+
+        let alone = provider().combine(readings, None, &location, now);
+        assert_eq!(alone.weather_code, 63);
+        assert_eq!(alone.attribution, "Local weather station");
+    }
+}
@@ -1,18 +1,20 @@
 //! US Government Astronomical Applications Department
 
 use async_trait::async_trait;
-use chrono::{Local, NaiveTime};
+use chrono::{DateTime, FixedOffset, NaiveTime};
 use serde::Deserialize;
 use serde_json::Value;
 
 use crate::{
     error::{DataError, NetworkError, WeatherError},
+    http::{DEFAULT_TIMEOUT_SECS, RequestOptions},
     weather::{
         WeatherLocation, WeatherUnits,
         provider::supplementary::{
             SupplementaryProviderRequest, SupplementaryProviderResponse,
             SupplementaryWeatherProvider,
         },
+        timezone::TimeZoneLookup,
         types::CelestialEvents,
     },
 };
@@ -32,29 +34,48 @@ impl AADProvider {
         Self
     }
 
-    fn build_url(
-        &self,
-        wanted: &SupplementaryProviderRequest,
-        location: &WeatherLocation,
-    ) -> String {
-        let now = chrono::Local::now();
+    /// Both requests use the one-day report, whose illuminated fraction
+    /// gives a smoother moon phase than the list of principal phases. The
+    /// day and the times in it are on the clock at `location`, which reads
+    /// `now`; its offset already counts any daylight saving.
+    fn build_url(&self, location: &WeatherLocation, now: DateTime<FixedOffset>) -> String {
         let date = now.format("%Y-%m-%d").to_string();
-        let offset_seconds = now.offset().local_minus_utc();
-        let offset_hours = offset_seconds / 3600;
+        let offset_hours = now.offset().local_minus_utc() as f64 / 3600.0;
 
-        match wanted {
-            SupplementaryProviderRequest::PhasesOfMoon => {
-                format!("{BASE_URL}moon/phases/date?date={date}&nump=1")
-            }
-            SupplementaryProviderRequest::SunAndMoonForOneDay => {
-                format!(
-                    "{BASE_URL}rstt/oneday?date={date}&coords={},{}&tz={}&dst=true",
-                    location.latitude, location.longitude, offset_hours
-                )
-            }
+        format!(
+            "{BASE_URL}rstt/oneday?date={date}&coords={},{}&tz={}&dst=false",
+            location.latitude, location.longitude, offset_hours
+        )
+    }
+
+    /// Phase from the illuminated fraction (0.0 - 1.0) and whether the moon
+    /// is waxing. Illumination is (1 - cos 2πp) / 2 for phase p.
+    fn phase_from_illumination(fraction: f64, waxing: bool) -> f64 {
+        let half = (1.0 - 2.0 * fraction.clamp(0.0, 1.0)).acos() / std::f64::consts::TAU;
+        if waxing {
+            half
+        } else {
+            (1.0 - half).rem_euclid(1.0)
         }
     }
 
+    /// Moon phase of a one-day report: from `fracillum` (e.g. "87%") and
+    /// the waxing/waning state of `curphase`, else the phase name alone.
+    fn moon_phase(data: &Value) -> Option<f64> {
+        let name = data["curphase"].as_str()?;
+        let fraction = data["fracillum"]
+            .as_str()
+            .and_then(|f| f.trim().trim_end_matches('%').parse::<f64>().ok());
+
+        Some(match fraction {
+            Some(percent) => {
+                let waxing = name.starts_with("Waxing") || name == "First Quarter";
+                Self::phase_from_illumination(percent / 100.0, waxing)
+            }
+            None => Self::convert_string_to_moon_phase(name),
+        })
+    }
+
     fn convert_string_to_moon_phase(value: &str) -> f64 {
         match value {
             // New Moon
@@ -78,47 +99,32 @@ impl SupplementaryWeatherProvider for AADProvider {
         #[allow(unused_variables)] units: &WeatherUnits,
         wanted: SupplementaryProviderRequest,
     ) -> Result<SupplementaryProviderResponse, WeatherError> {
-        let url = self.build_url(&wanted, location);
-
-        let response = reqwest::get(&url)
-            .await
-            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
-
-        let data: Value = response
-            .json()
-            .await
-            .map_err(|e| WeatherError::Network(NetworkError::from_reqwest(e, &url, 30)))?;
-
-        let now = Local::now();
+        let now = TimeZoneLookup::shared().clock_at(location).await.now();
+        let url = self.build_url(location, now);
+
+        let client = RequestOptions::default()
+            .client()
+            .map_err(WeatherError::Network)?;
+        let response = client.get(&url).send().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+        })?;
+
+        let data: Value = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+        })?;
+
+        let data = &data["properties"]["data"];
+        let Some(current_moon_phase) = Self::moon_phase(data) else {
+            return Err(WeatherError::Data(DataError::BadData(
+                "No moon phases".to_string(),
+            )));
+        };
 
         match wanted {
-            SupplementaryProviderRequest::PhasesOfMoon => {
-                // TODO: Consider using the Fracillum / 10
-                let phase_data = &data["phasedata"];
-
-                let phases: Vec<MoonPhase> = serde_json::from_value(phase_data.clone())
-                    .map_err(|e| WeatherError::Data(DataError::SerdeParseError(e)))?;
-
-                if let Some(current_phase) = phases.first() {
-                    let phase = AADProvider::convert_string_to_moon_phase(&current_phase.phase);
-                    Ok(SupplementaryProviderResponse::PhasesOfMoon(Some(phase)))
-                } else {
-                    Err(WeatherError::Data(DataError::BadData(
-                        "No moon phases".to_string(),
-                    )))
-                }
-            }
+            SupplementaryProviderRequest::PhasesOfMoon => Ok(
+                SupplementaryProviderResponse::PhasesOfMoon(Some(current_moon_phase)),
+            ),
             SupplementaryProviderRequest::SunAndMoonForOneDay => {
-                let data = &data["properties"]["data"];
-                let current_moon_phase = data["curphase"].as_str();
-
-                let Some(current_moon_phase) = current_moon_phase else {
-                    return Err(WeatherError::Data(DataError::BadData(
-                        "No moon phases".to_string(),
-                    )));
-                };
-
-                let current_moon_phase = Self::convert_string_to_moon_phase(current_moon_phase);
                 let sun_data: Vec<SunData> = serde_json::from_value(data["sundata"].clone())
                     .map_err(|e| WeatherError::Data(DataError::SerdeParseError(e)))?;
 
@@ -167,7 +173,7 @@ impl SupplementaryWeatherProvider for AADProvider {
     }
 
     fn get_attribution(&self) -> &'static str {
-        ""
+        "Sun and moon data from the U.S. Naval Observatory"
     }
 
     fn capabilities(&self) -> Vec<SupplementaryProviderRequest> {
@@ -194,15 +200,6 @@ enum CelestialPhenomena {
     EndCivilTwilight,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-struct MoonPhase {
-    // day: u8,
-    // month: u8,
-    phase: String,
-    // time: String,
-    // year: u16,
-}
-
 #[derive(Debug, Clone, Deserialize)]
 struct SunData {
     pub phen: CelestialPhenomena,
@@ -223,25 +220,36 @@ impl SunData {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::scheduler::Scheduler;
     use crate::weather::WeatherLocation;
 
     #[test]
     fn tz_test() {
-        let now = chrono::Local::now();
-        let date = now.format("%Y-%m-%d").to_string();
-        let offset_seconds = now.offset().local_minus_utc();
-        let offset_hours = offset_seconds / 3600;
-        println!("{date} {offset_hours}");
-
-        let location = WeatherLocation {
-            latitude: 52.52,
-            longitude: 13.41,
+        let at = |zone: &str, h| {
+            Scheduler::for_zone(zone).at(
+                chrono::NaiveDate::from_ymd_opt(2024, 6, 21).unwrap(),
+                NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            )
+        };
+        let place = |latitude, longitude| WeatherLocation {
+            latitude,
+            longitude,
             elevation: None,
         };
 
-        println!(
-            "{BASE_URL}rstt/oneday?date={date}&coords={},{}&tz={}&dst=true",
-            location.latitude, location.longitude, offset_hours
+        // The 21st in Tokyo while it's still the evening of the 20th in Europe
+        let tokyo_morning = at("Asia/Tokyo", 7);
+        assert_eq!(
+            AADProvider::new().build_url(&place(35.68, 139.69), tokyo_morning),
+            format!("{BASE_URL}rstt/oneday?date=2024-06-21&coords=35.68,139.69&tz=9&dst=false")
+        );
+        assert_eq!(
+            AADProvider::new().build_url(&place(28.61, 77.21), at("Asia/Kolkata", 12)),
+            format!("{BASE_URL}rstt/oneday?date=2024-06-21&coords=28.61,77.21&tz=5.5&dst=false")
+        );
+        assert_eq!(
+            AADProvider::new().build_url(&place(40.71, -74.01), at("America/New_York", 12)),
+            format!("{BASE_URL}rstt/oneday?date=2024-06-21&coords=40.71,-74.01&tz=-4&dst=false")
         );
     }
 
@@ -250,4 +258,29 @@ mod test {
         let step = (0.15f64 * 8.0).round() as usize % 8;
         println!("{}", step);
     }
+
+    #[test]
+    fn moon_phase_from_illumination() {
+        let phase = |curphase: &str, fracillum: &str| {
+            AADProvider::moon_phase(&serde_json::json!({
+                "curphase": curphase,
+                "fracillum": fracillum,
+            }))
+            .unwrap()
+        };
+
+        assert!((phase("First Quarter", "50%") - 0.25).abs() < 1e-9);
+        assert!((phase("Last Quarter", "50%") - 0.75).abs() < 1e-9);
+        assert!((phase("Full Moon", "100%") - 0.5).abs() < 1e-9);
+        assert!(phase("New Moon", "0%") < 1e-9);
+
+        let waxing = phase("Waxing Gibbous", "87%");
+        let waning = phase("Waning Gibbous", "87%");
+        assert!(waxing > 0.25 && waxing < 0.5);
+        assert!((waxing + waning - 1.0).abs() < 1e-9);
+
+        // Older responses without the fraction fall back to the phase name
+        let named = AADProvider::moon_phase(&serde_json::json!({"curphase": "Waning Crescent"}));
+        assert_eq!(named, Some(0.85));
+    }
 }
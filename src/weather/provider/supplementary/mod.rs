@@ -6,11 +6,14 @@ use crate::{
 };
 
 pub mod aad;
+pub mod registry;
+
+pub use registry::SupplementaryRegistry;
 
 #[async_trait]
 /// This trait is used supplement a weather provider if it cannot by itself provide all data for `WeatherProviderResponse`
 /// An Example would be the Met Office doesn't give Sun & Moon information
-pub trait SupplementaryWeatherProvider {
+pub trait SupplementaryWeatherProvider: Send + Sync {
     async fn get_supplementary_weather(
         &self,
         location: &WeatherLocation,
@@ -18,13 +21,30 @@ pub trait SupplementaryWeatherProvider {
         wanted: SupplementaryProviderRequest,
     ) -> Result<SupplementaryProviderResponse, WeatherError>;
 
-    #[allow(unused)]
     fn get_attribution(&self) -> &'static str;
 
-    #[allow(unused)] // I want to have a way for sup-providers to add their own capabilites to a list for mix&matching if a sup-provider is unavailable
+    /// Requests this provider can answer, used by `SupplementaryRegistry`
+    /// to route missing fields.
     fn capabilities(&self) -> Vec<SupplementaryProviderRequest>;
 }
 
+/// A `WeatherProviderResponse` field a supplementary provider can fill in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupplementaryField {
+    Sun,
+    MoonPhase,
+}
+
+impl SupplementaryProviderRequest {
+    /// Fields the response to this request carries.
+    pub fn fields(&self) -> &'static [SupplementaryField] {
+        match self {
+            Self::PhasesOfMoon => &[SupplementaryField::MoonPhase],
+            Self::SunAndMoonForOneDay => &[SupplementaryField::Sun, SupplementaryField::MoonPhase],
+        }
+    }
+}
+
 /// Helper macro - TODO: Remove `#[allow(dead_code)]`
 macro_rules! provider_enums {
     (
@@ -33,6 +53,7 @@ macro_rules! provider_enums {
             $payload:tt
         ),* $(,)?
     ) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         pub enum SupplementaryProviderRequest {
             #[allow(dead_code)]
             $(
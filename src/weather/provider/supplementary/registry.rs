@@ -0,0 +1,221 @@
+use crate::weather::{
+    WeatherLocation, WeatherUnits,
+    astro::AstroProvider,
+    provider::{
+        WeatherProviderResponse,
+        supplementary::{
+            SupplementaryField, SupplementaryProviderRequest, SupplementaryProviderResponse,
+            SupplementaryWeatherProvider, aad::AADProvider,
+        },
+    },
+};
+
+/// Routes missing `WeatherProviderResponse` fields to the supplementary
+/// providers able to fill them, in registration order.
+pub struct SupplementaryRegistry {
+    providers: Vec<Box<dyn SupplementaryWeatherProvider>>,
+}
+
+impl Default for SupplementaryRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SupplementaryRegistry {
+    /// A registry with every built-in supplementary provider, offline
+    /// calculations first.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Box::new(AstroProvider));
+        registry.register(Box::new(AADProvider::new()));
+        registry
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            providers: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, provider: Box<dyn SupplementaryWeatherProvider>) {
+        self.providers.push(provider);
+    }
+
+    /// Fills `missing` fields of `response`. For each provider the request
+    /// covering the most still-missing fields is tried; fields a provider
+    /// fails to deliver fall through to the next one. Returns the
+    /// attributions of the providers that contributed.
+    pub async fn fill(
+        &self,
+        location: &WeatherLocation,
+        units: &WeatherUnits,
+        response: &mut WeatherProviderResponse,
+        missing: &[SupplementaryField],
+    ) -> Vec<&'static str> {
+        let mut missing = missing.to_vec();
+        let mut attributions = Vec::new();
+
+        for provider in &self.providers {
+            if missing.is_empty() {
+                break;
+            }
+
+            let Some(request) = best_request(&provider.capabilities(), &missing) else {
+                continue;
+            };
+
+            let Ok(supplement) = provider
+                .get_supplementary_weather(location, units, request)
+                .await
+            else {
+                continue;
+            };
+
+            let filled = merge(response, supplement, &missing);
+            if !filled.is_empty() {
+                missing.retain(|field| !filled.contains(field));
+                let attribution = provider.get_attribution();
+                if !attribution.is_empty() {
+                    attributions.push(attribution);
+                }
+            }
+        }
+
+        attributions
+    }
+}
+
+fn best_request(
+    capabilities: &[SupplementaryProviderRequest],
+    missing: &[SupplementaryField],
+) -> Option<SupplementaryProviderRequest> {
+    capabilities
+        .iter()
+        .map(|request| {
+            let covered = request
+                .fields()
+                .iter()
+                .filter(|field| missing.contains(field))
+                .count();
+            (*request, covered)
+        })
+        .filter(|(_, covered)| *covered > 0)
+        .max_by_key(|(_, covered)| *covered)
+        .map(|(request, _)| request)
+}
+
+/// Copies the wanted fields out of `supplement`, returning those set.
+fn merge(
+    response: &mut WeatherProviderResponse,
+    supplement: SupplementaryProviderResponse,
+    wanted: &[SupplementaryField],
+) -> Vec<SupplementaryField> {
+    let (sun, moon_phase) = match supplement {
+        SupplementaryProviderResponse::PhasesOfMoon(phase) => (None, phase),
+        SupplementaryProviderResponse::SunAndMoonForOneDay { sun, moon_phase } => {
+            (Some(sun), moon_phase)
+        }
+    };
+
+    let mut filled = Vec::new();
+    if let Some(sun) = sun
+        && wanted.contains(&SupplementaryField::Sun)
+    {
+        response.sun = sun;
+        filled.push(SupplementaryField::Sun);
+    }
+    if let Some(phase) = moon_phase
+        && wanted.contains(&SupplementaryField::MoonPhase)
+    {
+        response.moon_phase = Some(phase);
+        filled.push(SupplementaryField::MoonPhase);
+    }
+
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{DataError, WeatherError};
+    use async_trait::async_trait;
+
+    struct MoonOnly(Result<f64, ()>);
+
+    #[async_trait]
+    impl SupplementaryWeatherProvider for MoonOnly {
+        async fn get_supplementary_weather(
+            &self,
+            _location: &WeatherLocation,
+            _units: &WeatherUnits,
+            _wanted: SupplementaryProviderRequest,
+        ) -> Result<SupplementaryProviderResponse, WeatherError> {
+            self.0
+                .map(|phase| SupplementaryProviderResponse::PhasesOfMoon(Some(phase)))
+                .map_err(|_| WeatherError::Data(DataError::NoData))
+        }
+
+        fn get_attribution(&self) -> &'static str {
+            "Moon"
+        }
+
+        fn capabilities(&self) -> Vec<SupplementaryProviderRequest> {
+            vec![SupplementaryProviderRequest::PhasesOfMoon]
+        }
+    }
+
+    fn response() -> WeatherProviderResponse {
+        WeatherProviderResponse::sample(0)
+    }
+
+    fn location() -> WeatherLocation {
+        WeatherLocation {
+            latitude: 51.5,
+            longitude: -0.1,
+            elevation: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_provider_falls_through_to_next() {
+        let mut registry = SupplementaryRegistry::empty();
+        registry.register(Box::new(MoonOnly(Err(()))));
+        registry.register(Box::new(MoonOnly(Ok(0.25))));
+
+        let mut response = response();
+        let attributions = registry
+            .fill(
+                &location(),
+                &WeatherUnits::metric(),
+                &mut response,
+                &[SupplementaryField::MoonPhase],
+            )
+            .await;
+
+        assert_eq!(response.moon_phase, Some(0.25));
+        assert_eq!(attributions, vec!["Moon"]);
+    }
+
+    #[test]
+    fn test_best_request_covers_most_missing_fields() {
+        let capabilities = [
+            SupplementaryProviderRequest::PhasesOfMoon,
+            SupplementaryProviderRequest::SunAndMoonForOneDay,
+        ];
+        assert_eq!(
+            best_request(
+                &capabilities,
+                &[SupplementaryField::Sun, SupplementaryField::MoonPhase]
+            ),
+            Some(SupplementaryProviderRequest::SunAndMoonForOneDay)
+        );
+        assert_eq!(
+            best_request(
+                &[SupplementaryProviderRequest::PhasesOfMoon],
+                &[SupplementaryField::Sun]
+            ),
+            None
+        );
+    }
+}
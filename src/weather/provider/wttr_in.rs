@@ -0,0 +1,279 @@
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::http::{self, DEFAULT_TIMEOUT_SECS, RequestOptions};
+use crate::weather::astro;
+use crate::weather::provider::{WeatherProvider, WeatherProviderResponse};
+use crate::weather::timezone::TimeZoneLookup;
+use crate::weather::types::{TemperatureRange, WeatherDetails, WeatherLocation, WeatherUnits};
+use crate::weather::units::kmh_to_ms;
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use serde::Deserialize;
+
+const WTTR_IN_BASE_URL: &str = "https://wttr.in";
+
+/// Keyless last resort, tried when the configured provider and Open-Meteo
+/// both fail.
+pub struct WttrInProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WttrInResponse {
+    current_condition: Vec<WttrInCondition>,
+    /// Today first, then the next two days.
+    #[serde(default)]
+    weather: Vec<WttrInDay>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WttrInDay {
+    #[serde(rename = "mintempC", default)]
+    min_temp_c: String,
+    #[serde(rename = "maxtempC", default)]
+    max_temp_c: String,
+}
+
+/// Every value in the `j1` format is a string.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WttrInCondition {
+    #[serde(rename = "temp_C")]
+    temp_c: String,
+    weather_code: String,
+    #[serde(rename = "precipMM", default)]
+    precip_mm: String,
+    #[serde(rename = "windspeedKmph", default)]
+    windspeed_kmph: String,
+    #[serde(rename = "winddirDegree", default)]
+    winddir_degree: String,
+    #[serde(default)]
+    pressure: String,
+    #[serde(default)]
+    humidity: String,
+    #[serde(rename = "FeelsLikeC", default)]
+    feels_like_c: String,
+    #[serde(default)]
+    uv_index: String,
+    /// In kilometres.
+    #[serde(default)]
+    visibility: String,
+    /// Observation time at the location, e.g. "2024-01-15 06:00 PM".
+    #[serde(default)]
+    local_obs_date_time: String,
+}
+
+impl Default for WttrInProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WttrInProvider {
+    pub fn new() -> Self {
+        let client = RequestOptions::default()
+            .client()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            base_url: WTTR_IN_BASE_URL.to_string(),
+        }
+    }
+
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        format!(
+            "{}/{:.4},{:.4}?format=j1",
+            self.base_url, location.latitude, location.longitude
+        )
+    }
+
+    /// Maps a World Weather Online condition code, which wttr.in passes
+    /// through, onto a WMO weather code.
+    /// See: https://www.worldweatheronline.com/weather-api/api/docs/weather-icons.aspx
+    fn condition_to_wmo(code: u16) -> i32 {
+        match code {
+            113 => 0,
+            116 => 2,
+            119 | 122 => 3,
+            143 | 248 => 45,
+            260 => 48,
+            176 | 293 | 296 => 61,
+            263 | 266 => 51,
+            185 | 281 => 56,
+            284 => 57,
+            299 | 302 => 63,
+            305 | 308 => 65,
+            182 | 311 | 317 => 66,
+            314 | 320 => 67,
+            179 | 323 | 326 => 71,
+            227 | 329 | 332 => 73,
+            230 | 335 | 338 => 75,
+            350 | 374 | 377 => 77,
+            353 => 80,
+            356 | 362 => 81,
+            359 | 365 => 82,
+            368 => 85,
+            371 => 86,
+            200 | 386 | 389 | 392 | 395 => 95,
+            _ => 0,
+        }
+    }
+
+    /// The response as of `now` on the clock at `location`.
+    fn to_response(
+        &self,
+        data: WttrInResponse,
+        location: &WeatherLocation,
+        now: DateTime<FixedOffset>,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let current = data
+            .current_condition
+            .into_iter()
+            .next()
+            .ok_or(WeatherError::Data(DataError::NoData))?;
+
+        let number = |value: &str| value.trim().parse::<f64>().ok();
+        let temperature = number(&current.temp_c).ok_or_else(|| {
+            WeatherError::Data(DataError::BadData(format!(
+                "invalid temperature {:?}",
+                current.temp_c
+            )))
+        })?;
+        let code = current.weather_code.trim().parse().unwrap_or(113);
+
+        let timestamp =
+            NaiveDateTime::parse_from_str(&current.local_obs_date_time, "%Y-%m-%d %I:%M %p")
+                .unwrap_or_else(|_| now.naive_local());
+
+        Ok(WeatherProviderResponse {
+            weather_code: Self::condition_to_wmo(code),
+            temperature,
+            precipitation: number(&current.precip_mm).unwrap_or(0.0),
+            wind_speed: kmh_to_ms(number(&current.windspeed_kmph).unwrap_or(0.0)),
+            wind_direction: number(&current.winddir_degree).unwrap_or(0.0),
+            sun: astro::celestial_events(location.latitude, location.longitude, now),
+            moon_phase: Some(astro::moon_phase(now.with_timezone(&Utc))),
+            pressure: number(&current.pressure),
+            disagreement: None,
+            humidity: number(&current.humidity),
+            apparent_temperature: number(&current.feels_like_c),
+            uv_index: number(&current.uv_index),
+            source: "wttr".to_string(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: data.weather.first().and_then(|day| {
+                Some(TemperatureRange {
+                    min: number(&day.min_temp_c)?,
+                    max: number(&day.max_temp_c)?,
+                })
+            }),
+            details: WeatherDetails {
+                visibility: number(&current.visibility).map(|km| km * 1000.0),
+                ..WeatherDetails::default()
+            },
+            timestamp: timestamp.format("%Y-%m-%dT%H:%M").to_string(),
+            attribution: self.get_attribution().to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for WttrInProvider {
+    fn get_attribution(&self) -> &'static str {
+        "Weather data by wttr.in"
+    }
+
+    async fn get_current_weather(
+        &self,
+        location: &WeatherLocation,
+        _units: &WeatherUnits,
+    ) -> Result<WeatherProviderResponse, WeatherError> {
+        let url = self.build_url(location);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+            .and_then(|resp| http::error_for_status(resp, &url, DEFAULT_TIMEOUT_SECS))
+            .map_err(WeatherError::Network)?;
+
+        let data: WttrInResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+        })?;
+
+        let now = TimeZoneLookup::shared().clock_at(location).await.now();
+        self.to_response(data, location, now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+    use crate::weather::normalizer::WeatherNormalizer;
+
+    #[test]
+    fn test_condition_mapping() {
+        let cases = [
+            (113, WeatherCondition::Clear),
+            (122, WeatherCondition::Overcast),
+            (248, WeatherCondition::Fog),
+            (308, WeatherCondition::HeavyRain),
+            (326, WeatherCondition::LightSnow),
+            (332, WeatherCondition::Snow),
+            (356, WeatherCondition::RainShowers),
+            (389, WeatherCondition::Thunderstorm),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(
+                WeatherNormalizer::wmo_code_to_condition(WttrInProvider::condition_to_wmo(code)),
+                expected,
+                "code {}",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_response_conversion() {
+        let json = r#"{
+            "current_condition": [{
+                "FeelsLikeC": "1",
+                "localObsDateTime": "2024-01-15 06:00 PM",
+                "precipMM": "0.3",
+                "pressure": "1008",
+                "temp_C": "4",
+                "weatherCode": "296",
+                "weatherDesc": [{"value": "Light rain"}],
+                "winddirDegree": "220",
+                "windspeedKmph": "18"
+            }],
+            "nearest_area": [],
+            "weather": [{"maxtempC": "7", "mintempC": "2"}]
+        }"#;
+        let data: WttrInResponse = serde_json::from_str(json).unwrap();
+        let location = WeatherLocation {
+            latitude: 51.5074,
+            longitude: -0.1278,
+            elevation: None,
+        };
+        let response = WttrInProvider::new()
+            .to_response(data, &location, Utc::now().fixed_offset())
+            .unwrap();
+
+        assert_eq!(response.weather_code, 61);
+        assert_eq!(response.temperature, 4.0);
+        assert_eq!(response.precipitation, 0.3);
+        assert!((response.wind_speed - 5.0).abs() < 1e-9);
+        assert_eq!(response.wind_direction, 220.0);
+        assert_eq!(response.pressure, Some(1008.0));
+        assert_eq!(response.timestamp, "2024-01-15T18:00");
+        assert_eq!(
+            response.today,
+            Some(TemperatureRange { min: 2.0, max: 7.0 })
+        );
+    }
+}
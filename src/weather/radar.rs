@@ -0,0 +1,356 @@
+//! A coarse precipitation map around the location, built from Open-Meteo's
+//! 15-minutely precipitation at a grid of points. Thunderstorm weather codes
+//! at the same points show how far off a storm is.
+
+use crate::error::{DataError, NetworkError, WeatherError};
+use crate::http::{DEFAULT_TIMEOUT_SECS, RequestOptions};
+use crate::weather::types::WeatherLocation;
+use serde::Deserialize;
+
+const OPEN_METEO_FORECAST_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+pub const RADAR_COLUMNS: usize = 9;
+pub const RADAR_ROWS: usize = 5;
+/// Degrees of latitude between grid rows, about 17 km.
+const GRID_SPACING: f64 = 0.15;
+/// Kilometres between neighbouring grid points, across or down.
+const GRID_KM: f64 = GRID_SPACING * 111.2;
+/// Quarter hours before the current one, so the map covers the last hour.
+const PAST_STEPS: u32 = 4;
+
+/// Glyphs from dry to heavy, indexed by [`intensity_level`].
+pub const INTENSITY_GLYPHS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+/// Precipitation at every grid point for one quarter hour.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadarFrame {
+    /// Local time, e.g. "2026-10-16T14:15".
+    pub time: String,
+    /// mm/h, row by row from the north-west corner.
+    pub cells: Vec<f64>,
+    /// Whether each cell has a thunderstorm, in the same order.
+    pub thunder: Vec<bool>,
+}
+
+impl RadarFrame {
+    pub fn cell(&self, column: usize, row: usize) -> f64 {
+        self.cells
+            .get(row * RADAR_COLUMNS + column)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Distance in km from the location to the nearest thunderstorm cell.
+    fn nearest_storm_km(&self) -> Option<f64> {
+        let (center_column, center_row) = (RADAR_COLUMNS / 2, RADAR_ROWS / 2);
+        self.thunder
+            .iter()
+            .enumerate()
+            .filter(|&(_, &thunder)| thunder)
+            .map(|(i, _)| {
+                let columns = (i % RADAR_COLUMNS).abs_diff(center_column) as f64;
+                let rows = (i / RADAR_COLUMNS).abs_diff(center_row) as f64;
+                columns.hypot(rows) * GRID_KM
+            })
+            .min_by(f64::total_cmp)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StormMovement {
+    Approaching,
+    Receding,
+    Stationary,
+}
+
+impl StormMovement {
+    pub fn label(self) -> &'static str {
+        match self {
+            StormMovement::Approaching => "approaching",
+            StormMovement::Receding => "moving away",
+            StormMovement::Stationary => "stationary",
+        }
+    }
+}
+
+/// The nearest thunderstorm on the map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Storm {
+    /// 0 when it's overhead.
+    pub distance_km: f64,
+    /// `None` when there was no storm on the map an hour ago to compare with.
+    pub movement: Option<StormMovement>,
+}
+
+/// The frames of the last hour, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadarMap {
+    pub frames: Vec<RadarFrame>,
+}
+
+impl RadarMap {
+    /// The nearest storm in the latest frame, and whether it has come
+    /// closer since the first.
+    pub fn storm(&self) -> Option<Storm> {
+        let distance_km = self.frames.last()?.nearest_storm_km()?;
+        let earlier = self.frames.first().and_then(RadarFrame::nearest_storm_km);
+        let movement = earlier.map(|earlier| match distance_km - earlier {
+            change if change <= -GRID_KM / 2.0 => StormMovement::Approaching,
+            change if change >= GRID_KM / 2.0 => StormMovement::Receding,
+            _ => StormMovement::Stationary,
+        });
+        Some(Storm {
+            distance_km,
+            movement,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PointResponse {
+    minutely_15: PointSeries,
+}
+
+#[derive(Debug, Deserialize)]
+struct PointSeries {
+    time: Vec<String>,
+    precipitation: Vec<Option<f64>>,
+    #[serde(default)]
+    weather_code: Vec<Option<u8>>,
+}
+
+pub struct OpenMeteoRadar {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl OpenMeteoRadar {
+    pub fn new() -> Self {
+        let client = RequestOptions::default()
+            .client()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            base_url: OPEN_METEO_FORECAST_URL.to_string(),
+        }
+    }
+
+    fn build_url(&self, points: &[(f64, f64)]) -> String {
+        let join = |coordinate: fn(&(f64, f64)) -> f64| {
+            points
+                .iter()
+                .map(|point| format!("{:.4}", coordinate(point)))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        format!(
+            "{}?latitude={}&longitude={}&minutely_15=precipitation,weather_code&past_minutely_15={}&forecast_minutely_15=1&timezone=auto",
+            self.base_url,
+            join(|point| point.0),
+            join(|point| point.1),
+            PAST_STEPS
+        )
+    }
+
+    /// Fetches the last hour of precipitation around `location`.
+    pub async fn fetch(&self, location: &WeatherLocation) -> Result<RadarMap, WeatherError> {
+        let url = self.build_url(&grid_points(location));
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+            })?;
+
+        let points: Vec<PointResponse> = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+        })?;
+
+        Self::to_map(points)
+    }
+
+    fn to_map(points: Vec<PointResponse>) -> Result<RadarMap, WeatherError> {
+        if points.len() != RADAR_COLUMNS * RADAR_ROWS {
+            return Err(WeatherError::Data(DataError::BadData(format!(
+                "expected {} radar points, got {}",
+                RADAR_COLUMNS * RADAR_ROWS,
+                points.len()
+            ))));
+        }
+        let times = &points[0].minutely_15.time;
+        if times.is_empty() {
+            return Err(WeatherError::Data(DataError::NoData));
+        }
+
+        let frames = times
+            .iter()
+            .enumerate()
+            .map(|(i, time)| RadarFrame {
+                time: time.clone(),
+                // Each value is the total of the quarter hour before it
+                cells: points
+                    .iter()
+                    .map(|point| {
+                        point
+                            .minutely_15
+                            .precipitation
+                            .get(i)
+                            .copied()
+                            .flatten()
+                            .unwrap_or(0.0)
+                            * 4.0
+                    })
+                    .collect(),
+                thunder: points
+                    .iter()
+                    .map(|point| {
+                        point
+                            .minutely_15
+                            .weather_code
+                            .get(i)
+                            .copied()
+                            .flatten()
+                            .is_some_and(|code| (95..=99).contains(&code))
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(RadarMap { frames })
+    }
+}
+
+impl Default for OpenMeteoRadar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Grid points as (latitude, longitude), row by row from the north-west
+/// corner, with `location` in the middle. Longitude steps widen with
+/// latitude so the cells stay roughly square.
+fn grid_points(location: &WeatherLocation) -> Vec<(f64, f64)> {
+    let lon_spacing = GRID_SPACING / location.latitude.to_radians().cos().max(0.1);
+    let (half_columns, half_rows) = ((RADAR_COLUMNS / 2) as f64, (RADAR_ROWS / 2) as f64);
+
+    let mut points = Vec::with_capacity(RADAR_COLUMNS * RADAR_ROWS);
+    for row in 0..RADAR_ROWS {
+        let latitude = location.latitude + (half_rows - row as f64) * GRID_SPACING;
+        for column in 0..RADAR_COLUMNS {
+            let longitude = location.longitude + (column as f64 - half_columns) * lon_spacing;
+            points.push((latitude.clamp(-90.0, 90.0), wrap_longitude(longitude)));
+        }
+    }
+    points
+}
+
+fn wrap_longitude(longitude: f64) -> f64 {
+    (longitude + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Index into [`INTENSITY_GLYPHS`] for a rate in mm/h.
+pub fn intensity_level(mm_per_hour: f64) -> usize {
+    match mm_per_hour {
+        r if r < 0.1 => 0,
+        r if r < 1.0 => 1,
+        r if r < 4.0 => 2,
+        r if r < 10.0 => 3,
+        _ => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_centers_on_location() {
+        let location = WeatherLocation {
+            latitude: 60.0,
+            longitude: 179.9,
+            elevation: None,
+        };
+        let points = grid_points(&location);
+        assert_eq!(points.len(), RADAR_COLUMNS * RADAR_ROWS);
+
+        let center = points[(RADAR_ROWS / 2) * RADAR_COLUMNS + RADAR_COLUMNS / 2];
+        assert!((center.0 - 60.0).abs() < 1e-9);
+        assert!((center.1 - 179.9).abs() < 1e-9);
+        // North first, and twice the longitude step at 60°
+        assert!((points[0].0 - 60.3).abs() < 1e-9);
+        assert!((points[1].1 - points[0].1 - 0.3).abs() < 1e-9);
+        // East of the antimeridian wraps round
+        assert!(points[RADAR_COLUMNS - 1].1 < -178.0);
+    }
+
+    #[test]
+    fn test_to_map_scales_quarter_hours_to_rates() {
+        let point = |rain: Option<f64>| PointResponse {
+            minutely_15: PointSeries {
+                time: vec!["2026-10-16T14:00".into(), "2026-10-16T14:15".into()],
+                precipitation: vec![Some(0.0), rain],
+                weather_code: Vec::new(),
+            },
+        };
+        let mut points: Vec<_> = (0..RADAR_COLUMNS * RADAR_ROWS - 1)
+            .map(|_| point(None))
+            .collect();
+        points.push(point(Some(0.5)));
+
+        let map = OpenMeteoRadar::to_map(points).unwrap();
+        assert_eq!(map.frames.len(), 2);
+        assert_eq!(map.frames[1].time, "2026-10-16T14:15");
+        assert_eq!(map.frames[1].cell(RADAR_COLUMNS - 1, RADAR_ROWS - 1), 2.0);
+        assert_eq!(map.frames[1].cell(0, 0), 0.0);
+
+        assert!(OpenMeteoRadar::to_map(vec![point(None)]).is_err());
+    }
+
+    #[test]
+    fn test_storm_distance_and_movement() {
+        let frame = |storm: Option<(usize, usize)>| {
+            let mut thunder = vec![false; RADAR_COLUMNS * RADAR_ROWS];
+            if let Some((column, row)) = storm {
+                thunder[row * RADAR_COLUMNS + column] = true;
+            }
+            RadarFrame {
+                time: String::new(),
+                cells: Vec::new(),
+                thunder,
+            }
+        };
+
+        // From the western edge to two cells west of the location
+        let map = RadarMap {
+            frames: vec![frame(Some((0, 2))), frame(Some((2, 2)))],
+        };
+        let storm = map.storm().unwrap();
+        assert!((storm.distance_km - 2.0 * GRID_KM).abs() < 1e-9);
+        assert_eq!(storm.movement, Some(StormMovement::Approaching));
+
+        let map = RadarMap {
+            frames: vec![frame(None), frame(Some((4, 2)))],
+        };
+        let storm = map.storm().unwrap();
+        assert_eq!(storm.distance_km, 0.0);
+        assert_eq!(storm.movement, None);
+
+        let map = RadarMap {
+            frames: vec![frame(Some((4, 1))), frame(None)],
+        };
+        assert_eq!(map.storm(), None);
+    }
+
+    #[test]
+    fn test_intensity_levels() {
+        assert_eq!(INTENSITY_GLYPHS[intensity_level(0.0)], '·');
+        assert_eq!(INTENSITY_GLYPHS[intensity_level(0.5)], '░');
+        assert_eq!(INTENSITY_GLYPHS[intensity_level(2.5)], '▒');
+        assert_eq!(INTENSITY_GLYPHS[intensity_level(7.5)], '▓');
+        assert_eq!(INTENSITY_GLYPHS[intensity_level(25.0)], '█');
+    }
+}
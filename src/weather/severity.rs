@@ -0,0 +1,145 @@
+use crate::weather::types::{WeatherCondition, WeatherData};
+use chrono::NaiveDateTime;
+use std::time::Duration;
+
+/// Gale force (Beaufort 8) and up, in m/s.
+const GALE_MS: f64 = 17.2;
+/// Storm force (Beaufort 10) and up, in m/s.
+const STORM_MS: f64 = 24.5;
+
+/// How dangerous the current weather is, for the border drawn around the
+/// screen: yellow for a watch, red for a warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Watch,
+    Warning,
+}
+
+impl Severity {
+    /// `None` for everyday weather.
+    pub fn of(weather: &WeatherData) -> Option<Self> {
+        let by_condition = Self::of_condition(weather.condition);
+        let by_wind = if weather.wind_speed >= STORM_MS {
+            Some(Self::Warning)
+        } else if weather.wind_speed >= GALE_MS {
+            Some(Self::Watch)
+        } else {
+            None
+        };
+        by_condition.max(by_wind)
+    }
+
+    fn of_condition(condition: WeatherCondition) -> Option<Self> {
+        match condition {
+            WeatherCondition::ThunderstormHail
+            | WeatherCondition::ThunderstormHeavyHail
+            | WeatherCondition::ViolentRainShowers
+            | WeatherCondition::HeavyFreezingRain => Some(Self::Warning),
+            WeatherCondition::Thunderstorm
+            | WeatherCondition::FreezingRain
+            | WeatherCondition::HeavySnow
+            | WeatherCondition::HeavySnowShowers => Some(Self::Watch),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Watch => "watch",
+            Self::Warning => "warning",
+        }
+    }
+
+    /// How often to fetch in storm watch mode, the worse the weather the
+    /// sooner.
+    pub fn poll_interval(self) -> Duration {
+        match self {
+            Self::Watch => Duration::from_secs(120),
+            Self::Warning => Duration::from_secs(60),
+        }
+    }
+}
+
+/// When the hourly forecast has the severe weather easing: the first hour
+/// after the reading without it, in the location's local time. `None` when
+/// it's the wind that makes it severe, which the hourly forecast lacks, or
+/// when it lasts beyond the forecast.
+pub fn easing_at(weather: &WeatherData) -> Option<NaiveDateTime> {
+    Severity::of_condition(weather.condition)?;
+    let now = weather.timestamp.get(..13)?;
+    weather
+        .hourly
+        .iter()
+        .filter(|hour| hour.time.get(..13).is_some_and(|hour| hour > now))
+        .find(|hour| Severity::of_condition(hour.condition).is_none())
+        .and_then(|hour| NaiveDateTime::parse_from_str(hour.time.get(..16)?, "%Y-%m-%dT%H:%M").ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::types::HourlyForecast;
+
+    fn weather(condition: WeatherCondition, wind_speed: f64) -> WeatherData {
+        WeatherData {
+            temperature: 15.0,
+            wind_speed,
+            ..WeatherData::sample(condition)
+        }
+    }
+
+    #[test]
+    fn test_everyday_weather_is_not_severe() {
+        assert_eq!(Severity::of(&weather(WeatherCondition::Rain, 8.0)), None);
+    }
+
+    #[test]
+    fn test_severe_conditions() {
+        assert_eq!(
+            Severity::of(&weather(WeatherCondition::Thunderstorm, 3.0)),
+            Some(Severity::Watch)
+        );
+        assert_eq!(
+            Severity::of(&weather(WeatherCondition::ThunderstormHail, 3.0)),
+            Some(Severity::Warning)
+        );
+    }
+
+    #[test]
+    fn test_easing_from_the_hourly_forecast() {
+        let hour = |time: &str, condition| HourlyForecast {
+            time: time.to_string(),
+            condition,
+            temperature: 15.0,
+            precipitation_probability: None,
+            humidity: None,
+        };
+        let mut storm = weather(WeatherCondition::Thunderstorm, 3.0);
+        storm.timestamp = "2026-07-01T15:20".to_string();
+        storm.hourly = vec![
+            hour("2026-07-01T15:00", WeatherCondition::Rain),
+            hour("2026-07-01T16:00", WeatherCondition::ThunderstormHail),
+            hour("2026-07-01T17:00", WeatherCondition::Rain),
+        ];
+        assert_eq!(
+            easing_at(&storm).unwrap().format("%H:%M").to_string(),
+            "17:00"
+        );
+
+        storm.hourly.truncate(2);
+        assert_eq!(easing_at(&storm), None);
+        assert_eq!(easing_at(&weather(WeatherCondition::Clear, 30.0)), None);
+    }
+
+    #[test]
+    fn test_strong_wind_raises_severity() {
+        assert_eq!(
+            Severity::of(&weather(WeatherCondition::Clear, 18.0)),
+            Some(Severity::Watch)
+        );
+        assert_eq!(
+            Severity::of(&weather(WeatherCondition::Thunderstorm, 26.0)),
+            Some(Severity::Warning)
+        );
+    }
+}
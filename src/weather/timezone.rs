@@ -0,0 +1,147 @@
+//! The IANA time zone at a place, from the Open-Meteo API, for weather from
+//! providers that don't report one.
+
+use crate::error::{NetworkError, WeatherError};
+use crate::http::{DEFAULT_TIMEOUT_SECS, RequestOptions};
+use crate::scheduler::Scheduler;
+use crate::weather::types::WeatherLocation;
+use serde::Deserialize;
+use std::sync::OnceLock;
+use tokio::sync::RwLock;
+
+const OPEN_METEO_URL: &str = "https://api.open-meteo.com/v1/forecast";
+/// Places remembered, enough for the panes of `weathr compare`.
+const MAX_PLACES: usize = 8;
+
+static SHARED: OnceLock<TimeZoneLookup> = OnceLock::new();
+
+#[derive(Debug, Deserialize)]
+struct ZoneResponse {
+    timezone: String,
+}
+
+/// Looks the zone up once per place; a failed lookup is tried again next
+/// time.
+pub struct TimeZoneLookup {
+    client: reqwest::Client,
+    base_url: String,
+    /// Most recently found last.
+    found: RwLock<Vec<((f64, f64), String)>>,
+}
+
+impl TimeZoneLookup {
+    pub fn new() -> Self {
+        let client = RequestOptions::default()
+            .client()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            base_url: OPEN_METEO_URL.to_string(),
+            found: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// The one lookup the weather client and the providers share, so a
+    /// place is only looked up once.
+    pub fn shared() -> &'static Self {
+        SHARED.get_or_init(Self::new)
+    }
+
+    /// No weather is asked for, so the reply is just the place's details.
+    fn build_url(&self, location: &WeatherLocation) -> String {
+        format!(
+            "{}?latitude={}&longitude={}&timezone=auto",
+            self.base_url, location.latitude, location.longitude
+        )
+    }
+
+    /// The zone at `location`, e.g. "Asia/Tokyo", or `None` when it can't
+    /// be looked up.
+    pub async fn at(&self, location: &WeatherLocation) -> Option<String> {
+        let place = (location.latitude, location.longitude);
+        if let Some((_, zone)) = self
+            .found
+            .read()
+            .await
+            .iter()
+            .find(|(found_for, _)| *found_for == place)
+        {
+            return Some(zone.clone());
+        }
+
+        let zone = self.fetch(location).await.ok()?;
+        let mut found = self.found.write().await;
+        if found.len() >= MAX_PLACES {
+            found.remove(0);
+        }
+        found.push((place, zone.clone()));
+        Some(zone)
+    }
+
+    /// The clock at `location`, or the system's when its zone can't be
+    /// looked up.
+    pub async fn clock_at(&self, location: &WeatherLocation) -> Scheduler {
+        self.at(location)
+            .await
+            .map_or_else(Scheduler::default, |zone| Scheduler::for_zone(&zone))
+    }
+
+    async fn fetch(&self, location: &WeatherLocation) -> Result<String, WeatherError> {
+        let url = self.build_url(location);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+            })?;
+
+        let data: ZoneResponse = response.json().await.map_err(|e| {
+            WeatherError::Network(NetworkError::from_reqwest(e, &url, DEFAULT_TIMEOUT_SECS))
+        })?;
+        Ok(data.timezone)
+    }
+}
+
+impl Default for TimeZoneLookup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zone_parsing() {
+        let json = r#"{"latitude": 35.7, "longitude": 139.6875, "generationtime_ms": 0.02,
+            "utc_offset_seconds": 32400, "timezone": "Asia/Tokyo",
+            "timezone_abbreviation": "GMT+9", "elevation": 40.0}"#;
+        let data: ZoneResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(data.timezone, "Asia/Tokyo");
+    }
+
+    #[tokio::test]
+    async fn test_found_zone_is_kept_for_the_place() {
+        let lookup = TimeZoneLookup::new();
+        let tokyo = WeatherLocation {
+            latitude: 35.68,
+            longitude: 139.69,
+            elevation: None,
+        };
+        lookup
+            .found
+            .write()
+            .await
+            .push(((35.68, 139.69), "Asia/Tokyo".to_string()));
+        assert_eq!(lookup.at(&tokyo).await.as_deref(), Some("Asia/Tokyo"));
+        assert_eq!(
+            lookup.clock_at(&tokyo).await,
+            Scheduler::for_zone("Asia/Tokyo")
+        );
+    }
+}
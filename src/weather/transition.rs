@@ -0,0 +1,208 @@
+use crate::weather::types::{WeatherCondition, WeatherData};
+
+/// Steps from a clear sky up to `condition`, passing through the milder
+/// conditions an observer would see on the way.
+fn ladder(condition: WeatherCondition) -> Vec<WeatherCondition> {
+    use WeatherCondition::*;
+
+    let dry = [Clear, PartlyCloudy, Cloudy, Overcast];
+    let (base, extra): (&[WeatherCondition], &[WeatherCondition]) = match condition {
+        Clear => (&dry[..1], &[]),
+        MainlyClear => (&dry[..1], &[MainlyClear]),
+        PartlyCloudy => (&dry[..2], &[]),
+        Cloudy => (&dry[..3], &[]),
+        Overcast => (&dry, &[]),
+        Fog => (&dry[..3], &[Fog]),
+        RimeFog => (&dry[..3], &[Fog, RimeFog]),
+        LightDrizzle => (&dry, &[LightDrizzle]),
+        Drizzle => (&dry, &[Drizzle]),
+        DenseDrizzle => (&dry, &[Drizzle, DenseDrizzle]),
+        FreezingDrizzle => (&dry, &[LightDrizzle, FreezingDrizzle]),
+        DenseFreezingDrizzle => (&dry, &[LightDrizzle, FreezingDrizzle, DenseFreezingDrizzle]),
+        LightRain => (&dry, &[Drizzle, LightRain]),
+        Rain => (&dry, &[Drizzle, Rain]),
+        HeavyRain => (&dry, &[Drizzle, Rain, HeavyRain]),
+        RainShowers => (&dry, &[Drizzle, RainShowers]),
+        ViolentRainShowers => (&dry, &[Drizzle, RainShowers, ViolentRainShowers]),
+        FreezingRain => (&dry, &[Drizzle, FreezingRain]),
+        HeavyFreezingRain => (&dry, &[Drizzle, FreezingRain, HeavyFreezingRain]),
+        Thunderstorm => (&dry, &[Drizzle, Rain, Thunderstorm]),
+        ThunderstormHail => (&dry, &[Drizzle, Rain, Thunderstorm, ThunderstormHail]),
+        ThunderstormHeavyHail => (
+            &dry,
+            &[
+                Drizzle,
+                Rain,
+                Thunderstorm,
+                ThunderstormHail,
+                ThunderstormHeavyHail,
+            ],
+        ),
+        SnowGrains => (&dry, &[SnowGrains]),
+        LightSnow => (&dry, &[SnowGrains, LightSnow]),
+        SnowShowers => (&dry, &[SnowGrains, SnowShowers]),
+        HeavySnowShowers => (&dry, &[SnowGrains, SnowShowers, HeavySnowShowers]),
+        Snow => (&dry, &[SnowGrains, SnowShowers, Snow]),
+        HeavySnow => (&dry, &[SnowGrains, SnowShowers, Snow, HeavySnow]),
+    };
+
+    base.iter().chain(extra).copied().collect()
+}
+
+/// Conditions visited when weather changes from `from` to `to`: winding
+/// `from` down to the last state both share, then building up to `to`.
+pub fn transition_path(from: WeatherCondition, to: WeatherCondition) -> Vec<WeatherCondition> {
+    let down = ladder(from);
+    let up = ladder(to);
+    let shared = down
+        .iter()
+        .zip(&up)
+        .take_while(|(a, b)| a == b)
+        .count()
+        .max(1);
+
+    let mut path: Vec<_> = down[shared - 1..].iter().rev().copied().collect();
+    path.extend_from_slice(&up[shared..]);
+    path
+}
+
+/// A scripted change between two weather states.
+pub struct Transition {
+    path: Vec<WeatherCondition>,
+    from: WeatherData,
+    to: WeatherData,
+}
+
+impl Transition {
+    pub fn new(from: WeatherData, to: WeatherData) -> Self {
+        Self {
+            path: transition_path(from.condition, to.condition),
+            from,
+            to,
+        }
+    }
+
+    /// Weather at `progress` (0.0 to 1.0) through the transition. Numeric
+    /// fields are interpolated, directions and the moon's phase the short
+    /// way round; the condition steps along the path, and day turns to night
+    /// (or back) halfway, for the scene to fade its lighting across.
+    pub fn at(&self, progress: f64) -> WeatherData {
+        let progress = progress.clamp(0.0, 1.0);
+        let lerp = |a: f64, b: f64| a + (b - a) * progress;
+        let lerp_round = |a: f64, b: f64, full: f64| {
+            let shortest = (b - a + full * 1.5).rem_euclid(full) - full / 2.0;
+            (a + shortest * progress).rem_euclid(full)
+        };
+        let ahead = if progress < 0.5 { &self.from } else { &self.to };
+        let step = (progress * (self.path.len() - 1) as f64).round() as usize;
+
+        WeatherData {
+            condition: self.path[step],
+            temperature: lerp(self.from.temperature, self.to.temperature),
+            precipitation: lerp(self.from.precipitation, self.to.precipitation),
+            wind_speed: lerp(self.from.wind_speed, self.to.wind_speed),
+            wind_direction: lerp_round(self.from.wind_direction, self.to.wind_direction, 360.0),
+            sun: ahead.sun,
+            moon_phase: match (self.from.moon_phase, self.to.moon_phase) {
+                (Some(from), Some(to)) => Some(lerp_round(from, to, 1.0)),
+                (_, to) => to,
+            },
+            pressure: match (self.from.pressure, self.to.pressure) {
+                (Some(from), Some(to)) => Some(lerp(from, to)),
+                (_, to) => to,
+            },
+            disagreement: self.to.disagreement,
+            humidity: self.to.humidity,
+            // Worked out from the blended temperature instead
+            apparent_temperature: None,
+            uv_index: self.to.uv_index,
+            source: self.to.source.clone(),
+            nowcast: self.to.nowcast.clone(),
+            hourly: self.to.hourly.clone(),
+            today: self.to.today,
+            details: self.to.details.clone(),
+            timestamp: self.to.timestamp.clone(),
+            attribution: self.to.attribution.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use WeatherCondition::*;
+
+    fn weather(condition: WeatherCondition, precipitation: f64) -> WeatherData {
+        WeatherData {
+            temperature: 15.0,
+            precipitation,
+            wind_speed: 3.0,
+            wind_direction: 180.0,
+            timestamp: "simulated".to_string(),
+            ..WeatherData::sample(condition)
+        }
+    }
+
+    #[test]
+    fn test_rain_to_clear_passes_through_clouds() {
+        assert_eq!(
+            transition_path(Rain, Clear),
+            vec![Rain, Drizzle, Overcast, Cloudy, PartlyCloudy, Clear]
+        );
+    }
+
+    #[test]
+    fn test_rain_to_snow_meets_at_overcast() {
+        assert_eq!(
+            transition_path(Rain, Snow),
+            vec![Rain, Drizzle, Overcast, SnowGrains, SnowShowers, Snow]
+        );
+    }
+
+    #[test]
+    fn test_same_condition_is_single_step() {
+        assert_eq!(transition_path(Fog, Fog), vec![Fog]);
+    }
+
+    #[test]
+    fn test_transition_interpolates_values() {
+        let transition = Transition::new(weather(Rain, 4.0), weather(Clear, 0.0));
+
+        let start = transition.at(0.0);
+        assert_eq!(start.condition, Rain);
+        assert_eq!(start.precipitation, 4.0);
+
+        let middle = transition.at(0.5);
+        assert_eq!(middle.precipitation, 2.0);
+
+        let end = transition.at(1.0);
+        assert_eq!(end.condition, Clear);
+        assert_eq!(end.precipitation, 0.0);
+    }
+
+    #[test]
+    fn test_wind_turns_the_short_way() {
+        let blowing = |wind_direction| WeatherData {
+            wind_direction,
+            ..weather(Rain, 2.0)
+        };
+        let transition = Transition::new(blowing(350.0), blowing(10.0));
+        assert!((transition.at(0.25).wind_direction - 355.0).abs() < 1e-9);
+        assert!(transition.at(0.5).wind_direction.abs() < 1e-9);
+        assert!((transition.at(0.75).wind_direction - 5.0).abs() < 1e-9);
+
+        let back = Transition::new(blowing(10.0), blowing(350.0));
+        assert!((back.at(0.25).wind_direction - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_night_falls_halfway() {
+        let mut night = weather(Clear, 0.0);
+        night.sun.is_day = false;
+        let transition = Transition::new(weather(Rain, 4.0), night);
+
+        assert!(transition.at(0.0).sun.is_day);
+        assert!(transition.at(0.4).sun.is_day);
+        assert!(!transition.at(0.6).sun.is_day);
+    }
+}
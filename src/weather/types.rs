@@ -5,19 +5,33 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "snake_case")]
 pub enum WeatherCondition {
     Clear,
+    MainlyClear,
     PartlyCloudy,
     Cloudy,
     Overcast,
     Fog,
+    RimeFog,
+    LightDrizzle,
     Drizzle,
+    DenseDrizzle,
+    FreezingDrizzle,
+    DenseFreezingDrizzle,
+    LightRain,
     Rain,
+    HeavyRain,
     FreezingRain,
+    HeavyFreezingRain,
+    LightSnow,
     Snow,
+    HeavySnow,
     SnowGrains,
     RainShowers,
+    ViolentRainShowers,
     SnowShowers,
+    HeavySnowShowers,
     Thunderstorm,
     ThunderstormHail,
+    ThunderstormHeavyHail,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -33,6 +47,8 @@ pub enum SnowIntensity {
     Light,
     Medium,
     Heavy,
+    /// Heavy snow driven by a gale, see [`WeatherData::snow_intensity`].
+    Blizzard,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -45,37 +61,65 @@ pub enum FogIntensity {
 impl WeatherCondition {
     pub const ALL: &'static [WeatherCondition] = &[
         Self::Clear,
+        Self::MainlyClear,
         Self::PartlyCloudy,
         Self::Cloudy,
         Self::Overcast,
         Self::Fog,
+        Self::RimeFog,
+        Self::LightDrizzle,
         Self::Drizzle,
+        Self::DenseDrizzle,
+        Self::FreezingDrizzle,
+        Self::DenseFreezingDrizzle,
+        Self::LightRain,
         Self::Rain,
+        Self::HeavyRain,
         Self::FreezingRain,
+        Self::HeavyFreezingRain,
         Self::RainShowers,
+        Self::ViolentRainShowers,
+        Self::LightSnow,
         Self::Snow,
+        Self::HeavySnow,
         Self::SnowGrains,
         Self::SnowShowers,
+        Self::HeavySnowShowers,
         Self::Thunderstorm,
         Self::ThunderstormHail,
+        Self::ThunderstormHeavyHail,
     ];
 
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Clear => "clear",
+            Self::MainlyClear => "mainly-clear",
             Self::PartlyCloudy => "partly-cloudy",
             Self::Cloudy => "cloudy",
             Self::Overcast => "overcast",
             Self::Fog => "fog",
+            Self::RimeFog => "rime-fog",
+            Self::LightDrizzle => "light-drizzle",
             Self::Drizzle => "drizzle",
+            Self::DenseDrizzle => "dense-drizzle",
+            Self::FreezingDrizzle => "freezing-drizzle",
+            Self::DenseFreezingDrizzle => "dense-freezing-drizzle",
+            Self::LightRain => "light-rain",
             Self::Rain => "rain",
+            Self::HeavyRain => "heavy-rain",
             Self::FreezingRain => "freezing-rain",
+            Self::HeavyFreezingRain => "heavy-freezing-rain",
             Self::RainShowers => "rain-showers",
+            Self::ViolentRainShowers => "violent-rain-showers",
+            Self::LightSnow => "light-snow",
             Self::Snow => "snow",
+            Self::HeavySnow => "heavy-snow",
             Self::SnowGrains => "snow-grains",
             Self::SnowShowers => "snow-showers",
+            Self::HeavySnowShowers => "heavy-snow-showers",
             Self::Thunderstorm => "thunderstorm",
             Self::ThunderstormHail => "thunderstorm-hail",
+            Self::ThunderstormHeavyHail => "thunderstorm-heavy-hail",
         }
     }
 
@@ -83,50 +127,177 @@ impl WeatherCondition {
     pub fn description(&self) -> &'static str {
         match self {
             Self::Clear => "Clear sunny sky",
+            Self::MainlyClear => "Mainly clear sky",
             Self::PartlyCloudy => "Partial cloud coverage",
             Self::Cloudy => "Cloudy sky",
             Self::Overcast => "Overcast sky",
             Self::Fog => "Foggy conditions",
-            Self::Drizzle => "Light drizzle",
+            Self::RimeFog => "Depositing rime fog",
+            Self::LightDrizzle => "Light drizzle",
+            Self::Drizzle => "Moderate drizzle",
+            Self::DenseDrizzle => "Dense drizzle",
+            Self::FreezingDrizzle => "Light freezing drizzle",
+            Self::DenseFreezingDrizzle => "Dense freezing drizzle",
+            Self::LightRain => "Slight rain",
             Self::Rain => "Rain",
+            Self::HeavyRain => "Heavy rain",
             Self::FreezingRain => "Freezing rain",
+            Self::HeavyFreezingRain => "Heavy freezing rain",
             Self::RainShowers => "Rain showers",
+            Self::ViolentRainShowers => "Violent rain showers",
+            Self::LightSnow => "Slight snow fall",
             Self::Snow => "Snow",
+            Self::HeavySnow => "Heavy snow fall",
             Self::SnowGrains => "Snow grains",
             Self::SnowShowers => "Snow showers",
+            Self::HeavySnowShowers => "Heavy snow showers",
             Self::Thunderstorm => "Thunderstorm",
             Self::ThunderstormHail => "Thunderstorm with hail",
+            Self::ThunderstormHeavyHail => "Thunderstorm with heavy hail",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Clear => "Clear",
+            Self::MainlyClear => "Mainly Clear",
+            Self::PartlyCloudy => "Partly Cloudy",
+            Self::Cloudy => "Cloudy",
+            Self::Overcast => "Overcast",
+            Self::Fog => "Fog",
+            Self::RimeFog => "Rime Fog",
+            Self::LightDrizzle => "Light Drizzle",
+            Self::Drizzle => "Drizzle",
+            Self::DenseDrizzle => "Dense Drizzle",
+            Self::FreezingDrizzle => "Freezing Drizzle",
+            Self::DenseFreezingDrizzle => "Dense Freezing Drizzle",
+            Self::LightRain => "Light Rain",
+            Self::Rain => "Rain",
+            Self::HeavyRain => "Heavy Rain",
+            Self::FreezingRain => "Freezing Rain",
+            Self::HeavyFreezingRain => "Heavy Freezing Rain",
+            Self::RainShowers => "Rain Showers",
+            Self::ViolentRainShowers => "Violent Rain Showers",
+            Self::LightSnow => "Light Snow",
+            Self::Snow => "Snow",
+            Self::HeavySnow => "Heavy Snow",
+            Self::SnowGrains => "Snow Grains",
+            Self::SnowShowers => "Snow Showers",
+            Self::HeavySnowShowers => "Heavy Snow Showers",
+            Self::Thunderstorm => "Thunderstorm",
+            Self::ThunderstormHail => "Thunderstorm with Hail",
+            Self::ThunderstormHeavyHail => "Thunderstorm with Heavy Hail",
+        }
+    }
+
+    /// A single-column symbol for compact views like the forecast strip.
+    /// Nerd Font icons tell more conditions apart than the other sets.
+    pub fn icon(&self, icons: IconSet) -> &'static str {
+        match icons {
+            IconSet::Nerd => self.nerd_icon(),
+            IconSet::Unicode | IconSet::Ascii => {
+                let [unicode, ascii] = match self {
+                    Self::Clear | Self::MainlyClear => ["☼", "O"],
+                    Self::PartlyCloudy | Self::Cloudy | Self::Overcast => ["☁", "~"],
+                    Self::Fog | Self::RimeFog => ["≡", "="],
+                    Self::LightSnow
+                    | Self::Snow
+                    | Self::HeavySnow
+                    | Self::SnowGrains
+                    | Self::SnowShowers
+                    | Self::HeavySnowShowers => ["*", "*"],
+                    Self::Thunderstorm | Self::ThunderstormHail | Self::ThunderstormHeavyHail => {
+                        ["ϟ", "!"]
+                    }
+                    _ => ["☂", "/"],
+                };
+                if icons == IconSet::Ascii {
+                    ascii
+                } else {
+                    unicode
+                }
+            }
+        }
+    }
+
+    /// From the Weather Icons set as patched into Nerd Fonts (`nf-weather-*`).
+    fn nerd_icon(&self) -> &'static str {
+        match self {
+            Self::Clear | Self::MainlyClear => "\u{e30d}",
+            Self::PartlyCloudy => "\u{e302}",
+            Self::Cloudy | Self::Overcast => "\u{e312}",
+            Self::Fog | Self::RimeFog => "\u{e313}",
+            Self::LightDrizzle | Self::Drizzle | Self::DenseDrizzle => "\u{e31b}",
+            Self::FreezingDrizzle
+            | Self::DenseFreezingDrizzle
+            | Self::FreezingRain
+            | Self::HeavyFreezingRain => "\u{e316}",
+            Self::RainShowers | Self::ViolentRainShowers => "\u{e319}",
+            Self::LightSnow
+            | Self::Snow
+            | Self::HeavySnow
+            | Self::SnowGrains
+            | Self::SnowShowers
+            | Self::HeavySnowShowers => "\u{e31a}",
+            Self::Thunderstorm => "\u{e31d}",
+            Self::ThunderstormHail | Self::ThunderstormHeavyHail => "\u{e314}",
+            Self::LightRain | Self::Rain | Self::HeavyRain => "\u{e318}",
         }
     }
 
     #[allow(dead_code)]
     pub fn group(&self) -> &'static str {
         match self {
-            Self::Clear | Self::PartlyCloudy | Self::Cloudy | Self::Overcast => "Clear Skies",
-            Self::Fog | Self::Drizzle | Self::Rain | Self::FreezingRain | Self::RainShowers => {
-                "Precipitation"
-            }
-            Self::Snow | Self::SnowGrains | Self::SnowShowers => "Snow",
-            Self::Thunderstorm | Self::ThunderstormHail => "Storms",
+            Self::Clear
+            | Self::MainlyClear
+            | Self::PartlyCloudy
+            | Self::Cloudy
+            | Self::Overcast => "Clear Skies",
+            Self::Fog
+            | Self::RimeFog
+            | Self::LightDrizzle
+            | Self::Drizzle
+            | Self::DenseDrizzle
+            | Self::FreezingDrizzle
+            | Self::DenseFreezingDrizzle
+            | Self::LightRain
+            | Self::Rain
+            | Self::HeavyRain
+            | Self::FreezingRain
+            | Self::HeavyFreezingRain
+            | Self::RainShowers
+            | Self::ViolentRainShowers => "Precipitation",
+            Self::LightSnow
+            | Self::Snow
+            | Self::HeavySnow
+            | Self::SnowGrains
+            | Self::SnowShowers
+            | Self::HeavySnowShowers => "Snow",
+            Self::Thunderstorm | Self::ThunderstormHail | Self::ThunderstormHeavyHail => "Storms",
         }
     }
 
     pub fn rain_intensity(&self) -> RainIntensity {
         match self {
-            Self::Drizzle => RainIntensity::Drizzle,
+            Self::LightDrizzle | Self::Drizzle | Self::FreezingDrizzle => RainIntensity::Drizzle,
+            Self::DenseDrizzle | Self::DenseFreezingDrizzle | Self::LightRain => {
+                RainIntensity::Light
+            }
             Self::Rain | Self::RainShowers => RainIntensity::Light,
-            Self::FreezingRain => RainIntensity::Heavy,
-            Self::Thunderstorm => RainIntensity::Heavy,
-            Self::ThunderstormHail => RainIntensity::Storm,
+            Self::HeavyRain | Self::FreezingRain | Self::Thunderstorm => RainIntensity::Heavy,
+            Self::HeavyFreezingRain
+            | Self::ViolentRainShowers
+            | Self::ThunderstormHail
+            | Self::ThunderstormHeavyHail => RainIntensity::Storm,
             _ => RainIntensity::Light,
         }
     }
 
     pub fn snow_intensity(&self) -> SnowIntensity {
         match self {
-            Self::SnowGrains => SnowIntensity::Light,
-            Self::SnowShowers => SnowIntensity::Medium,
-            Self::Snow => SnowIntensity::Heavy,
+            Self::LightSnow | Self::SnowGrains => SnowIntensity::Light,
+            Self::Snow | Self::SnowShowers => SnowIntensity::Medium,
+            Self::HeavySnow | Self::HeavySnowShowers => SnowIntensity::Heavy,
             _ => SnowIntensity::Light,
         }
     }
@@ -134,6 +305,7 @@ impl WeatherCondition {
     pub fn fog_intensity(&self) -> FogIntensity {
         match self {
             Self::Fog => FogIntensity::Medium,
+            Self::RimeFog => FogIntensity::Heavy,
             _ => FogIntensity::Light,
         }
     }
@@ -141,21 +313,41 @@ impl WeatherCondition {
     pub fn is_raining(&self) -> bool {
         matches!(
             self,
-            Self::Drizzle
+            Self::LightDrizzle
+                | Self::Drizzle
+                | Self::DenseDrizzle
+                | Self::FreezingDrizzle
+                | Self::DenseFreezingDrizzle
+                | Self::LightRain
                 | Self::Rain
-                | Self::RainShowers
+                | Self::HeavyRain
                 | Self::FreezingRain
+                | Self::HeavyFreezingRain
+                | Self::RainShowers
+                | Self::ViolentRainShowers
                 | Self::Thunderstorm
                 | Self::ThunderstormHail
+                | Self::ThunderstormHeavyHail
         )
     }
 
     pub fn is_snowing(&self) -> bool {
-        matches!(self, Self::Snow | Self::SnowGrains | Self::SnowShowers)
+        matches!(
+            self,
+            Self::LightSnow
+                | Self::Snow
+                | Self::HeavySnow
+                | Self::SnowGrains
+                | Self::SnowShowers
+                | Self::HeavySnowShowers
+        )
     }
 
     pub fn is_thunderstorm(&self) -> bool {
-        matches!(self, Self::Thunderstorm | Self::ThunderstormHail)
+        matches!(
+            self,
+            Self::Thunderstorm | Self::ThunderstormHail | Self::ThunderstormHeavyHail
+        )
     }
 
     pub fn is_cloudy(&self) -> bool {
@@ -163,7 +355,7 @@ impl WeatherCondition {
     }
 
     pub fn is_foggy(&self) -> bool {
-        matches!(self, Self::Fog)
+        matches!(self, Self::Fog | Self::RimeFog)
     }
 }
 
@@ -180,6 +372,24 @@ impl std::str::FromStr for WeatherCondition {
     }
 }
 
+/// Symbols for weather conditions: Nerd Font weather icons, which need a
+/// patched font, the Unicode ones most fonts have, or plain ASCII.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IconSet {
+    Nerd,
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+impl IconSet {
+    /// Falls back to ASCII where the terminal can't show anything else.
+    pub fn supported(self, unicode: bool) -> Self {
+        if unicode { self } else { Self::Ascii }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TemperatureUnit {
@@ -203,6 +413,21 @@ pub enum PrecipitationUnit {
     Inch,
 }
 
+/// For visibility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceUnit {
+    Km,
+    Mi,
+}
+
+/// A day's low and high temperature in °C.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TemperatureRange {
+    pub min: f64,
+    pub max: f64,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WeatherData {
     pub condition: WeatherCondition,
@@ -212,30 +437,266 @@ pub struct WeatherData {
     pub wind_direction: f64,
     pub sun: CelestialEvents,
     pub moon_phase: Option<f64>,
+    /// Mean sea level pressure in hPa.
+    #[serde(default)]
+    pub pressure: Option<f64>,
+    /// Set in consensus mode when the providers differ noticeably.
+    #[serde(default)]
+    pub disagreement: Option<Disagreement>,
+    /// Relative humidity in percent.
+    #[serde(default)]
+    pub humidity: Option<f64>,
+    /// Feels-like temperature in °C, when the provider reports one.
+    #[serde(default)]
+    pub apparent_temperature: Option<f64>,
+    #[serde(default)]
+    pub uv_index: Option<f64>,
+    /// Short name of the provider(s) the data came from, e.g. "OM" or
+    /// "OM+MetNo"; empty for simulated weather.
+    #[serde(default)]
+    pub source: String,
+    /// Precipitation over the next couple of hours in 15-minute steps. Empty
+    /// when the provider has no nowcast.
+    #[serde(default)]
+    pub nowcast: Vec<NowcastStep>,
+    /// The coming hours, starting with the current one. Empty when the
+    /// provider has no forecast.
+    #[serde(default)]
+    pub hourly: Vec<HourlyForecast>,
+    /// Today's forecast low and high, when the provider has them.
+    #[serde(default)]
+    pub today: Option<TemperatureRange>,
+    #[serde(default)]
+    pub details: WeatherDetails,
     pub timestamp: String,
     pub attribution: String,
 }
 
-#[derive(Debug, Clone, Copy, serde::Deserialize)]
+/// Snowfall rate, in cm/h, and wind or gusts, in m/s (35 mph, the NWS
+/// threshold), from which falling snow counts as a blizzard.
+const BLIZZARD_SNOWFALL: f64 = 1.0;
+const BLIZZARD_WIND: f64 = 15.6;
+
+#[cfg(test)]
+impl WeatherData {
+    /// A calm day at 10°C in `condition`, for tests to change what they need.
+    pub fn sample(condition: WeatherCondition) -> Self {
+        Self {
+            condition,
+            temperature: 10.0,
+            precipitation: 0.0,
+            wind_speed: 0.0,
+            wind_direction: 0.0,
+            sun: CelestialEvents::from_bool(true),
+            moon_phase: None,
+            pressure: None,
+            disagreement: None,
+            humidity: None,
+            apparent_temperature: None,
+            uv_index: None,
+            source: String::new(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: None,
+            details: Default::default(),
+            timestamp: String::new(),
+            attribution: String::new(),
+        }
+    }
+}
+
+impl WeatherData {
+    /// How hard it's snowing: the condition's intensity, or a blizzard when
+    /// the snow comes down fast in a gale. Without a snowfall figure the
+    /// precipitation stands in, a mm of water making about a cm of snow.
+    pub fn snow_intensity(&self) -> SnowIntensity {
+        let rate = self.details.snowfall.unwrap_or(self.precipitation);
+        let wind = self
+            .details
+            .wind_gusts
+            .map_or(self.wind_speed, |gusts| gusts.max(self.wind_speed));
+        if self.condition.is_snowing() && rate >= BLIZZARD_SNOWFALL && wind >= BLIZZARD_WIND {
+            SnowIntensity::Blizzard
+        } else {
+            self.condition.snow_intensity()
+        }
+    }
+
+    /// Feels-like temperature in °C: the provider's figure if it gives one,
+    /// otherwise worked out from temperature, wind and humidity.
+    pub fn feels_like(&self) -> f64 {
+        self.apparent_temperature.unwrap_or_else(|| {
+            super::units::apparent_temperature(self.temperature, self.wind_speed, self.humidity)
+        })
+    }
+}
+
+/// A 15-minute step of a short-range precipitation forecast.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NowcastStep {
+    /// Start of the step, in minutes after the observation time.
+    pub minutes_ahead: u32,
+    /// In mm/h.
+    pub precipitation: f64,
+    /// Chance of precipitation in percent, where the provider gives one.
+    pub probability: Option<f64>,
+}
+
+/// One hour of a provider's forecast, before normalization.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ForecastHour {
+    /// Local time, e.g. `2024-01-01T13:00`.
+    pub time: String,
+    pub weather_code: i32,
+    /// In °C.
+    pub temperature: f64,
+    /// Chance of precipitation in percent, where the provider gives one.
+    pub precipitation_probability: Option<f64>,
+    /// Relative humidity in percent, where the provider gives one.
+    #[serde(default)]
+    pub humidity: Option<f64>,
+}
+
+/// One hour of forecast as shown in the forecast strip.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HourlyForecast {
+    pub time: String,
+    pub condition: WeatherCondition,
+    /// In °C.
+    pub temperature: f64,
+    pub precipitation_probability: Option<f64>,
+    /// Relative humidity in percent.
+    #[serde(default)]
+    pub humidity: Option<f64>,
+}
+
+/// Readings beyond the HUD line, shown in the `d` detail panel. Providers
+/// fill in what they report.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
+pub struct WeatherDetails {
+    /// In °C; worked out from the humidity when not reported.
+    pub dew_point: Option<f64>,
+    /// In m/s.
+    pub wind_gusts: Option<f64>,
+    /// In metres.
+    pub visibility: Option<f64>,
+    /// At most one layer per level, low to high.
+    pub cloud_layers: Vec<CloudLayer>,
+    /// Fallen in the last hour, in cm.
+    pub snowfall: Option<f64>,
+    /// Lying on the ground, in metres.
+    pub snow_depth: Option<f64>,
+    /// The location's IANA time zone, e.g. "Asia/Tokyo".
+    pub timezone: Option<String>,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum CloudLevel {
+    /// Below 2 km.
+    Low,
+    /// 2 to 6 km.
+    Mid,
+    High,
+}
+
+impl CloudLevel {
+    pub fn from_base(metres: f64) -> Self {
+        if metres < 2000.0 {
+            Self::Low
+        } else if metres < 6000.0 {
+            Self::Mid
+        } else {
+            Self::High
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Mid => "mid",
+            Self::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CloudLayer {
+    pub level: CloudLevel,
+    /// In percent of the sky.
+    pub cover: f64,
+}
+
+/// How far apart the providers behind a consensus reading were.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Disagreement {
+    /// Highest minus lowest reported temperature, in °C.
+    pub temperature_spread: f64,
+    /// Some providers report precipitation and others don't.
+    pub precipitation: bool,
+}
+
+/// Set in config either unit by unit or as a preset: `units = "uk"`.
+#[derive(Debug, Clone, Copy)]
 pub struct WeatherUnits {
     pub temperature: TemperatureUnit,
     pub wind_speed: WindSpeedUnit,
     pub precipitation: PrecipitationUnit,
+    pub distance: DistanceUnit,
 }
 
 impl WeatherUnits {
+    pub const PRESETS: [&'static str; 3] = ["metric", "imperial", "uk"];
+
     pub fn imperial() -> Self {
         Self {
             temperature: TemperatureUnit::Fahrenheit,
             wind_speed: WindSpeedUnit::Mph,
             precipitation: PrecipitationUnit::Inch,
+            distance: DistanceUnit::Mi,
         }
     }
 
     pub fn metric() -> Self {
         Self::default()
     }
+
+    /// °C and mm, but miles per hour and miles, as in the UK.
+    pub fn uk() -> Self {
+        Self {
+            wind_speed: WindSpeedUnit::Mph,
+            distance: DistanceUnit::Mi,
+            ..Self::metric()
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "metric" => Some(Self::metric()),
+            "imperial" => Some(Self::imperial()),
+            "uk" => Some(Self::uk()),
+            _ => None,
+        }
+    }
+
+    /// The units usual in the country of a POSIX locale such as
+    /// "en_US.UTF-8": imperial in the US, Liberia and Myanmar, mph with °C
+    /// and mm in the UK, metric everywhere else.
+    pub fn for_locale(locale: &str) -> Self {
+        let language_region = locale.split(['.', '@']).next().unwrap_or_default();
+        let region = language_region
+            .split(['_', '-'])
+            .nth(1)
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        match region.as_str() {
+            "US" | "LR" | "MM" => Self::imperial(),
+            "GB" => Self::uk(),
+            _ => Self::metric(),
+        }
+    }
 }
 
 impl Default for WeatherUnits {
@@ -244,10 +705,73 @@ impl Default for WeatherUnits {
             temperature: TemperatureUnit::Celsius,
             wind_speed: WindSpeedUnit::Kmh,
             precipitation: PrecipitationUnit::Mm,
+            distance: DistanceUnit::Km,
         }
     }
 }
 
+/// The table form of `[units]`; the distance unit follows the precipitation
+/// one when left out.
+#[derive(Deserialize)]
+#[serde(default)]
+struct UnitsTable {
+    temperature: TemperatureUnit,
+    wind_speed: WindSpeedUnit,
+    precipitation: PrecipitationUnit,
+    distance: Option<DistanceUnit>,
+}
+
+impl Default for UnitsTable {
+    fn default() -> Self {
+        let units = WeatherUnits::default();
+        Self {
+            temperature: units.temperature,
+            wind_speed: units.wind_speed,
+            precipitation: units.precipitation,
+            distance: None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WeatherUnits {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct UnitsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for UnitsVisitor {
+            type Value = WeatherUnits;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a units table or one of \"metric\", \"imperial\" and \"uk\"")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<WeatherUnits, E> {
+                WeatherUnits::preset(name)
+                    .ok_or_else(|| E::unknown_variant(name, &WeatherUnits::PRESETS))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> Result<WeatherUnits, A::Error> {
+                let table =
+                    UnitsTable::deserialize(serde::de::value::MapAccessDeserializer::new(map))?;
+                let distance = table.distance.unwrap_or(match table.precipitation {
+                    PrecipitationUnit::Inch => DistanceUnit::Mi,
+                    PrecipitationUnit::Mm => DistanceUnit::Km,
+                });
+                Ok(WeatherUnits {
+                    temperature: table.temperature,
+                    wind_speed: table.wind_speed,
+                    precipitation: table.precipitation,
+                    distance,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(UnitsVisitor)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 #[allow(dead_code)]
 pub struct WeatherLocation {
@@ -1,4 +1,6 @@
-use super::types::{PrecipitationUnit, TemperatureUnit, WindSpeedUnit};
+use super::types::{DistanceUnit, PrecipitationUnit, TemperatureUnit, WindSpeedUnit};
+
+const METRES_PER_MILE: f64 = 1609.344;
 
 pub fn celsius_to_fahrenheit(celsius: f64) -> f64 {
     celsius * 9.0 / 5.0 + 32.0
@@ -40,6 +42,74 @@ pub fn inch_to_mm(inch: f64) -> f64 {
     inch * 25.4
 }
 
+/// Wind chill in Celsius (the index used by Environment Canada and the NWS).
+/// `None` above 10°C or in winds under 4.8 km/h, where it isn't defined.
+pub fn wind_chill(celsius: f64, wind_ms: f64) -> Option<f64> {
+    let wind_kmh = ms_to_kmh(wind_ms);
+    if celsius > 10.0 || wind_kmh <= 4.8 {
+        return None;
+    }
+    let v = wind_kmh.powf(0.16);
+    Some(13.12 + 0.6215 * celsius - 11.37 * v + 0.3965 * celsius * v)
+}
+
+/// Heat index in Celsius, from the NWS regression and its adjustments for
+/// very dry and very humid air. `None` below 26.7°C (80°F).
+pub fn heat_index(celsius: f64, humidity: f64) -> Option<f64> {
+    if celsius < 26.7 {
+        return None;
+    }
+    let t = celsius_to_fahrenheit(celsius);
+    let rh = humidity.clamp(0.0, 100.0);
+
+    let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+    if (simple + t) / 2.0 < 80.0 {
+        return Some(fahrenheit_to_celsius(simple));
+    }
+
+    let mut index = -42.379 + 2.04901523 * t + 10.14333127 * rh
+        - 0.22475541 * t * rh
+        - 0.00683783 * t * t
+        - 0.05481717 * rh * rh
+        + 0.00122874 * t * t * rh
+        + 0.00085282 * t * rh * rh
+        - 0.00000199 * t * t * rh * rh;
+    if rh < 13.0 && t <= 112.0 {
+        index -= (13.0 - rh) / 4.0 * ((17.0 - (t - 95.0).abs()) / 17.0).sqrt();
+    } else if rh > 85.0 && t <= 87.0 {
+        index += (rh - 85.0) / 10.0 * (87.0 - t) / 5.0;
+    }
+    Some(fahrenheit_to_celsius(index))
+}
+
+/// Dew point in Celsius from the Magnus formula, good to a few tenths of a
+/// degree in everyday weather.
+pub fn dew_point(celsius: f64, humidity: f64) -> f64 {
+    const B: f64 = 17.62;
+    const C: f64 = 243.12;
+    let gamma = (humidity.clamp(1.0, 100.0) / 100.0).ln() + B * celsius / (C + celsius);
+    C * gamma / (B - gamma)
+}
+
+/// Humidex (Environment Canada) from the temperature and dew point. `None`
+/// below 20°C, where it isn't used.
+pub fn humidex(celsius: f64, dew_point: f64) -> Option<f64> {
+    if celsius < 20.0 {
+        return None;
+    }
+    let vapour_pressure = 6.11 * (5417.7530 * (1.0 / 273.16 - 1.0 / (273.15 + dew_point))).exp();
+    Some(celsius + 0.5555 * (vapour_pressure - 10.0))
+}
+
+/// Feels-like temperature in Celsius: wind chill in cold, windy weather,
+/// the heat index in hot weather when the humidity is known, otherwise the
+/// air temperature itself.
+pub fn apparent_temperature(celsius: f64, wind_ms: f64, humidity: Option<f64>) -> f64 {
+    wind_chill(celsius, wind_ms)
+        .or_else(|| humidity.and_then(|rh| heat_index(celsius, rh)))
+        .unwrap_or(celsius)
+}
+
 pub fn format_temperature(celsius: f64, unit: TemperatureUnit) -> (f64, &'static str) {
     match unit {
         TemperatureUnit::Celsius => (celsius, "°C"),
@@ -57,6 +127,31 @@ pub fn format_wind_speed(ms: f64, unit: WindSpeedUnit) -> (f64, &'static str) {
     }
 }
 
+/// Upper bounds (m/s, exclusive) of Beaufort forces 0 to 11, with the
+/// name of each force and of hurricane force beyond.
+const BEAUFORT_SCALE: [(f64, &str); 12] = [
+    (0.5, "calm"),
+    (1.6, "light air"),
+    (3.4, "light breeze"),
+    (5.5, "gentle breeze"),
+    (8.0, "moderate breeze"),
+    (10.8, "fresh breeze"),
+    (13.9, "strong breeze"),
+    (17.2, "near gale"),
+    (20.8, "gale"),
+    (24.5, "strong gale"),
+    (28.5, "storm"),
+    (32.7, "violent storm"),
+];
+
+/// The Beaufort description of a wind speed in m/s, e.g. "fresh breeze".
+pub fn beaufort_description(ms: f64) -> &'static str {
+    BEAUFORT_SCALE
+        .iter()
+        .find(|(limit, _)| ms < *limit)
+        .map_or("hurricane force", |(_, name)| name)
+}
+
 /// The input value must be in Mm
 pub fn format_precipitation(mm: f64, unit: PrecipitationUnit) -> (f64, &'static str) {
     match unit {
@@ -65,6 +160,22 @@ pub fn format_precipitation(mm: f64, unit: PrecipitationUnit) -> (f64, &'static
     }
 }
 
+/// The input value must be in cm; snow is measured in cm rather than mm.
+pub fn format_snow(cm: f64, unit: PrecipitationUnit) -> (f64, &'static str) {
+    match unit {
+        PrecipitationUnit::Mm => (cm, "cm"),
+        PrecipitationUnit::Inch => (mm_to_inch(cm * 10.0), "in"),
+    }
+}
+
+/// The input value must be in metres
+pub fn format_distance(metres: f64, unit: DistanceUnit) -> (f64, &'static str) {
+    match unit {
+        DistanceUnit::Km => (metres / 1000.0, "km"),
+        DistanceUnit::Mi => (metres / METRES_PER_MILE, "mi"),
+    }
+}
+
 /// The input value must be in Celsius
 pub fn normalize_temperature(value: f64, unit: TemperatureUnit) -> f64 {
     match unit {
@@ -90,3 +201,82 @@ pub fn normalize_precipitation(value: f64, unit: PrecipitationUnit) -> f64 {
         PrecipitationUnit::Inch => inch_to_mm(value),
     }
 }
+
+/// Snowfall in cm, which providers give in inches for imperial units.
+pub fn normalize_snowfall(value: f64, unit: PrecipitationUnit) -> f64 {
+    match unit {
+        PrecipitationUnit::Mm => value,
+        PrecipitationUnit::Inch => inch_to_mm(value) / 10.0,
+    }
+}
+
+/// Snow depth in metres, which providers give in feet for imperial units.
+pub fn normalize_snow_depth(value: f64, unit: PrecipitationUnit) -> f64 {
+    match unit {
+        PrecipitationUnit::Mm => value,
+        PrecipitationUnit::Inch => inch_to_mm(value * 12.0) / 1000.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.1,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_wind_chill() {
+        // Environment Canada's table: -10°C in a 20 km/h wind feels like -18°C
+        assert_close(wind_chill(-10.0, kmh_to_ms(20.0)).unwrap(), -17.9);
+        assert_eq!(wind_chill(12.0, 10.0), None);
+        assert_eq!(wind_chill(-5.0, 1.0), None);
+    }
+
+    #[test]
+    fn test_heat_index() {
+        // NWS table: 90°F at 60% humidity is 100°F (rounded)
+        let index = heat_index(fahrenheit_to_celsius(90.0), 60.0).unwrap();
+        assert_close(celsius_to_fahrenheit(index), 99.7);
+        assert_eq!(heat_index(25.0, 90.0), None);
+    }
+
+    #[test]
+    fn test_dew_point_and_humidex() {
+        assert_close(dew_point(20.0, 50.0), 9.3);
+        assert_close(dew_point(10.0, 100.0), 10.0);
+        // Environment Canada's table: 30°C with a 15°C dew point is 34
+        assert_close(humidex(30.0, 15.0).unwrap(), 34.0);
+        assert_eq!(humidex(18.0, 15.0), None);
+    }
+
+    #[test]
+    fn test_beaufort_description() {
+        assert_eq!(beaufort_description(0.0), "calm");
+        assert_eq!(beaufort_description(kmh_to_ms(35.0)), "fresh breeze");
+        assert_eq!(beaufort_description(17.2), "gale");
+        assert_eq!(beaufort_description(40.0), "hurricane force");
+    }
+
+    #[test]
+    fn test_apparent_temperature_falls_back_to_air_temperature() {
+        assert_close(apparent_temperature(20.0, 8.0, Some(50.0)), 20.0);
+        assert_close(apparent_temperature(32.0, 2.0, None), 32.0);
+        assert!(apparent_temperature(32.0, 2.0, Some(70.0)) > 38.0);
+        assert!(apparent_temperature(-10.0, 5.0, Some(70.0)) < -10.0);
+    }
+
+    #[test]
+    fn test_snow_units() {
+        assert_close(normalize_snowfall(1.0, PrecipitationUnit::Inch), 2.54);
+        assert_close(normalize_snow_depth(2.0, PrecipitationUnit::Inch), 0.61);
+        assert_eq!(format_snow(2.5, PrecipitationUnit::Mm), (2.5, "cm"));
+        let (inches, unit) = format_snow(25.4, PrecipitationUnit::Inch);
+        assert_close(inches, 10.0);
+        assert_eq!(unit, "in");
+    }
+}
@@ -0,0 +1,106 @@
+//! Catches physically impossible readings before they reach the screen,
+//! such as a -999° placeholder passed through as a temperature.
+
+use crate::weather::types::WeatherData;
+use std::ops::RangeInclusive;
+
+/// Just beyond the coldest (-89.2°C) and hottest (56.7°C) air
+/// temperatures on record.
+const TEMPERATURE: RangeInclusive<f64> = -90.0..=60.0;
+/// Wind chill and heat index reach further than the air temperature.
+const APPARENT_TEMPERATURE: RangeInclusive<f64> = -110.0..=80.0;
+const HUMIDITY: RangeInclusive<f64> = 0.0..=100.0;
+/// hPa. Sea-level pressure has stayed within 870 and 1084, but a weather
+/// station's own reading falls well below that up a mountain.
+const PRESSURE: RangeInclusive<f64> = 300.0..=1100.0;
+/// m/s; the fastest gust measured was 113 m/s.
+const WIND_SPEED: RangeInclusive<f64> = 0.0..=120.0;
+/// mm/h.
+const PRECIPITATION: RangeInclusive<f64> = 0.0..=500.0;
+const UV_INDEX: RangeInclusive<f64> = 0.0..=25.0;
+
+/// The first reading in `data` that can't be right, described for the
+/// user, or `None` if everything is within physical limits.
+pub fn implausible_reading(data: &WeatherData) -> Option<String> {
+    let checks = [
+        ("temperature", Some(data.temperature), TEMPERATURE, "°C"),
+        (
+            "feels-like temperature",
+            data.apparent_temperature,
+            APPARENT_TEMPERATURE,
+            "°C",
+        ),
+        ("humidity", data.humidity, HUMIDITY, "%"),
+        ("pressure", data.pressure, PRESSURE, " hPa"),
+        ("wind speed", Some(data.wind_speed), WIND_SPEED, " m/s"),
+        (
+            "precipitation",
+            Some(data.precipitation),
+            PRECIPITATION,
+            " mm/h",
+        ),
+        ("UV index", data.uv_index, UV_INDEX, ""),
+    ];
+
+    checks.into_iter().find_map(|(name, value, range, unit)| {
+        // NaN isn't contained in any range either
+        value
+            .filter(|value| !range.contains(value))
+            .map(|value| format!("{} {}{}", name, value, unit))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::types::WeatherCondition;
+
+    fn weather() -> WeatherData {
+        WeatherData {
+            temperature: 21.0,
+            wind_speed: 3.0,
+            wind_direction: 180.0,
+            pressure: Some(1013.0),
+            humidity: Some(55.0),
+            apparent_temperature: Some(20.0),
+            uv_index: Some(4.0),
+            timestamp: "2026-10-16T12:00".to_string(),
+            ..WeatherData::sample(WeatherCondition::Clear)
+        }
+    }
+
+    #[test]
+    fn test_plausible_weather_passes() {
+        assert_eq!(implausible_reading(&weather()), None);
+
+        let mut missing = weather();
+        missing.pressure = None;
+        missing.humidity = None;
+        assert_eq!(implausible_reading(&missing), None);
+    }
+
+    #[test]
+    fn test_impossible_readings_are_named() {
+        let mut data = weather();
+        data.temperature = -999.0;
+        assert_eq!(
+            implausible_reading(&data).as_deref(),
+            Some("temperature -999°C")
+        );
+
+        let mut data = weather();
+        data.humidity = Some(140.0);
+        assert_eq!(implausible_reading(&data).as_deref(), Some("humidity 140%"));
+
+        let mut data = weather();
+        data.pressure = Some(-3.0);
+        assert_eq!(
+            implausible_reading(&data).as_deref(),
+            Some("pressure -3 hPa")
+        );
+
+        let mut data = weather();
+        data.wind_speed = f64::NAN;
+        assert!(implausible_reading(&data).is_some());
+    }
+}
@@ -1,6 +1,7 @@
 use std::fs;
 use std::io::Write;
 use weathr::config::{Config, LocationDisplay};
+use weathr::weather::WeatherUnits;
 
 #[test]
 fn test_config_integration_load_valid_file() {
@@ -13,7 +14,8 @@ fn test_config_integration_load_valid_file() {
     writeln!(file, "longitude = 139.6503").unwrap();
     drop(file);
 
-    let config = Config::load_from_path(&test_config_path).expect("Failed to load config");
+    let config = Config::load_from_path(&test_config_path, WeatherUnits::default())
+        .expect("Failed to load config");
 
     assert_eq!(config.location.latitude, 35.6762);
     assert_eq!(config.location.longitude, 139.6503);
@@ -41,7 +43,7 @@ fn test_config_integration_realistic_coordinates() {
         writeln!(file, "longitude = {}", lon).unwrap();
         drop(file);
 
-        let config = Config::load_from_path(&test_config_path)
+        let config = Config::load_from_path(&test_config_path, WeatherUnits::default())
             .unwrap_or_else(|_| panic!("Failed to load config for {}", city_name));
 
         assert_eq!(config.location.latitude, lat);
@@ -60,7 +62,7 @@ fn test_config_integration_malformed_toml() {
     writeln!(file, "[[[[invalid toml").unwrap();
     drop(file);
 
-    let result = Config::load_from_path(&test_config_path);
+    let result = Config::load_from_path(&test_config_path, WeatherUnits::default());
     assert!(result.is_err());
 
     use weathr::error::ConfigError;
@@ -86,8 +88,8 @@ fn test_config_integration_missing_fields() {
     .unwrap();
     drop(file);
 
-    let config =
-        Config::load_from_path(&test_config_path).expect("Should use defaults for missing fields");
+    let config = Config::load_from_path(&test_config_path, WeatherUnits::default())
+        .expect("Should use defaults for missing fields");
     assert_eq!(config.location.latitude, 52.52);
     assert_eq!(config.location.longitude, 13.41);
 
@@ -108,7 +110,8 @@ fn test_config_integration_extra_whitespace() {
     writeln!(file).unwrap();
     drop(file);
 
-    let config = Config::load_from_path(&test_config_path).expect("Should handle extra whitespace");
+    let config = Config::load_from_path(&test_config_path, WeatherUnits::default())
+        .expect("Should handle extra whitespace");
 
     assert_eq!(config.location.latitude, 48.8566);
     assert_eq!(config.location.longitude, 2.3522);
@@ -130,7 +133,7 @@ fn test_config_integration_city_and_display() {
     writeln!(file, r#"display = "city""#).unwrap();
     drop(file);
 
-    let config = Config::load_from_path(&test_config_path)
+    let config = Config::load_from_path(&test_config_path, WeatherUnits::default())
         .expect("Should load config with city and display");
 
     assert_eq!(config.location.city, Some("Minsk".to_string()));
@@ -152,8 +155,8 @@ fn test_config_integration_display_mixed() {
     writeln!(file, r#"display = "mixed""#).unwrap();
     drop(file);
 
-    let config =
-        Config::load_from_path(&test_config_path).expect("Should load config with mixed display");
+    let config = Config::load_from_path(&test_config_path, WeatherUnits::default())
+        .expect("Should load config with mixed display");
 
     assert_eq!(config.location.display, LocationDisplay::Mixed);
     assert_eq!(config.location.city, None);
@@ -172,8 +175,8 @@ fn test_config_integration_display_defaults_to_coordinates() {
     writeln!(file, "longitude = 13.41").unwrap();
     drop(file);
 
-    let config =
-        Config::load_from_path(&test_config_path).expect("Should default display to coordinates");
+    let config = Config::load_from_path(&test_config_path, WeatherUnits::default())
+        .expect("Should default display to coordinates");
 
     assert_eq!(config.location.display, LocationDisplay::Coordinates);
     assert_eq!(config.location.city, None);
@@ -194,7 +197,7 @@ fn test_config_integration_city_name_language() {
     writeln!(file, r#"city_name_language = "ru""#).unwrap();
     drop(file);
 
-    let config = Config::load_from_path(&test_config_path)
+    let config = Config::load_from_path(&test_config_path, WeatherUnits::default())
         .expect("Should load config with city_name_language");
 
     assert_eq!(config.location.city_name_language, "ru");
@@ -214,7 +217,7 @@ fn test_config_integration_city_name_language_defaults_to_auto() {
     writeln!(file, "longitude = 13.41").unwrap();
     drop(file);
 
-    let config = Config::load_from_path(&test_config_path)
+    let config = Config::load_from_path(&test_config_path, WeatherUnits::default())
         .expect("Should default city_name_language to auto");
 
     assert_eq!(config.location.city_name_language, "auto");
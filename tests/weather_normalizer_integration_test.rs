@@ -7,33 +7,33 @@ use weathr::weather::types::CelestialEvents;
 fn test_weather_normalizer_integration_all_wmo_codes() {
     let wmo_codes = vec![
         (0, WeatherCondition::Clear),
-        (1, WeatherCondition::PartlyCloudy),
+        (1, WeatherCondition::MainlyClear),
         (2, WeatherCondition::PartlyCloudy),
         (3, WeatherCondition::Overcast),
         (45, WeatherCondition::Fog),
-        (48, WeatherCondition::Fog),
-        (51, WeatherCondition::Drizzle),
+        (48, WeatherCondition::RimeFog),
+        (51, WeatherCondition::LightDrizzle),
         (53, WeatherCondition::Drizzle),
-        (55, WeatherCondition::Drizzle),
-        (56, WeatherCondition::FreezingRain),
-        (57, WeatherCondition::FreezingRain),
-        (61, WeatherCondition::Rain),
+        (55, WeatherCondition::DenseDrizzle),
+        (56, WeatherCondition::FreezingDrizzle),
+        (57, WeatherCondition::DenseFreezingDrizzle),
+        (61, WeatherCondition::LightRain),
         (63, WeatherCondition::Rain),
-        (65, WeatherCondition::Rain),
+        (65, WeatherCondition::HeavyRain),
         (66, WeatherCondition::FreezingRain),
-        (67, WeatherCondition::FreezingRain),
-        (71, WeatherCondition::Snow),
+        (67, WeatherCondition::HeavyFreezingRain),
+        (71, WeatherCondition::LightSnow),
         (73, WeatherCondition::Snow),
-        (75, WeatherCondition::Snow),
+        (75, WeatherCondition::HeavySnow),
         (77, WeatherCondition::SnowGrains),
         (80, WeatherCondition::RainShowers),
         (81, WeatherCondition::RainShowers),
-        (82, WeatherCondition::RainShowers),
+        (82, WeatherCondition::ViolentRainShowers),
         (85, WeatherCondition::SnowShowers),
-        (86, WeatherCondition::SnowShowers),
+        (86, WeatherCondition::HeavySnowShowers),
         (95, WeatherCondition::Thunderstorm),
         (96, WeatherCondition::ThunderstormHail),
-        (99, WeatherCondition::ThunderstormHail),
+        (99, WeatherCondition::ThunderstormHeavyHail),
     ];
 
     for (code, expected_condition) in wmo_codes {
@@ -45,6 +45,16 @@ fn test_weather_normalizer_integration_all_wmo_codes() {
             wind_direction: 180.0,
             sun: CelestialEvents::only_day(1),
             moon_phase: None,
+            pressure: None,
+            disagreement: None,
+            humidity: None,
+            apparent_temperature: None,
+            uv_index: None,
+            source: String::new(),
+            nowcast: Vec::new(),
+            hourly: Vec::new(),
+            today: None,
+            details: Default::default(),
             timestamp: "2024-01-01T12:00".to_string(),
             attribution: "".to_string(),
         };
@@ -68,6 +78,16 @@ fn test_weather_normalizer_integration_day_night() {
         wind_direction: 180.0,
         sun: CelestialEvents::only_day(1),
         moon_phase: None,
+        pressure: None,
+        disagreement: None,
+        humidity: None,
+        apparent_temperature: None,
+        uv_index: None,
+        source: String::new(),
+        nowcast: Vec::new(),
+        hourly: Vec::new(),
+        today: None,
+        details: Default::default(),
         timestamp: "2024-01-01T12:00".to_string(),
         attribution: "".to_string(),
     };
@@ -80,6 +100,16 @@ fn test_weather_normalizer_integration_day_night() {
         wind_direction: 180.0,
         sun: CelestialEvents::only_day(0),
         moon_phase: None,
+        pressure: None,
+        disagreement: None,
+        humidity: None,
+        apparent_temperature: None,
+        uv_index: None,
+        source: String::new(),
+        nowcast: Vec::new(),
+        hourly: Vec::new(),
+        today: None,
+        details: Default::default(),
         timestamp: "2024-01-01T00:00".to_string(),
         attribution: "".to_string(),
     };
@@ -101,6 +131,16 @@ fn test_weather_normalizer_integration_clear_conditions() {
         wind_direction: 90.0,
         sun: CelestialEvents::only_day(1),
         moon_phase: None,
+        pressure: None,
+        disagreement: None,
+        humidity: None,
+        apparent_temperature: None,
+        uv_index: None,
+        source: String::new(),
+        nowcast: Vec::new(),
+        hourly: Vec::new(),
+        today: None,
+        details: Default::default(),
         timestamp: "2024-06-15T14:00".to_string(),
         attribution: "".to_string(),
     };
@@ -116,13 +156,23 @@ fn test_weather_normalizer_integration_clear_conditions() {
 #[test]
 fn test_weather_normalizer_integration_rainy_conditions() {
     let response = WeatherProviderResponse {
-        weather_code: 61,
+        weather_code: 63,
         temperature: 15.0,
         precipitation: 5.2,
         wind_speed: 12.0,
         wind_direction: 270.0,
         sun: CelestialEvents::only_day(1),
         moon_phase: None,
+        pressure: None,
+        disagreement: None,
+        humidity: None,
+        apparent_temperature: None,
+        uv_index: None,
+        source: String::new(),
+        nowcast: Vec::new(),
+        hourly: Vec::new(),
+        today: None,
+        details: Default::default(),
         timestamp: "2024-03-20T10:00".to_string(),
         attribution: "".to_string(),
     };
@@ -143,13 +193,23 @@ fn test_weather_normalizer_integration_snowy_conditions() {
         wind_direction: 0.0,
         sun: CelestialEvents::only_day(0),
         moon_phase: None,
+        pressure: None,
+        disagreement: None,
+        humidity: None,
+        apparent_temperature: None,
+        uv_index: None,
+        source: String::new(),
+        nowcast: Vec::new(),
+        hourly: Vec::new(),
+        today: None,
+        details: Default::default(),
         timestamp: "2024-01-10T22:00".to_string(),
         attribution: "".to_string(),
     };
 
     let weather = WeatherNormalizer::normalize(response);
 
-    assert_eq!(weather.condition, WeatherCondition::Snow);
+    assert_eq!(weather.condition, WeatherCondition::LightSnow);
     assert!(weather.temperature < 0.0);
     assert!(!weather.sun.is_day);
 }